@@ -13,11 +13,15 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 use distance::Distance;
+use rabitq::bits::Bits;
 use rabitq::byte::CodeMetadata;
-use simd::{Floating, f16};
+use simd::{Floating, bf16, f16};
 use std::marker::PhantomData;
+use vector::rabitq1::Rabitq1Owned;
+use vector::rabitq2::Rabitq2Owned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
+use vector::veci8::Veci8Owned;
 use vector::vect::VectOwned;
 
 #[derive(Debug, Clone, Copy)]
@@ -389,6 +393,62 @@ impl Accessor2<f16, f16, (), ()> for DistanceAccessor<VectOwned<f16>, Dot> {
     }
 }
 
+impl Accessor2<bf16, bf16, (), ()> for DistanceAccessor<VectOwned<bf16>, L2S> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[bf16], input: &[bf16]) {
+        self.0 += bf16::reduce_sum_of_d2(target, input)
+    }
+
+    #[inline(always)]
+    fn finish(self, (): (), (): ()) -> Self::Output {
+        Distance::from_f32(self.0)
+    }
+}
+
+impl Accessor2<bf16, bf16, (), ()> for DistanceAccessor<VectOwned<bf16>, Dot> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[bf16], input: &[bf16]) {
+        self.0 += bf16::reduce_sum_of_xy(target, input)
+    }
+
+    #[inline(always)]
+    fn finish(self, (): (), (): ()) -> Self::Output {
+        Distance::from_f32(-self.0)
+    }
+}
+
+impl Accessor2<i8, i8, (), ()> for DistanceAccessor<Veci8Owned, L2S> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[i8], input: &[i8]) {
+        self.0 += simd::byte::reduce_sum_of_d2_i8(target, input) as f32
+    }
+
+    #[inline(always)]
+    fn finish(self, (): (), (): ()) -> Self::Output {
+        Distance::from_f32(self.0)
+    }
+}
+
+impl Accessor2<i8, i8, (), ()> for DistanceAccessor<Veci8Owned, Dot> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[i8], input: &[i8]) {
+        self.0 += simd::byte::reduce_sum_of_xy_i8(target, input) as f32
+    }
+
+    #[inline(always)]
+    fn finish(self, (): (), (): ()) -> Self::Output {
+        Distance::from_f32(-self.0)
+    }
+}
+
 pub trait DefaultWithDimension {
     fn default_with_dimension(dim: u32) -> Self;
 }
@@ -511,3 +571,105 @@ impl Accessor2<u8, u8, [f32; 4], [f32; 4]> for HalfbyteDistanceAccessor<Rabitq4O
         )
     }
 }
+
+#[derive(Debug)]
+pub struct BitsDistanceAccessor<V, D>(u32, u32, PhantomData<fn(V) -> V>, PhantomData<fn(D) -> D>);
+
+impl<V, D> DefaultWithDimension for BitsDistanceAccessor<V, D> {
+    #[inline(always)]
+    fn default_with_dimension(dim: u32) -> Self {
+        Self(dim, 0, PhantomData, PhantomData)
+    }
+}
+
+impl Accessor2<u8, u8, [f32; 4], [f32; 4]> for BitsDistanceAccessor<Rabitq1Owned, L2S> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[u8], input: &[u8]) {
+        self.1 += rabitq::bits::packed::accumulate(Bits::_1, target, input);
+    }
+
+    #[inline(always)]
+    fn finish(self, target: [f32; 4], input: [f32; 4]) -> Self::Output {
+        Distance::from_f32(
+            rabitq::bits::packed::half_process_l2s(
+                Bits::_1,
+                self.0,
+                self.1,
+                CodeMetadata::from_array(std::array::from_fn(|i| target[i])),
+                CodeMetadata::from_array(std::array::from_fn(|i| input[i])),
+            )
+            .0,
+        )
+    }
+}
+
+impl Accessor2<u8, u8, [f32; 4], [f32; 4]> for BitsDistanceAccessor<Rabitq1Owned, Dot> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[u8], input: &[u8]) {
+        self.1 += rabitq::bits::packed::accumulate(Bits::_1, target, input);
+    }
+
+    #[inline(always)]
+    fn finish(self, target: [f32; 4], input: [f32; 4]) -> Self::Output {
+        Distance::from_f32(
+            rabitq::bits::packed::half_process_dot(
+                Bits::_1,
+                self.0,
+                self.1,
+                CodeMetadata::from_array(std::array::from_fn(|i| target[i])),
+                CodeMetadata::from_array(std::array::from_fn(|i| input[i])),
+            )
+            .0,
+        )
+    }
+}
+
+impl Accessor2<u8, u8, [f32; 4], [f32; 4]> for BitsDistanceAccessor<Rabitq2Owned, L2S> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[u8], input: &[u8]) {
+        self.1 += rabitq::bits::packed::accumulate(Bits::_2, target, input);
+    }
+
+    #[inline(always)]
+    fn finish(self, target: [f32; 4], input: [f32; 4]) -> Self::Output {
+        Distance::from_f32(
+            rabitq::bits::packed::half_process_l2s(
+                Bits::_2,
+                self.0,
+                self.1,
+                CodeMetadata::from_array(std::array::from_fn(|i| target[i])),
+                CodeMetadata::from_array(std::array::from_fn(|i| input[i])),
+            )
+            .0,
+        )
+    }
+}
+
+impl Accessor2<u8, u8, [f32; 4], [f32; 4]> for BitsDistanceAccessor<Rabitq2Owned, Dot> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[u8], input: &[u8]) {
+        self.1 += rabitq::bits::packed::accumulate(Bits::_2, target, input);
+    }
+
+    #[inline(always)]
+    fn finish(self, target: [f32; 4], input: [f32; 4]) -> Self::Output {
+        Distance::from_f32(
+            rabitq::bits::packed::half_process_dot(
+                Bits::_2,
+                self.0,
+                self.1,
+                CodeMetadata::from_array(std::array::from_fn(|i| target[i])),
+                CodeMetadata::from_array(std::array::from_fn(|i| input[i])),
+            )
+            .0,
+        )
+    }
+}
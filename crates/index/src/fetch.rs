@@ -126,3 +126,14 @@ impl<'b, T, U, F: Fetch1 + Copy> Fetch<'b> for (T, AlwaysEqual<(&'b [F], U)>) {
         }
     }
 }
+
+impl<'b, T, U, V, F: Fetch1 + Copy> Fetch<'b> for (T, AlwaysEqual<(&'b [F], U, V)>) {
+    type Iter = Fetch1Iter<std::iter::Copied<std::slice::Iter<'b, F>>>;
+
+    #[inline(always)]
+    fn fetch(&self) -> Self::Iter {
+        Fetch1Iter {
+            iter: self.1.0.0.iter().copied(),
+        }
+    }
+}
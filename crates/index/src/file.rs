@@ -0,0 +1,545 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+//! Relations that keep their pages in a file.
+//!
+//! The file starts with a header of `PAGE_SIZE` bytes, followed by pages in
+//! the layout of [`MemoryPage`]. The header records a kind chosen by the
+//! writer, so that a reader can check that the file is what it expects.
+//!
+//! [`FileRelation`] reads and writes pages with `pread` and `pwrite`, and is
+//! used for building. [`MappedRelation`] maps the file read-only, and is used
+//! for searching.
+
+use crate::fetch::Fetch;
+use crate::memory::{Freespaces, MemoryPage, PAGE_SIZE, Slots};
+use crate::relation::{
+    Hints, Opaque, Page, PageGuard, PlainReadStream, Relation, RelationPrefetch, RelationRead,
+    RelationReadStream, RelationReadStreamTypes, RelationReadTypes, RelationWrite,
+    RelationWriteTypes,
+};
+use std::fs::File;
+use std::io;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::{Mutex, RwLockReadGuard, RwLockWriteGuard};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+const MAGIC: [u8; 8] = *b"vchordf\0";
+const VERSION: u32 = 1;
+const MAX_KIND_LEN: usize = 104;
+
+#[repr(C)]
+#[derive(Debug, Clone, FromBytes, IntoBytes, Immutable, KnownLayout)]
+struct FileHeader {
+    magic: [u8; 8],
+    version: u32,
+    page_size: u32,
+    kind_len: u32,
+    _padding: u32,
+    kind: [u8; MAX_KIND_LEN],
+}
+
+fn encode_header(kind: &str) -> io::Result<Vec<u8>> {
+    if kind.len() > MAX_KIND_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "kind is too long",
+        ));
+    }
+    let mut header = FileHeader {
+        magic: MAGIC,
+        version: VERSION,
+        page_size: PAGE_SIZE as u32,
+        kind_len: kind.len() as u32,
+        _padding: 0,
+        kind: [0; MAX_KIND_LEN],
+    };
+    header.kind[..kind.len()].copy_from_slice(kind.as_bytes());
+    let mut bytes = vec![0_u8; PAGE_SIZE];
+    bytes[..size_of::<FileHeader>()].copy_from_slice(header.as_bytes());
+    Ok(bytes)
+}
+
+fn decode_header(bytes: &[u8]) -> io::Result<String> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    let Ok((header, _)) = FileHeader::ref_from_prefix(bytes) else {
+        return Err(invalid("the file is too short"));
+    };
+    if header.magic != MAGIC {
+        return Err(invalid("bad magic number"));
+    }
+    if header.version != VERSION {
+        return Err(invalid("bad version number"));
+    }
+    if header.page_size as usize != PAGE_SIZE {
+        return Err(invalid("bad page size"));
+    }
+    let Some(kind) = header.kind.get(..header.kind_len as usize) else {
+        return Err(invalid("bad kind"));
+    };
+    String::from_utf8(kind.to_vec()).map_err(|_| invalid("bad kind"))
+}
+
+#[cfg(unix)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, offset)
+}
+
+#[cfg(unix)]
+fn write_at(file: &File, buf: &[u8], offset: u64) -> io::Result<()> {
+    std::os::unix::fs::FileExt::write_all_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> io::Result<()> {
+    while !buf.is_empty() {
+        match std::os::windows::fs::FileExt::seek_read(file, buf, offset) {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => (buf, offset) = (&mut buf[n..], offset + n as u64),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn write_at(file: &File, mut buf: &[u8], mut offset: u64) -> io::Result<()> {
+    while !buf.is_empty() {
+        match std::os::windows::fs::FileExt::seek_write(file, buf, offset) {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(n) => (buf, offset) = (&buf[n..], offset + n as u64),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+fn offset(id: u32) -> u64 {
+    (id as u64 + 1) * PAGE_SIZE as u64
+}
+
+pub struct FileReadGuard<'b, O> {
+    _lock: RwLockReadGuard<'b, ()>,
+    page: Box<MemoryPage<O>>,
+    id: u32,
+}
+
+impl<O> PageGuard for FileReadGuard<'_, O> {
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl<O> Deref for FileReadGuard<'_, O> {
+    type Target = MemoryPage<O>;
+
+    fn deref(&self) -> &MemoryPage<O> {
+        &self.page
+    }
+}
+
+pub struct FileWriteGuard<'b, O: Opaque> {
+    relation: &'b FileRelation<O>,
+    _lock: RwLockWriteGuard<'b, ()>,
+    page: Box<MemoryPage<O>>,
+    id: u32,
+    tracking_freespace: bool,
+}
+
+impl<O: Opaque> PageGuard for FileWriteGuard<'_, O> {
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl<O: Opaque> Deref for FileWriteGuard<'_, O> {
+    type Target = MemoryPage<O>;
+
+    fn deref(&self) -> &MemoryPage<O> {
+        &self.page
+    }
+}
+
+impl<O: Opaque> DerefMut for FileWriteGuard<'_, O> {
+    fn deref_mut(&mut self) -> &mut MemoryPage<O> {
+        &mut self.page
+    }
+}
+
+impl<O: Opaque> Drop for FileWriteGuard<'_, O> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        let bytes = self.page.as_bytes();
+        if let Err(e) = write_at(&self.relation.file, bytes, offset(self.id)) {
+            let mut error = self.relation.error.lock().expect("failed to lock");
+            error.get_or_insert_with(|| {
+                io::Error::new(e.kind(), format!("failed to write page {}: {e}", self.id))
+            });
+            return;
+        }
+        if self.tracking_freespace {
+            self.relation
+                .freespaces
+                .record(self.id, self.page.freespace());
+        }
+    }
+}
+
+/// A relation that reads and writes pages of a file directly.
+///
+/// Pages are written back when write guards are dropped. A failed write is
+/// kept and returned by [`FileRelation::sync`], so the file must be synced
+/// before it is used.
+pub struct FileRelation<O> {
+    file: File,
+    kind: String,
+    locks: Slots<()>,
+    freespaces: Freespaces,
+    error: Mutex<Option<io::Error>>,
+    _phantom: PhantomData<fn(O) -> O>,
+}
+
+impl<O: Opaque> FileRelation<O> {
+    /// Creates an empty file, or truncates an existing one.
+    pub fn create(path: impl AsRef<Path>, kind: &str) -> io::Result<Self> {
+        let header = encode_header(kind)?;
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        write_at(&file, &header, 0)?;
+        Ok(Self {
+            file,
+            kind: kind.to_string(),
+            locks: Slots::new([]),
+            freespaces: Freespaces::default(),
+            error: Mutex::new(None),
+            _phantom: PhantomData,
+        })
+    }
+    /// Opens an existing file for reading and writing.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::options().read(true).write(true).open(path)?;
+        let mut header = vec![0_u8; PAGE_SIZE];
+        read_at(&file, &mut header, 0)?;
+        let kind = decode_header(&header)?;
+        let len = file.metadata()?.len();
+        if len % PAGE_SIZE as u64 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the file is truncated",
+            ));
+        }
+        let n = len / PAGE_SIZE as u64 - 1;
+        let Ok(n) = u32::try_from(n) else {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "too many pages"));
+        };
+        Ok(Self {
+            file,
+            kind,
+            locks: Slots::new((0..n).map(|_| ())),
+            // Like a lost free space map, pages are found again after they are
+            // written with free space tracked.
+            freespaces: Freespaces::default(),
+            error: Mutex::new(None),
+            _phantom: PhantomData,
+        })
+    }
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+    /// Returns the number of pages.
+    pub fn len(&self) -> u32 {
+        self.locks.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Flushes written pages to the disk, or returns the first failed write.
+    pub fn sync(&self) -> io::Result<()> {
+        if let Some(e) = self.error.lock().expect("failed to lock").take() {
+            return Err(e);
+        }
+        self.file.sync_all()
+    }
+    /// Appends a copy of a page, for example, one of a relation built in memory.
+    pub fn push(&self, page: &MemoryPage<O>) -> io::Result<u32> {
+        let (id, _lock) = self.locks.push(());
+        write_at(&self.file, page.as_bytes(), offset(id))?;
+        Ok(id)
+    }
+    fn load(&self, id: u32) -> Box<MemoryPage<O>> {
+        let mut bytes = Box::new([0_u8; PAGE_SIZE]);
+        if let Err(e) = read_at(&self.file, bytes.as_mut_slice(), offset(id)) {
+            panic!("failed to read page {id}: {e}");
+        }
+        Box::new(MemoryPage::from_bytes(&bytes))
+    }
+}
+
+impl<O: Opaque> Relation for FileRelation<O> {
+    type Page = MemoryPage<O>;
+}
+
+impl<O: Opaque> RelationReadTypes for FileRelation<O> {
+    type ReadGuard<'b> = FileReadGuard<'b, O>;
+}
+
+impl<O: Opaque> RelationRead for FileRelation<O> {
+    fn read(&self, id: u32) -> FileReadGuard<'_, O> {
+        let lock = self.locks.read(id);
+        let page = self.load(id);
+        FileReadGuard {
+            _lock: lock,
+            page,
+            id,
+        }
+    }
+}
+
+impl<O: Opaque> RelationWriteTypes for FileRelation<O> {
+    type WriteGuard<'b> = FileWriteGuard<'b, O>;
+}
+
+impl<O: Opaque> RelationWrite for FileRelation<O> {
+    fn write(&self, id: u32, tracking_freespace: bool) -> FileWriteGuard<'_, O> {
+        let lock = self.locks.write(id);
+        let page = self.load(id);
+        FileWriteGuard {
+            relation: self,
+            _lock: lock,
+            page,
+            id,
+            tracking_freespace,
+        }
+    }
+    fn extend(&self, opaque: O, tracking_freespace: bool) -> FileWriteGuard<'_, O> {
+        let (id, lock) = self.locks.push(());
+        FileWriteGuard {
+            relation: self,
+            _lock: lock,
+            page: Box::new(MemoryPage::new(opaque)),
+            id,
+            tracking_freespace,
+        }
+    }
+    fn search(&self, freespace: usize) -> Option<FileWriteGuard<'_, O>> {
+        loop {
+            let id = self.freespaces.find(freespace)?;
+            let write = self.write(id, true);
+            if (write.freespace() as usize) < freespace {
+                // the free space is recorded incorrectly
+                self.freespaces.record(id, write.freespace());
+                continue;
+            }
+            return Some(write);
+        }
+    }
+}
+
+impl<O: Opaque> RelationPrefetch for FileRelation<O> {
+    fn prefetch(&self, _: u32) {}
+}
+
+impl<O: Opaque> RelationReadStreamTypes for FileRelation<O> {
+    type ReadStream<'b, I: Iterator>
+        = PlainReadStream<'b, Self, I>
+    where
+        I::Item: Fetch<'b>;
+}
+
+impl<O: Opaque> RelationReadStream for FileRelation<O> {
+    fn read_stream<'b, I: Iterator>(
+        &'b self,
+        iter: I,
+        _hints: Hints,
+    ) -> PlainReadStream<'b, Self, I>
+    where
+        I::Item: Fetch<'b>,
+    {
+        PlainReadStream::new(self, iter)
+    }
+}
+
+pub struct MappedReadGuard<'b, O> {
+    page: &'b MemoryPage<O>,
+    id: u32,
+}
+
+impl<O> PageGuard for MappedReadGuard<'_, O> {
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl<O> Deref for MappedReadGuard<'_, O> {
+    type Target = MemoryPage<O>;
+
+    fn deref(&self) -> &MemoryPage<O> {
+        self.page
+    }
+}
+
+/// A relation that maps a file read-only.
+///
+/// The file must not be modified while it is mapped.
+pub struct MappedRelation<O> {
+    map: memmap2::Mmap,
+    kind: String,
+    _phantom: PhantomData<fn(O) -> O>,
+}
+
+impl<O: Opaque> MappedRelation<O> {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: it's documented that the file must not be modified while it
+        // is mapped.
+        #[allow(unsafe_code)]
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        let kind = decode_header(&map)?;
+        if map.len() % PAGE_SIZE != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the file is truncated",
+            ));
+        }
+        if u32::try_from(map.len() / PAGE_SIZE - 1).is_err() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "too many pages"));
+        }
+        Ok(Self {
+            map,
+            kind,
+            _phantom: PhantomData,
+        })
+    }
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+    /// Returns the number of pages.
+    pub fn len(&self) -> u32 {
+        (self.map.len() / PAGE_SIZE - 1) as u32
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<O: Opaque> Relation for MappedRelation<O> {
+    type Page = MemoryPage<O>;
+}
+
+impl<O: Opaque> RelationReadTypes for MappedRelation<O> {
+    type ReadGuard<'b> = MappedReadGuard<'b, O>;
+}
+
+impl<O: Opaque> RelationRead for MappedRelation<O> {
+    fn read(&self, id: u32) -> MappedReadGuard<'_, O> {
+        assert!(id < self.len(), "no such page");
+        let start = offset(id) as usize;
+        let bytes = self.map[start..start + PAGE_SIZE]
+            .try_into()
+            .expect("bad page");
+        MappedReadGuard {
+            page: MemoryPage::from_ref(bytes),
+            id,
+        }
+    }
+}
+
+impl<O: Opaque> RelationPrefetch for MappedRelation<O> {
+    fn prefetch(&self, id: u32) {
+        assert!(id < self.len(), "no such page");
+        let advice = memmap2::Advice::WillNeed;
+        let _ = self
+            .map
+            .advise_range(advice, offset(id) as usize, PAGE_SIZE);
+    }
+}
+
+impl<O: Opaque> RelationReadStreamTypes for MappedRelation<O> {
+    type ReadStream<'b, I: Iterator>
+        = PlainReadStream<'b, Self, I>
+    where
+        I::Item: Fetch<'b>;
+}
+
+impl<O: Opaque> RelationReadStream for MappedRelation<O> {
+    fn read_stream<'b, I: Iterator>(
+        &'b self,
+        iter: I,
+        _hints: Hints,
+    ) -> PlainReadStream<'b, Self, I>
+    where
+        I::Item: Fetch<'b>,
+    {
+        PlainReadStream::new(self, iter)
+    }
+}
+
+#[test]
+fn test_file_relation() {
+    #[repr(C, align(8))]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout)]
+    struct TestOpaque {
+        next: u32,
+        skip: u32,
+    }
+
+    #[allow(unsafe_code)]
+    unsafe impl Opaque for TestOpaque {}
+
+    let path = std::env::temp_dir().join(format!("vchord-test-{}.index", std::process::id()));
+    let items = {
+        let relation = FileRelation::create(&path, "test").unwrap();
+        let mut items = Vec::new();
+        for k in 0..10_u32 {
+            let mut guard = relation.extend(TestOpaque { next: k, skip: 0 }, true);
+            items.push((guard.id(), guard.alloc(&k.to_ne_bytes()).unwrap()));
+        }
+        relation.sync().unwrap();
+        items
+    };
+    {
+        let relation = FileRelation::<TestOpaque>::open(&path).unwrap();
+        assert_eq!((relation.kind(), relation.len()), ("test", 10));
+        assert!(relation.search(4000).is_none());
+        let mut guard = relation.write(0, true);
+        guard.free(items[0].1);
+        let i = guard.alloc(&[7; 4000]).unwrap();
+        assert_eq!(i, 1);
+    }
+    {
+        let relation = MappedRelation::<TestOpaque>::open(&path).unwrap();
+        assert_eq!((relation.kind(), relation.len()), ("test", 10));
+        assert_eq!(relation.read(0).get(1), Some(&[7; 4000][..]));
+        for (k, &(id, i)) in items.iter().enumerate().skip(1) {
+            relation.prefetch(id);
+            let guard = relation.read(id);
+            assert_eq!(guard.get_opaque().next, k as u32);
+            assert_eq!(guard.get(i), Some(&(k as u32).to_ne_bytes()[..]));
+        }
+    }
+    std::fs::remove_file(&path).unwrap();
+    std::fs::write(&path, [0; PAGE_SIZE]).unwrap();
+    assert!(MappedRelation::<TestOpaque>::open(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -15,7 +15,10 @@
 // pub mod accessor;
 pub mod bump;
 pub mod fetch;
+pub mod file;
+pub mod memory;
 pub mod packed;
 pub mod prefetcher;
 pub mod relation;
 pub mod tuples;
+pub mod wal;
@@ -0,0 +1,638 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+//! A relation that keeps its pages in memory.
+//!
+//! Pages use the slotted layout of PostgreSQL, so that algorithms observe the
+//! same item numbers and the same free space as they do in a PostgreSQL index.
+
+use crate::fetch::Fetch;
+use crate::relation::{
+    Hints, Opaque, Page, PageGuard, PlainReadStream, Relation, RelationPrefetch, RelationRead,
+    RelationReadStream, RelationReadStreamTypes, RelationReadTypes, RelationWrite,
+    RelationWriteTypes,
+};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+pub const PAGE_SIZE: usize = 8192;
+
+const MAXIMUM_ALIGNOF: usize = 8;
+const PD_HAS_FREE_LINES: u16 = 0x0001;
+const PG_PAGE_LAYOUT_VERSION: u16 = 4;
+const LP_UNUSED: u32 = 0;
+const LP_NORMAL: u32 = 1;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, FromBytes, IntoBytes, Immutable, KnownLayout)]
+struct Header {
+    lsn: u64,
+    checksum: u16,
+    flags: u16,
+    lower: u16,
+    upper: u16,
+    special: u16,
+    pagesize_version: u16,
+    prune_xid: u32,
+}
+
+const SIZE_OF_HEADER: usize = size_of::<Header>();
+const SIZE_OF_ITEM_ID: usize = size_of::<u32>();
+
+const _: () = assert!(SIZE_OF_HEADER == 24);
+
+// An item identifier is `lp_off:15`, `lp_flags:2` and `lp_len:15`, from the
+// lowest bit to the highest bit.
+#[derive(Debug, Clone, Copy)]
+struct ItemId(u32);
+
+impl ItemId {
+    fn new(off: usize, flags: u32, len: usize) -> Self {
+        Self((off as u32 & 0x7fff) | (flags & 0x3) << 15 | (len as u32 & 0x7fff) << 17)
+    }
+    fn off(self) -> usize {
+        (self.0 & 0x7fff) as usize
+    }
+    fn flags(self) -> u32 {
+        (self.0 >> 15) & 0x3
+    }
+    fn len(self) -> usize {
+        (self.0 >> 17) as usize
+    }
+}
+
+#[repr(C, align(8))]
+pub struct MemoryPage<O> {
+    bytes: [u8; PAGE_SIZE],
+    _phantom: PhantomData<fn(O) -> O>,
+}
+
+const _: () = assert!(size_of::<MemoryPage<()>>() == PAGE_SIZE);
+
+impl<O: Opaque> MemoryPage<O> {
+    pub fn new(opaque: O) -> Self {
+        let mut this = Self {
+            bytes: [0; PAGE_SIZE],
+            _phantom: PhantomData,
+        };
+        this.clear(opaque);
+        this
+    }
+    pub fn from_bytes(bytes: &[u8; PAGE_SIZE]) -> Self {
+        let this = Self {
+            bytes: *bytes,
+            _phantom: PhantomData,
+        };
+        this.check();
+        this
+    }
+    /// Views aligned bytes as a page.
+    pub fn from_ref(bytes: &[u8; PAGE_SIZE]) -> &Self {
+        assert!(bytes.as_ptr().cast::<Self>().is_aligned(), "unaligned page");
+        // SAFETY: `Self` is a transparent wrapper of the bytes with a stricter
+        // alignment, which is checked above.
+        #[allow(unsafe_code)]
+        let this = unsafe { &*bytes.as_ptr().cast::<Self>() };
+        this.check();
+        this
+    }
+    pub fn as_bytes(&self) -> &[u8; PAGE_SIZE] {
+        &self.bytes
+    }
+    fn check(&self) {
+        let header = self.header();
+        assert!(header.special as usize + size_of::<O>() == PAGE_SIZE);
+        assert!(SIZE_OF_HEADER <= header.lower as usize);
+        assert!(header.lower <= header.upper && header.upper <= header.special);
+    }
+    fn header(&self) -> &Header {
+        Header::ref_from_prefix(&self.bytes).expect("bad page").0
+    }
+    fn header_mut(&mut self) -> &mut Header {
+        Header::mut_from_prefix(&mut self.bytes)
+            .expect("bad page")
+            .0
+    }
+    fn item_id(&self, i: u16) -> ItemId {
+        let offset = SIZE_OF_HEADER + (i as usize - 1) * SIZE_OF_ITEM_ID;
+        let bytes = &self.bytes[offset..offset + SIZE_OF_ITEM_ID];
+        ItemId(u32::from_ne_bytes(bytes.try_into().expect("bad page")))
+    }
+    fn set_item_id(&mut self, i: u16, item_id: ItemId) {
+        let offset = SIZE_OF_HEADER + (i as usize - 1) * SIZE_OF_ITEM_ID;
+        self.bytes[offset..offset + SIZE_OF_ITEM_ID].copy_from_slice(&item_id.0.to_ne_bytes());
+    }
+    fn item(&self, i: u16) -> Option<(usize, usize)> {
+        if i == 0 || i > self.len() {
+            return None;
+        }
+        let item_id = self.item_id(i);
+        match item_id.flags() {
+            LP_UNUSED => return None,
+            LP_NORMAL => (),
+            _ => unimplemented!(),
+        }
+        let (off, len) = (item_id.off(), item_id.len());
+        assert!(SIZE_OF_HEADER <= off && off + len <= PAGE_SIZE);
+        Some((off, len))
+    }
+}
+
+impl<O: Opaque> Clone for MemoryPage<O> {
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<O: Opaque> Page for MemoryPage<O> {
+    type Opaque = O;
+    fn get_opaque(&self) -> &O {
+        let special = self.header().special as usize;
+        assert!(special + size_of::<O>() == PAGE_SIZE);
+        let ptr = self.bytes[special..].as_ptr().cast::<O>();
+        assert!(ptr.is_aligned());
+        // SAFETY: the pointer is aligned and in bounds, and `O` is `FromBytes`.
+        #[allow(unsafe_code)]
+        unsafe {
+            &*ptr
+        }
+    }
+    fn get_opaque_mut(&mut self) -> &mut O {
+        let special = self.header().special as usize;
+        assert!(special + size_of::<O>() == PAGE_SIZE);
+        let ptr = self.bytes[special..].as_mut_ptr().cast::<O>();
+        assert!(ptr.is_aligned());
+        // SAFETY: the pointer is aligned and in bounds, and `O` is `FromBytes`
+        // and `IntoBytes`.
+        #[allow(unsafe_code)]
+        unsafe {
+            &mut *ptr
+        }
+    }
+    fn len(&self) -> u16 {
+        let header = self.header();
+        let lower = header.lower as usize;
+        assert!(SIZE_OF_HEADER <= lower && lower <= header.upper as usize);
+        ((lower - SIZE_OF_HEADER) / SIZE_OF_ITEM_ID) as u16
+    }
+    fn get(&self, i: u16) -> Option<&[u8]> {
+        let (off, len) = self.item(i)?;
+        Some(&self.bytes[off..off + len])
+    }
+    fn get_mut(&mut self, i: u16) -> Option<&mut [u8]> {
+        let (off, len) = self.item(i)?;
+        Some(&mut self.bytes[off..off + len])
+    }
+    // See `PageAddItemExtended`.
+    fn alloc(&mut self, data: &[u8]) -> Option<u16> {
+        let n = self.len();
+        let header = *self.header();
+        let mut i = n + 1;
+        if header.flags & PD_HAS_FREE_LINES != 0 {
+            if let Some(j) = (1..=n).find(|&j| self.item_id(j).flags() == LP_UNUSED) {
+                i = j;
+            } else {
+                self.header_mut().flags &= !PD_HAS_FREE_LINES;
+            }
+        }
+        let lower = header.lower as usize + if i > n { SIZE_OF_ITEM_ID } else { 0 };
+        let aligned = data.len().next_multiple_of(MAXIMUM_ALIGNOF);
+        if header.upper as usize <= aligned || lower > header.upper as usize - aligned {
+            return None;
+        }
+        let upper = header.upper as usize - aligned;
+        self.set_item_id(i, ItemId::new(upper, LP_NORMAL, data.len()));
+        self.bytes[upper..upper + data.len()].copy_from_slice(data);
+        let header = self.header_mut();
+        header.lower = lower as u16;
+        header.upper = upper as u16;
+        Some(i)
+    }
+    // See `PageIndexTupleDeleteNoCompact`.
+    fn free(&mut self, i: u16) {
+        let mut n = self.len();
+        assert!(1 <= i && i <= n, "no such item");
+        let item_id = self.item_id(i);
+        if item_id.flags() == LP_UNUSED {
+            return;
+        }
+        let size = item_id.len().next_multiple_of(MAXIMUM_ALIGNOF);
+        let offset = item_id.off();
+        if i < n {
+            self.set_item_id(i, ItemId(0));
+        } else {
+            n -= 1;
+            while n > 0 && self.item_id(n).flags() == LP_UNUSED {
+                n -= 1;
+            }
+            self.header_mut().lower = (SIZE_OF_HEADER + n as usize * SIZE_OF_ITEM_ID) as u16;
+        }
+        let upper = self.header().upper as usize;
+        if offset > upper {
+            self.bytes.copy_within(upper..offset, upper + size);
+        }
+        self.header_mut().upper = (upper + size) as u16;
+        for j in 1..=n {
+            let item_id = self.item_id(j);
+            if item_id.flags() != LP_UNUSED && item_id.off() <= offset {
+                let moved = ItemId::new(item_id.off() + size, item_id.flags(), item_id.len());
+                self.set_item_id(j, moved);
+            }
+        }
+    }
+    // See `PageGetFreeSpace`.
+    fn freespace(&self) -> u16 {
+        let header = self.header();
+        let space = header.upper as usize - header.lower as usize;
+        space.saturating_sub(SIZE_OF_ITEM_ID) as u16
+    }
+    // See `PageInit`.
+    fn clear(&mut self, opaque: O) {
+        let special = PAGE_SIZE - size_of::<O>().next_multiple_of(MAXIMUM_ALIGNOF);
+        self.bytes = [0; PAGE_SIZE];
+        *self.header_mut() = Header {
+            lsn: 0,
+            checksum: 0,
+            flags: 0,
+            lower: SIZE_OF_HEADER as u16,
+            upper: special as u16,
+            special: special as u16,
+            pagesize_version: PAGE_SIZE as u16 | PG_PAGE_LAYOUT_VERSION,
+            prune_xid: 0,
+        };
+        *self.get_opaque_mut() = opaque;
+    }
+}
+
+pub struct MemoryReadGuard<'b, O> {
+    guard: RwLockReadGuard<'b, MemoryPage<O>>,
+    id: u32,
+}
+
+impl<O> PageGuard for MemoryReadGuard<'_, O> {
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl<O> Deref for MemoryReadGuard<'_, O> {
+    type Target = MemoryPage<O>;
+
+    fn deref(&self) -> &MemoryPage<O> {
+        &self.guard
+    }
+}
+
+pub struct MemoryWriteGuard<'b, O: Opaque> {
+    relation: &'b MemoryRelation<O>,
+    guard: RwLockWriteGuard<'b, MemoryPage<O>>,
+    id: u32,
+    tracking_freespace: bool,
+}
+
+impl<O: Opaque> PageGuard for MemoryWriteGuard<'_, O> {
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl<O: Opaque> Deref for MemoryWriteGuard<'_, O> {
+    type Target = MemoryPage<O>;
+
+    fn deref(&self) -> &MemoryPage<O> {
+        &self.guard
+    }
+}
+
+impl<O: Opaque> DerefMut for MemoryWriteGuard<'_, O> {
+    fn deref_mut(&mut self) -> &mut MemoryPage<O> {
+        &mut self.guard
+    }
+}
+
+impl<O: Opaque> Drop for MemoryWriteGuard<'_, O> {
+    fn drop(&mut self) {
+        if self.tracking_freespace && !std::thread::panicking() {
+            self.relation
+                .freespaces
+                .record(self.id, self.guard.freespace());
+        }
+    }
+}
+
+/// A list of locks that only grows.
+///
+/// Locks are boxed and never removed, so a lock is taken without holding the
+/// lock of the list.
+pub(crate) struct Slots<T> {
+    slots: RwLock<Vec<Box<RwLock<T>>>>,
+}
+
+impl<T> Slots<T> {
+    pub(crate) fn new(values: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            slots: RwLock::new(
+                values
+                    .into_iter()
+                    .map(|x| Box::new(RwLock::new(x)))
+                    .collect(),
+            ),
+        }
+    }
+    pub(crate) fn into_inner(self) -> Vec<T> {
+        let slots = self.slots.into_inner().unwrap_or_else(|e| e.into_inner());
+        slots
+            .into_iter()
+            .map(|x| x.into_inner().unwrap_or_else(|e| e.into_inner()))
+            .collect()
+    }
+    pub(crate) fn len(&self) -> u32 {
+        self.slots.read().unwrap_or_else(|e| e.into_inner()).len() as u32
+    }
+    pub(crate) fn read(&self, id: u32) -> RwLockReadGuard<'_, T> {
+        self.get(id).read().unwrap_or_else(|e| e.into_inner())
+    }
+    pub(crate) fn write(&self, id: u32) -> RwLockWriteGuard<'_, T> {
+        self.get(id).write().unwrap_or_else(|e| e.into_inner())
+    }
+    /// Appends a value, and returns it locked, so that no one else sees it
+    /// before it is unlocked.
+    pub(crate) fn push(&self, value: T) -> (u32, RwLockWriteGuard<'_, T>) {
+        let mut slots = self.slots.write().unwrap_or_else(|e| e.into_inner());
+        let id = u32::try_from(slots.len()).expect("too many pages");
+        assert!(id != u32::MAX, "too many pages");
+        slots.push(Box::new(RwLock::new(value)));
+        let slot = self.extend_lifetime(&slots[id as usize]);
+        let guard = slot.write().unwrap_or_else(|e| e.into_inner());
+        drop(slots);
+        (id, guard)
+    }
+    fn get(&self, id: u32) -> &RwLock<T> {
+        let slots = self.slots.read().unwrap_or_else(|e| e.into_inner());
+        self.extend_lifetime(slots.get(id as usize).expect("no such page"))
+    }
+    fn extend_lifetime<'a>(&'a self, slot: &RwLock<T>) -> &'a RwLock<T> {
+        let slot: *const RwLock<T> = slot;
+        // SAFETY: slots are boxed and never removed, so a slot lives as long
+        // as the list.
+        #[allow(unsafe_code)]
+        unsafe {
+            &*slot
+        }
+    }
+}
+
+/// Free space of pages, recorded by write guards that track free space.
+#[derive(Default)]
+pub(crate) struct Freespaces {
+    freespaces: Mutex<Vec<u16>>,
+}
+
+impl Freespaces {
+    pub(crate) fn record(&self, id: u32, freespace: u16) {
+        let mut freespaces = self.freespaces.lock().unwrap_or_else(|e| e.into_inner());
+        if freespaces.len() <= id as usize {
+            freespaces.resize(id as usize + 1, 0);
+        }
+        freespaces[id as usize] = freespace;
+    }
+    /// Finds a page whose recorded free space is at least `freespace`.
+    pub(crate) fn find(&self, freespace: usize) -> Option<u32> {
+        let freespaces = self.freespaces.lock().unwrap_or_else(|e| e.into_inner());
+        let id = freespaces.iter().position(|&x| x as usize >= freespace)?;
+        Some(id as u32)
+    }
+}
+
+/// A relation that keeps its pages in memory.
+pub struct MemoryRelation<O> {
+    pages: Slots<MemoryPage<O>>,
+    freespaces: Freespaces,
+}
+
+impl<O: Opaque> Default for MemoryRelation<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O: Opaque> MemoryRelation<O> {
+    pub fn new() -> Self {
+        Self::from_pages(Vec::new())
+    }
+    pub fn from_pages(pages: Vec<MemoryPage<O>>) -> Self {
+        Self {
+            pages: Slots::new(pages),
+            freespaces: Freespaces::default(),
+        }
+    }
+    pub fn into_pages(self) -> Vec<MemoryPage<O>> {
+        self.pages.into_inner()
+    }
+    /// Returns the number of pages.
+    pub fn len(&self) -> u32 {
+        self.pages.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<O: Opaque> Relation for MemoryRelation<O> {
+    type Page = MemoryPage<O>;
+}
+
+impl<O: Opaque> RelationReadTypes for MemoryRelation<O> {
+    type ReadGuard<'b> = MemoryReadGuard<'b, O>;
+}
+
+impl<O: Opaque> RelationRead for MemoryRelation<O> {
+    fn read(&self, id: u32) -> MemoryReadGuard<'_, O> {
+        let guard = self.pages.read(id);
+        MemoryReadGuard { guard, id }
+    }
+}
+
+impl<O: Opaque> RelationWriteTypes for MemoryRelation<O> {
+    type WriteGuard<'b> = MemoryWriteGuard<'b, O>;
+}
+
+impl<O: Opaque> RelationWrite for MemoryRelation<O> {
+    fn write(&self, id: u32, tracking_freespace: bool) -> MemoryWriteGuard<'_, O> {
+        let guard = self.pages.write(id);
+        MemoryWriteGuard {
+            relation: self,
+            guard,
+            id,
+            tracking_freespace,
+        }
+    }
+    fn extend(&self, opaque: O, tracking_freespace: bool) -> MemoryWriteGuard<'_, O> {
+        let (id, guard) = self.pages.push(MemoryPage::new(opaque));
+        MemoryWriteGuard {
+            relation: self,
+            guard,
+            id,
+            tracking_freespace,
+        }
+    }
+    fn search(&self, freespace: usize) -> Option<MemoryWriteGuard<'_, O>> {
+        loop {
+            let id = self.freespaces.find(freespace)?;
+            let write = self.write(id, true);
+            if (write.freespace() as usize) < freespace {
+                // the free space is recorded incorrectly
+                self.freespaces.record(id, write.freespace());
+                continue;
+            }
+            return Some(write);
+        }
+    }
+}
+
+impl<O: Opaque> RelationPrefetch for MemoryRelation<O> {
+    fn prefetch(&self, _: u32) {}
+}
+
+impl<O: Opaque> RelationReadStreamTypes for MemoryRelation<O> {
+    type ReadStream<'b, I: Iterator>
+        = PlainReadStream<'b, Self, I>
+    where
+        I::Item: Fetch<'b>;
+}
+
+impl<O: Opaque> RelationReadStream for MemoryRelation<O> {
+    fn read_stream<'b, I: Iterator>(
+        &'b self,
+        iter: I,
+        _hints: Hints,
+    ) -> PlainReadStream<'b, Self, I>
+    where
+        I::Item: Fetch<'b>,
+    {
+        PlainReadStream::new(self, iter)
+    }
+}
+
+#[cfg(test)]
+#[repr(C, align(8))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout)]
+struct TestOpaque {
+    next: u32,
+    skip: u32,
+}
+
+#[cfg(test)]
+#[allow(unsafe_code)]
+unsafe impl Opaque for TestOpaque {}
+
+#[test]
+fn test_page_alloc_and_free() {
+    let opaque = TestOpaque { next: 7, skip: 9 };
+    let mut page = MemoryPage::new(opaque);
+    assert_eq!(*page.get_opaque(), opaque);
+    assert_eq!(page.len(), 0);
+    assert_eq!(
+        page.freespace() as usize,
+        PAGE_SIZE - SIZE_OF_HEADER - size_of::<TestOpaque>() - SIZE_OF_ITEM_ID
+    );
+    let a = page.alloc(&[1; 10]).unwrap();
+    let b = page.alloc(&[2; 20]).unwrap();
+    let c = page.alloc(&[3; 30]).unwrap();
+    assert_eq!((a, b, c), (1, 2, 3));
+    page.free(b);
+    assert_eq!(page.len(), 3);
+    assert_eq!(page.get(a), Some(&[1; 10][..]));
+    assert_eq!(page.get(b), None);
+    assert_eq!(page.get(c), Some(&[3; 30][..]));
+    // freed items are not reused, as in PostgreSQL
+    assert_eq!(page.alloc(&[4; 8]), Some(4));
+    page.free(4);
+    page.free(c);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(a), Some(&[1; 10][..]));
+    page.get_mut(a).unwrap().fill(5);
+    assert_eq!(page.get(a), Some(&[5; 10][..]));
+    assert_eq!(*page.get_opaque(), opaque);
+    page.clear(TestOpaque { next: 0, skip: 0 });
+    assert!(page.is_empty());
+}
+
+#[test]
+fn test_page_full() {
+    let mut page = MemoryPage::new(TestOpaque { next: 0, skip: 0 });
+    let mut count = 0;
+    while let Some(i) = page.alloc(&[0; 100]) {
+        count += 1;
+        assert_eq!(i, count);
+        assert!(page.get(i).is_some());
+    }
+    assert_eq!(count, page.len());
+    assert!((page.freespace() as usize) < 104);
+    let bytes = *page.as_bytes();
+    let page = MemoryPage::<TestOpaque>::from_bytes(&bytes);
+    assert_eq!(page.len(), count);
+}
+
+#[test]
+fn test_relation() {
+    use crate::relation::ReadStream;
+    let relation = MemoryRelation::new();
+    assert!(relation.is_empty());
+    let first = relation.extend(TestOpaque { next: 1, skip: 0 }, false).id();
+    let mut second = relation.extend(TestOpaque { next: 2, skip: 0 }, true);
+    assert_eq!((first, second.id()), (0, 1));
+    let i = second.alloc(&[0; 4000]).unwrap();
+    drop(second);
+    assert_eq!(relation.len(), 2);
+    assert_eq!(relation.read(0).get_opaque().next, 1);
+    assert_eq!(relation.read(1).get(i), Some(&[0; 4000][..]));
+    assert_eq!(relation.search(2000).map(|guard| guard.id()), Some(1));
+    assert!(relation.search(6000).is_none());
+    {
+        let mut guard = relation.write(1, true);
+        guard.free(i);
+    }
+    assert_eq!(relation.search(6000).map(|guard| guard.id()), Some(1));
+    let mut stream = relation.read_stream([1_u32, 0].into_iter(), Hints::default());
+    let (id, mut guards) = stream.next().unwrap();
+    assert_eq!((id, guards.next().map(|guard| guard.id())), (1, Some(1)));
+    assert!(stream.next_if(|&id| id == 1).is_none());
+    assert_eq!(stream.into_inner().collect::<Vec<_>>(), vec![0]);
+}
+
+#[test]
+fn test_relation_concurrency() {
+    let relation = MemoryRelation::new();
+    relation.extend(TestOpaque { next: 0, skip: 0 }, false);
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..1000 {
+                    let mut guard = relation.write(0, false);
+                    guard.get_opaque_mut().next += 1;
+                    drop(guard);
+                    relation.extend(TestOpaque { next: 0, skip: 0 }, false);
+                }
+            });
+        }
+    });
+    assert_eq!(relation.read(0).get_opaque().next, 4000);
+    assert_eq!(relation.len(), 4001);
+}
@@ -13,6 +13,7 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 use crate::fetch::Fetch;
+use std::iter::Peekable;
 use std::ops::{Deref, DerefMut};
 use zerocopy::{FromBytes, IntoBytes};
 
@@ -91,6 +92,14 @@ pub trait RelationWrite: RelationWriteTypes {
     fn search(&self, freespace: usize) -> Option<Self::WriteGuard<'_>>;
 }
 
+pub trait RelationTruncate: Relation {
+    /// Locks the relation exclusively, calls `f` with the number of pages and
+    /// truncates the relation to the number of pages that `f` returns.
+    ///
+    /// Returns `None` without calling `f` if the relation is in use.
+    fn truncate(&self, f: impl FnOnce(u32) -> u32) -> Option<u32>;
+}
+
 pub trait RelationPrefetch: Relation {
     fn prefetch(&self, id: u32);
 }
@@ -134,3 +143,92 @@ pub trait RelationReadStream: RelationReadStreamTypes {
     where
         I::Item: Fetch<'b>;
 }
+
+/// A read stream that reads pages one by one, for relations that do not
+/// benefit from read-ahead.
+pub struct PlainReadStream<'b, R, I: Iterator> {
+    relation: &'b R,
+    iter: Peekable<I>,
+}
+
+impl<'b, R, I: Iterator> PlainReadStream<'b, R, I> {
+    #[inline]
+    pub fn new(relation: &'b R, iter: I) -> Self {
+        Self {
+            relation,
+            iter: iter.peekable(),
+        }
+    }
+}
+
+pub struct PlainReadStreamGuards<'b, R, L> {
+    relation: &'b R,
+    list: L,
+}
+
+impl<'b, R: RelationRead, L: Iterator<Item = u32>> Iterator for PlainReadStreamGuards<'b, R, L> {
+    type Item = R::ReadGuard<'b>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.list.next()?;
+        Some(self.relation.read(id))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.list.size_hint()
+    }
+}
+
+impl<R: RelationRead, L: ExactSizeIterator<Item = u32>> ExactSizeIterator
+    for PlainReadStreamGuards<'_, R, L>
+{
+}
+
+impl<'b, R: RelationRead, I: Iterator> ReadStream<'b> for PlainReadStream<'b, R, I>
+where
+    I::Item: Fetch<'b>,
+{
+    type Relation = R;
+
+    type Guards = PlainReadStreamGuards<'b, R, <I::Item as Fetch<'b>>::Iter>;
+
+    type Item = I::Item;
+
+    type Inner = Peekable<I>;
+
+    #[inline]
+    fn next(&mut self) -> Option<(I::Item, Self::Guards)> {
+        let e = self.iter.next()?;
+        let list = e.fetch();
+        Some((
+            e,
+            PlainReadStreamGuards {
+                relation: self.relation,
+                list,
+            },
+        ))
+    }
+
+    #[inline]
+    fn next_if<P: FnOnce(&I::Item) -> bool>(
+        &mut self,
+        predicate: P,
+    ) -> Option<(I::Item, Self::Guards)> {
+        let e = self.iter.next_if(predicate)?;
+        let list = e.fetch();
+        Some((
+            e,
+            PlainReadStreamGuards {
+                relation: self.relation,
+                list,
+            },
+        ))
+    }
+
+    #[inline]
+    fn into_inner(self) -> Self::Inner {
+        self.iter
+    }
+}
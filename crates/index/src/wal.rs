@@ -0,0 +1,361 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+//! Logical records of page modifications.
+//!
+//! A page reports every operation performed on it to a [`Recorder`], and the
+//! recorded operations are replayed by [`apply`] through the [`Page`] trait,
+//! without knowing the access method of the page.
+
+use crate::relation::Page;
+use zerocopy::{FromBytes, IntoBytes};
+
+#[cfg(test)]
+use crate::memory::MemoryPage;
+#[cfg(test)]
+use zerocopy::{Immutable, KnownLayout};
+
+const OP_CLEAR: u8 = 0;
+const OP_OPAQUE: u8 = 1;
+const OP_FREE: u8 = 2;
+const OP_ALLOC: u8 = 3;
+const OP_UPDATE: u8 = 4;
+
+const NAMES: [&str; 5] = ["clear", "opaque", "free", "alloc", "update"];
+
+// An update operation costs 7 bytes, so close changed ranges are merged.
+const GAP: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Record {
+    /// The page is modified by the operations.
+    Modify(Vec<u8>),
+    /// The page is initialized by the operations.
+    Init(Vec<u8>),
+    /// The page is overwritten as a whole, so only an image of it describes
+    /// the modification.
+    Image,
+}
+
+#[derive(Debug)]
+enum Pending {
+    Tuple(u16, Vec<u8>),
+    Opaque(Vec<u8>),
+}
+
+/// Records operations on a page, in the order they are performed.
+///
+/// Tuples and opaques modified through mutable references are compared with
+/// their copies taken when the references are returned. Since a page is not
+/// accessible while such a reference is alive, the comparison is made at the
+/// next operation or at [`Recorder::finish`].
+#[derive(Debug, Default)]
+pub struct Recorder {
+    data: Vec<u8>,
+    image: bool,
+    pending: Option<Pending>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Called after the page is cleared.
+    pub fn clear<P: Page>(&mut self, page: &P) {
+        self.pending = None;
+        self.data.clear();
+        self.data.push(OP_CLEAR);
+        self.data.extend_from_slice(&opaque_bytes(page));
+    }
+    /// Called after a tuple is allocated.
+    pub fn alloc<P: Page>(&mut self, page: &P, i: u16) {
+        self.flush(page);
+        let tuple = page.get(i).expect("no such tuple");
+        self.data.push(OP_ALLOC);
+        self.data.extend_from_slice(&i.to_le_bytes());
+        self.data
+            .extend_from_slice(&(tuple.len() as u16).to_le_bytes());
+        self.data.extend_from_slice(tuple);
+    }
+    /// Called before a tuple is freed.
+    pub fn free<P: Page>(&mut self, page: &P, i: u16) {
+        self.flush(page);
+        if page.get(i).is_some() {
+            self.data.push(OP_FREE);
+            self.data.extend_from_slice(&i.to_le_bytes());
+        }
+    }
+    /// Called before a mutable reference to a tuple is returned.
+    pub fn get_mut<P: Page>(&mut self, page: &P, i: u16) {
+        self.flush(page);
+        if let Some(tuple) = page.get(i) {
+            self.pending = Some(Pending::Tuple(i, tuple.to_vec()));
+        }
+    }
+    /// Called before a mutable reference to the opaque is returned.
+    pub fn get_opaque_mut<P: Page>(&mut self, page: &P) {
+        self.flush(page);
+        self.pending = Some(Pending::Opaque(opaque_bytes(page)));
+    }
+    /// Called after the page is overwritten without its operations.
+    pub fn image(&mut self) {
+        self.pending = None;
+        self.image = true;
+    }
+    /// Returns the record of the modification, or `None` if the page is not
+    /// modified.
+    pub fn finish<P: Page>(mut self, page: &P) -> Option<Record> {
+        self.flush(page);
+        if self.image {
+            Some(Record::Image)
+        } else if self.data.is_empty() {
+            None
+        } else if self.data[0] == OP_CLEAR {
+            Some(Record::Init(self.data))
+        } else {
+            Some(Record::Modify(self.data))
+        }
+    }
+    fn flush<P: Page>(&mut self, page: &P) {
+        match self.pending.take() {
+            None => (),
+            Some(Pending::Tuple(i, before)) => {
+                let after = page.get(i).expect("no such tuple");
+                assert_eq!(before.len(), after.len(), "tuple is resized");
+                let mut j = 0;
+                while j < before.len() {
+                    if before[j] == after[j] {
+                        j += 1;
+                        continue;
+                    }
+                    let start = j;
+                    let mut end = j + 1;
+                    let mut k = end;
+                    while k < before.len() && k - end < GAP {
+                        if before[k] != after[k] {
+                            end = k + 1;
+                        }
+                        k += 1;
+                    }
+                    self.data.push(OP_UPDATE);
+                    self.data.extend_from_slice(&i.to_le_bytes());
+                    self.data.extend_from_slice(&(start as u16).to_le_bytes());
+                    self.data
+                        .extend_from_slice(&((end - start) as u16).to_le_bytes());
+                    self.data.extend_from_slice(&after[start..end]);
+                    j = k;
+                }
+            }
+            Some(Pending::Opaque(before)) => {
+                let after = opaque_bytes(page);
+                if before != after {
+                    self.data.push(OP_OPAQUE);
+                    self.data.extend_from_slice(&after);
+                }
+            }
+        }
+    }
+}
+
+fn opaque_bytes<P: Page>(page: &P) -> Vec<u8> {
+    let mut opaque = *page.get_opaque();
+    opaque.as_mut_bytes().to_vec()
+}
+
+fn take<'a>(data: &mut &'a [u8], n: usize) -> Option<&'a [u8]> {
+    let (left, right) = data.split_at_checked(n)?;
+    *data = right;
+    Some(left)
+}
+
+fn take_u16(data: &mut &[u8]) -> Option<u16> {
+    take(data, 2).map(|x| u16::from_le_bytes([x[0], x[1]]))
+}
+
+fn step<P: Page>(page: &mut P, data: &mut &[u8]) -> Option<()> {
+    match take(data, 1)?[0] {
+        OP_CLEAR => {
+            let bytes = take(data, size_of::<P::Opaque>())?;
+            page.clear(P::Opaque::read_from_bytes(bytes).ok()?);
+        }
+        OP_OPAQUE => {
+            let bytes = take(data, size_of::<P::Opaque>())?;
+            page.get_opaque_mut().as_mut_bytes().copy_from_slice(bytes);
+        }
+        OP_FREE => {
+            let i = take_u16(data)?;
+            page.get(i)?;
+            page.free(i);
+        }
+        OP_ALLOC => {
+            let i = take_u16(data)?;
+            let len = take_u16(data)?;
+            let tuple = take(data, len as usize)?;
+            if page.alloc(tuple)? != i {
+                return None;
+            }
+        }
+        OP_UPDATE => {
+            let i = take_u16(data)?;
+            let offset = take_u16(data)? as usize;
+            let len = take_u16(data)? as usize;
+            let bytes = take(data, len)?;
+            page.get_mut(i)?
+                .get_mut(offset..offset + len)?
+                .copy_from_slice(bytes);
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Replays recorded operations on a page. Returns `false` if they are
+/// malformed or cannot be applied.
+#[must_use]
+pub fn apply<P: Page>(page: &mut P, mut data: &[u8]) -> bool {
+    while !data.is_empty() {
+        if step(page, &mut data).is_none() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Summarizes recorded operations as counts of each kind.
+pub fn describe(mut data: &[u8], size_of_opaque: usize) -> String {
+    let mut counts = [0_usize; NAMES.len()];
+    while let Some(&op) = data.first() {
+        let size = match op {
+            OP_CLEAR | OP_OPAQUE => 1 + size_of_opaque,
+            OP_FREE => 3,
+            OP_ALLOC if data.len() >= 5 => 5 + u16::from_le_bytes([data[3], data[4]]) as usize,
+            OP_UPDATE if data.len() >= 7 => 7 + u16::from_le_bytes([data[5], data[6]]) as usize,
+            _ => break,
+        };
+        counts[op as usize] += 1;
+        data = &data[std::cmp::min(size, data.len())..];
+    }
+    NAMES
+        .iter()
+        .zip(counts)
+        .filter(|(_, count)| *count != 0)
+        .map(|(name, count)| format!("{name} {count}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+#[repr(C, align(8))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout)]
+struct TestOpaque {
+    next: u32,
+    skip: u32,
+}
+
+#[cfg(test)]
+#[allow(unsafe_code)]
+unsafe impl crate::relation::Opaque for TestOpaque {}
+
+// Replays the record on a copy of the page before the modification.
+#[cfg(test)]
+fn replay(before: &MemoryPage<TestOpaque>, record: Option<Record>) -> MemoryPage<TestOpaque> {
+    let mut page = before.clone();
+    match record {
+        None => (),
+        Some(Record::Modify(data) | Record::Init(data)) => assert!(apply(&mut page, &data)),
+        Some(Record::Image) => panic!("unexpected image"),
+    }
+    page
+}
+
+#[test]
+fn test_record_and_apply() {
+    let mut page = MemoryPage::new(TestOpaque { next: 1, skip: 2 });
+    for i in 0..16_u8 {
+        let _ = page.alloc(&[i; 40]).unwrap();
+    }
+    let before = page.clone();
+    let mut recorder = Recorder::new();
+    recorder.free(&page, 3);
+    page.free(3);
+    recorder.free(&page, 7);
+    page.free(7);
+    recorder.get_mut(&page, 5);
+    page.get_mut(5).unwrap()[1] = 99;
+    recorder.get_mut(&page, 6);
+    page.get_mut(6).unwrap()[0..4].copy_from_slice(&[7, 7, 7, 7]);
+    page.get_mut(6).unwrap()[30] = 8;
+    let i = page.alloc(&[42; 24]).unwrap();
+    recorder.alloc(&page, i);
+    let j = page.alloc(&[43; 100]).unwrap();
+    recorder.alloc(&page, j);
+    recorder.get_opaque_mut(&page);
+    page.get_opaque_mut().skip = 3;
+    recorder.get_mut(&page, 1);
+    let _ = page.get_mut(1).unwrap();
+    let record = recorder.finish(&page);
+    let Some(Record::Modify(data)) = &record else {
+        panic!("unexpected record");
+    };
+    assert_eq!(
+        describe(data, size_of::<TestOpaque>()),
+        "opaque 1, free 2, alloc 2, update 3"
+    );
+    assert_eq!(replay(&before, record).as_bytes(), page.as_bytes());
+}
+
+#[test]
+fn test_record_init() {
+    let mut page = MemoryPage::new(TestOpaque { next: 1, skip: 2 });
+    for i in 0..4_u8 {
+        let _ = page.alloc(&[i; 16]).unwrap();
+    }
+    let before = page.clone();
+    let mut recorder = Recorder::new();
+    recorder.get_mut(&page, 2);
+    page.get_mut(2).unwrap()[0] = 1;
+    page.clear(TestOpaque { next: 5, skip: 6 });
+    recorder.clear(&page);
+    let i = page.alloc(&[9; 12]).unwrap();
+    recorder.alloc(&page, i);
+    let record = recorder.finish(&page);
+    let Some(Record::Init(data)) = &record else {
+        panic!("unexpected record");
+    };
+    assert_eq!(describe(data, size_of::<TestOpaque>()), "clear 1, alloc 1");
+    assert_eq!(replay(&before, record).as_bytes(), page.as_bytes());
+}
+
+#[test]
+fn test_record_nothing() {
+    let mut page = MemoryPage::new(TestOpaque { next: 1, skip: 2 });
+    let _ = page.alloc(&[1; 16]).unwrap();
+    let mut recorder = Recorder::new();
+    recorder.get_mut(&page, 1);
+    let _ = page.get_mut(1).unwrap();
+    recorder.get_opaque_mut(&page);
+    let _ = page.get_opaque_mut();
+    recorder.free(&page, 2);
+    assert_eq!(recorder.finish(&page), None);
+}
+
+#[test]
+fn test_apply_malformed() {
+    let mut page = MemoryPage::new(TestOpaque { next: 1, skip: 2 });
+    let _ = page.alloc(&[1; 16]).unwrap();
+    assert!(!apply(&mut page, &[OP_FREE, 2, 0]));
+    assert!(!apply(&mut page, &[OP_UPDATE, 1, 0, 12, 0, 8, 0]));
+    assert!(!apply(&mut page, &[OP_ALLOC, 5, 0, 1, 0, 0]));
+    assert!(!apply(&mut page, &[42]));
+}
@@ -115,3 +115,119 @@ pub mod binary {
         (rough,)
     }
 }
+
+/// Codes packed into bytes, lowest bits first, which are stored by `rabitq1`
+/// and `rabitq2` values.
+pub mod packed {
+    use crate::bits::Bits;
+    use crate::extended::CodeMetadata;
+
+    pub fn pack_code(bits: Bits, input: &[u8]) -> Vec<u8> {
+        let width = bits as usize;
+        input
+            .chunks(8 / width)
+            .map(|t| {
+                let mut result = 0_u8;
+                for (i, &x) in t.iter().enumerate() {
+                    result |= x << (i * width);
+                }
+                result
+            })
+            .collect()
+    }
+
+    pub fn unpack_code(bits: Bits, dim: u32, input: &[u8]) -> impl Iterator<Item = u8> {
+        let width = bits as usize;
+        let mask = (1_u8 << width) - 1;
+        input
+            .iter()
+            .flat_map(move |&x| (0..8 / width).map(move |i| (x >> (i * width)) & mask))
+            .take(dim as _)
+    }
+
+    pub fn accumulate(bits: Bits, x: &[u8], y: &[u8]) -> u32 {
+        assert_eq!(x.len(), y.len());
+        let (x_arrays, x_remainder) = x.as_chunks::<8>();
+        let (y_arrays, y_remainder) = y.as_chunks::<8>();
+        let (mut x_buffer, mut y_buffer) = ([0_u8; 8], [0_u8; 8]);
+        x_buffer[..x_remainder.len()].copy_from_slice(x_remainder);
+        y_buffer[..y_remainder.len()].copy_from_slice(y_remainder);
+        let words = std::iter::zip(x_arrays, y_arrays)
+            .chain(std::iter::once((&x_buffer, &y_buffer)))
+            .map(|(x, y)| (u64::from_le_bytes(*x), u64::from_le_bytes(*y)));
+        match bits {
+            Bits::_1 => words.map(|(x, y)| (x & y).count_ones()).sum(),
+            Bits::_2 => {
+                const LOW: u64 = 0x5555_5555_5555_5555;
+                words
+                    .map(|(x, y)| {
+                        let (x_0, x_1) = (x & LOW, (x >> 1) & LOW);
+                        let (y_0, y_1) = (y & LOW, (y >> 1) & LOW);
+                        (x_0 & y_0).count_ones()
+                            + (((x_0 & y_1).count_ones() + (x_1 & y_0).count_ones()) << 1)
+                            + ((x_1 & y_1).count_ones() << 2)
+                    })
+                    .sum()
+            }
+        }
+    }
+
+    pub fn half_process_dot(
+        bits: Bits,
+        dim: u32,
+        sum: u32,
+        lhs: CodeMetadata,
+        rhs: CodeMetadata,
+    ) -> (f32,) {
+        let rough = match bits {
+            Bits::_1 => crate::extended::half_process_dot::<1, 1>(dim, sum, lhs, rhs),
+            Bits::_2 => crate::extended::half_process_dot::<2, 2>(dim, sum, lhs, rhs),
+        };
+        (rough,)
+    }
+
+    pub fn half_process_l2s(
+        bits: Bits,
+        dim: u32,
+        sum: u32,
+        lhs: CodeMetadata,
+        rhs: CodeMetadata,
+    ) -> (f32,) {
+        let rough = match bits {
+            Bits::_1 => crate::extended::half_process_l2s::<1, 1>(dim, sum, lhs, rhs),
+            Bits::_2 => crate::extended::half_process_l2s::<2, 2>(dim, sum, lhs, rhs),
+        };
+        (rough,)
+    }
+
+    pub fn half_process_cos(
+        bits: Bits,
+        dim: u32,
+        sum: u32,
+        lhs: CodeMetadata,
+        rhs: CodeMetadata,
+    ) -> (f32,) {
+        let rough = match bits {
+            Bits::_1 => crate::extended::half_process_cos::<1, 1>(dim, sum, lhs, rhs),
+            Bits::_2 => crate::extended::half_process_cos::<2, 2>(dim, sum, lhs, rhs),
+        };
+        (rough,)
+    }
+}
+
+#[test]
+fn test_packed_accumulate() {
+    for bits in [Bits::_1, Bits::_2] {
+        let mask = (1_u8 << bits as u8) - 1;
+        for n in [1_usize, 7, 8, 63, 64, 65, 300] {
+            let x = (0..n).map(|i| (i * 7 + 3) as u8 & mask).collect::<Vec<_>>();
+            let y = (0..n).map(|i| (i * i + 1) as u8 & mask).collect::<Vec<_>>();
+            let expected = std::iter::zip(&x, &y)
+                .map(|(&x, &y)| x as u32 * y as u32)
+                .sum::<u32>();
+            let (p, q) = (packed::pack_code(bits, &x), packed::pack_code(bits, &y));
+            assert_eq!(packed::accumulate(bits, &p, &q), expected);
+            assert_eq!(packed::unpack_code(bits, n as _, &p).collect::<Vec<_>>(), x);
+        }
+    }
+}
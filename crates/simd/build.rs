@@ -23,6 +23,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     match target_arch.as_str() {
         "aarch64" => {
             let mut build = cc::Build::new();
+            build.file("./cshim/aarch64_bf16.c");
             build.file("./cshim/aarch64_byte.c");
             build.file("./cshim/aarch64_halfbyte.c");
             if target_endian == "little" {
@@ -0,0 +1,90 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+#if defined(__clang__)
+#if !(__clang_major__ >= 16)
+#error "Clang version must be at least 16."
+#endif
+#elif defined(__GNUC__)
+#if !(__GNUC__ >= 14)
+#error "GCC version must be at least 14."
+#endif
+#else
+#error "This file requires Clang or GCC."
+#endif
+
+#include <arm_neon.h>
+#include <stddef.h>
+#include <stdint.h>
+
+__attribute__((target("+bf16"))) float
+bf16_reduce_sum_of_xy_a2_bf16(size_t n, uint16_t *restrict a,
+                              uint16_t *restrict b) {
+  float32x4_t sum_0 = vdupq_n_f32(0.0f);
+  float32x4_t sum_1 = vdupq_n_f32(0.0f);
+  while (n >= 16) {
+    bfloat16x8_t x_0 = vreinterpretq_bf16_u16(vld1q_u16(a + 0));
+    bfloat16x8_t y_0 = vreinterpretq_bf16_u16(vld1q_u16(b + 0));
+    bfloat16x8_t x_1 = vreinterpretq_bf16_u16(vld1q_u16(a + 8));
+    bfloat16x8_t y_1 = vreinterpretq_bf16_u16(vld1q_u16(b + 8));
+    sum_0 = vbfdotq_f32(sum_0, x_0, y_0);
+    sum_1 = vbfdotq_f32(sum_1, x_1, y_1);
+    n -= 16, a += 16, b += 16;
+  }
+  if (n >= 8) {
+    bfloat16x8_t x_0 = vreinterpretq_bf16_u16(vld1q_u16(a));
+    bfloat16x8_t y_0 = vreinterpretq_bf16_u16(vld1q_u16(b));
+    sum_0 = vbfdotq_f32(sum_0, x_0, y_0);
+    n -= 8, a += 8, b += 8;
+  }
+  if (n > 0) {
+    uint16_t _a[8] = {}, _b[8] = {};
+    for (size_t i = 0; i < n; i += 1) {
+      _a[i] = a[i], _b[i] = b[i];
+    }
+    a = _a, b = _b;
+    bfloat16x8_t x_1 = vreinterpretq_bf16_u16(vld1q_u16(a));
+    bfloat16x8_t y_1 = vreinterpretq_bf16_u16(vld1q_u16(b));
+    sum_1 = vbfdotq_f32(sum_1, x_1, y_1);
+  }
+  return vaddvq_f32(vaddq_f32(sum_0, sum_1));
+}
+
+__attribute__((target("+bf16"))) float
+bf16_reduce_sum_of_x2_a2_bf16(size_t n, uint16_t *restrict a) {
+  float32x4_t sum_0 = vdupq_n_f32(0.0f);
+  float32x4_t sum_1 = vdupq_n_f32(0.0f);
+  while (n >= 16) {
+    bfloat16x8_t x_0 = vreinterpretq_bf16_u16(vld1q_u16(a + 0));
+    bfloat16x8_t x_1 = vreinterpretq_bf16_u16(vld1q_u16(a + 8));
+    sum_0 = vbfdotq_f32(sum_0, x_0, x_0);
+    sum_1 = vbfdotq_f32(sum_1, x_1, x_1);
+    n -= 16, a += 16;
+  }
+  if (n >= 8) {
+    bfloat16x8_t x_0 = vreinterpretq_bf16_u16(vld1q_u16(a));
+    sum_0 = vbfdotq_f32(sum_0, x_0, x_0);
+    n -= 8, a += 8;
+  }
+  if (n > 0) {
+    uint16_t _a[8] = {};
+    for (size_t i = 0; i < n; i += 1) {
+      _a[i] = a[i];
+    }
+    a = _a;
+    bfloat16x8_t x_1 = vreinterpretq_bf16_u16(vld1q_u16(a));
+    sum_1 = vbfdotq_f32(sum_1, x_1, x_1);
+  }
+  return vaddvq_f32(vaddq_f32(sum_0, sum_1));
+}
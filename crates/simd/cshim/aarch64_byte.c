@@ -50,3 +50,51 @@ byte_reduce_sum_of_xy_a2_dotprod(size_t n, uint8_t *restrict a,
   }
   return vaddvq_u32(sum);
 }
+
+__attribute__((target("+dotprod"))) int32_t
+byte_reduce_sum_of_xy_i8_a2_dotprod(size_t n, int8_t *restrict a,
+                                    int8_t *restrict b) {
+  int32x4_t sum = vdupq_n_s32(0);
+  while (n >= 16) {
+    int8x16_t x = vld1q_s8(a);
+    int8x16_t y = vld1q_s8(b);
+    sum = vdotq_s32(sum, x, y);
+    n -= 16, a += 16, b += 16;
+  }
+  if (n > 0) {
+    int8_t _a[16] = {}, _b[16] = {};
+    for (size_t i = 0; i < n; i += 1) {
+      _a[i] = a[i], _b[i] = b[i];
+    }
+    a = _a, b = _b;
+    int8x16_t x = vld1q_s8(a);
+    int8x16_t y = vld1q_s8(b);
+    sum = vdotq_s32(sum, x, y);
+  }
+  return vaddvq_s32(sum);
+}
+
+__attribute__((target("+dotprod"))) uint32_t
+byte_reduce_sum_of_d2_i8_a2_dotprod(size_t n, int8_t *restrict a,
+                                    int8_t *restrict b) {
+  uint32x4_t sum = vdupq_n_u32(0);
+  while (n >= 16) {
+    int8x16_t x = vld1q_s8(a);
+    int8x16_t y = vld1q_s8(b);
+    uint8x16_t d = vreinterpretq_u8_s8(vabdq_s8(x, y));
+    sum = vdotq_u32(sum, d, d);
+    n -= 16, a += 16, b += 16;
+  }
+  if (n > 0) {
+    int8_t _a[16] = {}, _b[16] = {};
+    for (size_t i = 0; i < n; i += 1) {
+      _a[i] = a[i], _b[i] = b[i];
+    }
+    a = _a, b = _b;
+    int8x16_t x = vld1q_s8(a);
+    int8x16_t y = vld1q_s8(b);
+    uint8x16_t d = vreinterpretq_u8_s8(vabdq_s8(x, y));
+    sum = vdotq_u32(sum, d, d);
+  }
+  return vaddvq_u32(sum);
+}
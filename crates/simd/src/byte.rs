@@ -609,3 +609,637 @@ mod reduce_sum_of_x {
 pub fn reduce_sum_of_x(vector: &[u8]) -> u32 {
     reduce_sum_of_x::reduce_sum_of_x(vector)
 }
+
+#[cfg_attr(feature = "internal", simd_macros::public)]
+mod reduce_sum_of_xy_i8 {
+    // `dpbusd` multiplies unsigned bytes by signed bytes, so `x` is offset
+    // by 128 and the offset is subtracted with the sum of `y`.
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v4")]
+    #[target_feature(enable = "avx512vnni")]
+    fn reduce_sum_of_xy_i8_v4_avx512vnni(lhs: &[i8], rhs: &[i8]) -> i32 {
+        use core::arch::x86_64::*;
+        assert_eq!(lhs.len(), rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let sign = _mm512_set1_epi8(-128);
+        let i8_1 = _mm512_set1_epi8(1);
+        let mut _0 = _mm512_setzero_si512();
+        let mut _1 = _mm512_setzero_si512();
+        while n >= 64 {
+            let x = unsafe { _mm512_loadu_epi8(a.cast()) };
+            let y = unsafe { _mm512_loadu_epi8(b.cast()) };
+            _0 = _mm512_dpbusd_epi32(_0, _mm512_xor_si512(sign, x), y);
+            _1 = _mm512_dpbusd_epi32(_1, i8_1, y);
+            (n, a, b) = unsafe { (n - 64, a.add(64), b.add(64)) };
+        }
+        if n > 0 {
+            let mask = _bzhi_u64(0xffffffffffffffff, n as u32);
+            let x = unsafe { _mm512_maskz_loadu_epi8(mask, a.cast()) };
+            let y = unsafe { _mm512_maskz_loadu_epi8(mask, b.cast()) };
+            _0 = _mm512_dpbusd_epi32(_0, _mm512_xor_si512(sign, x), y);
+            _1 = _mm512_dpbusd_epi32(_1, i8_1, y);
+        }
+        _mm512_reduce_add_epi32(_mm512_sub_epi32(_0, _mm512_slli_epi32(_1, 7)))
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_xy_i8_v4_avx512vnni_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("v4") || !crate::is_feature_detected!("avx512vnni") {
+            println!("test {} ... skipped (v4:avx512vnni)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_i8_v4_avx512vnni(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v4")]
+    fn reduce_sum_of_xy_i8_v4(lhs: &[i8], rhs: &[i8]) -> i32 {
+        use core::arch::x86_64::*;
+        assert_eq!(lhs.len(), rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = _mm512_setzero_si512();
+        while n >= 32 {
+            let x = _mm512_cvtepi8_epi16(unsafe { _mm256_loadu_si256(a.cast()) });
+            let y = _mm512_cvtepi8_epi16(unsafe { _mm256_loadu_si256(b.cast()) });
+            sum = _mm512_add_epi32(sum, _mm512_madd_epi16(x, y));
+            (n, a, b) = unsafe { (n - 32, a.add(32), b.add(32)) };
+        }
+        if n > 0 {
+            let mask = _bzhi_u32(0xffffffff, n as u32);
+            let x = _mm512_cvtepi8_epi16(unsafe { _mm256_maskz_loadu_epi8(mask, a.cast()) });
+            let y = _mm512_cvtepi8_epi16(unsafe { _mm256_maskz_loadu_epi8(mask, b.cast()) });
+            sum = _mm512_add_epi32(sum, _mm512_madd_epi16(x, y));
+        }
+        _mm512_reduce_add_epi32(sum)
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_xy_i8_v4_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("v4") {
+            println!("test {} ... skipped (v4)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_i8_v4(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v3")]
+    fn reduce_sum_of_xy_i8_v3(lhs: &[i8], rhs: &[i8]) -> i32 {
+        use crate::emulate::{emulate_mm256_reduce_add_epi32, partial_load};
+        use core::arch::x86_64::*;
+        assert_eq!(lhs.len(), rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = _mm256_setzero_si256();
+        while n >= 16 {
+            let x = _mm256_cvtepi8_epi16(unsafe { _mm_loadu_si128(a.cast()) });
+            let y = _mm256_cvtepi8_epi16(unsafe { _mm_loadu_si128(b.cast()) });
+            sum = _mm256_add_epi32(sum, _mm256_madd_epi16(x, y));
+            (n, a, b) = unsafe { (n - 16, a.add(16), b.add(16)) };
+        }
+        if n > 0 {
+            let (_a, _b) = unsafe { partial_load!(16, n, a, b) };
+            (a, b) = (_a.as_ptr(), _b.as_ptr());
+            let x = _mm256_cvtepi8_epi16(unsafe { _mm_loadu_si128(a.cast()) });
+            let y = _mm256_cvtepi8_epi16(unsafe { _mm_loadu_si128(b.cast()) });
+            sum = _mm256_add_epi32(sum, _mm256_madd_epi16(x, y));
+        }
+        emulate_mm256_reduce_add_epi32(sum)
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    fn reduce_sum_of_xy_i8_v3_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("v3") {
+            println!("test {} ... skipped (v3)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_i8_v3(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v2")]
+    fn reduce_sum_of_xy_i8_v2(lhs: &[i8], rhs: &[i8]) -> i32 {
+        use crate::emulate::{emulate_mm_reduce_add_epi32, partial_load};
+        use core::arch::x86_64::*;
+        assert_eq!(lhs.len(), rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = _mm_setzero_si128();
+        while n >= 8 {
+            let x = _mm_cvtepi8_epi16(unsafe { _mm_loadl_epi64(a.cast()) });
+            let y = _mm_cvtepi8_epi16(unsafe { _mm_loadl_epi64(b.cast()) });
+            sum = _mm_add_epi32(sum, _mm_madd_epi16(x, y));
+            (n, a, b) = unsafe { (n - 8, a.add(8), b.add(8)) };
+        }
+        if n > 0 {
+            let (_a, _b) = unsafe { partial_load!(8, n, a, b) };
+            (a, b) = (_a.as_ptr(), _b.as_ptr());
+            let x = _mm_cvtepi8_epi16(unsafe { _mm_loadl_epi64(a.cast()) });
+            let y = _mm_cvtepi8_epi16(unsafe { _mm_loadl_epi64(b.cast()) });
+            sum = _mm_add_epi32(sum, _mm_madd_epi16(x, y));
+        }
+        emulate_mm_reduce_add_epi32(sum)
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    fn reduce_sum_of_xy_i8_v2_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("v2") {
+            println!("test {} ... skipped (v2)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_i8_v2(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[crate::target_cpu(enable = "a2")]
+    #[target_feature(enable = "dotprod")]
+    fn reduce_sum_of_xy_i8_a2_dotprod(lhs: &[i8], rhs: &[i8]) -> i32 {
+        unsafe extern "C" {
+            #[link_name = "byte_reduce_sum_of_xy_i8_a2_dotprod"]
+            unsafe fn f(n: usize, a: *const i8, b: *const i8) -> i32;
+        }
+        assert_eq!(lhs.len(), rhs.len());
+        let n = lhs.len();
+        let a = lhs.as_ptr();
+        let b = rhs.as_ptr();
+        unsafe { f(n, a, b) }
+    }
+
+    #[cfg(all(target_arch = "aarch64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_xy_i8_a2_dotprod_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("a2") || !crate::is_feature_detected!("dotprod") {
+            println!("test {} ... skipped (a2:dotprod)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_i8_a2_dotprod(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[crate::target_cpu(enable = "a2")]
+    fn reduce_sum_of_xy_i8_a2(lhs: &[i8], rhs: &[i8]) -> i32 {
+        use crate::emulate::partial_load;
+        use core::arch::aarch64::*;
+        assert_eq!(lhs.len(), rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut _0 = vdupq_n_s32(0);
+        let mut _1 = vdupq_n_s32(0);
+        while n >= 16 {
+            let x = unsafe { vld1q_s8(a.cast()) };
+            let y = unsafe { vld1q_s8(b.cast()) };
+            _0 = vpadalq_s16(_0, vmull_s8(vget_low_s8(x), vget_low_s8(y)));
+            _1 = vpadalq_s16(_1, vmull_s8(vget_high_s8(x), vget_high_s8(y)));
+            (n, a, b) = unsafe { (n - 16, a.add(16), b.add(16)) };
+        }
+        if n > 0 {
+            let (_a, _b) = unsafe { partial_load!(16, n, a, b) };
+            (a, b) = (_a.as_ptr(), _b.as_ptr());
+            let x = unsafe { vld1q_s8(a.cast()) };
+            let y = unsafe { vld1q_s8(b.cast()) };
+            _0 = vpadalq_s16(_0, vmull_s8(vget_low_s8(x), vget_low_s8(y)));
+            _1 = vpadalq_s16(_1, vmull_s8(vget_high_s8(x), vget_high_s8(y)));
+        }
+        vaddvq_s32(vaddq_s32(_0, _1))
+    }
+
+    #[cfg(all(target_arch = "aarch64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_xy_i8_a2_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("a2") {
+            println!("test {} ... skipped (a2)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_i8_a2(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[crate::multiversion(@"v4:avx512vnni", @"v4", @"v3", @"v2", @"a2:dotprod", @"a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1")]
+    pub fn reduce_sum_of_xy_i8(s: &[i8], t: &[i8]) -> i32 {
+        assert_eq!(s.len(), t.len());
+        let n = s.len();
+        let mut result = 0;
+        for i in 0..n {
+            result += (s[i] as i32) * (t[i] as i32);
+        }
+        result
+    }
+}
+
+#[inline(always)]
+pub fn reduce_sum_of_xy_i8(s: &[i8], t: &[i8]) -> i32 {
+    reduce_sum_of_xy_i8::reduce_sum_of_xy_i8(s, t)
+}
+
+#[cfg_attr(feature = "internal", simd_macros::public)]
+mod reduce_sum_of_d2_i8 {
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v4")]
+    fn reduce_sum_of_d2_i8_v4(lhs: &[i8], rhs: &[i8]) -> u32 {
+        use core::arch::x86_64::*;
+        assert_eq!(lhs.len(), rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = _mm512_setzero_si512();
+        while n >= 32 {
+            let x = _mm512_cvtepi8_epi16(unsafe { _mm256_loadu_si256(a.cast()) });
+            let y = _mm512_cvtepi8_epi16(unsafe { _mm256_loadu_si256(b.cast()) });
+            let d = _mm512_sub_epi16(x, y);
+            sum = _mm512_add_epi32(sum, _mm512_madd_epi16(d, d));
+            (n, a, b) = unsafe { (n - 32, a.add(32), b.add(32)) };
+        }
+        if n > 0 {
+            let mask = _bzhi_u32(0xffffffff, n as u32);
+            let x = _mm512_cvtepi8_epi16(unsafe { _mm256_maskz_loadu_epi8(mask, a.cast()) });
+            let y = _mm512_cvtepi8_epi16(unsafe { _mm256_maskz_loadu_epi8(mask, b.cast()) });
+            let d = _mm512_sub_epi16(x, y);
+            sum = _mm512_add_epi32(sum, _mm512_madd_epi16(d, d));
+        }
+        _mm512_reduce_add_epi32(sum) as u32
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_d2_i8_v4_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("v4") {
+            println!("test {} ... skipped (v4)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_d2_i8_v4(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v3")]
+    fn reduce_sum_of_d2_i8_v3(lhs: &[i8], rhs: &[i8]) -> u32 {
+        use crate::emulate::{emulate_mm256_reduce_add_epi32, partial_load};
+        use core::arch::x86_64::*;
+        assert_eq!(lhs.len(), rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = _mm256_setzero_si256();
+        while n >= 16 {
+            let x = _mm256_cvtepi8_epi16(unsafe { _mm_loadu_si128(a.cast()) });
+            let y = _mm256_cvtepi8_epi16(unsafe { _mm_loadu_si128(b.cast()) });
+            let d = _mm256_sub_epi16(x, y);
+            sum = _mm256_add_epi32(sum, _mm256_madd_epi16(d, d));
+            (n, a, b) = unsafe { (n - 16, a.add(16), b.add(16)) };
+        }
+        if n > 0 {
+            let (_a, _b) = unsafe { partial_load!(16, n, a, b) };
+            (a, b) = (_a.as_ptr(), _b.as_ptr());
+            let x = _mm256_cvtepi8_epi16(unsafe { _mm_loadu_si128(a.cast()) });
+            let y = _mm256_cvtepi8_epi16(unsafe { _mm_loadu_si128(b.cast()) });
+            let d = _mm256_sub_epi16(x, y);
+            sum = _mm256_add_epi32(sum, _mm256_madd_epi16(d, d));
+        }
+        emulate_mm256_reduce_add_epi32(sum) as u32
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    fn reduce_sum_of_d2_i8_v3_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("v3") {
+            println!("test {} ... skipped (v3)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_d2_i8_v3(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v2")]
+    fn reduce_sum_of_d2_i8_v2(lhs: &[i8], rhs: &[i8]) -> u32 {
+        use crate::emulate::{emulate_mm_reduce_add_epi32, partial_load};
+        use core::arch::x86_64::*;
+        assert_eq!(lhs.len(), rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = _mm_setzero_si128();
+        while n >= 8 {
+            let x = _mm_cvtepi8_epi16(unsafe { _mm_loadl_epi64(a.cast()) });
+            let y = _mm_cvtepi8_epi16(unsafe { _mm_loadl_epi64(b.cast()) });
+            let d = _mm_sub_epi16(x, y);
+            sum = _mm_add_epi32(sum, _mm_madd_epi16(d, d));
+            (n, a, b) = unsafe { (n - 8, a.add(8), b.add(8)) };
+        }
+        if n > 0 {
+            let (_a, _b) = unsafe { partial_load!(8, n, a, b) };
+            (a, b) = (_a.as_ptr(), _b.as_ptr());
+            let x = _mm_cvtepi8_epi16(unsafe { _mm_loadl_epi64(a.cast()) });
+            let y = _mm_cvtepi8_epi16(unsafe { _mm_loadl_epi64(b.cast()) });
+            let d = _mm_sub_epi16(x, y);
+            sum = _mm_add_epi32(sum, _mm_madd_epi16(d, d));
+        }
+        emulate_mm_reduce_add_epi32(sum) as u32
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    fn reduce_sum_of_d2_i8_v2_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("v2") {
+            println!("test {} ... skipped (v2)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_d2_i8_v2(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[crate::target_cpu(enable = "a2")]
+    #[target_feature(enable = "dotprod")]
+    fn reduce_sum_of_d2_i8_a2_dotprod(lhs: &[i8], rhs: &[i8]) -> u32 {
+        unsafe extern "C" {
+            #[link_name = "byte_reduce_sum_of_d2_i8_a2_dotprod"]
+            unsafe fn f(n: usize, a: *const i8, b: *const i8) -> u32;
+        }
+        assert_eq!(lhs.len(), rhs.len());
+        let n = lhs.len();
+        let a = lhs.as_ptr();
+        let b = rhs.as_ptr();
+        unsafe { f(n, a, b) }
+    }
+
+    #[cfg(all(target_arch = "aarch64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_d2_i8_a2_dotprod_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("a2") || !crate::is_feature_detected!("dotprod") {
+            println!("test {} ... skipped (a2:dotprod)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_d2_i8_a2_dotprod(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[crate::target_cpu(enable = "a2")]
+    fn reduce_sum_of_d2_i8_a2(lhs: &[i8], rhs: &[i8]) -> u32 {
+        use crate::emulate::partial_load;
+        use core::arch::aarch64::*;
+        assert_eq!(lhs.len(), rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut _0 = vdupq_n_u32(0);
+        let mut _1 = vdupq_n_u32(0);
+        while n >= 16 {
+            let x = unsafe { vld1q_s8(a.cast()) };
+            let y = unsafe { vld1q_s8(b.cast()) };
+            let d = vreinterpretq_u8_s8(vabdq_s8(x, y));
+            _0 = vpadalq_u16(_0, vmull_u8(vget_low_u8(d), vget_low_u8(d)));
+            _1 = vpadalq_u16(_1, vmull_u8(vget_high_u8(d), vget_high_u8(d)));
+            (n, a, b) = unsafe { (n - 16, a.add(16), b.add(16)) };
+        }
+        if n > 0 {
+            let (_a, _b) = unsafe { partial_load!(16, n, a, b) };
+            (a, b) = (_a.as_ptr(), _b.as_ptr());
+            let x = unsafe { vld1q_s8(a.cast()) };
+            let y = unsafe { vld1q_s8(b.cast()) };
+            let d = vreinterpretq_u8_s8(vabdq_s8(x, y));
+            _0 = vpadalq_u16(_0, vmull_u8(vget_low_u8(d), vget_low_u8(d)));
+            _1 = vpadalq_u16(_1, vmull_u8(vget_high_u8(d), vget_high_u8(d)));
+        }
+        vaddvq_u32(vaddq_u32(_0, _1))
+    }
+
+    #[cfg(all(target_arch = "aarch64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_d2_i8_a2_test() {
+        use rand::RngExt;
+        if !crate::is_cpu_detected!("a2") {
+            println!("test {} ... skipped (a2)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            let rhs = (0..n).map(|_| rng.random()).collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_d2_i8_a2(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    specialized == fallback,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[crate::multiversion(@"v4", @"v3", @"v2", @"a2:dotprod", @"a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1")]
+    pub fn reduce_sum_of_d2_i8(s: &[i8], t: &[i8]) -> u32 {
+        assert_eq!(s.len(), t.len());
+        let n = s.len();
+        let mut result = 0;
+        for i in 0..n {
+            let d = (s[i] as i32) - (t[i] as i32);
+            result += (d * d) as u32;
+        }
+        result
+    }
+}
+
+#[inline(always)]
+pub fn reduce_sum_of_d2_i8(s: &[i8], t: &[i8]) -> u32 {
+    reduce_sum_of_d2_i8::reduce_sum_of_d2_i8(s, t)
+}
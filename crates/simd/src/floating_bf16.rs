@@ -0,0 +1,1306 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::{Floating, bf16};
+
+impl Floating for bf16 {
+    #[inline(always)]
+    fn zero() -> Self {
+        bf16::ZERO
+    }
+
+    #[inline(always)]
+    fn infinity() -> Self {
+        bf16::INFINITY
+    }
+
+    #[inline(always)]
+    fn mask(self, m: bool) -> Self {
+        bf16::from_bits(self.to_bits() & (m as u16).wrapping_neg())
+    }
+
+    #[inline(always)]
+    fn scalar_neg(this: Self) -> Self {
+        -this
+    }
+
+    #[inline(always)]
+    fn scalar_add(lhs: Self, rhs: Self) -> Self {
+        lhs + rhs
+    }
+
+    #[inline(always)]
+    fn scalar_sub(lhs: Self, rhs: Self) -> Self {
+        lhs - rhs
+    }
+
+    #[inline(always)]
+    fn scalar_mul(lhs: Self, rhs: Self) -> Self {
+        lhs * rhs
+    }
+
+    #[inline(always)]
+    fn reduce_or_of_is_zero_x(this: &[bf16]) -> bool {
+        reduce_or_of_is_zero_x::reduce_or_of_is_zero_x(this)
+    }
+
+    #[inline(always)]
+    fn reduce_sum_of_x(this: &[bf16]) -> f32 {
+        reduce_sum_of_x::reduce_sum_of_x(this)
+    }
+
+    #[inline(always)]
+    fn reduce_sum_of_abs_x(this: &[bf16]) -> f32 {
+        reduce_sum_of_abs_x::reduce_sum_of_abs_x(this)
+    }
+
+    #[inline(always)]
+    fn reduce_sum_of_x2(this: &[bf16]) -> f32 {
+        reduce_sum_of_x2::reduce_sum_of_x2(this)
+    }
+
+    #[inline(always)]
+    fn reduce_min_max_of_x(this: &[bf16]) -> (f32, f32) {
+        reduce_min_max_of_x::reduce_min_max_of_x(this)
+    }
+
+    #[inline(always)]
+    fn reduce_sum_of_xy(lhs: &[Self], rhs: &[Self]) -> f32 {
+        reduce_sum_of_xy::reduce_sum_of_xy(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn reduce_sum_of_d2(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        reduce_sum_of_d2::reduce_sum_of_d2(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn reduce_sum_of_xy_sparse(lidx: &[u32], lval: &[bf16], ridx: &[u32], rval: &[bf16]) -> f32 {
+        reduce_sum_of_xy_sparse::reduce_sum_of_xy_sparse(lidx, lval, ridx, rval)
+    }
+
+    #[inline(always)]
+    fn reduce_sum_of_d2_sparse(lidx: &[u32], lval: &[bf16], ridx: &[u32], rval: &[bf16]) -> f32 {
+        reduce_sum_of_d2_sparse::reduce_sum_of_d2_sparse(lidx, lval, ridx, rval)
+    }
+
+    #[inline(always)]
+    fn reduce_sum_of_max_xy(lhs: &[&[bf16]], rhs: &[&[bf16]]) -> f32 {
+        reduce_sum_of_max_xy::reduce_sum_of_max_xy(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn vector_from_f32(this: &[f32]) -> Vec<Self> {
+        vector_from_f32::vector_from_f32(this)
+    }
+
+    #[inline(always)]
+    fn vector_to_f32(this: &[Self]) -> Vec<f32> {
+        vector_to_f32::vector_to_f32(this)
+    }
+
+    #[inline(always)]
+    fn vector_add(lhs: &[Self], rhs: &[Self]) -> Vec<Self> {
+        vector_add::vector_add(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn vector_add_inplace(lhs: &mut [Self], rhs: &[Self]) {
+        vector_add_inplace::vector_add_inplace(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn vector_sub(lhs: &[Self], rhs: &[Self]) -> Vec<Self> {
+        vector_sub::vector_sub(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn vector_mul(lhs: &[Self], rhs: &[Self]) -> Vec<Self> {
+        vector_mul::vector_mul(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn vector_mul_scalar(lhs: &[Self], rhs: f32) -> Vec<Self> {
+        vector_mul_scalar::vector_mul_scalar(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn vector_mul_scalar_inplace(lhs: &mut [Self], rhs: f32) {
+        vector_mul_scalar_inplace::vector_mul_scalar_inplace(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn vector_to_f32_borrowed(this: &[Self]) -> impl AsRef<[f32]> {
+        Self::vector_to_f32(this)
+    }
+
+    #[inline(always)]
+    fn vector_abs_inplace(this: &mut [Self]) {
+        vector_abs_inplace::vector_abs_inplace(this);
+    }
+}
+
+mod reduce_or_of_is_zero_x {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn reduce_or_of_is_zero_x(this: &[bf16]) -> bool {
+        for &x in this {
+            if x == bf16::ZERO {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+mod reduce_sum_of_x {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn reduce_sum_of_x(this: &[bf16]) -> f32 {
+        let n = this.len();
+        let mut sum = 0.0f32;
+        for i in 0..n {
+            sum += this[i].to_f32();
+        }
+        sum
+    }
+}
+
+mod reduce_sum_of_abs_x {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn reduce_sum_of_abs_x(this: &[bf16]) -> f32 {
+        let n = this.len();
+        let mut sum = 0.0f32;
+        for i in 0..n {
+            sum += this[i].to_f32().abs();
+        }
+        sum
+    }
+}
+
+mod reduce_sum_of_x2 {
+    use crate::bf16;
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v4")]
+    #[target_feature(enable = "avx512bf16")]
+    fn reduce_sum_of_x2_v4_avx512bf16(this: &[bf16]) -> f32 {
+        use core::arch::x86_64::*;
+        use std::mem::transmute;
+        let mut n = this.len();
+        let mut a = this.as_ptr();
+        let mut _0 = _mm512_setzero_ps();
+        let mut _1 = _mm512_setzero_ps();
+        while n >= 64 {
+            let x_0: __m512bh = unsafe { transmute(_mm512_loadu_epi16(a.add(0).cast())) };
+            let x_1: __m512bh = unsafe { transmute(_mm512_loadu_epi16(a.add(32).cast())) };
+            _0 = _mm512_dpbf16_ps(_0, x_0, x_0);
+            _1 = _mm512_dpbf16_ps(_1, x_1, x_1);
+            (n, a) = unsafe { (n - 64, a.add(64)) };
+        }
+        if n >= 32 {
+            let x_0: __m512bh = unsafe { transmute(_mm512_loadu_epi16(a.add(0).cast())) };
+            _0 = _mm512_dpbf16_ps(_0, x_0, x_0);
+            (n, a) = unsafe { (n - 32, a.add(32)) };
+        }
+        if n > 0 {
+            let mask = _bzhi_u32(0xffffffff, n as u32);
+            let x_1: __m512bh = unsafe { transmute(_mm512_maskz_loadu_epi16(mask, a.cast())) };
+            _1 = _mm512_dpbf16_ps(_1, x_1, x_1);
+        }
+        _mm512_reduce_add_ps(_mm512_add_ps(_0, _1))
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_x2_v4_avx512bf16_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("v4") || !crate::is_feature_detected!("avx512bf16") {
+            println!("test {} ... skipped (v4:avx512bf16)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let this = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let this = &this[..z];
+                let specialized = unsafe { reduce_sum_of_x2_v4_avx512bf16(this) };
+                let fallback = fallback(this);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v4")]
+    fn reduce_sum_of_x2_v4(this: &[bf16]) -> f32 {
+        use core::arch::x86_64::*;
+        let mut n = this.len();
+        let mut a = this.as_ptr();
+        let mut sum = _mm512_setzero_ps();
+        while n >= 16 {
+            let x = unsafe { _mm512_cvtepu16_epi32(_mm256_loadu_epi16(a.cast())) };
+            let x = _mm512_castsi512_ps(_mm512_slli_epi32::<16>(x));
+            sum = _mm512_fmadd_ps(x, x, sum);
+            (n, a) = unsafe { (n - 16, a.add(16)) };
+        }
+        if n > 0 {
+            let mask = _bzhi_u32(0xffff, n as u32) as u16;
+            let x = unsafe { _mm512_cvtepu16_epi32(_mm256_maskz_loadu_epi16(mask, a.cast())) };
+            let x = _mm512_castsi512_ps(_mm512_slli_epi32::<16>(x));
+            sum = _mm512_fmadd_ps(x, x, sum);
+        }
+        _mm512_reduce_add_ps(sum)
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_x2_v4_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("v4") {
+            println!("test {} ... skipped (v4)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let this = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let this = &this[..z];
+                let specialized = unsafe { reduce_sum_of_x2_v4(this) };
+                let fallback = fallback(this);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v3")]
+    fn reduce_sum_of_x2_v3(this: &[bf16]) -> f32 {
+        use crate::emulate::{emulate_mm256_reduce_add_ps, partial_load};
+        use core::arch::x86_64::*;
+        let mut n = this.len();
+        let mut a = this.as_ptr();
+        let mut sum = _mm256_setzero_ps();
+        while n >= 8 {
+            let x = unsafe { _mm256_cvtepu16_epi32(_mm_loadu_si128(a.cast())) };
+            let x = _mm256_castsi256_ps(_mm256_slli_epi32::<16>(x));
+            sum = _mm256_fmadd_ps(x, x, sum);
+            (n, a) = unsafe { (n - 8, a.add(8)) };
+        }
+        if n > 0 {
+            let (_a,) = unsafe { partial_load!(8, n, a) };
+            a = _a.as_ptr();
+            let x = unsafe { _mm256_cvtepu16_epi32(_mm_loadu_si128(a.cast())) };
+            let x = _mm256_castsi256_ps(_mm256_slli_epi32::<16>(x));
+            sum = _mm256_fmadd_ps(x, x, sum);
+        }
+        emulate_mm256_reduce_add_ps(sum)
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_x2_v3_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("v3") {
+            println!("test {} ... skipped (v3)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let this = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let this = &this[..z];
+                let specialized = unsafe { reduce_sum_of_x2_v3(this) };
+                let fallback = fallback(this);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[crate::target_cpu(enable = "a2")]
+    #[target_feature(enable = "bf16")]
+    fn reduce_sum_of_x2_a2_bf16(this: &[bf16]) -> f32 {
+        unsafe extern "C" {
+            #[link_name = "bf16_reduce_sum_of_x2_a2_bf16"]
+            unsafe fn f(n: usize, a: *const bf16) -> f32;
+        }
+        let n = this.len();
+        let a = this.as_ptr();
+        unsafe { f(n, a) }
+    }
+
+    #[cfg(all(target_arch = "aarch64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_x2_a2_bf16_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("a2") || !crate::is_feature_detected!("bf16") {
+            println!("test {} ... skipped (a2:bf16)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let this = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let this = &this[..z];
+                let specialized = unsafe { reduce_sum_of_x2_a2_bf16(this) };
+                let fallback = fallback(this);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[crate::target_cpu(enable = "a2")]
+    fn reduce_sum_of_x2_a2(this: &[bf16]) -> f32 {
+        use crate::emulate::partial_load;
+        use core::arch::aarch64::*;
+        let mut n = this.len();
+        let mut a = this.as_ptr();
+        let mut sum = vdupq_n_f32(0.0);
+        while n >= 4 {
+            let x = vreinterpretq_f32_u32(vshll_n_u16::<16>(unsafe { vld1_u16(a.cast()) }));
+            sum = vfmaq_f32(sum, x, x);
+            (n, a) = unsafe { (n - 4, a.add(4)) };
+        }
+        if n > 0 {
+            let (_a,) = unsafe { partial_load!(4, n, a) };
+            a = _a.as_ptr();
+            let x = vreinterpretq_f32_u32(vshll_n_u16::<16>(unsafe { vld1_u16(a.cast()) }));
+            sum = vfmaq_f32(sum, x, x);
+        }
+        vaddvq_f32(sum)
+    }
+
+    #[cfg(all(target_arch = "aarch64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_x2_a2_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("a2") {
+            println!("test {} ... skipped (a2)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let this = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let this = &this[..z];
+                let specialized = unsafe { reduce_sum_of_x2_a2(this) };
+                let fallback = fallback(this);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[crate::multiversion(@"v4:avx512bf16", @"v4", @"v3", @"a2:bf16", @"a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1")]
+    pub fn reduce_sum_of_x2(this: &[bf16]) -> f32 {
+        let n = this.len();
+        let mut sum = 0.0f32;
+        for i in 0..n {
+            sum += this[i].to_f32() * this[i].to_f32();
+        }
+        sum
+    }
+}
+
+mod reduce_min_max_of_x {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn reduce_min_max_of_x(this: &[bf16]) -> (f32, f32) {
+        let mut min = f32::INFINITY;
+        let mut max = f32::NEG_INFINITY;
+        let n = this.len();
+        for i in 0..n {
+            min = min.min(this[i].to_f32());
+            max = max.max(this[i].to_f32());
+        }
+        (min, max)
+    }
+}
+
+mod reduce_sum_of_xy {
+    use crate::bf16;
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v4")]
+    #[target_feature(enable = "avx512bf16")]
+    fn reduce_sum_of_xy_v4_avx512bf16(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        assert!(lhs.len() == rhs.len());
+        use core::arch::x86_64::*;
+        use std::mem::transmute;
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut _0 = _mm512_setzero_ps();
+        let mut _1 = _mm512_setzero_ps();
+        while n >= 64 {
+            let x_0: __m512bh = unsafe { transmute(_mm512_loadu_epi16(a.add(0).cast())) };
+            let y_0: __m512bh = unsafe { transmute(_mm512_loadu_epi16(b.add(0).cast())) };
+            let x_1: __m512bh = unsafe { transmute(_mm512_loadu_epi16(a.add(32).cast())) };
+            let y_1: __m512bh = unsafe { transmute(_mm512_loadu_epi16(b.add(32).cast())) };
+            _0 = _mm512_dpbf16_ps(_0, x_0, y_0);
+            _1 = _mm512_dpbf16_ps(_1, x_1, y_1);
+            (n, a, b) = unsafe { (n - 64, a.add(64), b.add(64)) };
+        }
+        if n >= 32 {
+            let x_0: __m512bh = unsafe { transmute(_mm512_loadu_epi16(a.add(0).cast())) };
+            let y_0: __m512bh = unsafe { transmute(_mm512_loadu_epi16(b.add(0).cast())) };
+            _0 = _mm512_dpbf16_ps(_0, x_0, y_0);
+            (n, a, b) = unsafe { (n - 32, a.add(32), b.add(32)) };
+        }
+        if n > 0 {
+            let mask = _bzhi_u32(0xffffffff, n as u32);
+            let x_1: __m512bh = unsafe { transmute(_mm512_maskz_loadu_epi16(mask, a.cast())) };
+            let y_1: __m512bh = unsafe { transmute(_mm512_maskz_loadu_epi16(mask, b.cast())) };
+            _1 = _mm512_dpbf16_ps(_1, x_1, y_1);
+        }
+        _mm512_reduce_add_ps(_mm512_add_ps(_0, _1))
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_xy_v4_avx512bf16_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("v4") || !crate::is_feature_detected!("avx512bf16") {
+            println!("test {} ... skipped (v4:avx512bf16)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            let rhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_v4_avx512bf16(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v4")]
+    fn reduce_sum_of_xy_v4(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        assert!(lhs.len() == rhs.len());
+        use core::arch::x86_64::*;
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = _mm512_setzero_ps();
+        while n >= 16 {
+            let x = unsafe { _mm512_cvtepu16_epi32(_mm256_loadu_epi16(a.cast())) };
+            let y = unsafe { _mm512_cvtepu16_epi32(_mm256_loadu_epi16(b.cast())) };
+            let x = _mm512_castsi512_ps(_mm512_slli_epi32::<16>(x));
+            let y = _mm512_castsi512_ps(_mm512_slli_epi32::<16>(y));
+            sum = _mm512_fmadd_ps(x, y, sum);
+            (n, a, b) = unsafe { (n - 16, a.add(16), b.add(16)) };
+        }
+        if n > 0 {
+            let mask = _bzhi_u32(0xffff, n as u32) as u16;
+            let x = unsafe { _mm512_cvtepu16_epi32(_mm256_maskz_loadu_epi16(mask, a.cast())) };
+            let y = unsafe { _mm512_cvtepu16_epi32(_mm256_maskz_loadu_epi16(mask, b.cast())) };
+            let x = _mm512_castsi512_ps(_mm512_slli_epi32::<16>(x));
+            let y = _mm512_castsi512_ps(_mm512_slli_epi32::<16>(y));
+            sum = _mm512_fmadd_ps(x, y, sum);
+        }
+        _mm512_reduce_add_ps(sum)
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_xy_v4_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("v4") {
+            println!("test {} ... skipped (v4)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            let rhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_v4(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v3")]
+    fn reduce_sum_of_xy_v3(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        use crate::emulate::{emulate_mm256_reduce_add_ps, partial_load};
+        use core::arch::x86_64::*;
+        assert!(lhs.len() == rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = _mm256_setzero_ps();
+        while n >= 8 {
+            let x = unsafe { _mm256_cvtepu16_epi32(_mm_loadu_si128(a.cast())) };
+            let y = unsafe { _mm256_cvtepu16_epi32(_mm_loadu_si128(b.cast())) };
+            let x = _mm256_castsi256_ps(_mm256_slli_epi32::<16>(x));
+            let y = _mm256_castsi256_ps(_mm256_slli_epi32::<16>(y));
+            sum = _mm256_fmadd_ps(x, y, sum);
+            (n, a, b) = unsafe { (n - 8, a.add(8), b.add(8)) };
+        }
+        if n > 0 {
+            let (_a, _b) = unsafe { partial_load!(8, n, a, b) };
+            (a, b) = (_a.as_ptr(), _b.as_ptr());
+            let x = unsafe { _mm256_cvtepu16_epi32(_mm_loadu_si128(a.cast())) };
+            let y = unsafe { _mm256_cvtepu16_epi32(_mm_loadu_si128(b.cast())) };
+            let x = _mm256_castsi256_ps(_mm256_slli_epi32::<16>(x));
+            let y = _mm256_castsi256_ps(_mm256_slli_epi32::<16>(y));
+            sum = _mm256_fmadd_ps(x, y, sum);
+        }
+        emulate_mm256_reduce_add_ps(sum)
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_xy_v3_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("v3") {
+            println!("test {} ... skipped (v3)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            let rhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_v3(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[crate::target_cpu(enable = "a2")]
+    #[target_feature(enable = "bf16")]
+    fn reduce_sum_of_xy_a2_bf16(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        unsafe extern "C" {
+            #[link_name = "bf16_reduce_sum_of_xy_a2_bf16"]
+            unsafe fn f(n: usize, a: *const bf16, b: *const bf16) -> f32;
+        }
+        assert!(lhs.len() == rhs.len());
+        let n = lhs.len();
+        let a = lhs.as_ptr();
+        let b = rhs.as_ptr();
+        unsafe { f(n, a, b) }
+    }
+
+    #[cfg(all(target_arch = "aarch64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_xy_a2_bf16_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("a2") || !crate::is_feature_detected!("bf16") {
+            println!("test {} ... skipped (a2:bf16)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            let rhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_a2_bf16(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[crate::target_cpu(enable = "a2")]
+    fn reduce_sum_of_xy_a2(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        use crate::emulate::partial_load;
+        use core::arch::aarch64::*;
+        assert!(lhs.len() == rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = vdupq_n_f32(0.0);
+        while n >= 4 {
+            let x = vreinterpretq_f32_u32(vshll_n_u16::<16>(unsafe { vld1_u16(a.cast()) }));
+            let y = vreinterpretq_f32_u32(vshll_n_u16::<16>(unsafe { vld1_u16(b.cast()) }));
+            sum = vfmaq_f32(sum, x, y);
+            (n, a, b) = unsafe { (n - 4, a.add(4), b.add(4)) };
+        }
+        if n > 0 {
+            let (_a, _b) = unsafe { partial_load!(4, n, a, b) };
+            (a, b) = (_a.as_ptr(), _b.as_ptr());
+            let x = vreinterpretq_f32_u32(vshll_n_u16::<16>(unsafe { vld1_u16(a.cast()) }));
+            let y = vreinterpretq_f32_u32(vshll_n_u16::<16>(unsafe { vld1_u16(b.cast()) }));
+            sum = vfmaq_f32(sum, x, y);
+        }
+        vaddvq_f32(sum)
+    }
+
+    #[cfg(all(target_arch = "aarch64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_xy_a2_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("a2") {
+            println!("test {} ... skipped (a2)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            let rhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_xy_a2(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[crate::multiversion(@"v4:avx512bf16", @"v4", @"v3", @"a2:bf16", @"a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1")]
+    pub fn reduce_sum_of_xy(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        assert!(lhs.len() == rhs.len());
+        let n = lhs.len();
+        let mut sum = 0.0f32;
+        for i in 0..n {
+            sum += lhs[i].to_f32() * rhs[i].to_f32();
+        }
+        sum
+    }
+}
+
+mod reduce_sum_of_d2 {
+    // There are no bf16 dot product paths here: the difference of two bf16
+    // values is not a bf16 value, so it is computed and squared in f32.
+
+    use crate::bf16;
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v4")]
+    fn reduce_sum_of_d2_v4(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        assert!(lhs.len() == rhs.len());
+        use core::arch::x86_64::*;
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = _mm512_setzero_ps();
+        while n >= 16 {
+            let x = unsafe { _mm512_cvtepu16_epi32(_mm256_loadu_epi16(a.cast())) };
+            let y = unsafe { _mm512_cvtepu16_epi32(_mm256_loadu_epi16(b.cast())) };
+            let x = _mm512_castsi512_ps(_mm512_slli_epi32::<16>(x));
+            let y = _mm512_castsi512_ps(_mm512_slli_epi32::<16>(y));
+            let d = _mm512_sub_ps(x, y);
+            sum = _mm512_fmadd_ps(d, d, sum);
+            (n, a, b) = unsafe { (n - 16, a.add(16), b.add(16)) };
+        }
+        if n > 0 {
+            let mask = _bzhi_u32(0xffff, n as u32) as u16;
+            let x = unsafe { _mm512_cvtepu16_epi32(_mm256_maskz_loadu_epi16(mask, a.cast())) };
+            let y = unsafe { _mm512_cvtepu16_epi32(_mm256_maskz_loadu_epi16(mask, b.cast())) };
+            let x = _mm512_castsi512_ps(_mm512_slli_epi32::<16>(x));
+            let y = _mm512_castsi512_ps(_mm512_slli_epi32::<16>(y));
+            let d = _mm512_sub_ps(x, y);
+            sum = _mm512_fmadd_ps(d, d, sum);
+        }
+        _mm512_reduce_add_ps(sum)
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_d2_v4_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("v4") {
+            println!("test {} ... skipped (v4)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            let rhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_d2_v4(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v3")]
+    fn reduce_sum_of_d2_v3(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        use crate::emulate::{emulate_mm256_reduce_add_ps, partial_load};
+        use core::arch::x86_64::*;
+        assert!(lhs.len() == rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = _mm256_setzero_ps();
+        while n >= 8 {
+            let x = unsafe { _mm256_cvtepu16_epi32(_mm_loadu_si128(a.cast())) };
+            let y = unsafe { _mm256_cvtepu16_epi32(_mm_loadu_si128(b.cast())) };
+            let x = _mm256_castsi256_ps(_mm256_slli_epi32::<16>(x));
+            let y = _mm256_castsi256_ps(_mm256_slli_epi32::<16>(y));
+            let d = _mm256_sub_ps(x, y);
+            sum = _mm256_fmadd_ps(d, d, sum);
+            (n, a, b) = unsafe { (n - 8, a.add(8), b.add(8)) };
+        }
+        if n > 0 {
+            let (_a, _b) = unsafe { partial_load!(8, n, a, b) };
+            (a, b) = (_a.as_ptr(), _b.as_ptr());
+            let x = unsafe { _mm256_cvtepu16_epi32(_mm_loadu_si128(a.cast())) };
+            let y = unsafe { _mm256_cvtepu16_epi32(_mm_loadu_si128(b.cast())) };
+            let x = _mm256_castsi256_ps(_mm256_slli_epi32::<16>(x));
+            let y = _mm256_castsi256_ps(_mm256_slli_epi32::<16>(y));
+            let d = _mm256_sub_ps(x, y);
+            sum = _mm256_fmadd_ps(d, d, sum);
+        }
+        emulate_mm256_reduce_add_ps(sum)
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_d2_v3_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("v3") {
+            println!("test {} ... skipped (v3)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            let rhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_d2_v3(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[crate::target_cpu(enable = "a2")]
+    fn reduce_sum_of_d2_a2(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        use crate::emulate::partial_load;
+        use core::arch::aarch64::*;
+        assert!(lhs.len() == rhs.len());
+        let mut n = lhs.len();
+        let mut a = lhs.as_ptr();
+        let mut b = rhs.as_ptr();
+        let mut sum = vdupq_n_f32(0.0);
+        while n >= 4 {
+            let x = vreinterpretq_f32_u32(vshll_n_u16::<16>(unsafe { vld1_u16(a.cast()) }));
+            let y = vreinterpretq_f32_u32(vshll_n_u16::<16>(unsafe { vld1_u16(b.cast()) }));
+            let d = vsubq_f32(x, y);
+            sum = vfmaq_f32(sum, d, d);
+            (n, a, b) = unsafe { (n - 4, a.add(4), b.add(4)) };
+        }
+        if n > 0 {
+            let (_a, _b) = unsafe { partial_load!(4, n, a, b) };
+            (a, b) = (_a.as_ptr(), _b.as_ptr());
+            let x = vreinterpretq_f32_u32(vshll_n_u16::<16>(unsafe { vld1_u16(a.cast()) }));
+            let y = vreinterpretq_f32_u32(vshll_n_u16::<16>(unsafe { vld1_u16(b.cast()) }));
+            let d = vsubq_f32(x, y);
+            sum = vfmaq_f32(sum, d, d);
+        }
+        vaddvq_f32(sum)
+    }
+
+    #[cfg(all(target_arch = "aarch64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_d2_a2_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 2.0;
+        if !crate::is_cpu_detected!("a2") {
+            println!("test {} ... skipped (a2)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let n = 4016;
+            let lhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            let rhs = (0..n)
+                .map(|_| bf16::from_f32(rng.random_range(-1.0..=1.0)))
+                .collect::<Vec<_>>();
+            for z in 3984..4016 {
+                let lhs = &lhs[..z];
+                let rhs = &rhs[..z];
+                let specialized = unsafe { reduce_sum_of_d2_a2(lhs, rhs) };
+                let fallback = fallback(lhs, rhs);
+                assert!(
+                    (specialized - fallback).abs() < EPSILON,
+                    "specialized = {specialized}, fallback = {fallback}."
+                );
+            }
+        }
+    }
+
+    #[crate::multiversion(@"v4", @"v3", @"a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1")]
+    pub fn reduce_sum_of_d2(lhs: &[bf16], rhs: &[bf16]) -> f32 {
+        assert!(lhs.len() == rhs.len());
+        let n = lhs.len();
+        let mut sum = 0.0f32;
+        for i in 0..n {
+            let d = lhs[i].to_f32() - rhs[i].to_f32();
+            sum += d * d;
+        }
+        sum
+    }
+}
+
+mod reduce_sum_of_xy_sparse {
+    // There is no manually-implemented SIMD version.
+
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn reduce_sum_of_xy_sparse(
+        lidx: &[u32],
+        lval: &[bf16],
+        ridx: &[u32],
+        rval: &[bf16],
+    ) -> f32 {
+        use std::cmp::Ordering;
+        assert_eq!(lidx.len(), lval.len());
+        assert_eq!(ridx.len(), rval.len());
+        let (mut lp, ln) = (0, lidx.len());
+        let (mut rp, rn) = (0, ridx.len());
+        let mut sum = 0.0f32;
+        while lp < ln && rp < rn {
+            match Ord::cmp(&lidx[lp], &ridx[rp]) {
+                Ordering::Equal => {
+                    sum += lval[lp].to_f32() * rval[rp].to_f32();
+                    lp += 1;
+                    rp += 1;
+                }
+                Ordering::Less => {
+                    lp += 1;
+                }
+                Ordering::Greater => {
+                    rp += 1;
+                }
+            }
+        }
+        sum
+    }
+}
+
+mod reduce_sum_of_d2_sparse {
+    // There is no manually-implemented SIMD version.
+
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn reduce_sum_of_d2_sparse(
+        lidx: &[u32],
+        lval: &[bf16],
+        ridx: &[u32],
+        rval: &[bf16],
+    ) -> f32 {
+        use std::cmp::Ordering;
+        assert_eq!(lidx.len(), lval.len());
+        assert_eq!(ridx.len(), rval.len());
+        let (mut lp, ln) = (0, lidx.len());
+        let (mut rp, rn) = (0, ridx.len());
+        let mut sum = 0.0f32;
+        while lp < ln && rp < rn {
+            match Ord::cmp(&lidx[lp], &ridx[rp]) {
+                Ordering::Equal => {
+                    let d = lval[lp].to_f32() - rval[rp].to_f32();
+                    sum += d * d;
+                    lp += 1;
+                    rp += 1;
+                }
+                Ordering::Less => {
+                    sum += lval[lp].to_f32() * lval[lp].to_f32();
+                    lp += 1;
+                }
+                Ordering::Greater => {
+                    sum += rval[rp].to_f32() * rval[rp].to_f32();
+                    rp += 1;
+                }
+            }
+        }
+        for i in lp..ln {
+            sum += lval[i].to_f32() * lval[i].to_f32();
+        }
+        for i in rp..rn {
+            sum += rval[i].to_f32() * rval[i].to_f32();
+        }
+        sum
+    }
+}
+
+mod reduce_sum_of_max_xy {
+    // There is no manually-implemented SIMD version. Each pair is computed by
+    // the dispatched `reduce_sum_of_xy`.
+
+    use crate::bf16;
+
+    pub fn reduce_sum_of_max_xy(lhs: &[&[bf16]], rhs: &[&[bf16]]) -> f32 {
+        let d = lhs.iter().chain(rhs).next().map_or(0, |x| x.len());
+        assert!(lhs.iter().chain(rhs).all(|x| x.len() == d));
+        let mut sum = 0.0f32;
+        for x in lhs {
+            let mut max = f32::NEG_INFINITY;
+            for y in rhs {
+                max = max.max(super::reduce_sum_of_xy::reduce_sum_of_xy(x, y));
+            }
+            sum += max;
+        }
+        sum
+    }
+}
+
+mod vector_add {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn vector_add(lhs: &[bf16], rhs: &[bf16]) -> Vec<bf16> {
+        assert_eq!(lhs.len(), rhs.len());
+        let n = lhs.len();
+        let mut r = Vec::<bf16>::with_capacity(n);
+        for i in 0..n {
+            unsafe {
+                r.as_mut_ptr().add(i).write(lhs[i] + rhs[i]);
+            }
+        }
+        unsafe {
+            r.set_len(n);
+        }
+        r
+    }
+}
+
+mod vector_add_inplace {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn vector_add_inplace(lhs: &mut [bf16], rhs: &[bf16]) {
+        assert_eq!(lhs.len(), rhs.len());
+        let n = lhs.len();
+        for i in 0..n {
+            lhs[i] += rhs[i];
+        }
+    }
+}
+
+mod vector_sub {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn vector_sub(lhs: &[bf16], rhs: &[bf16]) -> Vec<bf16> {
+        assert_eq!(lhs.len(), rhs.len());
+        let n = lhs.len();
+        let mut r = Vec::<bf16>::with_capacity(n);
+        for i in 0..n {
+            unsafe {
+                r.as_mut_ptr().add(i).write(lhs[i] - rhs[i]);
+            }
+        }
+        unsafe {
+            r.set_len(n);
+        }
+        r
+    }
+}
+
+mod vector_mul {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn vector_mul(lhs: &[bf16], rhs: &[bf16]) -> Vec<bf16> {
+        assert_eq!(lhs.len(), rhs.len());
+        let n = lhs.len();
+        let mut r = Vec::<bf16>::with_capacity(n);
+        for i in 0..n {
+            unsafe {
+                r.as_mut_ptr().add(i).write(lhs[i] * rhs[i]);
+            }
+        }
+        unsafe {
+            r.set_len(n);
+        }
+        r
+    }
+}
+
+mod vector_mul_scalar {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn vector_mul_scalar(lhs: &[bf16], rhs: f32) -> Vec<bf16> {
+        let rhs = bf16::from_f32(rhs);
+        let n = lhs.len();
+        let mut r = Vec::<bf16>::with_capacity(n);
+        for i in 0..n {
+            unsafe {
+                r.as_mut_ptr().add(i).write(lhs[i] * rhs);
+            }
+        }
+        unsafe {
+            r.set_len(n);
+        }
+        r
+    }
+}
+
+mod vector_mul_scalar_inplace {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn vector_mul_scalar_inplace(lhs: &mut [bf16], rhs: f32) {
+        let rhs = bf16::from_f32(rhs);
+        let n = lhs.len();
+        for i in 0..n {
+            lhs[i] *= rhs;
+        }
+    }
+}
+
+mod vector_abs_inplace {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn vector_abs_inplace(this: &mut [bf16]) {
+        let n = this.len();
+        for i in 0..n {
+            this[i] = bf16::from_f32(this[i].to_f32().abs());
+        }
+    }
+}
+
+mod vector_from_f32 {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn vector_from_f32(this: &[f32]) -> Vec<bf16> {
+        let n = this.len();
+        let mut r = Vec::<bf16>::with_capacity(n);
+        for i in 0..n {
+            unsafe {
+                r.as_mut_ptr().add(i).write(bf16::from_f32(this[i]));
+            }
+        }
+        unsafe {
+            r.set_len(n);
+        }
+        r
+    }
+}
+
+mod vector_to_f32 {
+    use crate::bf16;
+
+    #[crate::multiversion(
+        "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
+    )]
+    pub fn vector_to_f32(this: &[bf16]) -> Vec<f32> {
+        let n = this.len();
+        let mut r = Vec::<f32>::with_capacity(n);
+        for i in 0..n {
+            unsafe {
+                r.as_mut_ptr().add(i).write(this[i].to_f32());
+            }
+        }
+        unsafe {
+            r.set_len(n);
+        }
+        r
+    }
+}
@@ -95,6 +95,11 @@ impl Floating for f32 {
         reduce_sum_of_d2_sparse::reduce_sum_of_d2_sparse(lidx, lval, ridx, rval)
     }
 
+    #[inline(always)]
+    fn reduce_sum_of_max_xy(lhs: &[&[f32]], rhs: &[&[f32]]) -> f32 {
+        reduce_sum_of_max_xy::reduce_sum_of_max_xy(lhs, rhs)
+    }
+
     #[inline(always)]
     fn vector_add(lhs: &[f32], rhs: &[f32]) -> Vec<f32> {
         vector_add::vector_add(lhs, rhs)
@@ -1946,6 +1951,313 @@ mod reduce_sum_of_d2_sparse {
     }
 }
 
+#[cfg_attr(feature = "internal", simd_macros::public)]
+mod reduce_sum_of_max_xy {
+    // Rows are processed in register tiles. A tile that runs past the last row
+    // repeats the last row, which is harmless since `max` is idempotent.
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v4")]
+    fn reduce_sum_of_max_xy_v4(lhs: &[&[f32]], rhs: &[&[f32]]) -> f32 {
+        use core::arch::x86_64::*;
+        let (m, n) = (lhs.len(), rhs.len());
+        let d = lhs.iter().chain(rhs).next().map_or(0, |x| x.len());
+        assert!(lhs.iter().chain(rhs).all(|x| x.len() == d));
+        let mut maxs = vec![f32::NEG_INFINITY; m];
+        for i in (0..m).step_by(4) {
+            let p = [
+                i,
+                (i + 1).min(m - 1),
+                (i + 2).min(m - 1),
+                (i + 3).min(m - 1),
+            ];
+            for j in (0..n).step_by(4) {
+                let q = [
+                    j,
+                    (j + 1).min(n - 1),
+                    (j + 2).min(n - 1),
+                    (j + 3).min(n - 1),
+                ];
+                let a = p.map(|u| lhs[u].as_ptr());
+                let b = q.map(|v| rhs[v].as_ptr());
+                let mut sum = [[_mm512_setzero_ps(); 4]; 4];
+                let mut k = 0;
+                while k + 16 <= d {
+                    let mut x = [_mm512_setzero_ps(); 4];
+                    let mut y = [_mm512_setzero_ps(); 4];
+                    for u in 0..4 {
+                        x[u] = unsafe { _mm512_loadu_ps(a[u].add(k)) };
+                        y[u] = unsafe { _mm512_loadu_ps(b[u].add(k)) };
+                    }
+                    for u in 0..4 {
+                        for v in 0..4 {
+                            sum[u][v] = _mm512_fmadd_ps(x[u], y[v], sum[u][v]);
+                        }
+                    }
+                    k += 16;
+                }
+                if k < d {
+                    let mask = _bzhi_u32(0xffff, (d - k) as u32) as u16;
+                    let mut x = [_mm512_setzero_ps(); 4];
+                    let mut y = [_mm512_setzero_ps(); 4];
+                    for u in 0..4 {
+                        x[u] = unsafe { _mm512_maskz_loadu_ps(mask, a[u].add(k)) };
+                        y[u] = unsafe { _mm512_maskz_loadu_ps(mask, b[u].add(k)) };
+                    }
+                    for u in 0..4 {
+                        for v in 0..4 {
+                            sum[u][v] = _mm512_fmadd_ps(x[u], y[v], sum[u][v]);
+                        }
+                    }
+                }
+                for u in 0..4 {
+                    for v in 0..4 {
+                        maxs[p[u]] = maxs[p[u]].max(_mm512_reduce_add_ps(sum[u][v]));
+                    }
+                }
+            }
+        }
+        let mut sum = 0.0f32;
+        for max in maxs {
+            sum += max;
+        }
+        sum
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    fn reduce_sum_of_max_xy_v4_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 0.004;
+        if !crate::is_cpu_detected!("v4") {
+            println!("test {} ... skipped (v4)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let m = rng.random_range(0..=11);
+            let n = rng.random_range(0..=11);
+            let d = rng.random_range(96..=160);
+            let lhs = (0..m)
+                .map(|_| (0..d).map(|_| rng.random_range(-1.0..=1.0)).collect())
+                .collect::<Vec<Vec<_>>>();
+            let rhs = (0..n)
+                .map(|_| (0..d).map(|_| rng.random_range(-1.0..=1.0)).collect())
+                .collect::<Vec<Vec<_>>>();
+            let lhs = lhs.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let rhs = rhs.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let specialized = unsafe { reduce_sum_of_max_xy_v4(&lhs, &rhs) };
+            let fallback = fallback(&lhs, &rhs);
+            assert!(
+                specialized == fallback || (specialized - fallback).abs() < EPSILON,
+                "specialized = {specialized}, fallback = {fallback}."
+            );
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "x86_64")]
+    #[crate::target_cpu(enable = "v3")]
+    fn reduce_sum_of_max_xy_v3(lhs: &[&[f32]], rhs: &[&[f32]]) -> f32 {
+        use crate::emulate::emulate_mm256_reduce_add_ps;
+        use core::arch::x86_64::*;
+        let (m, n) = (lhs.len(), rhs.len());
+        let d = lhs.iter().chain(rhs).next().map_or(0, |x| x.len());
+        assert!(lhs.iter().chain(rhs).all(|x| x.len() == d));
+        let mut maxs = vec![f32::NEG_INFINITY; m];
+        for i in (0..m).step_by(4) {
+            let p = [
+                i,
+                (i + 1).min(m - 1),
+                (i + 2).min(m - 1),
+                (i + 3).min(m - 1),
+            ];
+            for j in (0..n).step_by(2) {
+                let q = [j, (j + 1).min(n - 1)];
+                let a = p.map(|u| lhs[u].as_ptr());
+                let b = q.map(|v| rhs[v].as_ptr());
+                let mut sum = [[_mm256_setzero_ps(); 2]; 4];
+                let mut k = 0;
+                while k + 8 <= d {
+                    let mut x = [_mm256_setzero_ps(); 4];
+                    let mut y = [_mm256_setzero_ps(); 2];
+                    for u in 0..4 {
+                        x[u] = unsafe { _mm256_loadu_ps(a[u].add(k)) };
+                    }
+                    for v in 0..2 {
+                        y[v] = unsafe { _mm256_loadu_ps(b[v].add(k)) };
+                    }
+                    for u in 0..4 {
+                        for v in 0..2 {
+                            sum[u][v] = _mm256_fmadd_ps(x[u], y[v], sum[u][v]);
+                        }
+                    }
+                    k += 8;
+                }
+                for u in 0..4 {
+                    for v in 0..2 {
+                        let mut s = emulate_mm256_reduce_add_ps(sum[u][v]);
+                        for t in k..d {
+                            s += unsafe { a[u].add(t).read() * b[v].add(t).read() };
+                        }
+                        maxs[p[u]] = maxs[p[u]].max(s);
+                    }
+                }
+            }
+        }
+        let mut sum = 0.0f32;
+        for max in maxs {
+            sum += max;
+        }
+        sum
+    }
+
+    #[cfg(all(target_arch = "x86_64", test))]
+    #[test]
+    fn reduce_sum_of_max_xy_v3_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 0.004;
+        if !crate::is_cpu_detected!("v3") {
+            println!("test {} ... skipped (v3)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let m = rng.random_range(0..=11);
+            let n = rng.random_range(0..=11);
+            let d = rng.random_range(96..=160);
+            let lhs = (0..m)
+                .map(|_| (0..d).map(|_| rng.random_range(-1.0..=1.0)).collect())
+                .collect::<Vec<Vec<_>>>();
+            let rhs = (0..n)
+                .map(|_| (0..d).map(|_| rng.random_range(-1.0..=1.0)).collect())
+                .collect::<Vec<Vec<_>>>();
+            let lhs = lhs.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let rhs = rhs.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let specialized = unsafe { reduce_sum_of_max_xy_v3(&lhs, &rhs) };
+            let fallback = fallback(&lhs, &rhs);
+            assert!(
+                specialized == fallback || (specialized - fallback).abs() < EPSILON,
+                "specialized = {specialized}, fallback = {fallback}."
+            );
+        }
+    }
+
+    #[cfg_attr(feature = "internal", simd_macros::public)]
+    #[inline]
+    #[cfg(target_arch = "aarch64")]
+    #[crate::target_cpu(enable = "a2")]
+    fn reduce_sum_of_max_xy_a2(lhs: &[&[f32]], rhs: &[&[f32]]) -> f32 {
+        use core::arch::aarch64::*;
+        let (m, n) = (lhs.len(), rhs.len());
+        let d = lhs.iter().chain(rhs).next().map_or(0, |x| x.len());
+        assert!(lhs.iter().chain(rhs).all(|x| x.len() == d));
+        let mut maxs = vec![f32::NEG_INFINITY; m];
+        for i in (0..m).step_by(4) {
+            let p = [
+                i,
+                (i + 1).min(m - 1),
+                (i + 2).min(m - 1),
+                (i + 3).min(m - 1),
+            ];
+            for j in (0..n).step_by(4) {
+                let q = [
+                    j,
+                    (j + 1).min(n - 1),
+                    (j + 2).min(n - 1),
+                    (j + 3).min(n - 1),
+                ];
+                let a = p.map(|u| lhs[u].as_ptr());
+                let b = q.map(|v| rhs[v].as_ptr());
+                let mut sum = [[vdupq_n_f32(0.0); 4]; 4];
+                let mut k = 0;
+                while k + 4 <= d {
+                    let mut x = [vdupq_n_f32(0.0); 4];
+                    let mut y = [vdupq_n_f32(0.0); 4];
+                    for u in 0..4 {
+                        x[u] = unsafe { vld1q_f32(a[u].add(k)) };
+                        y[u] = unsafe { vld1q_f32(b[u].add(k)) };
+                    }
+                    for u in 0..4 {
+                        for v in 0..4 {
+                            sum[u][v] = vfmaq_f32(sum[u][v], x[u], y[v]);
+                        }
+                    }
+                    k += 4;
+                }
+                for u in 0..4 {
+                    for v in 0..4 {
+                        let mut s = vaddvq_f32(sum[u][v]);
+                        for t in k..d {
+                            s += unsafe { a[u].add(t).read() * b[v].add(t).read() };
+                        }
+                        maxs[p[u]] = maxs[p[u]].max(s);
+                    }
+                }
+            }
+        }
+        let mut sum = 0.0f32;
+        for max in maxs {
+            sum += max;
+        }
+        sum
+    }
+
+    #[cfg(all(target_arch = "aarch64", test))]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reduce_sum_of_max_xy_a2_test() {
+        use rand::RngExt;
+        const EPSILON: f32 = 0.004;
+        if !crate::is_cpu_detected!("a2") {
+            println!("test {} ... skipped (a2)", module_path!());
+            return;
+        }
+        let mut rng = rand::rng();
+        for _ in 0..if cfg!(not(miri)) { 256 } else { 1 } {
+            let m = rng.random_range(0..=11);
+            let n = rng.random_range(0..=11);
+            let d = rng.random_range(96..=160);
+            let lhs = (0..m)
+                .map(|_| (0..d).map(|_| rng.random_range(-1.0..=1.0)).collect())
+                .collect::<Vec<Vec<_>>>();
+            let rhs = (0..n)
+                .map(|_| (0..d).map(|_| rng.random_range(-1.0..=1.0)).collect())
+                .collect::<Vec<Vec<_>>>();
+            let lhs = lhs.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let rhs = rhs.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let specialized = unsafe { reduce_sum_of_max_xy_a2(&lhs, &rhs) };
+            let fallback = fallback(&lhs, &rhs);
+            assert!(
+                specialized == fallback || (specialized - fallback).abs() < EPSILON,
+                "specialized = {specialized}, fallback = {fallback}."
+            );
+        }
+    }
+
+    #[crate::multiversion(@"v4", @"v3", "v2", @"a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1")]
+    pub fn reduce_sum_of_max_xy(lhs: &[&[f32]], rhs: &[&[f32]]) -> f32 {
+        let d = lhs.iter().chain(rhs).next().map_or(0, |x| x.len());
+        assert!(lhs.iter().chain(rhs).all(|x| x.len() == d));
+        let mut sum = 0.0f32;
+        for x in lhs {
+            let mut max = f32::NEG_INFINITY;
+            for y in rhs {
+                let mut dot = 0.0f32;
+                for k in 0..d {
+                    dot += x[k] * y[k];
+                }
+                max = max.max(dot);
+            }
+            sum += max;
+        }
+        sum
+    }
+}
+
 mod vector_add {
     #[crate::multiversion(
         "v4", "v3", "v2", "a2", "z17", "z16", "z15", "z14", "z13", "p9", "p8", "p7", "r1"
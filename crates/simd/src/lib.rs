@@ -25,6 +25,12 @@
 mod aligned;
 mod emulate;
 
+#[cfg(not(feature = "internal"))]
+mod floating_bf16;
+
+#[cfg(feature = "internal")]
+pub mod floating_bf16;
+
 #[cfg(not(feature = "internal"))]
 mod floating_f16;
 
@@ -51,6 +57,8 @@ pub use half::f16;
 #[cfg(feature = "nightly_f16")]
 pub use f16;
 
+pub use half::bf16;
+
 pub trait F16: Sized {
     const _ZERO: Self;
 
@@ -124,6 +132,7 @@ pub trait Floating:
     fn reduce_sum_of_d2(lhs: &[Self], rhs: &[Self]) -> f32;
     fn reduce_sum_of_xy_sparse(lidx: &[u32], lval: &[Self], ridx: &[u32], rval: &[Self]) -> f32;
     fn reduce_sum_of_d2_sparse(lidx: &[u32], lval: &[Self], ridx: &[u32], rval: &[Self]) -> f32;
+    fn reduce_sum_of_max_xy(lhs: &[&[Self]], rhs: &[&[Self]]) -> f32;
 
     fn vector_from_f32(this: &[f32]) -> Vec<Self>;
     fn vector_to_f32(this: &[Self]) -> Vec<f32>;
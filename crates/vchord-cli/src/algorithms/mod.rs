@@ -12,14 +12,5 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
-use serde::{Deserialize, Serialize};
-use validator::Validate;
-use vchordg::types::VchordgIndexOptions;
-
-#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
-#[serde(deny_unknown_fields)]
-pub struct VchordgIndexingOptions {
-    #[serde(flatten)]
-    #[validate(nested)]
-    pub index: VchordgIndexOptions,
-}
+pub mod vchordg;
+pub mod vchordrq;
@@ -0,0 +1,123 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use index::fetch::Fetch;
+use index::memory::MemoryRelation;
+use index::prefetcher::{PlainPrefetcher, PrefetcherSequenceFamily, Sequence};
+use index::relation::{Page, RelationRead};
+use rayon::prelude::*;
+use std::error::Error;
+use std::num::NonZero;
+use std::sync::atomic::{AtomicU64, Ordering};
+use vchordg::operator::Operator;
+use vchordg::types::*;
+use vector::VectorOwned;
+use vector::vect::{VectBorrowed, VectOwned};
+
+pub fn build<O: Operator<Vector = VectOwned<f32>>>(
+    index: &MemoryRelation<vchordg::Opaque>,
+    vector_options: VectorOptions,
+    options: VchordgIndexingOptions,
+    base: &[Vec<f32>],
+    payloads: &[NonZero<u64>],
+    pool: &rayon::ThreadPool,
+) -> Result<(), Box<dyn Error>> {
+    vchordg::build::<_, O>(vector_options, options.index, index);
+    let inserted = AtomicU64::new(0);
+    pool.install(|| {
+        base.par_iter().enumerate().for_each(|(i, vector)| {
+            let payload = payloads[i];
+            let bump = bumpalo::Bump::new();
+            let projected = VectOwned::new(rabitq::rotate::rotate(vector));
+            vchordg::insert::<_, O>(
+                index,
+                projected.as_borrowed(),
+                payload,
+                &[],
+                &bump,
+                MakePlainPrefetcher { index },
+                MakePlainPrefetcher { index },
+            );
+            let inserted = inserted.fetch_add(1, Ordering::Relaxed) + 1;
+            if inserted % 100000 == 0 {
+                eprintln!("inserting: {inserted} vectors");
+            }
+        });
+    });
+    Ok(())
+}
+
+pub fn search<'b, R: RelationRead, O: Operator<Vector = VectOwned<f32>>>(
+    index: &'b R,
+    query: &[f32],
+    k: usize,
+    ef_search: u32,
+    beam_search: u32,
+    bump: &'b bumpalo::Bump,
+) -> Vec<NonZero<u64>>
+where
+    R::Page: Page<Opaque = vchordg::Opaque>,
+{
+    let projected = bump.alloc_slice_copy(&rabitq::rotate::rotate(query));
+    vchordg::search::<_, O>(
+        index,
+        VectBorrowed::new(projected),
+        ef_search,
+        beam_search,
+        None,
+        bump,
+        MakePlainPrefetcher { index },
+        MakePlainPrefetcher { index },
+    )
+    .take(k)
+    .map(|(_, payload)| payload)
+    .collect()
+}
+
+/// Checks that the index can be searched with these vectors.
+pub fn check<R: RelationRead>(index: &R, dim: u32) -> Result<(), Box<dyn Error>>
+where
+    R::Page: Page<Opaque = vchordg::Opaque>,
+{
+    let index_dim = vchordg::dimension(index)?;
+    if index_dim != dim {
+        return Err(format!(
+            "the index has {index_dim} dimensions, but queries have {dim} dimensions"
+        )
+        .into());
+    }
+    Ok(())
+}
+
+struct MakePlainPrefetcher<'b, R> {
+    index: &'b R,
+}
+
+impl<'b, R: RelationRead> PrefetcherSequenceFamily<'b, R> for MakePlainPrefetcher<'b, R> {
+    type P<S: Sequence>
+        = PlainPrefetcher<'b, R, S>
+    where
+        S::Item: Fetch<'b>;
+
+    fn prefetch<S: Sequence>(&mut self, seq: S) -> Self::P<S>
+    where
+        S::Item: Fetch<'b>,
+    {
+        PlainPrefetcher::new(self.index, seq)
+    }
+
+    fn is_not_plain(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,425 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use index::fetch::Fetch;
+use index::memory::MemoryRelation;
+use index::prefetcher::{
+    PlainPrefetcher, PrefetcherHeapFamily, PrefetcherSequenceFamily, Sequence,
+};
+use index::relation::{Page, RelationRead};
+use k_means::square::Square;
+use rayon::prelude::*;
+use simd::Floating;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::io::Write;
+use std::num::NonZero;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use vchordrq::operator::Operator;
+use vchordrq::types::*;
+use vchordrq::{FastHeap, InsertChooser, MaintainChooser, RerankMethod};
+use vector::VectorOwned;
+use vector::vect::{VectBorrowed, VectOwned};
+
+pub fn build<O: Operator<Vector = VectOwned<f32>>>(
+    index: &MemoryRelation<vchordrq::Opaque>,
+    vector_options: VectorOptions,
+    options: VchordrqIndexingOptions,
+    base: &[Vec<f32>],
+    payloads: &[NonZero<u64>],
+    pool: &rayon::ThreadPool,
+) -> Result<(), Box<dyn Error>> {
+    if options.index.rerank_in_table {
+        return Err("`rerank_in_table` is not supported without a table".into());
+    }
+    let structures = match options.build.source {
+        VchordrqBuildSourceOptions::Default(_) => vec![Structure {
+            centroids: vec![vec![0.0f32; vector_options.dim as usize]],
+            children: vec![vec![]],
+        }],
+        VchordrqBuildSourceOptions::Internal(internal_build) => {
+            make_internal_build(vector_options, internal_build, base)?
+        }
+        VchordrqBuildSourceOptions::External(_) => {
+            return Err("`build.external` is not supported without a database".into());
+        }
+    };
+    let structures = structures
+        .into_iter()
+        .map(
+            |Structure {
+                 centroids,
+                 children,
+             }| Structure {
+                centroids: centroids
+                    .into_iter()
+                    .map(|mut centroid| {
+                        rabitq::rotate::rotate_inplace(&mut centroid);
+                        VectOwned::new(centroid)
+                    })
+                    .collect(),
+                children,
+            },
+        )
+        .collect();
+    vchordrq::build::<_, O>(vector_options, options.index, index, structures);
+
+    struct IdChooser(usize);
+    impl InsertChooser for IdChooser {
+        fn choose(&mut self, n: NonZero<usize>) -> usize {
+            self.0 % n.get()
+        }
+    }
+
+    let inserted = AtomicU64::new(0);
+    pool.install(|| {
+        base.par_iter().enumerate().for_each(|(i, vector)| {
+            let payload = payloads[i];
+            let bump = bumpalo::Bump::new();
+            let vector = VectBorrowed::new(vector);
+            let projected = VectOwned::new(rabitq::rotate::rotate(vector.slice()));
+            let key =
+                vchordrq::insert_vector::<_, O>(index, payload, vector, &mut IdChooser(i), true);
+            vchordrq::insert::<_, O>(
+                index,
+                payload,
+                projected.as_borrowed(),
+                key,
+                &bump,
+                MakeH1PlainPrefetcherForInsertion { index },
+                true,
+            );
+            let inserted = inserted.fetch_add(1, Ordering::Relaxed) + 1;
+            if inserted % 100000 == 0 {
+                eprintln!("inserting: {inserted} vectors");
+            }
+        });
+    });
+
+    struct ChooseAll;
+    impl MaintainChooser for ChooseAll {
+        fn choose(&mut self, _: usize) -> bool {
+            true
+        }
+    }
+
+    vchordrq::maintain::<_, O>(
+        index,
+        MakeH0PlainPrefetcher { index },
+        &mut ChooseAll,
+        || (),
+    );
+    Ok(())
+}
+
+pub fn search<'b, R: RelationRead, O: Operator<Vector = VectOwned<f32>>>(
+    index: &'b R,
+    query: &[f32],
+    k: usize,
+    probes: &[u32],
+    epsilon: f32,
+    bump: &'b bumpalo::Bump,
+) -> Vec<NonZero<u64>>
+where
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    let projected = VectOwned::new(rabitq::rotate::rotate(query));
+    let results = vchordrq::default_search::<_, O>(
+        index,
+        None,
+        projected.as_borrowed(),
+        probes.to_vec(),
+        epsilon,
+        bump,
+        MakeH1PlainPrefetcher { index },
+        MakeH0PlainPrefetcher { index },
+    );
+    let prefetcher = PlainPrefetcher::new(index, BinaryHeap::from(results));
+    vchordrq::rerank_index::<O, _, _, _>(VectOwned::new(query.to_vec()), prefetcher)
+        .take(k)
+        .map(|(_, payload)| payload)
+        .collect()
+}
+
+/// Checks that the index can be searched with these vectors and probes.
+pub fn check<R: RelationRead>(index: &R, dim: u32, probes: &[u32]) -> Result<(), Box<dyn Error>>
+where
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    let cost = vchordrq::cost(index);
+    if cost.dim != dim {
+        return Err(format!(
+            "the index has {} dimensions, but queries have {dim} dimensions",
+            cost.dim
+        )
+        .into());
+    }
+    if cost.cells.len() != 1 + probes.len() {
+        return Err(format!(
+            "need {} probes, but {} probes provided",
+            cost.cells.len() - 1,
+            probes.len()
+        )
+        .into());
+    }
+    if let RerankMethod::Heap = vchordrq::how(index) {
+        return Err("`rerank_in_table` is not supported without a table".into());
+    }
+    Ok(())
+}
+
+/// Writes the centroid tree as JSON lines, parents before children.
+pub fn dump_tree<R: RelationRead, O: Operator<Vector = VectOwned<f32>>>(
+    index: &R,
+    path: &Path,
+) -> Result<(), Box<dyn Error>>
+where
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    for (id, node) in vchordrq::tree::<_, O>(index).into_iter().enumerate() {
+        let mut centroid = node.centroid.into_vec();
+        rabitq::rotate::rotate_reversed_inplace(&mut centroid);
+        let line = serde_json::json!({
+            "id": id,
+            "parent": node.parent,
+            "height": node.height,
+            "size": node.size,
+            "centroid": centroid,
+        });
+        writeln!(file, "{line}")?;
+    }
+    file.flush()?;
+    Ok(())
+}
+
+fn make_internal_build(
+    vector_options: VectorOptions,
+    internal_build: VchordrqInternalBuildOptions,
+    base: &[Vec<f32>],
+) -> Result<Vec<Structure<Vec<f32>>>, Box<dyn Error>> {
+    let dim = vector_options.dim as usize;
+    let reduction = match internal_build.kmeans_dimension {
+        Some(d) if (d as usize) < dim => Some(d as usize),
+        Some(d) => {
+            eprintln!(
+                "ignoring `kmeans_dimension = {d}` because it is less than the vector dimension {dim}"
+            );
+            None
+        }
+        None => None,
+    };
+    let max_number_of_samples = internal_build
+        .lists
+        .last()
+        .map(|x| x.saturating_mul(internal_build.sampling_factor))
+        .unwrap_or_default() as usize;
+    let samples = if base.len() <= max_number_of_samples {
+        base.iter().map(Vec::as_slice).collect::<Vec<_>>()
+    } else {
+        let mut rng = rand::rng();
+        rand::seq::index::sample(&mut rng, base.len(), max_number_of_samples)
+            .into_iter()
+            .map(|i| base[i].as_slice())
+            .collect()
+    };
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(internal_build.build_threads as usize)
+        .build()?;
+    let mut result = Vec::<Structure<Vec<f32>>>::new();
+    for w in internal_build
+        .lists
+        .iter()
+        .rev()
+        .copied()
+        .chain(std::iter::once(1))
+    {
+        let mut input = if let Some(structure) = result.last() {
+            let mut input = Square::with_capacity(dim, structure.centroids.len());
+            for slice in structure.centroids.iter() {
+                input.push_slice(slice);
+            }
+            input
+        } else {
+            let sample_dim = reduction.unwrap_or(dim);
+            let mut input = Square::with_capacity(sample_dim, samples.len());
+            for &sample in samples.iter() {
+                if let Some(sample_dim) = reduction {
+                    let mut x = rabitq::rotate::rotate(sample);
+                    x.truncate(sample_dim);
+                    input.push_slice(&x);
+                } else {
+                    input.push_slice(sample);
+                }
+            }
+            input
+        };
+        let num_points = input.len();
+        let num_dim = input.d();
+        let num_lists = w as usize;
+        let num_iterations = internal_build.kmeans_iterations;
+        if num_lists > 1 {
+            eprintln!(
+                "clustering: starting, clustering {num_points} vectors of {num_dim} dimension into {num_lists} clusters, in {num_iterations} iterations"
+            );
+        }
+        let algorithm = match (result.last(), &internal_build.kmeans_algorithm) {
+            (None, KMeansAlgorithm::Hierarchical {}) => k_means::hierarchical_k_means,
+            _ => k_means::lloyd_k_means,
+        };
+        let mut f = algorithm(
+            &pool,
+            num_dim,
+            input.as_mut_view(),
+            num_lists,
+            [7; 32],
+            internal_build.spherical_centroids,
+        );
+        for i in 0..num_iterations {
+            if num_lists > 1 {
+                eprintln!("clustering: iteration {}", i + 1);
+            }
+            f.assign();
+            f.update();
+        }
+        let centroids = if let (None, Some(sample_dim)) = (result.last(), reduction) {
+            // centroids of full dimension are means of samples in each cluster
+            let index = f.index();
+            let targets = pool.install(|| {
+                samples
+                    .par_iter()
+                    .map(|&sample| {
+                        let mut x = rabitq::rotate::rotate(sample);
+                        x.truncate(sample_dim);
+                        index(&x).1
+                    })
+                    .collect::<Vec<_>>()
+            });
+            drop(index);
+            let mut centroids = Square::from_zeros(dim, num_lists);
+            let mut count = vec![0.0f32; num_lists];
+            for (&sample, &target) in std::iter::zip(&samples, &targets) {
+                f32::vector_add_inplace(&mut centroids[target], sample);
+                count[target] += 1.0;
+            }
+            for (target, &count) in count.iter().enumerate() {
+                let centroid = &mut centroids[target];
+                if count == 0.0 {
+                    centroid.fill_with(|| rand::random_range(-1.0..=1.0));
+                } else {
+                    f32::vector_mul_scalar_inplace(centroid, 1.0 / count);
+                }
+                if internal_build.spherical_centroids {
+                    let l = f32::reduce_sum_of_x2(centroid).sqrt();
+                    f32::vector_mul_scalar_inplace(centroid, 1.0 / l);
+                }
+            }
+            centroids
+        } else {
+            f.finish()
+        };
+        if num_lists > 1 {
+            eprintln!("clustering: finished");
+        }
+        if let Some(structure) = result.last() {
+            let mut children = vec![Vec::new(); centroids.len()];
+            for i in 0..structure.len() as u32 {
+                let target = k_means::k_means_lookup(&structure.centroids[i as usize], &centroids);
+                children[target].push(i);
+            }
+            let (centroids, children) = std::iter::zip(&centroids, children)
+                .filter(|(_, children)| !children.is_empty())
+                .map(|(centroids, children)| (centroids.to_vec(), children))
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            result.push(Structure {
+                centroids,
+                children,
+            });
+        } else {
+            let children = vec![Vec::new(); centroids.len()];
+            result.push(Structure {
+                centroids: centroids.into_iter().map(|x| x.to_vec()).collect(),
+                children,
+            });
+        }
+    }
+    Ok(result)
+}
+
+struct MakeH1PlainPrefetcherForInsertion<'b, R> {
+    index: &'b R,
+}
+
+impl<'b, R: RelationRead> PrefetcherHeapFamily<'b, R> for MakeH1PlainPrefetcherForInsertion<'b, R> {
+    type P<T>
+        = PlainPrefetcher<'b, R, FastHeap<T>>
+    where
+        T: Ord + Fetch<'b>;
+
+    fn prefetch<T>(&mut self, seq: Vec<T>) -> Self::P<T>
+    where
+        T: Ord + Fetch<'b>,
+    {
+        PlainPrefetcher::new(self.index, FastHeap::from(seq))
+    }
+
+    fn is_not_plain(&self) -> bool {
+        false
+    }
+}
+
+struct MakeH1PlainPrefetcher<'b, R> {
+    index: &'b R,
+}
+
+impl<'b, R: RelationRead> PrefetcherHeapFamily<'b, R> for MakeH1PlainPrefetcher<'b, R> {
+    type P<T>
+        = PlainPrefetcher<'b, R, BinaryHeap<T>>
+    where
+        T: Ord + Fetch<'b>;
+
+    fn prefetch<T>(&mut self, seq: Vec<T>) -> Self::P<T>
+    where
+        T: Ord + Fetch<'b>,
+    {
+        PlainPrefetcher::new(self.index, BinaryHeap::from(seq))
+    }
+
+    fn is_not_plain(&self) -> bool {
+        false
+    }
+}
+
+struct MakeH0PlainPrefetcher<'b, R> {
+    index: &'b R,
+}
+
+impl<'b, R: RelationRead> PrefetcherSequenceFamily<'b, R> for MakeH0PlainPrefetcher<'b, R> {
+    type P<S: Sequence>
+        = PlainPrefetcher<'b, R, S>
+    where
+        S::Item: Fetch<'b>;
+
+    fn prefetch<S: Sequence>(&mut self, seq: S) -> Self::P<S>
+    where
+        S::Item: Fetch<'b>,
+    {
+        PlainPrefetcher::new(self.index, seq)
+    }
+
+    fn is_not_plain(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,74 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use index::relation::{Relation, RelationRead, RelationReadTypes};
+use std::cell::{Cell, RefCell};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Touched {
+    /// number of reads
+    pub reads: u64,
+    /// number of distinct pages that are read
+    pub pages: u64,
+}
+
+/// A relation that counts pages read through it.
+pub struct CountingRelation<R> {
+    inner: R,
+    touched: Cell<Touched>,
+    // the generation in which a page is last read
+    stamps: RefCell<Vec<u32>>,
+    generation: Cell<u32>,
+}
+
+impl<R> CountingRelation<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            touched: Cell::new(Touched::default()),
+            stamps: RefCell::new(Vec::new()),
+            generation: Cell::new(1),
+        }
+    }
+    /// Returns the pages touched since the last call.
+    pub fn take(&self) -> Touched {
+        self.generation.set(self.generation.get() + 1);
+        self.touched.take()
+    }
+}
+
+impl<R: Relation> Relation for CountingRelation<R> {
+    type Page = R::Page;
+}
+
+impl<R: RelationReadTypes> RelationReadTypes for CountingRelation<R> {
+    type ReadGuard<'b> = R::ReadGuard<'b>;
+}
+
+impl<R: RelationRead> RelationRead for CountingRelation<R> {
+    fn read(&self, id: u32) -> Self::ReadGuard<'_> {
+        let mut touched = self.touched.get();
+        touched.reads += 1;
+        let mut stamps = self.stamps.borrow_mut();
+        if stamps.len() <= id as usize {
+            stamps.resize(id as usize + 1, 0);
+        }
+        if stamps[id as usize] != self.generation.get() {
+            stamps[id as usize] = self.generation.get();
+            touched.pages += 1;
+        }
+        self.touched.set(touched);
+        self.inner.read(id)
+    }
+}
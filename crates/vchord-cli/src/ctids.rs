@@ -0,0 +1,78 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+//! Readers of heap TIDs, one `(block,offset)` per line, as written by
+//! `COPY (SELECT ctid FROM t ORDER BY ...) TO ...`.
+//!
+//! Indexes store heap TIDs as payloads, in the same encoding as the extension.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::num::NonZero;
+use std::path::Path;
+
+/// Reads heap TIDs, and returns them as payloads.
+pub fn read_ctids(path: &Path) -> Result<Vec<NonZero<u64>>, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let payloads = parse(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(payloads)
+}
+
+fn parse(text: &str) -> Result<Vec<NonZero<u64>>, String> {
+    let mut result = Vec::new();
+    let mut seen = HashSet::new();
+    for (i, line) in text.lines().enumerate() {
+        let Some((block, offset)) = line
+            .trim()
+            .strip_prefix('(')
+            .and_then(|x| x.strip_suffix(')'))
+            .and_then(|x| x.split_once(','))
+        else {
+            return Err(format!("line {}: {line:?} is not a ctid", i + 1));
+        };
+        let (Ok(block), Ok(offset)) = (block.trim().parse::<u32>(), offset.trim().parse::<u16>())
+        else {
+            return Err(format!("line {}: {line:?} is not a ctid", i + 1));
+        };
+        if offset == 0 {
+            return Err(format!("line {}: {line:?} is not a ctid", i + 1));
+        }
+        let payload = encode(block, offset);
+        if !seen.insert(payload) {
+            return Err(format!("line {}: {line:?} is duplicated", i + 1));
+        }
+        result.push(payload);
+    }
+    Ok(result)
+}
+
+/// Encodes a heap TID as a payload, which is `pointer_to_kv` of the extension
+/// with no extra value.
+pub fn encode(block: u32, offset: u16) -> NonZero<u64> {
+    let x = (block as u64) << 32 | (offset as u64) << 16;
+    NonZero::new(x).expect("invalid ctid")
+}
+
+#[test]
+fn test_parse() {
+    let payloads = parse("(0,1)\n(0,2)\n(65537,3)\n").unwrap();
+    assert_eq!(
+        payloads.iter().map(|x| x.get()).collect::<Vec<_>>(),
+        [0x1_0000, 0x2_0000, 0x0001_0001_0003_0000]
+    );
+    assert!(parse("(0,0)").is_err());
+    assert!(parse("(0,1)\n(0,1)").is_err());
+    assert!(parse("0,1").is_err());
+    assert!(parse("(-1,1)").is_err());
+}
@@ -0,0 +1,577 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+mod algorithms;
+mod counting;
+mod ctids;
+mod vecs;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use counting::{CountingRelation, Touched};
+use index::file::{FileRelation, MappedRelation};
+use index::memory::MemoryRelation;
+use index::relation::Opaque;
+use index_accessor::{Dot, L2S};
+use simd::Floating;
+use std::collections::HashMap;
+use std::error::Error;
+use std::num::NonZero;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use validator::Validate;
+use vector::vect::VectOwned;
+
+/// Builds and searches indexes without PostgreSQL.
+///
+/// Index files are named by operator classes, such as
+/// `vchordrq_vector_l2_ops`. Only files built with `--ctids` can be loaded
+/// with `vchordrq_import` and `vchordg_import`, into an index on the table
+/// that the heap TIDs are taken from.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Builds an index of base vectors
+    Build(BuildArgs),
+    /// Searches an index, and reports recall, QPS and pages touched
+    Bench(BenchArgs),
+}
+
+#[derive(Args)]
+struct BuildArgs {
+    #[arg(long, value_enum)]
+    algorithm: Algorithm,
+    #[arg(long, value_enum, default_value = "l2")]
+    metric: Metric,
+    /// Base vectors in `.fvecs` or `.bvecs`
+    #[arg(long)]
+    base: PathBuf,
+    /// Options in TOML, the same as the `options` storage parameter
+    #[arg(long)]
+    options: Option<PathBuf>,
+    /// Heap TIDs of base vectors, one `(block,offset)` per line in the same
+    /// order, for example, written by `COPY (SELECT ctid FROM t ORDER BY id)
+    /// TO ...`. Without it, payloads are positions of base vectors, and the
+    /// index is only for `bench`.
+    #[arg(long)]
+    ctids: Option<PathBuf>,
+    /// Number of threads for insertion, defaulting to the number of CPUs
+    #[arg(long, default_value_t = 0)]
+    threads: usize,
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[arg(long)]
+    index: PathBuf,
+    /// Query vectors in `.fvecs` or `.bvecs`
+    #[arg(long)]
+    query: PathBuf,
+    /// Nearest neighbors of queries in `.ivecs`, as positions in base vectors
+    #[arg(long)]
+    groundtruth: PathBuf,
+    /// Heap TIDs of base vectors, if the index is built with them
+    #[arg(long)]
+    ctids: Option<PathBuf>,
+    #[arg(short, default_value_t = 10)]
+    k: usize,
+    /// vchordrq: probes from the top level down, separated by commas
+    #[arg(long, value_delimiter = ',')]
+    probes: Vec<u32>,
+    /// vchordrq: `vchordrq.epsilon`
+    #[arg(long, default_value_t = 1.9)]
+    epsilon: f32,
+    /// vchordg: `vchordg.ef_search`
+    #[arg(long, default_value_t = 64)]
+    ef_search: u32,
+    /// vchordg: `vchordg.beam_search`
+    #[arg(long, default_value_t = 1)]
+    beam_search: u32,
+    /// vchordrq: writes the centroid tree to this file as JSON lines
+    #[arg(long)]
+    dump_tree: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Algorithm {
+    Vchordrq,
+    Vchordg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Metric {
+    L2,
+    Ip,
+    Cosine,
+}
+
+fn kind(algorithm: Algorithm, metric: Metric) -> String {
+    let algorithm = match algorithm {
+        Algorithm::Vchordrq => "vchordrq",
+        Algorithm::Vchordg => "vchordg",
+    };
+    let metric = match metric {
+        Metric::L2 => "l2",
+        Metric::Ip => "ip",
+        Metric::Cosine => "cosine",
+    };
+    format!("{algorithm}_vector_{metric}_ops")
+}
+
+fn parse_kind(kind: &str) -> Option<(Algorithm, Metric)> {
+    let (algorithm, rest) = if let Some(rest) = kind.strip_prefix("vchordrq_vector_") {
+        (Algorithm::Vchordrq, rest)
+    } else if let Some(rest) = kind.strip_prefix("vchordg_vector_") {
+        (Algorithm::Vchordg, rest)
+    } else {
+        return None;
+    };
+    let metric = match rest {
+        "l2_ops" => Metric::L2,
+        "ip_ops" => Metric::Ip,
+        "cosine_ops" => Metric::Cosine,
+        _ => return None,
+    };
+    Some((algorithm, metric))
+}
+
+fn read_vectors(path: &Path, metric: Metric) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    let mut vectors = vecs::read_vectors(path)?;
+    if metric == Metric::Cosine {
+        for vector in vectors.iter_mut() {
+            let l = f32::reduce_sum_of_x2(vector).sqrt();
+            // zero vectors are kept, since they have no direction
+            if l != 0.0 {
+                f32::vector_mul_scalar_inplace(vector, 1.0 / l);
+            }
+        }
+    }
+    Ok(vectors)
+}
+
+fn write<O: Opaque>(
+    path: &Path,
+    kind: &str,
+    index: MemoryRelation<O>,
+) -> Result<u32, Box<dyn Error>> {
+    let file = FileRelation::<O>::create(path, kind)?;
+    for page in index.into_pages() {
+        file.push(&page)?;
+    }
+    file.sync()?;
+    Ok(file.len())
+}
+
+/// Reads payloads of base vectors, which are heap TIDs if they are given, and
+/// positions otherwise.
+fn read_payloads(ctids: Option<&Path>, n: usize) -> Result<Vec<NonZero<u64>>, Box<dyn Error>> {
+    let Some(path) = ctids else {
+        return Ok((1..=n as u64)
+            .map(|x| NonZero::new(x).expect("too many vectors"))
+            .collect());
+    };
+    let payloads = ctids::read_ctids(path)?;
+    if payloads.len() != n {
+        return Err(format!(
+            "{}: {} ctids are found, but there are {n} base vectors",
+            path.display(),
+            payloads.len()
+        )
+        .into());
+    }
+    Ok(payloads)
+}
+
+fn build(
+    algorithm: Algorithm,
+    metric: Metric,
+    base: &[Vec<f32>],
+    payloads: &[NonZero<u64>],
+    options: &str,
+    pool: &rayon::ThreadPool,
+    output: &Path,
+) -> Result<u32, Box<dyn Error>> {
+    let dim = base[0].len() as u32;
+    let kind = kind(algorithm, metric);
+    let pages = match algorithm {
+        Algorithm::Vchordrq => {
+            use ::vchordrq::operator::Op;
+            use ::vchordrq::types::*;
+            let options = toml::from_str::<VchordrqIndexingOptions>(options)?;
+            options.validate()?;
+            let d = match metric {
+                Metric::L2 => DistanceKind::L2S,
+                Metric::Ip | Metric::Cosine => DistanceKind::Dot,
+            };
+            let vector_options = VectorOptions {
+                dim,
+                v: VectorKind::Vecf32,
+                d,
+            };
+            vector_options.validate()?;
+            let index = MemoryRelation::new();
+            match d {
+                DistanceKind::L2S => algorithms::vchordrq::build::<Op<VectOwned<f32>, L2S>>(
+                    &index,
+                    vector_options,
+                    options,
+                    base,
+                    payloads,
+                    pool,
+                )?,
+                DistanceKind::Dot => algorithms::vchordrq::build::<Op<VectOwned<f32>, Dot>>(
+                    &index,
+                    vector_options,
+                    options,
+                    base,
+                    payloads,
+                    pool,
+                )?,
+            }
+            write(output, &kind, index)?
+        }
+        Algorithm::Vchordg => {
+            use ::vchordg::operator::Op;
+            use ::vchordg::types::*;
+            let options = toml::from_str::<VchordgIndexingOptions>(options)?;
+            options.validate()?;
+            let d = match metric {
+                Metric::L2 => DistanceKind::L2S,
+                Metric::Ip | Metric::Cosine => DistanceKind::Dot,
+            };
+            let vector_options = VectorOptions {
+                dim,
+                v: VectorKind::Vecf32,
+                d,
+            };
+            vector_options.validate()?;
+            let index = MemoryRelation::new();
+            match d {
+                DistanceKind::L2S => algorithms::vchordg::build::<Op<VectOwned<f32>, L2S>>(
+                    &index,
+                    vector_options,
+                    options,
+                    base,
+                    payloads,
+                    pool,
+                )?,
+                DistanceKind::Dot => algorithms::vchordg::build::<Op<VectOwned<f32>, Dot>>(
+                    &index,
+                    vector_options,
+                    options,
+                    base,
+                    payloads,
+                    pool,
+                )?,
+            }
+            write(output, &kind, index)?
+        }
+    };
+    Ok(pages)
+}
+
+fn bench<R>(
+    index: &CountingRelation<R>,
+    queries: &[Vec<f32>],
+    groundtruth: &[Vec<u32>],
+    positions: &HashMap<NonZero<u64>, u32>,
+    k: usize,
+    search: impl Fn(&CountingRelation<R>, &[f32], &bumpalo::Bump) -> Vec<NonZero<u64>>,
+) {
+    let mut hits = 0_usize;
+    let mut touched = Touched::default();
+    let start = Instant::now();
+    for (query, truth) in std::iter::zip(queries, groundtruth) {
+        let bump = bumpalo::Bump::new();
+        let results = search(index, query, &bump);
+        let truth = &truth[..k];
+        hits += results
+            .iter()
+            .filter_map(|payload| positions.get(payload))
+            .filter(|&id| truth.contains(id))
+            .count();
+        let Touched { reads, pages } = index.take();
+        touched.reads += reads;
+        touched.pages += pages;
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    let n = queries.len() as f64;
+    println!("recall@{k}: {:.4}", hits as f64 / (n * k as f64));
+    println!("qps: {:.2}", n / elapsed);
+    println!("pages touched per query: {:.2}", touched.pages as f64 / n);
+    println!("page reads per query: {:.2}", touched.reads as f64 / n);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    match cli.command {
+        Commands::Build(BuildArgs {
+            algorithm,
+            metric,
+            base,
+            options,
+            ctids,
+            threads,
+            output,
+        }) => {
+            let base = read_vectors(&base, metric)?;
+            let payloads = read_payloads(ctids.as_deref(), base.len())?;
+            let options = match options {
+                Some(path) => std::fs::read_to_string(path)?,
+                None => String::new(),
+            };
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()?;
+            let start = Instant::now();
+            let pages = build(
+                algorithm, metric, &base, &payloads, &options, &pool, &output,
+            )?;
+            eprintln!(
+                "built {} of {} vectors in {:.2}s, {pages} pages",
+                kind(algorithm, metric),
+                base.len(),
+                start.elapsed().as_secs_f64()
+            );
+        }
+        Commands::Bench(BenchArgs {
+            index,
+            query,
+            groundtruth,
+            ctids,
+            k,
+            probes,
+            epsilon,
+            ef_search,
+            beam_search,
+            dump_tree,
+        }) => {
+            let path = index;
+            let kind = MappedRelation::<::vchordrq::Opaque>::open(&path)?
+                .kind()
+                .to_string();
+            let Some((algorithm, metric)) = parse_kind(&kind) else {
+                return Err(format!("{} is not supported", kind).into());
+            };
+            let queries = read_vectors(&query, metric)?;
+            let groundtruth = vecs::read_ids(&groundtruth)?;
+            let dim = queries[0].len() as u32;
+            if groundtruth.len() < queries.len() {
+                return Err("the ground truth is shorter than queries".into());
+            }
+            if k == 0 || groundtruth.iter().any(|x| x.len() < k) {
+                return Err(format!("the ground truth has less than {k} neighbors").into());
+            }
+            let payloads = match ctids.as_deref() {
+                Some(path) => ctids::read_ctids(path)?,
+                None => {
+                    let n = groundtruth.iter().flatten().max().map_or(0, |&x| x + 1);
+                    read_payloads(None, n as usize)?
+                }
+            };
+            let positions = payloads.into_iter().zip(0..).collect::<HashMap<_, u32>>();
+            match (algorithm, metric) {
+                (Algorithm::Vchordrq, metric) => {
+                    use ::vchordrq::operator::Op;
+                    let index = MappedRelation::<::vchordrq::Opaque>::open(&path)?;
+                    algorithms::vchordrq::check(&index, dim, &probes)?;
+                    let index = CountingRelation::new(index);
+                    if metric == Metric::L2 {
+                        type O = Op<VectOwned<f32>, L2S>;
+                        if let Some(path) = dump_tree {
+                            algorithms::vchordrq::dump_tree::<_, O>(&index, &path)?;
+                            index.take();
+                        }
+                        bench(
+                            &index,
+                            &queries,
+                            &groundtruth,
+                            &positions,
+                            k,
+                            |index, query, bump| {
+                                algorithms::vchordrq::search::<_, O>(
+                                    index, query, k, &probes, epsilon, bump,
+                                )
+                            },
+                        );
+                    } else {
+                        type O = Op<VectOwned<f32>, Dot>;
+                        if let Some(path) = dump_tree {
+                            algorithms::vchordrq::dump_tree::<_, O>(&index, &path)?;
+                            index.take();
+                        }
+                        bench(
+                            &index,
+                            &queries,
+                            &groundtruth,
+                            &positions,
+                            k,
+                            |index, query, bump| {
+                                algorithms::vchordrq::search::<_, O>(
+                                    index, query, k, &probes, epsilon, bump,
+                                )
+                            },
+                        );
+                    }
+                }
+                (Algorithm::Vchordg, metric) => {
+                    use ::vchordg::operator::Op;
+                    if dump_tree.is_some() {
+                        return Err("vchordg indexes have no centroid tree".into());
+                    }
+                    let index = MappedRelation::<::vchordg::Opaque>::open(&path)?;
+                    algorithms::vchordg::check(&index, dim)?;
+                    let index = CountingRelation::new(index);
+                    if metric == Metric::L2 {
+                        type O = Op<VectOwned<f32>, L2S>;
+                        bench(
+                            &index,
+                            &queries,
+                            &groundtruth,
+                            &positions,
+                            k,
+                            |index, query, bump| {
+                                algorithms::vchordg::search::<_, O>(
+                                    index,
+                                    query,
+                                    k,
+                                    ef_search,
+                                    beam_search,
+                                    bump,
+                                )
+                            },
+                        );
+                    } else {
+                        type O = Op<VectOwned<f32>, Dot>;
+                        bench(
+                            &index,
+                            &queries,
+                            &groundtruth,
+                            &positions,
+                            k,
+                            |index, query, bump| {
+                                algorithms::vchordg::search::<_, O>(
+                                    index,
+                                    query,
+                                    k,
+                                    ef_search,
+                                    beam_search,
+                                    bump,
+                                )
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_build_and_search() {
+    let dir = std::env::temp_dir().join(format!("vchord-cli-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let n = 500_usize;
+    let mut fvecs = Vec::new();
+    let mut ctids = String::new();
+    for i in 0..n {
+        fvecs.extend(16_i32.to_le_bytes());
+        for j in 0..16 {
+            let x = if i == 0 {
+                0.0
+            } else {
+                ((i * 16 + j) as f32 * 0.7).sin()
+            };
+            fvecs.extend(x.to_le_bytes());
+        }
+        ctids.push_str(&format!("({},{})\n", i / 100, i % 100 + 1));
+    }
+    std::fs::write(dir.join("base.fvecs"), fvecs).unwrap();
+    std::fs::write(dir.join("ctids.txt"), ctids).unwrap();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .unwrap();
+    for (algorithm, metric, options) in [
+        (
+            Algorithm::Vchordrq,
+            Metric::L2,
+            "[build.internal]\nlists = [4]\n",
+        ),
+        (
+            Algorithm::Vchordrq,
+            Metric::Cosine,
+            "[build.internal]\nlists = [4]\n",
+        ),
+        (Algorithm::Vchordg, Metric::L2, ""),
+        (Algorithm::Vchordg, Metric::Cosine, ""),
+    ] {
+        let base = read_vectors(&dir.join("base.fvecs"), metric).unwrap();
+        assert!(base.iter().flatten().all(|x| x.is_finite()));
+        let payloads = read_payloads(Some(&dir.join("ctids.txt")), n).unwrap();
+        let output = dir.join(kind(algorithm, metric));
+        let pages = build(algorithm, metric, &base, &payloads, options, &pool, &output).unwrap();
+        assert!(pages > 0);
+        let bump = bumpalo::Bump::new();
+        for i in 1..n {
+            let results = match (algorithm, metric) {
+                (Algorithm::Vchordrq, Metric::L2) => {
+                    let index = MappedRelation::open(&output).unwrap();
+                    algorithms::vchordrq::check(&index, 16, &[4]).unwrap();
+                    algorithms::vchordrq::search::<_, ::vchordrq::operator::Op<VectOwned<f32>, L2S>>(
+                        &index,
+                        &base[i],
+                        1,
+                        &[4],
+                        1.9,
+                        &bump,
+                    )
+                }
+                (Algorithm::Vchordrq, _) => {
+                    let index = MappedRelation::open(&output).unwrap();
+                    algorithms::vchordrq::search::<_, ::vchordrq::operator::Op<VectOwned<f32>, Dot>>(
+                        &index,
+                        &base[i],
+                        1,
+                        &[4],
+                        1.9,
+                        &bump,
+                    )
+                }
+                (Algorithm::Vchordg, Metric::L2) => {
+                    let index = MappedRelation::open(&output).unwrap();
+                    algorithms::vchordg::check(&index, 16).unwrap();
+                    algorithms::vchordg::search::<_, ::vchordg::operator::Op<VectOwned<f32>, L2S>>(
+                        &index, &base[i], 1, 64, 1, &bump,
+                    )
+                }
+                (Algorithm::Vchordg, _) => {
+                    let index = MappedRelation::open(&output).unwrap();
+                    algorithms::vchordg::search::<_, ::vchordg::operator::Op<VectOwned<f32>, Dot>>(
+                        &index, &base[i], 1, 64, 1, &bump,
+                    )
+                }
+            };
+            assert_eq!(results, [payloads[i]], "{output:?}: vector {i}");
+        }
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
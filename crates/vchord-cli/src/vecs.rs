@@ -0,0 +1,103 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+//! Readers of `.fvecs`, `.bvecs` and `.ivecs` files.
+//!
+//! Each vector is stored as its dimension in a little-endian `i32`, followed
+//! by its elements, which are `f32`, `u8` and `i32` respectively.
+
+use std::error::Error;
+use std::path::Path;
+
+/// Reads a `.fvecs` or a `.bvecs` file.
+pub fn read_vectors(path: &Path) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let result = match path.extension().and_then(|x| x.to_str()) {
+        Some("fvecs") => parse(&bytes, f32::from_le_bytes),
+        Some("bvecs") => parse(&bytes, |[x]: [u8; 1]| x as f32),
+        _ => Err("the extension should be `.fvecs` or `.bvecs`".to_string()),
+    };
+    let result = result.map_err(|e| format!("{}: {e}", path.display()))?;
+    check(path, &result)?;
+    Ok(result)
+}
+
+/// Reads a `.ivecs` file, for example, the ground truth of queries.
+pub fn read_ids(path: &Path) -> Result<Vec<Vec<u32>>, Box<dyn Error>> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let result = match path.extension().and_then(|x| x.to_str()) {
+        Some("ivecs") => parse(&bytes, i32::from_le_bytes),
+        _ => Err("the extension should be `.ivecs`".to_string()),
+    };
+    let result = result.map_err(|e| format!("{}: {e}", path.display()))?;
+    let mut ids = Vec::with_capacity(result.len());
+    for vector in result {
+        let Ok(vector) = vector.into_iter().map(u32::try_from).collect() else {
+            return Err(format!("{}: negative ids are found", path.display()).into());
+        };
+        ids.push(vector);
+    }
+    Ok(ids)
+}
+
+fn check<T>(path: &Path, vectors: &[Vec<T>]) -> Result<(), Box<dyn Error>> {
+    let Some(first) = vectors.first() else {
+        return Err(format!("{}: the file is empty", path.display()).into());
+    };
+    if vectors.iter().any(|x| x.len() != first.len()) {
+        return Err(format!("{}: vectors have different dimensions", path.display()).into());
+    }
+    Ok(())
+}
+
+fn parse<T, const N: usize>(
+    mut bytes: &[u8],
+    f: impl Fn([u8; N]) -> T,
+) -> Result<Vec<Vec<T>>, String> {
+    let mut result = Vec::new();
+    while let Some((&dim, rest)) = bytes.split_first_chunk::<4>() {
+        let dim = i32::from_le_bytes(dim);
+        let Some(len) = usize::try_from(dim).ok().filter(|&dim| dim > 0) else {
+            return Err(format!("vector {} has a bad dimension {dim}", result.len()));
+        };
+        if rest.len() < len * N {
+            return Err("the file is truncated".to_string());
+        }
+        let (elements, rest) = rest.split_at(len * N);
+        let (chunks, _) = elements.as_chunks::<N>();
+        result.push(chunks.iter().map(|&x| f(x)).collect());
+        bytes = rest;
+    }
+    if !bytes.is_empty() {
+        return Err("the file is truncated".to_string());
+    }
+    Ok(result)
+}
+
+#[test]
+fn test_parse() {
+    let mut bytes = Vec::new();
+    for vector in [[1.0_f32, 2.0], [3.0, 4.0]] {
+        bytes.extend(2_i32.to_le_bytes());
+        for x in vector {
+            bytes.extend(x.to_le_bytes());
+        }
+    }
+    let vectors = parse(&bytes, f32::from_le_bytes).unwrap();
+    assert_eq!(vectors, [[1.0, 2.0], [3.0, 4.0]]);
+    assert!(parse(&bytes[..bytes.len() - 1], f32::from_le_bytes).is_err());
+    assert!(parse(&0_i32.to_le_bytes(), f32::from_le_bytes).is_err());
+    let vectors = parse(&[3, 0, 0, 0, 7, 8, 9], |[x]: [u8; 1]| x as f32).unwrap();
+    assert_eq!(vectors, [[7.0, 8.0, 9.0]]);
+}
@@ -70,4 +70,8 @@ where
     pub fn push(&mut self, item: P::Item) {
         self.heap.push(item);
     }
+    pub fn clear(&mut self) {
+        self.front = None;
+        self.heap.clear();
+    }
 }
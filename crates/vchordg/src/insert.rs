@@ -14,6 +14,7 @@
 
 use crate::Opaque;
 use crate::candidates::Candidates;
+use crate::labels::overlaps;
 use crate::operator::{CloneAccessor, Operator, Vector};
 use crate::results::Results;
 use crate::tuples::*;
@@ -35,6 +36,7 @@ pub fn insert<'b, R: RelationRead + RelationWrite, O: Operator>(
     index: &'b R,
     vector: <O::Vector as VectorOwned>::Borrowed<'b>,
     payload: NonZero<u64>,
+    labels: &'b [i32],
     bump: &'b impl Bump,
     mut prefetch_vertices: impl PrefetcherSequenceFamily<'b, R> + 'b,
     prefetch_vectors: impl PrefetcherSequenceFamily<'b, R> + 'b,
@@ -81,6 +83,7 @@ pub fn insert<'b, R: RelationRead + RelationWrite, O: Operator>(
                 payload: Some(payload),
                 elements: rabitq::bits::pack_code(bits, &code.1),
                 pointers: vec![Pointer::new((u32::MAX, 0)); list_of_vector_bytes.len()], // a sentinel value
+                labels: labels.to_vec(),
             })
         };
         let mut vertex_guard = if let Some(guard) = index.search(vertex_bytes.len()) {
@@ -118,6 +121,11 @@ pub fn insert<'b, R: RelationRead + RelationWrite, O: Operator>(
         }
         (pointers_t, t)
     };
+    let starts = if !labels.is_empty() {
+        crate::labels::register(index, labels, t)
+    } else {
+        Vec::new()
+    };
     let start = if start.into_inner().is_none() {
         let mut meta_guard = index.write(0, false);
         let meta_bytes = meta_guard.get_mut(1).expect("data corruption");
@@ -133,120 +141,144 @@ pub fn insert<'b, R: RelationRead + RelationWrite, O: Operator>(
         start
     };
     let lut = O::Vector::preprocess(vector);
-    let mut visited = Visited::new();
     let mut candidates = Candidates::new(beam as usize, prefetch_vectors);
     let Some(s) = start.into_inner() else {
         return;
     };
-    {
-        visited.insert(s);
-        let vertex_guard = index.read(s.0);
-        let Some(vertex_bytes) = vertex_guard.get(s.1) else {
-            // the link is broken
-            return;
-        };
-        let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
-        let pointers_s: &[_] = bump.alloc_slice(vertex_tuple.pointers());
-        let score_s = O::process(
-            bits,
-            dim,
-            (vertex_tuple.metadata(), vertex_tuple.elements()),
-            &lut,
-        );
-        candidates.push((Reverse(score_s), AlwaysEqual((pointers_s, s))));
-    }
-    let mut iter = std::iter::from_fn(|| {
-        while let Some(((_, AlwaysEqual((pointers_u, u))), guards)) = candidates.pop() {
-            let Ok((dis_u, outs_u, _, _)) = crate::vectors::read::<R, O, _, _>(
-                by_prefetch::<R>(guards, pointers_u.iter().copied()),
-                LAccess::new(
-                    O::Vector::unpack(vector),
-                    O::DistanceAccessor::default_with_dimension(dim),
-                ),
-                copy_outs,
-            ) else {
+    // The unfiltered pass links `t` to its nearest vertices regardless of labels,
+    // which keeps the graph connected for unfiltered search. The filtered pass
+    // links `t` to the nearest vertices sharing labels with it.
+    let passes = std::iter::once((vec![s], None))
+        .chain((!labels.is_empty()).then_some((starts, Some(labels))));
+    let mut trace = Vec::new();
+    for (seeds, filter) in passes {
+        let mut visited = Visited::new();
+        candidates.clear();
+        for s in seeds {
+            if visited.contains(s) {
+                continue;
+            }
+            visited.insert(s);
+            let vertex_guard = index.read(s.0);
+            let Some(vertex_bytes) = vertex_guard.get(s.1) else {
                 // the link is broken
                 continue;
             };
-            let mut iterator = prefetch_vertices.prefetch(
-                outs_u
-                    .into_iter()
-                    .filter(|&x| !visited.contains(x))
-                    .collect::<VecDeque<_>>(),
+            let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
+            let pointers_s: &[_] = bump.alloc_slice(vertex_tuple.pointers());
+            let labels_s: &[_] = bump.alloc_slice(vertex_tuple.labels());
+            let score_s = O::process(
+                bits,
+                dim,
+                (vertex_tuple.metadata(), vertex_tuple.elements()),
+                &lut,
             );
-            while let Some((v, guards)) = iterator.next() {
-                visited.insert(v);
-                let vertex_guard = {
-                    let mut guards = guards;
-                    let r = guards.next().expect("internal");
-                    assert!(guards.next().is_none(), "internal");
-                    drop(guards);
-                    r
-                };
-                let Some(vertex_bytes) = vertex_guard.get(v.1) else {
+            candidates.push((Reverse(score_s), AlwaysEqual((pointers_s, s, labels_s))));
+        }
+        let mut iter = std::iter::from_fn(|| {
+            while let Some(((_, AlwaysEqual((pointers_u, u, labels_u))), guards)) = candidates.pop()
+            {
+                let Ok((dis_u, outs_u, _, _)) = crate::vectors::read::<R, O, _, _>(
+                    by_prefetch::<R>(guards, pointers_u.iter().copied()),
+                    LAccess::new(
+                        O::Vector::unpack(vector),
+                        O::DistanceAccessor::default_with_dimension(dim),
+                    ),
+                    copy_outs,
+                ) else {
                     // the link is broken
                     continue;
                 };
-                let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
-                let pointers_v: &[_] = bump.alloc_slice(vertex_tuple.pointers());
-                let score_v = O::process(
-                    bits,
-                    dim,
-                    (vertex_tuple.metadata(), vertex_tuple.elements()),
-                    &lut,
+                let mut iterator = prefetch_vertices.prefetch(
+                    outs_u
+                        .into_iter()
+                        .filter(|&x| !visited.contains(x))
+                        .collect::<VecDeque<_>>(),
                 );
-                candidates.push((Reverse(score_v), AlwaysEqual((pointers_v, v))));
+                while let Some((v, guards)) = iterator.next() {
+                    visited.insert(v);
+                    let vertex_guard = {
+                        let mut guards = guards;
+                        let r = guards.next().expect("internal");
+                        assert!(guards.next().is_none(), "internal");
+                        drop(guards);
+                        r
+                    };
+                    let Some(vertex_bytes) = vertex_guard.get(v.1) else {
+                        // the link is broken
+                        continue;
+                    };
+                    let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
+                    if filter.is_some_and(|labels| !overlaps(labels, vertex_tuple.labels())) {
+                        continue;
+                    }
+                    let pointers_v: &[_] = bump.alloc_slice(vertex_tuple.pointers());
+                    let labels_v: &[_] = bump.alloc_slice(vertex_tuple.labels());
+                    let score_v = O::process(
+                        bits,
+                        dim,
+                        (vertex_tuple.metadata(), vertex_tuple.elements()),
+                        &lut,
+                    );
+                    candidates.push((Reverse(score_v), AlwaysEqual((pointers_v, v, labels_v))));
+                }
+                return Some((Reverse(dis_u), AlwaysEqual((pointers_u, u, labels_u))));
+            }
+            None
+        });
+        let mut results = Results::new(ef as _);
+        for element @ (Reverse(dis_c), _) in iter.by_ref() {
+            results.push(element);
+            if results
+                .peek_ef_th()
+                .map(|dis_e| dis_e < dis_c)
+                .unwrap_or_default()
+            {
+                break;
             }
-            return Some((Reverse(dis_u), AlwaysEqual((pointers_u, u))));
-        }
-        None
-    });
-    let mut results = Results::new(ef as _);
-    for element @ (Reverse(dis_c), _) in iter.by_ref() {
-        results.push(element);
-        if results
-            .peek_ef_th()
-            .map(|dis_e| dis_e < dis_c)
-            .unwrap_or_default()
-        {
-            break;
         }
-    }
-    let trace = {
         let (left, right) = results.into_inner();
-        left.into_iter()
-            .map(|(dis_v, v)| (Reverse(dis_v), v))
-            .chain(right)
-            .flat_map(|item| {
-                let (Reverse(dis_u), AlwaysEqual((pointers_u, u))) = item;
-                let Ok((vector_u, _, _, _)) = crate::vectors::read::<R, O, _, _>(
-                    by_read(index, pointers_u.iter().copied()),
-                    CloneAccessor::<O::Vector>::default_with_dimension(dim),
-                    copy_nothing,
-                ) else {
-                    // the link is broken
-                    return None;
-                };
-                Some(((Reverse(dis_u), AlwaysEqual((pointers_u, u))), vector_u))
-            })
-            .collect::<Vec<_>>()
-    };
+        trace.extend(
+            left.into_iter()
+                .map(|(dis_v, v)| (Reverse(dis_v), v))
+                .chain(right),
+        );
+    }
+    let trace = trace
+        .into_iter()
+        .flat_map(|item| {
+            let (Reverse(dis_u), AlwaysEqual((pointers_u, u, labels_u))) = item;
+            let Ok((vector_u, _, _, _)) = crate::vectors::read::<R, O, _, _>(
+                by_read(index, pointers_u.iter().copied()),
+                CloneAccessor::<O::Vector>::default_with_dimension(dim),
+                copy_nothing,
+            ) else {
+                // the link is broken
+                return None;
+            };
+            Some((
+                (Reverse(dis_u), AlwaysEqual((pointers_u, u, labels_u))),
+                vector_u,
+            ))
+        })
+        .collect::<Vec<_>>();
     let outs = crate::prune::prune(
         |x, y| O::distance(x.as_borrowed(), y.as_borrowed()),
-        (bump.alloc_slice(&pointers_t) as &[_], t),
+        (bump.alloc_slice(&pointers_t) as &[_], t, labels),
         trace.into_iter(),
         m,
         &alpha,
-        |(_, u)| *u,
+        |(_, u, _)| *u,
+        |&(_, _, labels)| labels,
         O::DISTANCE == DistanceKind::L2S,
     );
     let _ = update::<R, O>(
         (index, pointers_t.as_slice()),
         (version_t, VecDeque::new()),
         outs.iter()
-            .map(|&(Reverse(dis_u), AlwaysEqual((_, u)))| (u, dis_u)),
+            .map(|&(Reverse(dis_u), AlwaysEqual((_, u, _)))| (u, dis_u)),
     );
-    for (Reverse(dis_t), AlwaysEqual((pointers_u, u))) in outs {
+    for (Reverse(dis_t), AlwaysEqual((pointers_u, u, labels_u))) in outs {
         'occ: loop {
             let Ok((neighbours_u, _, version)) =
                 crate::vectors::read_without_accessor::<R, O, _>((index, pointers_u), copy_all)
@@ -268,10 +300,11 @@ pub fn insert<'b, R: RelationRead + RelationWrite, O: Operator>(
                     };
                     let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
                     let pointers_u = vertex_tuple.pointers().to_vec();
-                    Some((Reverse(dis_u), AlwaysEqual((pointers_u, u))))
+                    let labels_u = vertex_tuple.labels().to_vec();
+                    Some((Reverse(dis_u), AlwaysEqual((pointers_u, u, labels_u))))
                 })
                 .flat_map(|item| {
-                    let (Reverse(dis_u), AlwaysEqual((pointers_u, u))) = item;
+                    let (Reverse(dis_u), AlwaysEqual((pointers_u, u, labels_u))) = item;
                     let Ok((vector_u, _, _, _)) = crate::vectors::read::<R, O, _, _>(
                         by_read(index, pointers_u.iter().copied()),
                         CloneAccessor::<O::Vector>::default_with_dimension(dim),
@@ -280,23 +313,27 @@ pub fn insert<'b, R: RelationRead + RelationWrite, O: Operator>(
                         // the link is broken
                         return None;
                     };
-                    Some(((Reverse(dis_u), AlwaysEqual((pointers_u, u))), vector_u))
+                    Some((
+                        (Reverse(dis_u), AlwaysEqual((pointers_u, u, labels_u))),
+                        vector_u,
+                    ))
                 })
                 .collect::<Vec<_>>();
             let outs = crate::prune::prune(
                 |x, y| O::distance(x.as_borrowed(), y.as_borrowed()),
-                (pointers_u.to_vec(), u),
+                (pointers_u.to_vec(), u, labels_u.to_vec()),
                 trace.into_iter(),
                 m,
                 &alpha,
-                |(_, u)| *u,
+                |(_, u, _)| *u,
+                |(_, _, labels)| labels.as_slice(),
                 O::DISTANCE == DistanceKind::L2S,
             );
             if update::<R, O>(
                 (index, pointers_u),
                 (version, neighbours_u),
                 outs.iter()
-                    .map(|&(Reverse(dis_u), AlwaysEqual((_, u)))| (u, dis_u)),
+                    .map(|&(Reverse(dis_u), AlwaysEqual((_, u, _)))| (u, dis_u)),
             ) != Ok(false)
             {
                 break 'occ;
@@ -0,0 +1,146 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::Opaque;
+use crate::tuples::{LabelTuple, Pointer, Tuple, WithReader};
+use index::relation::{Page, PageGuard, RelationRead, RelationWrite};
+
+// Label tuples live on the meta page (after the meta tuple) and on the pages
+// chained from its `next`. Each label points to the first vertex inserted
+// with it, which serves as the entry point of filtered search.
+
+fn collect<P: Page>(page: &P, first: u16, labels: &[i32], starts: &mut [Option<(u32, u16)>]) {
+    for i in first..=page.len() {
+        if let Some(bytes) = page.get(i) {
+            let tuple = LabelTuple::deserialize_ref(bytes);
+            if let Ok(j) = labels.binary_search(&tuple.label()) {
+                starts[j] = Some(tuple.start().into_inner());
+            }
+        }
+    }
+}
+
+/// Returns the entry points of `labels`, which must be sorted and deduplicated.
+pub fn lookup<R: RelationRead>(index: &R, labels: &[i32]) -> Vec<Option<(u32, u16)>>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let mut starts = vec![None; labels.len()];
+    if labels.is_empty() {
+        return starts;
+    }
+    let mut current = 0;
+    while current != u32::MAX {
+        let guard = index.read(current);
+        let first = if current == 0 { 2 } else { 1 };
+        collect(&*guard, first, labels, &mut starts);
+        current = guard.get_opaque().next;
+    }
+    starts
+}
+
+/// Makes `t` the entry point of every label in `labels` that has none, and
+/// returns the entry points of the others.
+pub fn register<R: RelationRead + RelationWrite>(
+    index: &R,
+    labels: &[i32],
+    t: (u32, u16),
+) -> Vec<(u32, u16)>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let starts = lookup(index, labels);
+    if starts.iter().all(Option::is_some) {
+        return starts.into_iter().flatten().collect();
+    }
+    // the meta page lock serializes registrations
+    let mut meta_guard = index.write(0, false);
+    let mut starts = vec![None; labels.len()];
+    collect(&*meta_guard, 2, labels, &mut starts);
+    let mut tail = None;
+    let mut current = meta_guard.get_opaque().next;
+    while current != u32::MAX {
+        let guard = index.read(current);
+        collect(&*guard, 1, labels, &mut starts);
+        tail = Some(current);
+        current = guard.get_opaque().next;
+    }
+    let existing = starts.iter().flatten().copied().collect::<Vec<_>>();
+    let missing = labels
+        .iter()
+        .zip(starts.iter())
+        .filter(|(_, start)| start.is_none())
+        .map(|(&label, _)| label);
+    let mut tail_guard = tail.map(|tail| index.write(tail, false));
+    for label in missing {
+        let bytes = LabelTuple::serialize(&LabelTuple {
+            label,
+            start: Pointer::new(t),
+        });
+        let guard = match tail_guard.as_mut() {
+            Some(guard) => &mut **guard,
+            None => &mut *meta_guard,
+        };
+        if guard.alloc(&bytes).is_some() {
+            continue;
+        }
+        let mut extend = index.extend(
+            Opaque {
+                next: u32::MAX,
+                link: u32::MAX,
+            },
+            false,
+        );
+        guard.get_opaque_mut().next = extend.id();
+        extend
+            .alloc(&bytes)
+            .expect("implementation: a free page cannot accommodate a single tuple");
+        tail_guard = Some(extend);
+    }
+    existing
+}
+
+/// Returns the entry points of all labels.
+pub fn starts<R: RelationRead>(index: &R) -> Vec<(u32, u16)>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let mut starts = Vec::new();
+    let mut current = 0;
+    while current != u32::MAX {
+        let guard = index.read(current);
+        let first = if current == 0 { 2 } else { 1 };
+        for i in first..=guard.len() {
+            if let Some(bytes) = guard.get(i) {
+                let tuple = LabelTuple::deserialize_ref(bytes);
+                starts.push(tuple.start().into_inner());
+            }
+        }
+        current = guard.get_opaque().next;
+    }
+    starts
+}
+
+/// Returns whether two sorted label sets intersect.
+pub fn overlaps(a: &[i32], b: &[i32]) -> bool {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => return true,
+        }
+    }
+    false
+}
@@ -16,13 +16,17 @@ mod build;
 mod bulkdelete;
 mod candidates;
 mod insert;
+mod labels;
 mod maintain;
 mod prewarm;
 mod prune;
 mod results;
 mod search;
+mod truncate;
 mod tuples;
+mod upgrade;
 mod vectors;
+mod verify;
 mod visited;
 
 pub mod operator;
@@ -34,6 +38,9 @@ pub use insert::insert;
 pub use maintain::maintain;
 pub use prewarm::prewarm;
 pub use search::search;
+pub use truncate::truncate;
+pub use upgrade::upgrade;
+pub use verify::{Problem, Verification, dimension, reinsert, verify};
 
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
@@ -39,6 +39,9 @@ where
     let Some(s) = start.into_inner() else {
         return;
     };
+    let mut starts = crate::labels::starts(index);
+    starts.push(s);
+    starts.sort_unstable();
     // do it's best to remove broken edges
     {
         let mut current = link;
@@ -51,12 +54,13 @@ where
                     let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
                     if vertex_tuple.payload().is_some() {
                         let pointers_u = vertex_tuple.pointers().to_vec();
-                        members.push((pointers_u, (vertex_guard.id(), i)));
+                        let labels_u = vertex_tuple.labels().to_vec();
+                        members.push((pointers_u, (vertex_guard.id(), i), labels_u));
                     }
                 }
             }
             let next = { vertex_guard }.get_opaque().next;
-            for (pointers_u, u) in members {
+            for (pointers_u, u, labels_u) in members {
                 'occ: loop {
                     let Ok((vector_u, neighbours_u, _, version)) = crate::vectors::read::<R, O, _, _>(
                         by_read::<R>(index, pointers_u.iter().copied()),
@@ -77,6 +81,7 @@ where
                             };
                             let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
                             let pointers_v = vertex_tuple.pointers().to_vec();
+                            let labels_v = vertex_tuple.labels().to_vec();
                             let payload_v = vertex_tuple.payload();
                             drop(vertex_guard);
                             let Ok((vector_v, outs_v, _, _)) = crate::vectors::read::<R, O, _, _>(
@@ -89,7 +94,7 @@ where
                             };
                             if payload_v.is_some() {
                                 trace.push((
-                                    (Reverse(dis_v), AlwaysEqual((pointers_v, v))),
+                                    (Reverse(dis_v), AlwaysEqual((pointers_v, v, labels_v))),
                                     vector_v,
                                 ));
                             } else {
@@ -104,6 +109,7 @@ where
                             };
                             let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
                             let pointers_v = vertex_tuple.pointers().to_vec();
+                            let labels_v = vertex_tuple.labels().to_vec();
                             let payload_v = vertex_tuple.payload();
                             drop(vertex_guard);
                             let Ok((vector_v, _, _, _)) = crate::vectors::read::<R, O, _, _>(
@@ -118,7 +124,7 @@ where
                                 let dis_v =
                                     O::distance(vector_u.as_borrowed(), vector_v.as_borrowed());
                                 trace.push((
-                                    (Reverse(dis_v), AlwaysEqual((pointers_v, v))),
+                                    (Reverse(dis_v), AlwaysEqual((pointers_v, v, labels_v))),
                                     vector_v,
                                 ));
                             }
@@ -127,18 +133,19 @@ where
                     };
                     let outs = crate::prune::prune(
                         |x, y| O::distance(x.as_borrowed(), y.as_borrowed()),
-                        (pointers_u.to_vec(), u),
+                        (pointers_u.to_vec(), u, labels_u.clone()),
                         trace.into_iter(),
                         m,
                         &alpha,
-                        |(_, u)| *u,
+                        |(_, u, _)| *u,
+                        |(_, _, labels)| labels.as_slice(),
                         O::DISTANCE == DistanceKind::L2S,
                     );
                     if update::<R, O>(
                         (index, pointers_u.as_slice()),
                         (version, neighbours_u),
                         outs.iter()
-                            .map(|&(Reverse(dis_u), AlwaysEqual((_, u)))| (u, dis_u)),
+                            .map(|&(Reverse(dis_u), AlwaysEqual((_, u, _)))| (u, dis_u)),
                     ) != Ok(false)
                     {
                         break 'occ;
@@ -159,7 +166,7 @@ where
                 if let Some(bytes) = vertex_guard.get(i) {
                     let tuple = VertexTuple::deserialize_ref(bytes);
                     let p = tuple.payload();
-                    if p.is_none() && (current, i) != s {
+                    if p.is_none() && starts.binary_search(&(current, i)).is_err() {
                         vertex_guard.free(i);
                     } else {
                         let iter = tuple.pointers().iter().map(|pointer| pointer.into_inner());
@@ -15,15 +15,18 @@
 use crate::types::DistanceKind;
 use distance::Distance;
 use index_accessor::{
-    Accessor1, Accessor2, ByteDistanceAccessor, DefaultWithDimension, DistanceAccessor, Dot,
-    HalfbyteDistanceAccessor, L2S,
+    Accessor1, Accessor2, BitsDistanceAccessor, ByteDistanceAccessor, DefaultWithDimension,
+    DistanceAccessor, Dot, HalfbyteDistanceAccessor, L2S,
 };
 use rabitq::bits::Bits;
-use simd::{Floating, f16};
+use simd::{Floating, bf16, f16};
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use vector::rabitq1::Rabitq1Owned;
+use vector::rabitq2::Rabitq2Owned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
+use vector::veci8::Veci8Owned;
 use vector::vect::VectOwned;
 use vector::{VectorBorrowed, VectorOwned};
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
@@ -123,6 +126,46 @@ impl Vector for VectOwned<f16> {
     }
 }
 
+impl Vector for VectOwned<bf16> {
+    type Metadata = ();
+
+    type Element = bf16;
+
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (vector.slice(), ())
+    }
+
+    fn split(
+        vector: Self::Borrowed<'_>,
+        m: usize,
+    ) -> (Vec<&[Self::Element]>, (&[Self::Element], Self::Metadata)) {
+        let slice = vector.slice();
+        let tailing = (size_of::<crate::tuples::OptionNeighbour>() * m)
+            .next_multiple_of(crate::tuples::ALIGN);
+        assert!(tailing <= 8000);
+        if slice.len() <= (8000 - tailing) / size_of::<bf16>() {
+            return (vec![], (slice, ()));
+        }
+        let (l, r) = slice.split_at(slice.len() - (8000 - tailing) / size_of::<bf16>());
+        (
+            l.chunks(8000 / size_of::<bf16>()).collect::<Vec<_>>(),
+            (r, ()),
+        )
+    }
+
+    fn pack(_: u32, elements: Vec<Self::Element>, (): Self::Metadata) -> Self {
+        VectOwned::new(elements)
+    }
+
+    fn code(bits: Bits, vector: Self::Borrowed<'_>) -> rabitq::bits::Code {
+        rabitq::bits::code(bits, &bf16::vector_to_f32(vector.slice()))
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> rabitq::bits::binary::BinaryLut {
+        rabitq::bits::binary::preprocess(&bf16::vector_to_f32(vector.slice()))
+    }
+}
+
 impl Vector for Rabitq8Owned {
     type Metadata = [f32; 4];
 
@@ -249,6 +292,174 @@ impl Vector for Rabitq4Owned {
     }
 }
 
+impl Vector for Rabitq2Owned {
+    type Metadata = [f32; 4];
+
+    type Element = u8;
+
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (
+            vector.packed_code(),
+            [
+                vector.sum_of_x2(),
+                vector.norm_of_lattice(),
+                vector.sum_of_code(),
+                vector.sum_of_abs_x(),
+            ],
+        )
+    }
+
+    fn split(
+        vector: Self::Borrowed<'_>,
+        m: usize,
+    ) -> (Vec<&[Self::Element]>, (&[Self::Element], Self::Metadata)) {
+        let metadata = [
+            vector.sum_of_x2(),
+            vector.norm_of_lattice(),
+            vector.sum_of_code(),
+            vector.sum_of_abs_x(),
+        ];
+        let slice = vector.packed_code();
+        let tailing = (size_of::<crate::tuples::OptionNeighbour>() * m)
+            .next_multiple_of(crate::tuples::ALIGN);
+        assert!(tailing <= 8000);
+        if slice.len() <= 8000 - tailing {
+            return (vec![], (slice, metadata));
+        }
+        let (l, r) = slice.split_at(slice.len() - (8000 - tailing));
+        (l.chunks(8000).collect::<Vec<_>>(), (r, metadata))
+    }
+
+    fn pack(dim: u32, code: Vec<Self::Element>, [_0, _1, _2, _3]: Self::Metadata) -> Self {
+        Rabitq2Owned::new(dim, _0, _1, _2, _3, code)
+    }
+
+    fn code(bits: Bits, vector: Self::Borrowed<'_>) -> rabitq::bits::Code {
+        let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+        let mut result = Vec::with_capacity(vector.dim() as _);
+        for c in vector.unpacked_code() {
+            let base = -0.5 * ((1 << 2) - 1) as f32;
+            result.push((base + c as f32) * scale);
+        }
+        rabitq::bits::code(bits, &result)
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> rabitq::bits::binary::BinaryLut {
+        let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+        let mut result = Vec::with_capacity(vector.dim() as _);
+        for c in vector.unpacked_code() {
+            let base = -0.5 * ((1 << 2) - 1) as f32;
+            result.push((base + c as f32) * scale);
+        }
+        rabitq::bits::binary::preprocess(&result)
+    }
+}
+
+impl Vector for Rabitq1Owned {
+    type Metadata = [f32; 4];
+
+    type Element = u8;
+
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (
+            vector.packed_code(),
+            [
+                vector.sum_of_x2(),
+                vector.norm_of_lattice(),
+                vector.sum_of_code(),
+                vector.sum_of_abs_x(),
+            ],
+        )
+    }
+
+    fn split(
+        vector: Self::Borrowed<'_>,
+        m: usize,
+    ) -> (Vec<&[Self::Element]>, (&[Self::Element], Self::Metadata)) {
+        let metadata = [
+            vector.sum_of_x2(),
+            vector.norm_of_lattice(),
+            vector.sum_of_code(),
+            vector.sum_of_abs_x(),
+        ];
+        let slice = vector.packed_code();
+        let tailing = (size_of::<crate::tuples::OptionNeighbour>() * m)
+            .next_multiple_of(crate::tuples::ALIGN);
+        assert!(tailing <= 8000);
+        if slice.len() <= 8000 - tailing {
+            return (vec![], (slice, metadata));
+        }
+        let (l, r) = slice.split_at(slice.len() - (8000 - tailing));
+        (l.chunks(8000).collect::<Vec<_>>(), (r, metadata))
+    }
+
+    fn pack(dim: u32, code: Vec<Self::Element>, [_0, _1, _2, _3]: Self::Metadata) -> Self {
+        Rabitq1Owned::new(dim, _0, _1, _2, _3, code)
+    }
+
+    fn code(bits: Bits, vector: Self::Borrowed<'_>) -> rabitq::bits::Code {
+        let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+        let mut result = Vec::with_capacity(vector.dim() as _);
+        for c in vector.unpacked_code() {
+            let base = -0.5 * ((1 << 1) - 1) as f32;
+            result.push((base + c as f32) * scale);
+        }
+        rabitq::bits::code(bits, &result)
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> rabitq::bits::binary::BinaryLut {
+        let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+        let mut result = Vec::with_capacity(vector.dim() as _);
+        for c in vector.unpacked_code() {
+            let base = -0.5 * ((1 << 1) - 1) as f32;
+            result.push((base + c as f32) * scale);
+        }
+        rabitq::bits::binary::preprocess(&result)
+    }
+}
+
+impl Vector for Veci8Owned {
+    type Metadata = ();
+
+    type Element = i8;
+
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (vector.slice(), ())
+    }
+
+    fn split(
+        vector: Self::Borrowed<'_>,
+        m: usize,
+    ) -> (Vec<&[Self::Element]>, (&[Self::Element], Self::Metadata)) {
+        let slice = vector.slice();
+        let tailing = (size_of::<crate::tuples::OptionNeighbour>() * m)
+            .next_multiple_of(crate::tuples::ALIGN);
+        assert!(tailing <= 8000);
+        if slice.len() <= (8000 - tailing) / size_of::<i8>() {
+            return (vec![], (slice, ()));
+        }
+        let (l, r) = slice.split_at(slice.len() - (8000 - tailing) / size_of::<i8>());
+        (
+            l.chunks(8000 / size_of::<i8>()).collect::<Vec<_>>(),
+            (r, ()),
+        )
+    }
+
+    fn pack(_: u32, elements: Vec<Self::Element>, (): Self::Metadata) -> Self {
+        Veci8Owned::new(elements)
+    }
+
+    fn code(bits: Bits, vector: Self::Borrowed<'_>) -> rabitq::bits::Code {
+        let vector = vector.slice().iter().map(|&x| x as f32).collect::<Vec<_>>();
+        rabitq::bits::code(bits, &vector)
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> rabitq::bits::binary::BinaryLut {
+        let vector = vector.slice().iter().map(|&x| x as f32).collect::<Vec<_>>();
+        rabitq::bits::binary::preprocess(&vector)
+    }
+}
+
 pub trait Operator: 'static + Debug + Copy {
     const DISTANCE: DistanceKind;
 
@@ -418,6 +629,72 @@ impl Operator for Op<VectOwned<f16>, Dot> {
     }
 }
 
+impl Operator for Op<VectOwned<bf16>, L2S> {
+    const DISTANCE: DistanceKind = DistanceKind::L2S;
+
+    type Vector = VectOwned<bf16>;
+
+    type DistanceAccessor = DistanceAccessor<VectOwned<bf16>, L2S>;
+
+    fn process(
+        bits: Bits,
+        dim: u32,
+        code: ([f32; 3], &[u64]),
+        lut: &rabitq::bits::binary::BinaryLut,
+    ) -> Distance {
+        use rabitq::bits::CodeMetadata;
+        let sum = rabitq::bits::binary::accumulate(bits, code.1, &lut.1);
+        let (distance,) = rabitq::bits::binary::half_process_l2s(
+            bits,
+            dim,
+            sum,
+            CodeMetadata::from_array(code.0),
+            lut.0,
+        );
+        Distance::from_f32(distance)
+    }
+
+    fn distance(
+        lhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        rhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> Distance {
+        lhs.operator_l2s(rhs)
+    }
+}
+
+impl Operator for Op<VectOwned<bf16>, Dot> {
+    const DISTANCE: DistanceKind = DistanceKind::Dot;
+
+    type Vector = VectOwned<bf16>;
+
+    type DistanceAccessor = DistanceAccessor<VectOwned<bf16>, Dot>;
+
+    fn process(
+        bits: Bits,
+        dim: u32,
+        code: ([f32; 3], &[u64]),
+        lut: &rabitq::bits::binary::BinaryLut,
+    ) -> Distance {
+        use rabitq::bits::CodeMetadata;
+        let sum = rabitq::bits::binary::accumulate(bits, code.1, &lut.1);
+        let (distance,) = rabitq::bits::binary::half_process_dot(
+            bits,
+            dim,
+            sum,
+            CodeMetadata::from_array(code.0),
+            lut.0,
+        );
+        Distance::from_f32(distance)
+    }
+
+    fn distance(
+        lhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        rhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> Distance {
+        lhs.operator_dot(rhs)
+    }
+}
+
 impl Operator for Op<Rabitq8Owned, L2S> {
     const DISTANCE: DistanceKind = DistanceKind::L2S;
 
@@ -550,6 +827,204 @@ impl Operator for Op<Rabitq4Owned, Dot> {
     }
 }
 
+impl Operator for Op<Rabitq2Owned, L2S> {
+    const DISTANCE: DistanceKind = DistanceKind::L2S;
+
+    type Vector = Rabitq2Owned;
+
+    type DistanceAccessor = BitsDistanceAccessor<Rabitq2Owned, L2S>;
+
+    fn process(
+        bits: Bits,
+        dim: u32,
+        code: ([f32; 3], &[u64]),
+        lut: &rabitq::bits::binary::BinaryLut,
+    ) -> Distance {
+        use rabitq::bits::CodeMetadata;
+        let sum = rabitq::bits::binary::accumulate(bits, code.1, &lut.1);
+        let (distance,) = rabitq::bits::binary::half_process_l2s(
+            bits,
+            dim,
+            sum,
+            CodeMetadata::from_array(code.0),
+            lut.0,
+        );
+        Distance::from_f32(distance)
+    }
+
+    fn distance(
+        lhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        rhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> Distance {
+        lhs.operator_l2s(rhs)
+    }
+}
+
+impl Operator for Op<Rabitq2Owned, Dot> {
+    const DISTANCE: DistanceKind = DistanceKind::Dot;
+
+    type Vector = Rabitq2Owned;
+
+    type DistanceAccessor = BitsDistanceAccessor<Rabitq2Owned, Dot>;
+
+    fn process(
+        bits: Bits,
+        dim: u32,
+        code: ([f32; 3], &[u64]),
+        lut: &rabitq::bits::binary::BinaryLut,
+    ) -> Distance {
+        use rabitq::bits::CodeMetadata;
+        let sum = rabitq::bits::binary::accumulate(bits, code.1, &lut.1);
+        let (distance,) = rabitq::bits::binary::half_process_dot(
+            bits,
+            dim,
+            sum,
+            CodeMetadata::from_array(code.0),
+            lut.0,
+        );
+        Distance::from_f32(distance)
+    }
+
+    fn distance(
+        lhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        rhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> Distance {
+        lhs.operator_dot(rhs)
+    }
+}
+
+impl Operator for Op<Rabitq1Owned, L2S> {
+    const DISTANCE: DistanceKind = DistanceKind::L2S;
+
+    type Vector = Rabitq1Owned;
+
+    type DistanceAccessor = BitsDistanceAccessor<Rabitq1Owned, L2S>;
+
+    fn process(
+        bits: Bits,
+        dim: u32,
+        code: ([f32; 3], &[u64]),
+        lut: &rabitq::bits::binary::BinaryLut,
+    ) -> Distance {
+        use rabitq::bits::CodeMetadata;
+        let sum = rabitq::bits::binary::accumulate(bits, code.1, &lut.1);
+        let (distance,) = rabitq::bits::binary::half_process_l2s(
+            bits,
+            dim,
+            sum,
+            CodeMetadata::from_array(code.0),
+            lut.0,
+        );
+        Distance::from_f32(distance)
+    }
+
+    fn distance(
+        lhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        rhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> Distance {
+        lhs.operator_l2s(rhs)
+    }
+}
+
+impl Operator for Op<Rabitq1Owned, Dot> {
+    const DISTANCE: DistanceKind = DistanceKind::Dot;
+
+    type Vector = Rabitq1Owned;
+
+    type DistanceAccessor = BitsDistanceAccessor<Rabitq1Owned, Dot>;
+
+    fn process(
+        bits: Bits,
+        dim: u32,
+        code: ([f32; 3], &[u64]),
+        lut: &rabitq::bits::binary::BinaryLut,
+    ) -> Distance {
+        use rabitq::bits::CodeMetadata;
+        let sum = rabitq::bits::binary::accumulate(bits, code.1, &lut.1);
+        let (distance,) = rabitq::bits::binary::half_process_dot(
+            bits,
+            dim,
+            sum,
+            CodeMetadata::from_array(code.0),
+            lut.0,
+        );
+        Distance::from_f32(distance)
+    }
+
+    fn distance(
+        lhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        rhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> Distance {
+        lhs.operator_dot(rhs)
+    }
+}
+
+impl Operator for Op<Veci8Owned, L2S> {
+    const DISTANCE: DistanceKind = DistanceKind::L2S;
+
+    type Vector = Veci8Owned;
+
+    type DistanceAccessor = DistanceAccessor<Veci8Owned, L2S>;
+
+    fn process(
+        bits: Bits,
+        dim: u32,
+        code: ([f32; 3], &[u64]),
+        lut: &rabitq::bits::binary::BinaryLut,
+    ) -> Distance {
+        use rabitq::bits::CodeMetadata;
+        let sum = rabitq::bits::binary::accumulate(bits, code.1, &lut.1);
+        let (distance,) = rabitq::bits::binary::half_process_l2s(
+            bits,
+            dim,
+            sum,
+            CodeMetadata::from_array(code.0),
+            lut.0,
+        );
+        Distance::from_f32(distance)
+    }
+
+    fn distance(
+        lhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        rhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> Distance {
+        lhs.operator_l2s(rhs)
+    }
+}
+
+impl Operator for Op<Veci8Owned, Dot> {
+    const DISTANCE: DistanceKind = DistanceKind::Dot;
+
+    type Vector = Veci8Owned;
+
+    type DistanceAccessor = DistanceAccessor<Veci8Owned, Dot>;
+
+    fn process(
+        bits: Bits,
+        dim: u32,
+        code: ([f32; 3], &[u64]),
+        lut: &rabitq::bits::binary::BinaryLut,
+    ) -> Distance {
+        use rabitq::bits::CodeMetadata;
+        let sum = rabitq::bits::binary::accumulate(bits, code.1, &lut.1);
+        let (distance,) = rabitq::bits::binary::half_process_dot(
+            bits,
+            dim,
+            sum,
+            CodeMetadata::from_array(code.0),
+            lut.0,
+        );
+        Distance::from_f32(distance)
+    }
+
+    fn distance(
+        lhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        rhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> Distance {
+        lhs.operator_dot(rhs)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CloneAccessor<V: Vector>(u32, Vec<V::Element>);
 
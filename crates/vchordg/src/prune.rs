@@ -23,6 +23,7 @@ pub fn prune<T, V, K: Ord>(
     m: u32,
     alpha: &[f32],
     key: impl Fn(&T) -> K,
+    labels: impl Fn(&T) -> &[i32],
     is_l2s: bool,
 ) -> Vec<(Reverse<Distance>, AlwaysEqual<T>)> {
     // V ← (V ∪ Nout(p)) \ {p}
@@ -37,7 +38,7 @@ pub fn prune<T, V, K: Ord>(
         if result.len() == m as usize {
             break;
         }
-        trace = robust_prune(&mut d, m, alpha, &mut result, trace);
+        trace = robust_prune(&mut d, labels(&u), &labels, m, alpha, &mut result, trace);
     }
     if !(result.len() == m as usize) {
         result.extend(trace);
@@ -48,6 +49,8 @@ pub fn prune<T, V, K: Ord>(
 
 fn robust_prune<T, V>(
     mut d: impl FnMut(&V, &V) -> Distance,
+    labels_p: &[i32],
+    labels: impl Fn(&T) -> &[i32],
     m: u32,
     alpha: f32,
     result: &mut Vec<((Reverse<Distance>, AlwaysEqual<T>), V)>,
@@ -58,8 +61,10 @@ fn robust_prune<T, V>(
         if result.len() == m as usize {
             break;
         }
+        // a neighbour only dominates `u` if it carries all labels shared by `p` and `u`
         let check = result
             .iter()
+            .filter(|((_, AlwaysEqual(v)), _)| covers(labels_p, labels(&u), labels(v)))
             .map(|(_, vector_v)| d(&vector_u, vector_v))
             .all(|dis| dis_u.to_f32() < alpha * dis.to_f32());
         if check {
@@ -70,3 +75,31 @@ fn robust_prune<T, V>(
     }
     pruned
 }
+
+// whether `a ∩ b ⊆ c`, all of which are sorted
+fn covers(a: &[i32], b: &[i32], c: &[i32]) -> bool {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                if c.binary_search(&a[i]).is_err() {
+                    return false;
+                }
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    true
+}
+
+#[test]
+fn test_covers() {
+    assert!(covers(&[], &[1, 2], &[]));
+    assert!(covers(&[1, 3], &[2, 4], &[]));
+    assert!(covers(&[1, 2, 3], &[2, 3, 4], &[0, 2, 3]));
+    assert!(!covers(&[1, 2, 3], &[2, 3, 4], &[2]));
+    assert!(!covers(&[5], &[5], &[4, 6]));
+}
@@ -14,6 +14,7 @@
 
 use crate::Opaque;
 use crate::candidates::Candidates;
+use crate::labels::overlaps;
 use crate::operator::{Operator, Vector};
 use crate::results::Results;
 use crate::tuples::*;
@@ -36,6 +37,7 @@ pub fn search<'b, R: RelationRead, O: Operator>(
     vector: <O::Vector as VectorOwned>::Borrowed<'b>,
     ef_search: u32,
     beam_search: u32,
+    labels: Option<&'b [i32]>,
     bump: &'b impl Bump,
     mut prefetch_vertices: impl PrefetcherSequenceFamily<'b, R> + 'b,
     prefetch_vectors: impl PrefetcherSequenceFamily<'b, R> + 'b,
@@ -56,15 +58,22 @@ where
     let lut = O::Vector::preprocess(vector);
     let mut visited = Visited::new();
     let mut candidates = Candidates::new(beam as usize, prefetch_vectors);
-    let Some(s) = start.into_inner() else {
-        return Box::new(std::iter::empty());
+    let starts = match labels {
+        None => start.into_inner().into_iter().collect::<Vec<_>>(),
+        Some(labels) => crate::labels::lookup(index, labels)
+            .into_iter()
+            .flatten()
+            .collect(),
     };
-    {
+    for s in starts {
+        if visited.contains(s) {
+            continue;
+        }
         visited.insert(s);
         let vertex_guard = index.read(s.0);
         let Some(vertex_bytes) = vertex_guard.get(s.1) else {
             // the link is broken
-            return Box::new(std::iter::empty());
+            continue;
         };
         let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
         let pointers_s: &[_] = bump.alloc_slice(vertex_tuple.pointers());
@@ -109,6 +118,9 @@ where
                     continue;
                 };
                 let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
+                if labels.is_some_and(|labels| !overlaps(labels, vertex_tuple.labels())) {
+                    continue;
+                }
                 let pointers_v = bump.alloc_slice(vertex_tuple.pointers());
                 let score_v = O::process(
                     bits,
@@ -138,3 +150,79 @@ where
     });
     Box::new(search.filter_map(|(dis_u, AlwaysEqual(payload_u))| Some((dis_u, payload_u?))))
 }
+
+#[test]
+fn test_search_in_memory() {
+    use crate::operator::Op;
+    use crate::types::{DistanceKind, VchordgIndexOptions, VectorKind, VectorOptions};
+    use index::fetch::Fetch;
+    use index::memory::MemoryRelation;
+    use index::prefetcher::{PlainPrefetcher, Sequence};
+    use index_accessor::L2S;
+    use vector::vect::VectOwned;
+
+    struct MakePlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherSequenceFamily<'b, R> for MakePlainPrefetcher<'b, R> {
+        type P<S: Sequence>
+            = PlainPrefetcher<'b, R, S>
+        where
+            S::Item: Fetch<'b>;
+
+        fn prefetch<S: Sequence>(&mut self, seq: S) -> Self::P<S>
+        where
+            S::Item: Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, seq)
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    type O = Op<VectOwned<f32>, L2S>;
+    let dim = 16;
+    let vectors = (0..300)
+        .map(|_| VectOwned::new((0..dim).map(|_| rand::random::<f32>()).collect()))
+        .collect::<Vec<_>>();
+    let index = MemoryRelation::new();
+    let vector_options = VectorOptions {
+        dim,
+        v: VectorKind::Vecf32,
+        d: DistanceKind::L2S,
+    };
+    crate::build::<_, O>(vector_options, VchordgIndexOptions::default(), &index);
+    for (i, vector) in vectors.iter().enumerate() {
+        let bump = bumpalo::Bump::new();
+        let payload = NonZero::new(i as u64 + 1).unwrap();
+        crate::insert::<_, O>(
+            &index,
+            vector.as_borrowed(),
+            payload,
+            &[],
+            &bump,
+            MakePlainPrefetcher(&index),
+            MakePlainPrefetcher(&index),
+        );
+    }
+    for (i, vector) in vectors.iter().enumerate().step_by(10) {
+        let bump = bumpalo::Bump::new();
+        let mut results = search::<_, O>(
+            &index,
+            vector.as_borrowed(),
+            64,
+            1,
+            None,
+            &bump,
+            MakePlainPrefetcher(&index),
+            MakePlainPrefetcher(&index),
+        );
+        let (_, payload) = results.next().expect("no results");
+        assert_eq!(payload.get(), i as u64 + 1);
+    }
+    let verification = crate::verify::<_, O>(&index, index.len(), || (), |_| ());
+    assert!(verification.problems.is_empty());
+    assert!(verification.unreachable.is_empty());
+    assert_eq!(verification.live, vectors.len() as u64);
+}
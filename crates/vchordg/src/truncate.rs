@@ -0,0 +1,306 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::Opaque;
+use crate::tuples::{MetaTuple, WithWriter, neighbours_of};
+use index::relation::{Page, RelationRead, RelationTruncate, RelationWrite};
+use std::collections::HashSet;
+
+/// Removes empty pages at the end of the relation and truncates them. A vector
+/// page is removed from its list, and a vertex page is removed with its list
+/// if all of them are empty and no vertex is linked to it. The first vertex
+/// page is never removed.
+///
+/// Returns `None` if the relation is in use, so it's not truncated.
+pub fn truncate<R: RelationRead + RelationWrite + RelationTruncate>(
+    index: &R,
+    check: impl Fn(),
+) -> Option<usize>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let link = index.read(0).get_opaque().link;
+    let mut number_of_truncated_pages = 0_usize;
+    index.truncate(|len| {
+        let is_empty = |guard: &R::ReadGuard<'_>| (1..=guard.len()).all(|i| guard.get(i).is_none());
+        // the first page of a list of vectors is referenced by the vertex page
+        let mut vertices = Vec::new();
+        let mut empty = HashSet::new();
+        // vertex pages that vertices are linked to
+        let mut linked = HashSet::new();
+        let mut link_from = |guard: &R::ReadGuard<'_>| {
+            for i in 1..=guard.len() {
+                if let Some(neighbours) = guard.get(i).and_then(neighbours_of) {
+                    let iter = neighbours
+                        .iter()
+                        .filter_map(|neighbour| neighbour.into_inner());
+                    linked.extend(iter.map(|((id, _), _)| id));
+                }
+            }
+        };
+        // a vertex page and all pages of its list, which are removed together
+        let mut groups = Vec::new();
+        let mut current = link;
+        while current != u32::MAX {
+            check();
+            let vertex_guard = index.read(current);
+            let head = vertex_guard.get_opaque().link;
+            vertices.push((current, head));
+            let mut group = vec![current, head];
+            let mut all_empty = current != link && is_empty(&vertex_guard);
+            let head_guard = index.read(head);
+            all_empty &= is_empty(&head_guard);
+            link_from(&head_guard);
+            let mut current_vector = { head_guard }.get_opaque().next;
+            while current_vector != u32::MAX {
+                let vector_guard = index.read(current_vector);
+                if is_empty(&vector_guard) {
+                    empty.insert(current_vector);
+                } else {
+                    all_empty = false;
+                    link_from(&vector_guard);
+                }
+                group.push(current_vector);
+                current_vector = vector_guard.get_opaque().next;
+            }
+            if all_empty {
+                groups.push(group);
+            }
+            current = vertex_guard.get_opaque().next;
+        }
+        groups.retain(|group| !linked.contains(&group[0]));
+        let mut removable = empty.clone();
+        removable.extend(groups.iter().flat_map(|group| group[..2].iter().copied()));
+        let target = loop {
+            let mut target = len;
+            while target > 0 && removable.contains(&(target - 1)) {
+                target -= 1;
+            }
+            // a group is kept if any page of it is kept
+            let (kept, removed) = std::mem::take(&mut groups)
+                .into_iter()
+                .partition::<Vec<_>, _>(|group| group.iter().any(|&id| id < target));
+            groups = removed;
+            if kept
+                .iter()
+                .all(|group| group[..2].iter().all(|&id| id < target))
+            {
+                break target;
+            }
+            for group in kept {
+                removable.remove(&group[0]);
+                removable.remove(&group[1]);
+            }
+        };
+        if target == len {
+            return len;
+        }
+        let mut last = link;
+        for (vertex, head) in vertices {
+            if vertex >= target {
+                continue;
+            }
+            check();
+            last = vertex;
+            for (first, tracking_freespace) in [(vertex, true), (head, false)] {
+                let mut current = first;
+                loop {
+                    let next = index.read(current).get_opaque().next;
+                    if next == u32::MAX {
+                        break;
+                    }
+                    if next >= target {
+                        let skipped = index.read(next).get_opaque().next;
+                        let mut guard = index.write(current, tracking_freespace);
+                        guard.get_opaque_mut().next = skipped;
+                    } else if first == head {
+                        current = next;
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+        {
+            let mut meta_guard = index.write(0, false);
+            let meta_bytes = meta_guard.get_mut(1).expect("data corruption");
+            let mut meta_tuple = MetaTuple::deserialize_mut(meta_bytes);
+            if *meta_tuple.skip() >= target {
+                *meta_tuple.skip() = last;
+            }
+        }
+        number_of_truncated_pages = (len - target) as usize;
+        target
+    })?;
+    Some(number_of_truncated_pages)
+}
+
+#[test]
+fn test_truncate() {
+    use crate::operator::Op;
+    use crate::types::{DistanceKind, VchordgIndexOptions, VectorKind, VectorOptions};
+    use index::fetch::Fetch;
+    use index::memory::{MemoryPage, MemoryRelation};
+    use index::prefetcher::{PlainPrefetcher, PrefetcherSequenceFamily, Sequence};
+    use index::relation::{Relation, RelationReadTypes, RelationWriteTypes};
+    use index_accessor::L2S;
+    use std::cell::Cell;
+    use std::num::NonZero;
+    use vector::VectorOwned;
+    use vector::vect::VectOwned;
+
+    // pages of memory relations cannot be removed, so the truncation is only
+    // recorded, and no page after it should be referenced
+    struct TruncatedRelation {
+        inner: MemoryRelation<Opaque>,
+        target: Cell<Option<u32>>,
+    }
+
+    impl Relation for TruncatedRelation {
+        type Page = MemoryPage<Opaque>;
+    }
+
+    impl RelationReadTypes for TruncatedRelation {
+        type ReadGuard<'a> = <MemoryRelation<Opaque> as RelationReadTypes>::ReadGuard<'a>;
+    }
+
+    impl RelationRead for TruncatedRelation {
+        fn read(&self, id: u32) -> Self::ReadGuard<'_> {
+            assert!(id < self.target.get().unwrap_or(u32::MAX), "truncated");
+            self.inner.read(id)
+        }
+    }
+
+    impl RelationWriteTypes for TruncatedRelation {
+        type WriteGuard<'a> = <MemoryRelation<Opaque> as RelationWriteTypes>::WriteGuard<'a>;
+    }
+
+    impl RelationWrite for TruncatedRelation {
+        fn write(&self, id: u32, tracking_freespace: bool) -> Self::WriteGuard<'_> {
+            assert!(id < self.target.get().unwrap_or(u32::MAX), "truncated");
+            self.inner.write(id, tracking_freespace)
+        }
+
+        fn extend(&self, opaque: Opaque, tracking_freespace: bool) -> Self::WriteGuard<'_> {
+            assert!(self.target.get().is_none(), "truncated");
+            self.inner.extend(opaque, tracking_freespace)
+        }
+
+        fn search(&self, freespace: usize) -> Option<Self::WriteGuard<'_>> {
+            self.inner.search(freespace)
+        }
+    }
+
+    impl RelationTruncate for TruncatedRelation {
+        fn truncate(&self, f: impl FnOnce(u32) -> u32) -> Option<u32> {
+            let target = f(self.inner.len());
+            self.target.set(Some(target));
+            Some(target)
+        }
+    }
+
+    struct MakePlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherSequenceFamily<'b, R> for MakePlainPrefetcher<'b, R> {
+        type P<S: Sequence>
+            = PlainPrefetcher<'b, R, S>
+        where
+            S::Item: Fetch<'b>;
+
+        fn prefetch<S: Sequence>(&mut self, seq: S) -> Self::P<S>
+        where
+            S::Item: Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, seq)
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    let vertex_pages = |index: &TruncatedRelation| {
+        let mut result = 0;
+        let mut current = index.read(0).get_opaque().link;
+        while current != u32::MAX {
+            result += 1;
+            current = index.read(current).get_opaque().next;
+        }
+        result
+    };
+
+    type O = Op<VectOwned<f32>, L2S>;
+    let dim = 16;
+    let vectors = (0..2000)
+        .map(|_| VectOwned::new((0..dim).map(|_| rand::random::<f32>()).collect()))
+        .collect::<Vec<_>>();
+    let index = TruncatedRelation {
+        inner: MemoryRelation::new(),
+        target: Cell::new(None),
+    };
+    let vector_options = VectorOptions {
+        dim,
+        v: VectorKind::Vecf32,
+        d: DistanceKind::L2S,
+    };
+    crate::build::<_, O>(vector_options, VchordgIndexOptions::default(), &index);
+    for (i, vector) in vectors.iter().enumerate() {
+        let bump = bumpalo::Bump::new();
+        let payload = NonZero::new(i as u64 + 1).unwrap();
+        crate::insert::<_, O>(
+            &index,
+            vector.as_borrowed(),
+            payload,
+            &[],
+            &bump,
+            MakePlainPrefetcher(&index),
+            MakePlainPrefetcher(&index),
+        );
+    }
+    // vertices inserted lately are deleted, so vertex pages at the end become
+    // empty
+    let live = 500;
+    crate::bulkdelete::<_, O>(&index, || (), |payload| payload.get() > live);
+    crate::maintain::<_, O>(&index, || ());
+    let before = vertex_pages(&index);
+    let len = index.inner.len();
+    let truncated = truncate(&index, || ()).expect("not truncated");
+    let target = index.target.get().expect("not truncated");
+    assert!(truncated > 0);
+    assert_eq!(target as usize + truncated, len as usize);
+    assert!(vertex_pages(&index) < before);
+    let verification = crate::verify::<_, O>(&index, target, || (), |_| ());
+    assert!(
+        verification.problems.is_empty(),
+        "{:?}",
+        verification.problems
+    );
+    assert!(verification.unreachable.is_empty());
+    assert_eq!(verification.live, live);
+    for (i, vector) in vectors.iter().enumerate().take(live as usize).step_by(10) {
+        let bump = bumpalo::Bump::new();
+        let mut results = crate::search::<_, O>(
+            &index,
+            vector.as_borrowed(),
+            64,
+            1,
+            None,
+            &bump,
+            MakePlainPrefetcher(&index),
+            MakePlainPrefetcher(&index),
+        );
+        let (_, payload) = results.next().expect("no results");
+        assert_eq!(payload.get(), i as u64 + 1);
+    }
+}
@@ -21,7 +21,18 @@ use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 pub const ALIGN: usize = 8;
 pub type Tag = u64;
 const MAGIC: Tag = Tag::from_ne_bytes(*b"vchordg\0");
-const VERSION: u64 = 1001;
+// 1002: vertex tuples have labels, which were padding in 1001
+pub const VERSION: u64 = 1002;
+// indexes of older versions can be read and written, and they are rewritten
+// to the current version by `upgrade`
+pub const OLDEST_VERSION: u64 = 1001;
+
+#[inline(always)]
+fn check_version(version: u64) {
+    if !(OLDEST_VERSION..=VERSION).contains(&version) {
+        panic!("deserialization: bad version number");
+    }
+}
 
 #[inline(always)]
 fn tag(source: &[u8]) -> Tag {
@@ -127,9 +138,7 @@ impl WithReader for MetaTuple {
         match tag {
             MAGIC => {
                 let checker = RefChecker::new(source);
-                if VERSION != *checker.prefix::<u64>(size_of::<Tag>()) {
-                    panic!("deserialization: bad version number");
-                }
+                check_version(*checker.prefix::<u64>(size_of::<Tag>()));
                 let header: &MetaTupleHeader = checker.prefix(size_of::<Tag>());
                 let alpha = checker.bytes(header.alpha_s, header.alpha_e);
                 MetaTupleReader { header, alpha }
@@ -146,6 +155,9 @@ pub struct MetaTupleReader<'a> {
 }
 
 impl<'a> MetaTupleReader<'a> {
+    pub fn version(self) -> u64 {
+        self.header.version
+    }
     pub fn dim(self) -> u32 {
         self.header.dim
     }
@@ -179,9 +191,7 @@ impl WithWriter for MetaTuple {
         match tag {
             MAGIC => {
                 let checker = RefChecker::new(source);
-                if VERSION != *checker.prefix::<u64>(size_of::<Tag>()) {
-                    panic!("deserialization: bad version number");
-                }
+                check_version(*checker.prefix::<u64>(size_of::<Tag>()));
                 let mut checker = MutChecker::new(source);
                 let header: &mut MetaTupleHeader = checker.prefix(size_of::<Tag>());
                 MetaTupleWriter { header }
@@ -197,6 +207,9 @@ pub struct MetaTupleWriter<'a> {
 }
 
 impl<'a> MetaTupleWriter<'a> {
+    pub fn version(&mut self) -> &mut u64 {
+        &mut self.header.version
+    }
     pub fn start(&mut self) -> &mut OptionPointer {
         &mut self.header.start
     }
@@ -214,7 +227,8 @@ struct VertexTupleHeader {
     payload: Option<NonZero<u64>>,
     pointers_s: u16,
     pointers_e: u16,
-    _padding_0: [Padding; 4],
+    labels_s: u16,
+    labels_e: u16,
 }
 
 #[derive(Debug, Clone)]
@@ -223,6 +237,7 @@ pub struct VertexTuple {
     pub elements: Vec<u64>,
     pub payload: Option<NonZero<u64>>,
     pub pointers: Vec<Pointer>,
+    pub labels: Vec<i32>,
 }
 
 impl Tuple for VertexTuple {
@@ -243,6 +258,13 @@ impl Tuple for VertexTuple {
         while buffer.len() % ALIGN != 0 {
             buffer.push(0);
         }
+        // labels
+        let labels_s = buffer.len() as u16;
+        buffer.extend(self.labels.as_bytes());
+        let labels_e = buffer.len() as u16;
+        while buffer.len() % ALIGN != 0 {
+            buffer.push(0);
+        }
         // header
         buffer[..size_of::<VertexTupleHeader>()].copy_from_slice(
             VertexTupleHeader {
@@ -252,7 +274,8 @@ impl Tuple for VertexTuple {
                 payload: self.payload,
                 pointers_s,
                 pointers_e,
-                _padding_0: Default::default(),
+                labels_s,
+                labels_e,
             }
             .as_bytes(),
         );
@@ -268,10 +291,12 @@ impl WithReader for VertexTuple {
         let header: &VertexTupleHeader = checker.prefix(0_u16);
         let elements = checker.bytes(header.elements_s, header.elements_e);
         let pointers = checker.bytes(header.pointers_s, header.pointers_e);
+        let labels = checker.bytes(header.labels_s, header.labels_e);
         VertexTupleReader {
             header,
             elements,
             pointers,
+            labels,
         }
     }
 }
@@ -297,6 +322,7 @@ pub struct VertexTupleReader<'a> {
     header: &'a VertexTupleHeader,
     elements: &'a [u64],
     pointers: &'a [Pointer],
+    labels: &'a [i32],
 }
 
 impl<'a> VertexTupleReader<'a> {
@@ -312,6 +338,9 @@ impl<'a> VertexTupleReader<'a> {
     pub fn pointers(self) -> &'a [Pointer] {
         self.pointers
     }
+    pub fn labels(self) -> &'a [i32] {
+        self.labels
+    }
 }
 
 #[derive(Debug)]
@@ -338,6 +367,56 @@ impl VertexTupleWriter<'_> {
     }
 }
 
+#[repr(C, align(8))]
+#[derive(Debug, Clone, FromBytes, IntoBytes, Immutable, KnownLayout)]
+struct LabelTupleHeader {
+    label: i32,
+    _padding_0: [Padding; 4],
+    start: Pointer,
+}
+
+#[derive(Debug, Clone)]
+pub struct LabelTuple {
+    pub label: i32,
+    pub start: Pointer,
+}
+
+impl Tuple for LabelTuple {
+    fn serialize(&self) -> Vec<u8> {
+        LabelTupleHeader {
+            label: self.label,
+            _padding_0: Default::default(),
+            start: self.start,
+        }
+        .as_bytes()
+        .to_vec()
+    }
+}
+
+impl WithReader for LabelTuple {
+    type Reader<'a> = LabelTupleReader<'a>;
+
+    fn deserialize_ref(source: &[u8]) -> LabelTupleReader<'_> {
+        let checker = RefChecker::new(source);
+        let header: &LabelTupleHeader = checker.prefix(0_u16);
+        LabelTupleReader { header }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LabelTupleReader<'a> {
+    header: &'a LabelTupleHeader,
+}
+
+impl LabelTupleReader<'_> {
+    pub fn label(self) -> i32 {
+        self.header.label
+    }
+    pub fn start(self) -> Pointer {
+        self.header.start
+    }
+}
+
 #[repr(C, align(8))]
 #[derive(Debug, Clone, FromBytes, IntoBytes, Immutable, KnownLayout)]
 struct VectorTupleHeader0 {
@@ -360,6 +439,20 @@ struct VectorTupleHeader1 {
     index: u32,
 }
 
+/// Reads the neighbours of a vector tuple without knowing the type of vectors.
+/// Only the last vector tuple of a vertex has neighbours.
+pub fn neighbours_of(source: &[u8]) -> Option<&[OptionNeighbour]> {
+    match tag(source) {
+        0 => {
+            let checker = RefChecker::new(source);
+            let header: &VectorTupleHeader0 = checker.prefix(size_of::<Tag>());
+            Some(checker.bytes(header.neighbours_s, header.neighbours_e))
+        }
+        1 => None,
+        _ => panic!("deserialization: bad bytes"),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum VectorTuple<V: Vector> {
     _0 {
@@ -762,3 +855,23 @@ impl OptionNeighbour {
         Some((inner, self.distance))
     }
 }
+
+#[test]
+fn test_vertex_tuple_of_oldest_version() {
+    let mut bytes = VertexTuple::serialize(&VertexTuple {
+        metadata: [1.0, 2.0, 3.0],
+        elements: vec![7, 8],
+        payload: NonZero::new(9),
+        pointers: vec![Pointer::new((1, 2))],
+        labels: vec![3, 5],
+    });
+    assert_eq!(VertexTuple::deserialize_ref(&bytes).labels(), &[3, 5]);
+    // in version 1001, the offsets of labels were zeroed padding
+    let (header, _) = VertexTupleHeader::mut_from_prefix(&mut bytes).unwrap();
+    header.labels_s = 0;
+    header.labels_e = 0;
+    let vertex = VertexTuple::deserialize_ref(&bytes);
+    assert!(vertex.labels().is_empty());
+    assert_eq!(vertex.pointers(), &[Pointer::new((1, 2))]);
+    assert_eq!(vertex.elements(), &[7, 8]);
+}
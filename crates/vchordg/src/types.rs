@@ -13,10 +13,13 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 use serde::{Deserialize, Serialize};
-use simd::f16;
+use simd::{bf16, f16};
 use validator::{Validate, ValidationError};
+use vector::rabitq1::{Rabitq1Borrowed, Rabitq1Owned};
+use vector::rabitq2::{Rabitq2Borrowed, Rabitq2Owned};
 use vector::rabitq4::{Rabitq4Borrowed, Rabitq4Owned};
 use vector::rabitq8::{Rabitq8Borrowed, Rabitq8Owned};
+use vector::veci8::{Veci8Borrowed, Veci8Owned};
 use vector::vect::{VectBorrowed, VectOwned};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -87,6 +90,10 @@ pub enum OwnedVector {
     Vecf16(VectOwned<f16>),
     Rabitq8(Rabitq8Owned),
     Rabitq4(Rabitq4Owned),
+    Rabitq2(Rabitq2Owned),
+    Rabitq1(Rabitq1Owned),
+    Veci8(Veci8Owned),
+    Vecbf16(VectOwned<bf16>),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -95,6 +102,10 @@ pub enum BorrowedVector<'a> {
     Vecf16(VectBorrowed<'a, f16>),
     Rabitq8(Rabitq8Borrowed<'a>),
     Rabitq4(Rabitq4Borrowed<'a>),
+    Rabitq2(Rabitq2Borrowed<'a>),
+    Rabitq1(Rabitq1Borrowed<'a>),
+    Veci8(Veci8Borrowed<'a>),
+    Vecbf16(VectBorrowed<'a, bf16>),
 }
 
 #[repr(u8)]
@@ -111,6 +122,10 @@ pub enum VectorKind {
     Vecf16,
     Rabitq8,
     Rabitq4,
+    Rabitq2,
+    Rabitq1,
+    Veci8,
+    Vecbf16,
 }
 
 #[derive(Debug, Clone, Validate)]
@@ -144,3 +159,11 @@ impl<V> Structure<V> {
         self.children.is_empty()
     }
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct VchordgIndexingOptions {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub index: VchordgIndexOptions,
+}
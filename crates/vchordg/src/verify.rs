@@ -0,0 +1,327 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::Opaque;
+use crate::operator::{CloneAccessor, Operator};
+use crate::tuples::*;
+use crate::vectors::{by_read, copy_nothing};
+use index::relation::{Page, RelationRead, RelationWrite};
+use index_accessor::DefaultWithDimension;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::num::NonZero;
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub id: Option<u32>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Verification {
+    pub problems: Vec<Problem>,
+    /// number of vertices with a payload
+    pub live: u64,
+    /// vertices with a payload that are not reachable from any start vertex
+    pub unreachable: Vec<(u32, u16)>,
+}
+
+// Tuple readers panic on malformed bytes, so they are called with panics
+// caught. They never call into PostgreSQL, so this is safe to do.
+fn decode<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).map_err(|e| {
+        if let Some(message) = e.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = e.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "unknown error".to_string()
+        }
+    })
+}
+
+struct Vertex {
+    pointers: Vec<(u32, u16)>,
+    live: bool,
+}
+
+/// Returns the dimension recorded in the meta tuple, or why the meta tuple
+/// cannot be read, for example, that the index is built by another version.
+pub fn dimension<R: RelationRead>(index: &R) -> Result<u32, String>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let Some(meta_bytes) = meta_guard.get(1) else {
+        return Err("the meta tuple is missing".to_string());
+    };
+    decode(|| MetaTuple::deserialize_ref(meta_bytes).dim())
+}
+
+pub fn verify<R: RelationRead, O: Operator>(
+    index: &R,
+    nblocks: u32,
+    check: impl Fn(),
+    mut callback: impl FnMut(NonZero<u64>),
+) -> Verification
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let mut result = Verification::default();
+    let mut report = |id: Option<u32>, message: String| {
+        result.problems.push(Problem { id, message });
+    };
+
+    if nblocks == 0 {
+        report(None, "meta tuple: the index is empty".to_string());
+        return result;
+    }
+
+    let meta_guard = index.read(0);
+    let start = match meta_guard.get(1).map(|bytes| {
+        decode(|| {
+            let meta_tuple = MetaTuple::deserialize_ref(bytes);
+            meta_tuple.start().into_inner()
+        })
+    }) {
+        Some(Ok(start)) => start,
+        Some(Err(message)) => {
+            report(Some(0), format!("meta tuple: {message}"));
+            return result;
+        }
+        None => {
+            report(Some(0), "meta tuple: item 1 is unused".to_string());
+            return result;
+        }
+    };
+    let link = meta_guard.get_opaque().link;
+    drop(meta_guard);
+
+    // Every page belongs to at most one list, so a page that is reached twice
+    // indicates a cycle or two lists sharing a page.
+    let mut visited = HashSet::from([0_u32]);
+    let mut walk = |report: &mut dyn FnMut(Option<u32>, String), first: u32, what: &str| {
+        let mut pages = Vec::new();
+        let (mut from, mut current) = (0, first);
+        while current != u32::MAX {
+            check();
+            if current >= nblocks {
+                report(
+                    Some(from),
+                    format!("{what}: page {current} is out of range"),
+                );
+                break;
+            }
+            if !visited.insert(current) {
+                report(
+                    Some(from),
+                    format!("{what}: page {current} is reached more than once"),
+                );
+                break;
+            }
+            pages.push(current);
+            let guard = index.read(current);
+            (from, current) = (current, guard.get_opaque().next);
+        }
+        pages
+    };
+
+    // vertices
+    let mut vertices = HashMap::<(u32, u16), Vertex>::new();
+    let mut vector_pages = HashSet::new();
+    for id in walk(&mut report, link, "vertex pages") {
+        let guard = index.read(id);
+        for i in 1..=guard.len() {
+            let Some(bytes) = guard.get(i) else {
+                continue;
+            };
+            match decode(|| {
+                let tuple = VertexTuple::deserialize_ref(bytes);
+                let pointers = tuple.pointers().iter().map(|p| p.into_inner()).collect();
+                (pointers, tuple.payload())
+            }) {
+                Ok((pointers, payload)) => {
+                    if let Some(payload) = payload {
+                        callback(payload);
+                    }
+                    let live = payload.is_some();
+                    vertices.insert((id, i), Vertex { pointers, live });
+                }
+                Err(message) => report(Some(id), format!("vertex ({id},{i}): {message}")),
+            }
+        }
+        let vector_link = guard.get_opaque().link;
+        drop(guard);
+        vector_pages.extend(walk(&mut report, vector_link, "vector pages"));
+    }
+
+    // vectors and neighbours
+    let mut graph = HashMap::<(u32, u16), Vec<(u32, u16)>>::new();
+    let mut keys = vertices.keys().copied().collect::<Vec<_>>();
+    keys.sort_unstable();
+    for u in keys {
+        check();
+        let vertex = &vertices[&u];
+        let m = vertex.pointers.len();
+        if m == 0 {
+            report(Some(u.0), format!("vertex ({},{}): no vectors", u.0, u.1));
+            continue;
+        }
+        let mut outs = None;
+        for (k, &(id, i)) in vertex.pointers.iter().enumerate() {
+            if !vector_pages.contains(&id) {
+                report(
+                    Some(u.0),
+                    format!(
+                        "vertex ({},{}): pointer {k} refers to page {id}, which is not a vector page",
+                        u.0, u.1
+                    ),
+                );
+                break;
+            }
+            let guard = index.read(id);
+            let Some(bytes) = guard.get(i) else {
+                report(
+                    Some(u.0),
+                    format!(
+                        "vertex ({},{}): pointer {k} refers to the freed slot ({id},{i})",
+                        u.0, u.1
+                    ),
+                );
+                break;
+            };
+            let checked = decode(|| match VectorTuple::<O::Vector>::deserialize_ref(bytes) {
+                VectorTupleReader::_0(tuple) if k + 1 == m => Ok(Some(
+                    tuple
+                        .neighbours()
+                        .iter()
+                        .flat_map(|neighbour| neighbour.into_inner())
+                        .map(|(v, _)| v)
+                        .collect::<Vec<_>>(),
+                )),
+                VectorTupleReader::_1(tuple) if k + 1 != m => {
+                    if tuple.index() as usize == k {
+                        Ok(None)
+                    } else {
+                        Err(format!("index of the vector tuple is {}", tuple.index()))
+                    }
+                }
+                _ => Err("bad kind of the vector tuple".to_string()),
+            });
+            match checked {
+                Ok(Ok(x)) => outs = outs.or(x),
+                Ok(Err(message)) | Err(message) => {
+                    report(
+                        Some(u.0),
+                        format!("vertex ({},{}): pointer {k}: {message}", u.0, u.1),
+                    );
+                    break;
+                }
+            }
+        }
+        let Some(outs) = outs else {
+            continue;
+        };
+        for &v in outs.iter() {
+            if !vertices.contains_key(&v) {
+                report(
+                    Some(u.0),
+                    format!(
+                        "vertex ({},{}): neighbour ({},{}) refers to a freed slot",
+                        u.0, u.1, v.0, v.1
+                    ),
+                );
+            }
+        }
+        graph.insert(u, outs);
+    }
+
+    // reachability
+    let mut seeds = crate::labels::starts(index);
+    seeds.extend(start);
+    for &s in seeds.iter() {
+        if !vertices.contains_key(&s) {
+            report(
+                None,
+                format!("start vertex ({},{}) refers to a freed slot", s.0, s.1),
+            );
+        }
+    }
+    let mut reached = HashSet::new();
+    let mut queue = seeds
+        .into_iter()
+        .filter(|s| vertices.contains_key(s))
+        .collect::<VecDeque<_>>();
+    reached.extend(queue.iter().copied());
+    while let Some(u) = queue.pop_front() {
+        for &v in graph.get(&u).into_iter().flatten() {
+            if vertices.contains_key(&v) && reached.insert(v) {
+                queue.push_back(v);
+            }
+        }
+    }
+    let mut unreachable = vertices
+        .iter()
+        .filter(|(u, vertex)| vertex.live && !reached.contains(u))
+        .map(|(&u, _)| u)
+        .collect::<Vec<_>>();
+    unreachable.sort_unstable();
+    result.live = vertices.values().filter(|vertex| vertex.live).count() as u64;
+    result.unreachable = unreachable;
+    result
+}
+
+/// Reads a live vertex for reinsertion, and marks it deleted after `f`
+/// reinserts it. Returns `false` if the vertex is not live or cannot be read.
+pub fn reinsert<R: RelationRead + RelationWrite, O: Operator>(
+    index: &R,
+    u: (u32, u16),
+    f: impl FnOnce(O::Vector, NonZero<u64>, &[i32]),
+) -> bool
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let dim = meta_tuple.dim();
+    drop(meta_guard);
+    let vertex_guard = index.read(u.0);
+    let Some(vertex_bytes) = vertex_guard.get(u.1) else {
+        return false;
+    };
+    let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
+    let Some(payload) = vertex_tuple.payload() else {
+        return false;
+    };
+    let pointers = vertex_tuple.pointers().to_vec();
+    let labels = vertex_tuple.labels().to_vec();
+    drop(vertex_guard);
+    let Ok((vector, _, _, _)) = crate::vectors::read::<R, O, _, _>(
+        by_read::<R>(index, pointers.iter().copied()),
+        CloneAccessor::<O::Vector>::default_with_dimension(dim),
+        copy_nothing,
+    ) else {
+        // the link is broken
+        return false;
+    };
+    f(vector, payload, &labels);
+    let mut vertex_guard = index.write(u.0, false);
+    if let Some(vertex_bytes) = vertex_guard.get_mut(u.1) {
+        let mut vertex_tuple = VertexTuple::deserialize_mut(vertex_bytes);
+        if *vertex_tuple.payload() == Some(payload) {
+            *vertex_tuple.payload() = None;
+        }
+    }
+    true
+}
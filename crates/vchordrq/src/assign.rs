@@ -0,0 +1,146 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::closure_lifetime_binder::{id_0, id_1, id_2};
+use crate::operator::*;
+use crate::tape::by_next;
+use crate::tuples::*;
+use crate::{Opaque, centroids, tape};
+use distance::Distance;
+use index::relation::{Page, RelationRead};
+use index_accessor::{DefaultWithDimension, FunctionalAccessor, LAccess};
+use std::num::NonZero;
+use vector::{VectorBorrowed, VectorOwned};
+
+/// Descends the tree as [`insert`](crate::insert) does, taking the nearest
+/// centroid at each level, for at most `depth` levels.
+///
+/// Nodes are numbered level by level, in the order of tapes, so the last
+/// element of a full path is the id of the list that the vector falls into.
+pub fn assign<R: RelationRead, O: Operator>(
+    index: &R,
+    vector: <O::Vector as VectorOwned>::Borrowed<'_>,
+    depth: u32,
+) -> Vec<u32>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let dim = meta_tuple.dim();
+    let height_of_root = meta_tuple.height_of_root();
+    assert_eq!(dim, vector.dim(), "unmatched dimensions");
+
+    let mut state = vec![meta_tuple.first()];
+    let mut chosen = 0_usize;
+    drop(meta_guard);
+
+    let mut path = Vec::new();
+    for _ in (1..height_of_root).rev().take(depth as usize) {
+        let mut results = Vec::new();
+        let mut nearest = None::<(Distance, usize)>;
+        for (i, first) in state.into_iter().enumerate() {
+            tape::read_h1_tape::<R, _, _>(
+                by_next(index, first),
+                || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                |(), head, _, first, prefetch| {
+                    if i == chosen {
+                        let distance = centroids::read::<R, O, _>(
+                            prefetch.iter().map(|&id| index.read(id)),
+                            head,
+                            LAccess::new(
+                                O::Vector::unpack(vector),
+                                O::DistanceAccessor::default_with_dimension(dim),
+                            ),
+                        );
+                        if nearest.is_none_or(|(x, _)| distance < x) {
+                            nearest = Some((distance, results.len()));
+                        }
+                    }
+                    results.push(first);
+                },
+            );
+        }
+        let (_, position) = nearest.expect("invariant is violated: tree is not height-balanced");
+        path.push(position as u32);
+        chosen = position;
+        state = results;
+    }
+    path
+}
+
+/// Returns payloads of the list, from both its frozen tape and its appendable
+/// tape, or `None` if there is no such list.
+pub fn list_members<R: RelationRead>(index: &R, list: u32) -> Option<Vec<NonZero<u64>>>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let first = *list_firsts(index).get(list as usize)?;
+    Some(read_list(index, first))
+}
+
+/// Returns the first pages of all lists, indexed by list ids.
+pub fn list_firsts<R: RelationRead>(index: &R) -> Vec<u32>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let height_of_root = meta_tuple.height_of_root();
+
+    let mut state = vec![meta_tuple.first()];
+    drop(meta_guard);
+
+    for _ in (1..height_of_root).rev() {
+        let mut results = Vec::new();
+        for first in state {
+            tape::read_h1_tape::<R, _, _>(
+                by_next(index, first),
+                || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                |(), _, _, first, _| results.push(first),
+            );
+        }
+        state = results;
+    }
+    state
+}
+
+/// Returns payloads of the list starting at `first`, which is one of the
+/// pages returned by [`list_firsts`].
+pub fn read_list<R: RelationRead>(index: &R, first: u32) -> Vec<NonZero<u64>>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let jump_guard = index.read(first);
+    let jump_bytes = jump_guard.get(1).expect("data corruption");
+    let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+    let frozen_first = jump_tuple.frozen_first();
+    let appendable_first = jump_tuple.appendable_first();
+    drop(jump_guard);
+
+    let mut members = Vec::new();
+    tape::read_frozen_tape::<R, _, _>(
+        by_next(index, frozen_first),
+        || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+        id_2(|_, _, payload, _| members.push(payload)),
+    );
+    tape::read_appendable_tape::<R, _>(
+        by_next(index, appendable_first),
+        |_, _, _| (),
+        id_2(|_, _, payload, _| members.push(payload)),
+    );
+    members
+}
@@ -0,0 +1,415 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::closure_lifetime_binder::{id_0, id_1};
+use crate::operator::*;
+use crate::search::{batch_probe, batch_scan};
+use crate::tape::by_next;
+use crate::tuples::*;
+use crate::{Opaque, centroids, tape};
+use always_equal::AlwaysEqual;
+use distance::Distance;
+use index::bump::Bump;
+use index::fetch::BorrowedIter;
+use index::packed::PackedRefMut4;
+use index::prefetcher::PrefetcherHeapFamily;
+use index::relation::{Page, RelationRead};
+use index_accessor::{DefaultWithDimension, FunctionalAccessor, LAccess};
+use std::cmp::Reverse;
+use std::num::NonZero;
+use vector::{VectorBorrowed, VectorOwned};
+
+/// Chooses, once for every list, the lists that its members are compared with.
+///
+/// The centroid of every list descends the tree as a query does, and two lists
+/// are neighbours if either of them is probed by the centroid of the other.
+/// For the list at position `i` of [`list_firsts`](crate::list_firsts), the
+/// list itself and its neighbours after it are returned, as their first pages
+/// and the norms of their centroids, so every pair of lists is returned once.
+pub fn neighbour_lists<'b, R: RelationRead, O: Operator>(
+    index: &'b R,
+    probes: Vec<u32>,
+    epsilon: f32,
+    bump: &'b impl Bump,
+    prefetch_h1_vectors: impl PrefetcherHeapFamily<'b, R>,
+) -> Vec<Vec<(u32, f32)>>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let dim = meta_tuple.dim();
+    let height_of_root = meta_tuple.height_of_root();
+    if height_of_root == 1 {
+        return vec![vec![(meta_tuple.first(), meta_tuple.centroid_norm())]];
+    }
+
+    let mut state = vec![meta_tuple.first()];
+    drop(meta_guard);
+
+    let centroid = |prefetch: &[u32], head: u16| {
+        centroids::read::<R, O, _>(
+            prefetch.iter().map(|&id| index.read(id)),
+            head,
+            FunctionalAccessor::new(
+                (dim, Vec::new()),
+                id_0(|(_, elements): &mut (_, Vec<_>), slice| elements.extend_from_slice(slice)),
+                |(dim, elements), metadata| O::Vector::pack(dim, elements, metadata),
+            ),
+        )
+    };
+    let mut leaves = Vec::new();
+    for height in (1..height_of_root).rev() {
+        let mut results = Vec::new();
+        for first in state {
+            tape::read_h1_tape::<R, _, _>(
+                by_next(index, first),
+                || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                |(), head, norm, first, prefetch| {
+                    if height == 1 {
+                        leaves.push((first, norm, centroid(prefetch, head)));
+                    }
+                    results.push(first);
+                },
+            );
+        }
+        state = results;
+    }
+
+    let vectors = leaves
+        .iter()
+        .map(|(_, _, centroid)| centroid.as_borrowed())
+        .collect::<Vec<_>>();
+    let luts = vectors
+        .iter()
+        .map(|&vector| O::Vector::preprocess(vector))
+        .collect::<Vec<_>>();
+    let (_, states) = batch_probe::<R, O>(
+        index,
+        &vectors,
+        &luts,
+        &probes,
+        epsilon,
+        bump,
+        prefetch_h1_vectors,
+    );
+    let mut positions = leaves
+        .iter()
+        .enumerate()
+        .map(|(i, &(first, ..))| (first, i))
+        .collect::<Vec<_>>();
+    positions.sort_unstable();
+    let position = |first: u32| {
+        let i = positions
+            .binary_search_by_key(&first, |&(first, _)| first)
+            .expect("data corruption");
+        positions[i].1
+    };
+    let mut neighbours = (0..leaves.len()).map(|i| vec![i]).collect::<Vec<_>>();
+    for (i, state) in states.into_iter().enumerate() {
+        for (_, _, AlwaysEqual(first)) in state {
+            let j = position(first);
+            neighbours[i.min(j)].push(i.max(j));
+        }
+    }
+    neighbours
+        .into_iter()
+        .map(|mut neighbours| {
+            neighbours.sort_unstable();
+            neighbours.dedup();
+            neighbours
+                .into_iter()
+                .map(|j| (leaves[j].0, leaves[j].1))
+                .collect()
+        })
+        .collect()
+}
+
+/// Scores every vector against every list, which is given by its first page
+/// and the norm of its centroid, as [`neighbour_lists`] returns. Each list is
+/// read only once for all vectors.
+pub fn scan_lists<'b, R: RelationRead, O: Operator>(
+    index: &'b R,
+    vectors: &[<O::Vector as VectorOwned>::Borrowed<'_>],
+    lists: &[(u32, f32)],
+    epsilon: f32,
+    bump: &'b impl Bump,
+) -> Vec<
+    Vec<(
+        (Reverse<Distance>, AlwaysEqual<()>),
+        AlwaysEqual<PackedRefMut4<'b, (NonZero<u64>, u16, BorrowedIter<'b>)>>,
+    )>,
+>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let dim = meta_tuple.dim();
+    let is_residual = meta_tuple.is_residual();
+    drop(meta_guard);
+    for vector in vectors {
+        assert_eq!(dim, vector.dim(), "unmatched dimensions");
+    }
+
+    let luts = vectors
+        .iter()
+        .map(|&vector| O::Vector::preprocess(vector))
+        .collect::<Vec<_>>();
+    let mut results = vectors.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+    for &(first, norm) in lists {
+        let probers = if is_residual {
+            let jump_guard = index.read(first);
+            let jump_bytes = jump_guard.get(1).expect("data corruption");
+            let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+            let prefetch = jump_tuple.centroid_prefetch().to_vec();
+            let head = jump_tuple.centroid_head();
+            drop(jump_guard);
+            vectors
+                .iter()
+                .enumerate()
+                .map(|(i, &vector)| {
+                    let distance = centroids::read::<R, O, _>(
+                        prefetch.iter().map(|&id| index.read(id)),
+                        head,
+                        LAccess::new(
+                            O::Vector::unpack(vector),
+                            O::DistanceAccessor::default_with_dimension(dim),
+                        ),
+                    );
+                    (i, distance)
+                })
+                .collect::<Vec<_>>()
+        } else {
+            // fast path
+            (0..vectors.len())
+                .map(|i| (i, Distance::ZERO))
+                .collect::<Vec<_>>()
+        };
+        batch_scan::<R, O>(
+            index,
+            first,
+            norm,
+            is_residual,
+            &luts,
+            &probers,
+            epsilon,
+            bump,
+            |i, candidate| results[i].push(candidate),
+        );
+    }
+    results
+}
+
+#[test]
+fn test_near_duplicates() {
+    use crate::types::{DistanceKind, Structure, VchordrqIndexOptions, VectorKind, VectorOptions};
+    use crate::{InsertChooser, MaintainChooser};
+    use index::fetch::Fetch;
+    use index::memory::MemoryRelation;
+    use index::prefetcher::{PlainPrefetcher, PrefetcherSequenceFamily, Sequence};
+    use index_accessor::L2S;
+    use simd::Floating;
+    use std::collections::BinaryHeap;
+    use vector::vect::VectOwned;
+
+    struct MakeH1PlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherHeapFamily<'b, R> for MakeH1PlainPrefetcher<'b, R> {
+        type P<T>
+            = PlainPrefetcher<'b, R, BinaryHeap<T>>
+        where
+            T: Ord + Fetch<'b>;
+
+        fn prefetch<T>(&mut self, seq: Vec<T>) -> Self::P<T>
+        where
+            T: Ord + Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, BinaryHeap::from(seq))
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    struct MakeH0PlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherSequenceFamily<'b, R> for MakeH0PlainPrefetcher<'b, R> {
+        type P<S: Sequence>
+            = PlainPrefetcher<'b, R, S>
+        where
+            S::Item: Fetch<'b>;
+
+        fn prefetch<S: Sequence>(&mut self, seq: S) -> Self::P<S>
+        where
+            S::Item: Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, seq)
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    struct IdChooser(usize);
+
+    impl InsertChooser for IdChooser {
+        fn choose(&mut self, n: NonZero<usize>) -> usize {
+            self.0 % n.get()
+        }
+    }
+
+    struct ChooseAll;
+
+    impl MaintainChooser for ChooseAll {
+        fn choose(&mut self, _: usize) -> bool {
+            true
+        }
+    }
+
+    type O = Op<VectOwned<f32>, L2S>;
+    let dim = 16;
+    let n = 200;
+    // every vector has a near duplicate, and others are far away
+    let mut vectors = (0..n)
+        .map(|_| (0..dim).map(|_| rand::random::<f32>()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    for i in 0..n {
+        let duplicate = vectors[i]
+            .iter()
+            .map(|x| x + (rand::random::<f32>() - 0.5) * 0.01)
+            .collect::<Vec<_>>();
+        vectors.push(duplicate);
+    }
+    let projected = vectors
+        .iter()
+        .map(|vector| VectOwned::new(rabitq::rotate::rotate(vector)))
+        .collect::<Vec<_>>();
+    let index = MemoryRelation::new();
+    let vector_options = VectorOptions {
+        dim: dim as u32,
+        v: VectorKind::Vecf32,
+        d: DistanceKind::L2S,
+    };
+    // eight lists, whose centroids are the first eight vectors, under the root
+    let mut root = vec![0.0_f32; dim];
+    for centroid in projected[..8].iter() {
+        for (x, y) in root.iter_mut().zip(centroid.slice()) {
+            *x += y / 8.0;
+        }
+    }
+    let structures = vec![
+        Structure {
+            centroids: projected[..8].to_vec(),
+            children: vec![Vec::new(); 8],
+        },
+        Structure {
+            centroids: vec![VectOwned::new(root)],
+            children: vec![(0..8).collect()],
+        },
+    ];
+    crate::build::<_, O>(
+        vector_options,
+        VchordrqIndexOptions::default(),
+        &index,
+        structures,
+    );
+    let insert = |i: usize| {
+        let bump = bumpalo::Bump::new();
+        let payload = NonZero::new(i as u64 + 1).unwrap();
+        let vector = VectOwned::new(vectors[i].clone());
+        let key = crate::insert_vector::<_, O>(
+            &index,
+            payload,
+            vector.as_borrowed(),
+            &mut IdChooser(i),
+            true,
+        );
+        crate::insert::<_, O>(
+            &index,
+            payload,
+            projected[i].as_borrowed(),
+            key,
+            &bump,
+            MakeH1PlainPrefetcher(&index),
+            true,
+        );
+    };
+    (0..3 * n / 2).for_each(insert);
+    crate::maintain::<_, O>(&index, MakeH0PlainPrefetcher(&index), &mut ChooseAll, || ());
+    (3 * n / 2..2 * n).for_each(insert);
+
+    // fewer probes than lists
+    let bump = bumpalo::Bump::new();
+    let neighbours =
+        neighbour_lists::<_, O>(&index, vec![3], 1.9, &bump, MakeH1PlainPrefetcher(&index));
+    let firsts = crate::list_firsts(&index);
+    assert_eq!(neighbours.len(), firsts.len());
+    let mut lists = vec![0_usize; 2 * n];
+    for (l, &first) in firsts.iter().enumerate() {
+        assert_eq!(neighbours[l][0].0, first);
+        for payload in crate::read_list(&index, first) {
+            lists[payload.get() as usize - 1] = l;
+        }
+    }
+    let radius_2 = 0.1_f32 * 0.1;
+    let mut found = Vec::new();
+    for (l, &first) in firsts.iter().enumerate() {
+        let bump = bumpalo::Bump::new();
+        let members = crate::read_list(&index, first)
+            .into_iter()
+            .map(|payload| payload.get() as usize - 1)
+            .collect::<Vec<_>>();
+        let borrowed = members
+            .iter()
+            .map(|&i| projected[i].as_borrowed())
+            .collect::<Vec<_>>();
+        let candidates = scan_lists::<_, O>(&index, &borrowed, &neighbours[l], 1.9, &bump);
+        for (&a, candidates) in members.iter().zip(candidates) {
+            let prefetcher = PlainPrefetcher::new(&index, BinaryHeap::from(candidates));
+            let reranker =
+                crate::rerank_index::<O, _, _, _>(VectOwned::new(vectors[a].clone()), prefetcher);
+            for (distance, payload) in reranker.take_while(|&(d, _)| d.to_f32() <= radius_2) {
+                let b = payload.get() as usize - 1;
+                // a pair in the list is found from both sides
+                if a != b && (lists[b] != l || a < b) {
+                    found.push((a.min(b), a.max(b), distance.to_f32()));
+                }
+            }
+        }
+    }
+    found.sort_by_key(|x| (x.0, x.1));
+    let pairs = found.iter().map(|&(a, b, _)| (a, b)).collect::<Vec<_>>();
+    let mut expected = Vec::new();
+    for a in 0..2 * n {
+        for b in a + 1..2 * n {
+            let distance = f32::reduce_sum_of_d2(&vectors[a], &vectors[b]);
+            let (x, y) = (lists[a].min(lists[b]), lists[a].max(lists[b]));
+            let probed = neighbours[x].iter().any(|&(first, _)| first == firsts[y]);
+            if distance <= radius_2 && probed {
+                expected.push((a, b));
+            }
+        }
+    }
+    assert_eq!(pairs, expected);
+    for (a, b, distance) in found {
+        let exact = f32::reduce_sum_of_d2(&vectors[a], &vectors[b]);
+        assert!((distance - exact).abs() < 1e-4, "{distance} {exact}");
+    }
+    assert!(expected.len() >= n / 2);
+}
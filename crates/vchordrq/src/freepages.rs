@@ -14,7 +14,8 @@
 
 use crate::Opaque;
 use crate::tuples::*;
-use index::relation::{Page, RelationWrite};
+use index::relation::{Page, RelationRead, RelationWrite};
+use std::collections::{BTreeSet, HashMap};
 
 pub fn alloc<R: RelationWrite>(index: &R, freepages_first: u32) -> Option<R::WriteGuard<'_>>
 where
@@ -34,6 +35,105 @@ where
     }
 }
 
+/// The free list, read into memory so that pages are taken in order of ids.
+///
+/// A page is unlinked from the list only when it's taken, so no page is lost
+/// if an error is raised in the middle. The relation must be locked
+/// exclusively while it's used.
+pub struct Sorted {
+    freepages_first: u32,
+    first: u32,
+    ids: BTreeSet<u32>,
+    prev: HashMap<u32, u32>,
+    next: HashMap<u32, u32>,
+}
+
+impl Sorted {
+    pub fn read<R: RelationRead>(index: &R, freepages_first: u32) -> Self
+    where
+        R::Page: Page<Opaque = Opaque>,
+    {
+        let freepages_guard = index.read(freepages_first);
+        let freepages_bytes = freepages_guard.get(1).expect("data corruption");
+        let freepages_tuple = FreepagesTuple::deserialize_ref(freepages_bytes);
+        let mut current = freepages_tuple.first();
+        drop(freepages_guard);
+        let mut result = Self {
+            freepages_first,
+            first: current,
+            ids: BTreeSet::new(),
+            prev: HashMap::new(),
+            next: HashMap::new(),
+        };
+        let mut prev = u32::MAX;
+        while current != u32::MAX {
+            if !result.ids.insert(current) {
+                panic!("data corruption");
+            }
+            let next = index.read(current).get_opaque().next;
+            result.prev.insert(current, prev);
+            result.next.insert(current, next);
+            (prev, current) = (current, next);
+        }
+        result
+    }
+    pub fn contains(&self, id: u32) -> bool {
+        self.ids.contains(&id)
+    }
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+    pub fn last(&self) -> Option<u32> {
+        self.ids.last().copied()
+    }
+    /// Unlinks the page with the lowest id.
+    pub fn pop_first<R: RelationWrite>(&mut self, index: &R) -> Option<u32>
+    where
+        R::Page: Page<Opaque = Opaque>,
+    {
+        let id = self.ids.first().copied()?;
+        self.remove(index, id);
+        Some(id)
+    }
+    /// Links the page, which must be inaccessible in the graph.
+    pub fn free<R: RelationWrite>(&mut self, index: &R, id: u32)
+    where
+        R::Page: Page<Opaque = Opaque>,
+    {
+        assert!(self.ids.insert(id), "the page is already in the free list");
+        let next = self.first;
+        free(index, self.freepages_first, id);
+        self.first = id;
+        self.prev.insert(id, u32::MAX);
+        self.next.insert(id, next);
+        if next != u32::MAX {
+            self.prev.insert(next, id);
+        }
+    }
+    /// Unlinks the page.
+    pub fn remove<R: RelationWrite>(&mut self, index: &R, id: u32)
+    where
+        R::Page: Page<Opaque = Opaque>,
+    {
+        assert!(self.ids.remove(&id), "the page is not in the free list");
+        let prev = self.prev.remove(&id).expect("internal error");
+        let next = self.next.remove(&id).expect("internal error");
+        if prev != u32::MAX {
+            index.write(prev, false).get_opaque_mut().next = next;
+            self.next.insert(prev, next);
+        } else {
+            let mut freepages_guard = index.write(self.freepages_first, false);
+            let freepages_bytes = freepages_guard.get_mut(1).expect("data corruption");
+            let mut freepages_tuple = FreepagesTuple::deserialize_mut(freepages_bytes);
+            *freepages_tuple.first() = next;
+            self.first = next;
+        }
+        if next != u32::MAX {
+            self.prev.insert(next, prev);
+        }
+    }
+}
+
 // the page must be inaccessible in the graph
 pub fn free<R: RelationWrite>(index: &R, freepages_first: u32, id: u32)
 where
@@ -12,6 +12,7 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+mod assign;
 mod build;
 mod bulkdelete;
 mod cache;
@@ -19,6 +20,7 @@ mod centroids;
 mod closure_lifetime_binder;
 mod consume;
 mod cost;
+mod duplicates;
 mod fast_heap;
 mod freepages;
 mod insert;
@@ -29,23 +31,33 @@ mod rerank;
 mod search;
 mod tape;
 mod tape_writer;
+mod tree;
 mod tuples;
+mod upgrade;
+mod upper;
 mod vectors;
+mod verify;
 
 pub mod operator;
 pub mod types;
 
+pub use assign::{assign, list_firsts, list_members, read_list};
 pub use build::build;
 pub use bulkdelete::{bulkdelete, bulkdelete_vectors};
 pub use cache::cache;
 pub use consume::consume;
 pub use cost::cost;
+pub use duplicates::{neighbour_lists, scan_lists};
 pub use fast_heap::FastHeap;
 pub use insert::{InsertChooser, insert, insert_vector};
-pub use maintain::{MaintainChooser, maintain};
+pub use maintain::{Compact, MaintainChooser, appendable, compact, maintain};
 pub use prewarm::prewarm;
 pub use rerank::{how, rerank_heap, rerank_index};
-pub use search::{default_search, maxsim_search};
+pub use search::{batch_search, default_search, maxsim_search};
+pub use tree::{Node, tree};
+pub use upgrade::upgrade;
+pub use upper::{Upper, decode_upper};
+pub use verify::{Problem, dimension, verify};
 
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
@@ -19,11 +19,14 @@ use crate::tuples::*;
 use crate::{Branch, Opaque, freepages, tape};
 use index::prefetcher::PrefetcherSequenceFamily;
 use index::relation::{
-    Page, PageGuard, Relation, RelationRead, RelationReadTypes, RelationWrite, RelationWriteTypes,
+    Page, PageGuard, Relation, RelationRead, RelationReadTypes, RelationTruncate, RelationWrite,
+    RelationWriteTypes,
 };
 use index_accessor::FunctionalAccessor;
 use rabitq::packing::unpack;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeSet;
+use std::num::NonZero;
 
 pub trait MaintainChooser {
     fn choose(&mut self, i: usize) -> bool;
@@ -84,176 +87,656 @@ where
         number_of_freshly_allocated_pages: 0,
     });
 
+    let hook = id_3(|index: &R, opaque: Opaque, tracking_freespace: bool| {
+        if !tracking_freespace {
+            let mut buffers = buffers.borrow_mut();
+            if let Some(id) = buffers.pages.pop() {
+                drop(buffers);
+                let mut guard = index.write(id, false);
+                guard.clear(opaque);
+                guard
+            } else if let Some(mut guard) = freepages::alloc(index, freepages_first) {
+                buffers.number_of_formerly_allocated_pages += 1;
+                drop(buffers);
+                guard.clear(opaque);
+                guard
+            } else {
+                buffers.number_of_freshly_allocated_pages += 1;
+                drop(buffers);
+                index.extend(opaque, false)
+            }
+        } else {
+            index.extend(opaque, true)
+        }
+    });
+
     for (idx, first) in state.into_iter().enumerate() {
         if !chooser.choose(idx) {
             continue;
         }
+        let trace = rewrite::<R, O>(
+            index,
+            &mut prefetch_h0_tuples,
+            dim,
+            first,
+            &hook,
+            &mut |_, _, _| None,
+            &check,
+        );
+        buffers.borrow_mut().pages.extend(trace);
+    }
 
-        let mut jump_guard = index.write(first, false);
-        let jump_bytes = jump_guard.get_mut(1).expect("data corruption");
-        let mut jump_tuple = JumpTuple::deserialize_mut(jump_bytes);
-
-        let hooked_index = RelationHooked(index, {
-            id_3(|index: &R, opaque: Opaque, tracking_freespace: bool| {
-                if !tracking_freespace {
-                    let mut buffers = buffers.borrow_mut();
-                    if let Some(id) = buffers.pages.pop() {
-                        drop(buffers);
-                        let mut guard = index.write(id, false);
-                        guard.clear(opaque);
-                        guard
-                    } else if let Some(mut guard) = freepages::alloc(index, freepages_first) {
-                        buffers.number_of_formerly_allocated_pages += 1;
-                        drop(buffers);
-                        guard.clear(opaque);
-                        guard
-                    } else {
-                        buffers.number_of_freshly_allocated_pages += 1;
-                        drop(buffers);
-                        index.extend(opaque, false)
-                    }
-                } else {
-                    index.extend(opaque, true)
-                }
-            })
-        });
+    let buffers = RefCell::into_inner(buffers);
+    for id in buffers.pages.iter().copied() {
+        freepages::free(index, freepages_first, id);
+    }
 
-        let mut tape = FrozenTapeWriter::create(&hooked_index, O::Vector::count(dim) as _, false);
+    Maintain {
+        number_of_formerly_allocated_pages: buffers.number_of_formerly_allocated_pages,
+        number_of_freshly_allocated_pages: buffers.number_of_freshly_allocated_pages,
+        number_of_freed_pages: buffers.pages.len(),
+    }
+}
 
-        let mut trace_directory = Vec::new();
-        let mut trace_forzen = Vec::new();
-        let mut trace_appendable = Vec::new();
+/// Returns the number of live tuples in the appendable tape of each list, in
+/// the order that lists are passed to [`MaintainChooser`]. Deleted tuples are
+/// not counted, since they are removed by vacuum anyway.
+pub fn appendable<R: RelationRead>(index: &R, check: impl Fn()) -> Vec<u64>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let height_of_root = meta_tuple.height_of_root();
 
-        let mut tuples = 0_u64;
-        let mut callback = id_2(|(code, delta): (_, _), head, payload, prefetch: &[_]| {
-            tape.push(Branch {
-                code,
-                delta,
-                prefetch: prefetch.to_vec(),
-                head,
-                norm: 0.0,
-                extra: payload,
-            });
-            tuples += 1;
-        });
-        let directory = tape::read_directory_tape::<R>(
-            tape::by_next(index, *jump_tuple.directory_first())
-                .inspect(|_| check())
-                .inspect(|guard| trace_directory.push(guard.id())),
-        );
-        tape::read_frozen_tape::<R, _, _>(
-            tape::by_directory(&mut prefetch_h0_tuples, directory)
-                .inspect(|_| check())
-                .inspect(|guard| trace_forzen.push(guard.id())),
-            || {
-                FunctionalAccessor::new(
-                    Vec::<[u8; 16]>::new(),
-                    Vec::<[u8; 16]>::extend_from_slice,
-                    id_1(
-                        |elements: Vec<_>, (metadata, delta): (&[[f32; 32]; 4], &[f32; 32])| {
-                            let unpacked = unpack(&elements);
-                            std::array::from_fn(|i| {
-                                let f = |&x| [x & 1 != 0, x & 2 != 0, x & 4 != 0, x & 8 != 0];
-                                let signs = unpacked[i].iter().flat_map(f).collect::<Vec<_>>();
-                                (
-                                    (
-                                        rabitq::bit::CodeMetadata {
-                                            dis_u_2: metadata[0][i],
-                                            factor_cnt: metadata[1][i],
-                                            factor_ip: metadata[2][i],
-                                            factor_err: metadata[3][i],
-                                        },
-                                        signs,
-                                    ),
-                                    delta[i],
-                                )
-                            })
-                        },
-                    ),
-                )
-            },
-            &mut callback,
-        );
-        tape::read_appendable_tape::<R, _>(
-            tape::by_next(index, *jump_tuple.appendable_first())
-                .inspect(|_| check())
-                .inspect(|guard| trace_appendable.push(guard.id())),
-            |metadata, elements, delta| {
-                let signs = elements
-                    .iter()
-                    .flat_map(|x| std::array::from_fn::<_, 64, _>(|i| *x & (1 << i) != 0))
-                    .take(dim as _)
-                    .collect::<Vec<_>>();
-                (
-                    (
-                        rabitq::bit::CodeMetadata {
-                            dis_u_2: metadata[0],
-                            factor_cnt: metadata[1],
-                            factor_ip: metadata[2],
-                            factor_err: metadata[3],
-                        },
-                        signs,
-                    ),
-                    delta,
-                )
-            },
-            &mut callback,
-        );
+    type State = Vec<u32>;
+    let mut state: State = vec![meta_tuple.first()];
 
-        let (frozen_tape, branches) = tape.into_inner();
-
-        let mut appendable_tape = tape::TapeWriter::create(&hooked_index, false);
-
-        for branch in branches {
-            appendable_tape.push(AppendableTuple {
-                metadata: [
-                    branch.code.0.dis_u_2,
-                    branch.code.0.factor_cnt,
-                    branch.code.0.factor_ip,
-                    branch.code.0.factor_err,
-                ],
-                elements: rabitq::bit::binary::pack_code(&branch.code.1),
-                delta: branch.delta,
-                prefetch: branch.prefetch,
-                head: branch.head,
-                payload: Some(branch.extra),
-            });
+    drop(meta_guard);
+
+    let step = |state: State| {
+        let mut results = Vec::new();
+        for first in state {
+            tape::read_h1_tape::<R, _, _>(
+                tape::by_next(index, first).inspect(|_| check()),
+                || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                |(), _, _, first, _| results.push(first),
+            );
         }
+        results
+    };
 
-        let frozen_first = { frozen_tape }.first();
+    for _ in (1..height_of_root).rev() {
+        state = step(state);
+    }
 
-        let directory = tape::by_next(index, frozen_first)
+    let mut results = Vec::with_capacity(state.len());
+    for first in state {
+        let jump_guard = index.read(first);
+        let jump_bytes = jump_guard.get(1).expect("data corruption");
+        let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+        let appendable_first = jump_tuple.appendable_first();
+        drop(jump_guard);
+        let tuples = tape::by_next(index, appendable_first)
             .inspect(|_| check())
-            .map(|guard| guard.id())
-            .collect::<Vec<_>>();
+            .map(|guard| {
+                (1..=guard.len())
+                    .filter_map(|i| guard.get(i))
+                    .filter(|bytes| AppendableTuple::deserialize_ref(bytes).payload().is_some())
+                    .count() as u64
+            })
+            .sum();
+        results.push(tuples);
+    }
+    results
+}
+
+pub struct Compact {
+    pub number_of_freshly_allocated_pages: usize,
+    pub number_of_moved_vectors: usize,
+    pub number_of_free_pages: usize,
+    /// `None` if the relation is in use, so it's only maintained
+    pub number_of_truncated_pages: Option<usize>,
+}
+
+/// Rewrites all lists as [`maintain`] does, but with the relation locked
+/// exclusively, so that pages at the end could be truncated. Tapes are moved
+/// to free pages with the lowest ids, and vectors on the vector pages with the
+/// highest ids are moved to the free space of other vector pages.
+///
+/// If the relation is in use, it's only maintained.
+pub fn compact<'b, R: RelationRead + RelationWrite + RelationTruncate, O: Operator>(
+    index: &'b R,
+    mut prefetch_h0_tuples: impl PrefetcherSequenceFamily<'b, R>,
+    check: impl Fn(),
+) -> Compact
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let mut result = None;
+    let truncated = index.truncate(|len| {
+        let (compact, target) =
+            compact_exclusively::<R, O>(index, &mut prefetch_h0_tuples, len, &check);
+        result = Some(compact);
+        target
+    });
+    if let (Some(_), Some(compact)) = (truncated, result) {
+        return compact;
+    }
+    struct ChooseAll;
+    impl MaintainChooser for ChooseAll {
+        fn choose(&mut self, _: usize) -> bool {
+            true
+        }
+    }
+    let maintain = maintain::<R, O>(index, prefetch_h0_tuples, &mut ChooseAll, &check);
+    let freepages_first = {
+        let meta_guard = index.read(0);
+        let meta_bytes = meta_guard.get(1).expect("data corruption");
+        MetaTuple::deserialize_ref(meta_bytes).freepages_first()
+    };
+    Compact {
+        number_of_freshly_allocated_pages: maintain.number_of_freshly_allocated_pages,
+        number_of_moved_vectors: 0,
+        number_of_free_pages: freepages::Sorted::read(index, freepages_first).len(),
+        number_of_truncated_pages: None,
+    }
+}
 
-        let mut directory_tape = DirectoryTapeWriter::create(&hooked_index, false);
-        directory_tape.push(directory.as_slice());
-        let directory_tape = directory_tape.into_inner();
+fn compact_exclusively<'b, R, O>(
+    index: &'b R,
+    prefetch_h0_tuples: &mut impl PrefetcherSequenceFamily<'b, R>,
+    len: u32,
+    check: &impl Fn(),
+) -> (Compact, u32)
+where
+    R: RelationRead + RelationWrite,
+    R::Page: Page<Opaque = Opaque>,
+    O: Operator,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let dim = meta_tuple.dim();
+    let height_of_root = meta_tuple.height_of_root();
+    let freepages_first = meta_tuple.freepages_first();
+    let vectors_first = meta_tuple.vectors_first().to_vec();
 
-        *jump_tuple.directory_first() = { directory_tape }.first();
-        *jump_tuple.frozen_first() = frozen_first;
-        *jump_tuple.appendable_first() = { appendable_tape }.first();
-        *jump_tuple.tuples() = tuples;
+    type State = Vec<u32>;
+    let mut state: State = vec![meta_tuple.first()];
 
-        drop(jump_guard);
+    drop(meta_guard);
 
-        let mut buffers = buffers.borrow_mut();
-        buffers.pages.extend_from_slice(&trace_directory);
-        buffers.pages.extend_from_slice(&trace_forzen);
-        buffers.pages.extend_from_slice(&trace_appendable);
+    let step = |state: State| {
+        let mut results = Vec::new();
+        for first in state {
+            tape::read_h1_tape::<R, _, _>(
+                tape::by_next(index, first).inspect(|_| check()),
+                || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                |(), _, _, first, _| results.push(first),
+            );
+        }
+        results
+    };
+
+    for _ in (1..height_of_root).rev() {
+        state = step(state);
     }
 
-    let buffers = RefCell::into_inner(buffers);
-    for id in buffers.pages.iter().copied() {
-        freepages::free(index, freepages_first, id);
+    // pages are unlinked from the free list only when they are used
+    let freepages = RefCell::new(freepages::Sorted::read(index, freepages_first));
+    let number_of_freshly_allocated_pages = Cell::new(0_usize);
+
+    let hook = id_3(|index: &R, opaque: Opaque, tracking_freespace: bool| {
+        if !tracking_freespace {
+            let id = freepages.borrow_mut().pop_first(index);
+            if let Some(id) = id {
+                let mut guard = index.write(id, false);
+                guard.clear(opaque);
+                guard
+            } else {
+                number_of_freshly_allocated_pages.set(number_of_freshly_allocated_pages.get() + 1);
+                index.extend(opaque, false)
+            }
+        } else {
+            index.extend(opaque, true)
+        }
+    });
+
+    let mut mover = Mover::new(index, &vectors_first, check);
+
+    // pages of lists are freed in descending order, so that pages with lower
+    // ids are allocated first
+    let free = |trace: Vec<u32>| {
+        let mut freepages = freepages.borrow_mut();
+        for id in trace.into_iter().collect::<BTreeSet<_>>().into_iter().rev() {
+            freepages.free(index, id);
+        }
+    };
+
+    let mut trace = Vec::new();
+    for &first in state.iter() {
+        trace.extend(rewrite::<R, O>(
+            index,
+            prefetch_h0_tuples,
+            dim,
+            first,
+            &hook,
+            &mut |prefetch, head, payload| mover.relocate::<R, O>(index, prefetch, head, payload),
+            check,
+        ));
     }
+    free(trace);
 
-    Maintain {
-        number_of_formerly_allocated_pages: buffers.number_of_formerly_allocated_pages,
-        number_of_freshly_allocated_pages: buffers.number_of_freshly_allocated_pages,
-        number_of_freed_pages: buffers.pages.len(),
+    // lists are rewritten on freshly allocated pages at the end if there are
+    // not enough free pages, so they are rewritten again on the pages that are
+    // just freed
+    if number_of_freshly_allocated_pages.get() != 0 {
+        let mut trace = Vec::new();
+        for &first in state.iter() {
+            trace.extend(rewrite::<R, O>(
+                index,
+                prefetch_h0_tuples,
+                dim,
+                first,
+                &hook,
+                &mut |_, _, _| None,
+                check,
+            ));
+        }
+        free(trace);
+    }
+
+    let mut freepages = RefCell::into_inner(freepages);
+    let empty = mover.finish(index, check);
+    let mut target = len;
+    while target > 0 {
+        let id = target - 1;
+        if freepages.contains(id) || empty.contains(&id) {
+            target -= 1;
+        } else {
+            break;
+        }
     }
+    while let Some(id) = freepages.last().filter(|&id| id >= target) {
+        freepages.remove(index, id);
+    }
+    mover.unlink(index, target);
+
+    let compact = Compact {
+        number_of_freshly_allocated_pages: number_of_freshly_allocated_pages.get(),
+        number_of_moved_vectors: mover.moved,
+        number_of_free_pages: freepages.len(),
+        number_of_truncated_pages: Some((len - target) as usize),
+    };
+    (compact, target)
+}
+
+/// Moves vectors out of the vector pages with the highest ids, to the free
+/// space of other vector pages. The relation must be locked exclusively, so
+/// that all vectors are referenced by tuples of lists.
+struct Mover {
+    tapes: Vec<Vec<u32>>,
+    sources: BTreeSet<u32>,
+    targets: Vec<(u32, u16)>,
+    cursor: usize,
+    moved: usize,
+}
+
+impl Mover {
+    fn new<R: RelationRead>(index: &R, vectors_first: &[u32], check: &impl Fn()) -> Self
+    where
+        R::Page: Page<Opaque = Opaque>,
+    {
+        let mut tapes = Vec::new();
+        // (id, used, free, movable)
+        let mut pages = Vec::new();
+        for &first in vectors_first {
+            let mut tape = Vec::new();
+            for guard in tape::by_next(index, first).inspect(|_| check()) {
+                let used = (1..=guard.len())
+                    .filter_map(|i| guard.get(i))
+                    .map(|bytes| bytes.len() + 4)
+                    .sum::<usize>();
+                pages.push((
+                    guard.id(),
+                    used,
+                    guard.freespace() as usize,
+                    guard.id() != first,
+                ));
+                tape.push(guard.id());
+            }
+            tapes.push(tape);
+        }
+        pages.sort_unstable();
+        // the first page of a tape is referenced by the meta tuple, so it's kept
+        let mut used_of_sources = 0_usize;
+        let mut free_of_targets = pages.iter().map(|&(_, _, free, _)| free).sum::<usize>();
+        let mut cut = pages.len();
+        while let Some(&(_, used, free, movable)) = cut.checked_sub(1).map(|i| &pages[i]) {
+            if !movable {
+                cut -= 1;
+                continue;
+            }
+            if used_of_sources + used > free_of_targets - free {
+                break;
+            }
+            used_of_sources += used;
+            free_of_targets -= free;
+            cut -= 1;
+        }
+        let sources = pages[cut..]
+            .iter()
+            .filter(|&&(_, _, _, movable)| movable)
+            .map(|&(id, _, _, _)| id)
+            .collect::<BTreeSet<_>>();
+        let targets = pages
+            .iter()
+            .filter(|&&(id, _, _, _)| !sources.contains(&id))
+            .map(|&(id, _, free, _)| (id, free as u16))
+            .collect();
+        Self {
+            tapes,
+            sources,
+            targets,
+            cursor: 0,
+            moved: 0,
+        }
+    }
+
+    fn place<R: RelationWrite>(&mut self, index: &R, bytes: &[u8]) -> Option<(u32, u16)>
+    where
+        R::Page: Page<Opaque = Opaque>,
+    {
+        for j in self.cursor..self.targets.len() {
+            let (id, free) = self.targets[j];
+            if (free as usize) < bytes.len() {
+                if j == self.cursor {
+                    self.cursor += 1;
+                }
+                continue;
+            }
+            let mut guard = index.write(id, true);
+            let i = guard.alloc(bytes);
+            self.targets[j].1 = guard.freespace();
+            if let Some(i) = i {
+                return Some((id, i));
+            }
+        }
+        None
+    }
+
+    /// Moves the vector if it's on a source page, and returns where it's moved.
+    fn relocate<R: RelationRead + RelationWrite, O: Operator>(
+        &mut self,
+        index: &R,
+        prefetch: &[u32],
+        head: u16,
+        payload: NonZero<u64>,
+    ) -> Option<(Vec<u32>, u16)>
+    where
+        R::Page: Page<Opaque = Opaque>,
+    {
+        if !prefetch.iter().any(|id| self.sources.contains(id)) {
+            return None;
+        }
+        let mut slots = Vec::with_capacity(prefetch.len());
+        let mut pieces = Vec::with_capacity(prefetch.len());
+        let mut cursor = Err(head);
+        for &id in prefetch {
+            let Err(head) = cursor else {
+                return None;
+            };
+            let guard = index.read(id);
+            let bytes = guard.get(head)?;
+            let tuple = VectorTuple::<O::Vector>::deserialize_ref(bytes);
+            if tuple.payload() != Some(payload) {
+                return None;
+            }
+            slots.push(head);
+            pieces.push(tuple.elements().to_vec());
+            cursor = tuple.metadata_or_head();
+        }
+        let Ok(metadata) = cursor else {
+            return None;
+        };
+        // pieces are written from the last one, so that heads are known
+        let mut placed = Vec::with_capacity(pieces.len());
+        let mut chain = Ok(metadata);
+        for elements in pieces.into_iter().rev() {
+            let bytes = VectorTuple::<O::Vector>::serialize(&match chain {
+                Ok(metadata) => VectorTuple::_0 {
+                    payload: Some(payload),
+                    metadata,
+                    elements,
+                },
+                Err(head) => VectorTuple::_1 {
+                    payload: Some(payload),
+                    head,
+                    elements,
+                },
+            });
+            let Some((id, i)) = self.place(index, &bytes) else {
+                for (id, i) in placed {
+                    index.write(id, true).free(i);
+                }
+                return None;
+            };
+            placed.push((id, i));
+            chain = Err(i);
+        }
+        for (&id, &i) in std::iter::zip(prefetch, &slots) {
+            index.write(id, true).free(i);
+        }
+        self.moved += 1;
+        let head = chain.expect_err("internal error: 0-dimensional vector");
+        Some((placed.into_iter().rev().map(|(id, _)| id).collect(), head))
+    }
+
+    /// Frees vectors that are left on source pages, which are not referenced,
+    /// and returns vector pages that are empty and could be removed.
+    fn finish<R: RelationRead + RelationWrite>(&self, index: &R, check: &impl Fn()) -> BTreeSet<u32>
+    where
+        R::Page: Page<Opaque = Opaque>,
+    {
+        for &id in self.sources.iter() {
+            check();
+            let mut guard = index.write(id, true);
+            for i in 1..=guard.len() {
+                if guard.get(i).is_some() {
+                    guard.free(i);
+                }
+            }
+        }
+        let mut empty = BTreeSet::new();
+        for tape in self.tapes.iter() {
+            for &id in tape.iter().skip(1) {
+                check();
+                let guard = index.read(id);
+                if (1..=guard.len()).all(|i| guard.get(i).is_none()) {
+                    empty.insert(id);
+                }
+            }
+        }
+        empty
+    }
+
+    /// Removes vector pages that are not less than `target` from tapes.
+    fn unlink<R: RelationWrite>(&self, index: &R, target: u32)
+    where
+        R::Page: Page<Opaque = Opaque>,
+    {
+        for tape in self.tapes.iter() {
+            let kept = tape
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|&(k, id)| k == 0 || id < target)
+                .map(|(_, id)| id)
+                .collect::<Vec<_>>();
+            if kept.len() == tape.len() {
+                continue;
+            }
+            for (k, &id) in kept.iter().enumerate() {
+                let next = kept.get(k + 1).copied().unwrap_or(u32::MAX);
+                let mut guard = index.write(id, true);
+                guard.get_opaque_mut().next = next;
+                if k == 0 {
+                    guard.get_opaque_mut().skip = *kept.last().expect("internal error");
+                }
+            }
+        }
+    }
+}
+
+/// Rewrites the tapes of a list, and returns the pages that are no longer used.
+fn rewrite<'b, R, O>(
+    index: &'b R,
+    prefetch_h0_tuples: &mut impl PrefetcherSequenceFamily<'b, R>,
+    dim: u32,
+    first: u32,
+    hook: impl Clone + for<'a> Fn(&'a R, Opaque, bool) -> R::WriteGuard<'a>,
+    relocate: &mut impl FnMut(&[u32], u16, NonZero<u64>) -> Option<(Vec<u32>, u16)>,
+    check: &impl Fn(),
+) -> Vec<u32>
+where
+    R: RelationRead + RelationWrite,
+    R::Page: Page<Opaque = Opaque>,
+    O: Operator,
+{
+    let mut jump_guard = index.write(first, false);
+    let jump_bytes = jump_guard.get_mut(1).expect("data corruption");
+    let mut jump_tuple = JumpTuple::deserialize_mut(jump_bytes);
+
+    let hooked_index = RelationHooked(index, hook);
+
+    let mut tape = FrozenTapeWriter::create(&hooked_index, O::Vector::count(dim) as _, false);
+
+    let mut trace_directory = Vec::new();
+    let mut trace_forzen = Vec::new();
+    let mut trace_appendable = Vec::new();
+
+    let mut tuples = 0_u64;
+    let mut callback = id_2(|(code, delta): (_, _), head, payload, prefetch: &[_]| {
+        let (prefetch, head) =
+            relocate(prefetch, head, payload).unwrap_or_else(|| (prefetch.to_vec(), head));
+        tape.push(Branch {
+            code,
+            delta,
+            prefetch,
+            head,
+            norm: 0.0,
+            extra: payload,
+        });
+        tuples += 1;
+    });
+    let directory = tape::read_directory_tape::<R>(
+        tape::by_next(index, *jump_tuple.directory_first())
+            .inspect(|_| check())
+            .inspect(|guard| trace_directory.push(guard.id())),
+    );
+    tape::read_frozen_tape::<R, _, _>(
+        tape::by_directory(prefetch_h0_tuples, directory)
+            .inspect(|_| check())
+            .inspect(|guard| trace_forzen.push(guard.id())),
+        || {
+            FunctionalAccessor::new(
+                Vec::<[u8; 16]>::new(),
+                Vec::<[u8; 16]>::extend_from_slice,
+                id_1(
+                    |elements: Vec<_>, (metadata, delta): (&[[f32; 32]; 4], &[f32; 32])| {
+                        let unpacked = unpack(&elements);
+                        std::array::from_fn(|i| {
+                            let f = |&x| [x & 1 != 0, x & 2 != 0, x & 4 != 0, x & 8 != 0];
+                            let signs = unpacked[i].iter().flat_map(f).collect::<Vec<_>>();
+                            (
+                                (
+                                    rabitq::bit::CodeMetadata {
+                                        dis_u_2: metadata[0][i],
+                                        factor_cnt: metadata[1][i],
+                                        factor_ip: metadata[2][i],
+                                        factor_err: metadata[3][i],
+                                    },
+                                    signs,
+                                ),
+                                delta[i],
+                            )
+                        })
+                    },
+                ),
+            )
+        },
+        &mut callback,
+    );
+    tape::read_appendable_tape::<R, _>(
+        tape::by_next(index, *jump_tuple.appendable_first())
+            .inspect(|_| check())
+            .inspect(|guard| trace_appendable.push(guard.id())),
+        |metadata, elements, delta| {
+            let signs = elements
+                .iter()
+                .flat_map(|x| std::array::from_fn::<_, 64, _>(|i| *x & (1 << i) != 0))
+                .take(dim as _)
+                .collect::<Vec<_>>();
+            (
+                (
+                    rabitq::bit::CodeMetadata {
+                        dis_u_2: metadata[0],
+                        factor_cnt: metadata[1],
+                        factor_ip: metadata[2],
+                        factor_err: metadata[3],
+                    },
+                    signs,
+                ),
+                delta,
+            )
+        },
+        &mut callback,
+    );
+
+    let (frozen_tape, branches) = tape.into_inner();
+
+    let mut appendable_tape = tape::TapeWriter::create(&hooked_index, false);
+
+    for branch in branches {
+        appendable_tape.push(AppendableTuple {
+            metadata: [
+                branch.code.0.dis_u_2,
+                branch.code.0.factor_cnt,
+                branch.code.0.factor_ip,
+                branch.code.0.factor_err,
+            ],
+            elements: rabitq::bit::binary::pack_code(&branch.code.1),
+            delta: branch.delta,
+            prefetch: branch.prefetch,
+            head: branch.head,
+            payload: Some(branch.extra),
+        });
+    }
+
+    let frozen_first = { frozen_tape }.first();
+
+    let directory = tape::by_next(index, frozen_first)
+        .inspect(|_| check())
+        .map(|guard| guard.id())
+        .collect::<Vec<_>>();
+
+    let mut directory_tape = DirectoryTapeWriter::create(&hooked_index, false);
+    directory_tape.push(directory.as_slice());
+    let directory_tape = directory_tape.into_inner();
+
+    *jump_tuple.directory_first() = { directory_tape }.first();
+    *jump_tuple.frozen_first() = frozen_first;
+    *jump_tuple.appendable_first() = { appendable_tape }.first();
+    *jump_tuple.tuples() = tuples;
+
+    drop(jump_guard);
+
+    let mut trace = trace_directory;
+    trace.extend(trace_forzen);
+    trace.extend(trace_appendable);
+    trace
 }
 
 #[derive(Clone)]
@@ -314,3 +797,342 @@ where
         self.0.search(freespace)
     }
 }
+
+#[test]
+fn test_compact() {
+    use crate::InsertChooser;
+    use crate::operator::Op;
+    use crate::types::{DistanceKind, Structure, VchordrqIndexOptions, VectorKind, VectorOptions};
+    use index::fetch::Fetch;
+    use index::memory::{MemoryPage, MemoryRelation};
+    use index::prefetcher::{PlainPrefetcher, PrefetcherHeapFamily, Sequence};
+    use index_accessor::L2S;
+    use std::collections::BinaryHeap;
+    use vector::VectorOwned;
+    use vector::vect::VectOwned;
+
+    // pages of memory relations cannot be removed, so the truncation is only
+    // recorded, and no page after it should be referenced
+    struct TruncatedRelation {
+        inner: MemoryRelation<Opaque>,
+        target: Cell<Option<u32>>,
+    }
+
+    impl Relation for TruncatedRelation {
+        type Page = MemoryPage<Opaque>;
+    }
+
+    impl RelationReadTypes for TruncatedRelation {
+        type ReadGuard<'a> = <MemoryRelation<Opaque> as RelationReadTypes>::ReadGuard<'a>;
+    }
+
+    impl RelationRead for TruncatedRelation {
+        fn read(&self, id: u32) -> Self::ReadGuard<'_> {
+            assert!(id < self.target.get().unwrap_or(u32::MAX), "truncated");
+            self.inner.read(id)
+        }
+    }
+
+    impl RelationWriteTypes for TruncatedRelation {
+        type WriteGuard<'a> = <MemoryRelation<Opaque> as RelationWriteTypes>::WriteGuard<'a>;
+    }
+
+    impl RelationWrite for TruncatedRelation {
+        fn write(&self, id: u32, tracking_freespace: bool) -> Self::WriteGuard<'_> {
+            assert!(id < self.target.get().unwrap_or(u32::MAX), "truncated");
+            self.inner.write(id, tracking_freespace)
+        }
+
+        fn extend(&self, opaque: Opaque, tracking_freespace: bool) -> Self::WriteGuard<'_> {
+            assert!(self.target.get().is_none(), "truncated");
+            self.inner.extend(opaque, tracking_freespace)
+        }
+
+        fn search(&self, freespace: usize) -> Option<Self::WriteGuard<'_>> {
+            self.inner.search(freespace)
+        }
+    }
+
+    impl RelationTruncate for TruncatedRelation {
+        fn truncate(&self, f: impl FnOnce(u32) -> u32) -> Option<u32> {
+            let target = f(self.inner.len());
+            self.target.set(Some(target));
+            Some(target)
+        }
+    }
+
+    struct MakeH1PlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherHeapFamily<'b, R> for MakeH1PlainPrefetcher<'b, R> {
+        type P<T>
+            = PlainPrefetcher<'b, R, BinaryHeap<T>>
+        where
+            T: Ord + Fetch<'b>;
+
+        fn prefetch<T>(&mut self, seq: Vec<T>) -> Self::P<T>
+        where
+            T: Ord + Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, BinaryHeap::from(seq))
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    struct MakeH0PlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherSequenceFamily<'b, R> for MakeH0PlainPrefetcher<'b, R> {
+        type P<S: Sequence>
+            = PlainPrefetcher<'b, R, S>
+        where
+            S::Item: Fetch<'b>;
+
+        fn prefetch<S: Sequence>(&mut self, seq: S) -> Self::P<S>
+        where
+            S::Item: Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, seq)
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    struct IdChooser(usize);
+
+    impl InsertChooser for IdChooser {
+        fn choose(&mut self, n: NonZero<usize>) -> usize {
+            self.0 % n.get()
+        }
+    }
+
+    type O = Op<VectOwned<f32>, L2S>;
+    // a vector takes a quarter of a page, so that there are many vector pages
+    let dim = 500;
+    let vectors = (0..400)
+        .map(|_| (0..dim).map(|_| rand::random::<f32>()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let projected = vectors
+        .iter()
+        .map(|vector| VectOwned::new(rabitq::rotate::rotate(vector)))
+        .collect::<Vec<_>>();
+    let index = TruncatedRelation {
+        inner: MemoryRelation::new(),
+        target: Cell::new(None),
+    };
+    let vector_options = VectorOptions {
+        dim: dim as u32,
+        v: VectorKind::Vecf32,
+        d: DistanceKind::L2S,
+    };
+    let structures = vec![Structure {
+        centroids: projected[..1].to_vec(),
+        children: vec![Vec::new()],
+    }];
+    crate::build::<_, O>(
+        vector_options,
+        VchordrqIndexOptions::default(),
+        &index,
+        structures,
+    );
+    for i in 0..400 {
+        let bump = bumpalo::Bump::new();
+        let payload = NonZero::new(i as u64 + 1).unwrap();
+        let vector = VectOwned::new(vectors[i].clone());
+        let key = crate::insert_vector::<_, O>(
+            &index,
+            payload,
+            vector.as_borrowed(),
+            &mut IdChooser(i),
+            true,
+        );
+        crate::insert::<_, O>(
+            &index,
+            payload,
+            projected[i].as_borrowed(),
+            key,
+            &bump,
+            MakeH1PlainPrefetcher(&index),
+            true,
+        );
+    }
+    // most vectors inserted lately are deleted, so vector pages at the end
+    // become almost empty
+    let deleted = |payload: NonZero<u64>| payload.get() > 100 && payload.get() % 10 != 0;
+    crate::bulkdelete::<_, O>(&index, || (), deleted);
+    crate::bulkdelete_vectors::<_, O>(&index, || (), deleted);
+    let len = index.inner.len();
+    let compact = compact::<_, O>(&index, MakeH0PlainPrefetcher(&index), || ());
+    assert!(compact.number_of_moved_vectors > 0);
+    let truncated = compact.number_of_truncated_pages.expect("not truncated");
+    let target = index.target.get().expect("not truncated");
+    assert!(truncated > 0);
+    assert_eq!(target as usize + truncated, len as usize);
+    let expected = (1..=400)
+        .filter(|&x| !deleted(NonZero::new(x).unwrap()))
+        .collect::<Vec<_>>();
+    let mut payloads = Vec::new();
+    let problems = crate::verify::<_, O>(
+        &index,
+        target,
+        || (),
+        |payload| payloads.push(payload.get()),
+    );
+    assert!(problems.is_empty(), "{problems:?}");
+    payloads.sort_unstable();
+    assert_eq!(payloads, expected);
+    // all vectors are read from where they are moved to
+    let bump = bumpalo::Bump::new();
+    let results = crate::default_search::<_, O>(
+        &index,
+        None,
+        projected[0].as_borrowed(),
+        vec![],
+        1.9,
+        &bump,
+        MakeH1PlainPrefetcher(&index),
+        MakeH0PlainPrefetcher(&index),
+    );
+    let prefetcher = PlainPrefetcher::new(&index, BinaryHeap::from(results));
+    let mut reranked =
+        crate::rerank_index::<O, _, _, _>(VectOwned::new(vectors[0].clone()), prefetcher)
+            .map(|(_, payload)| payload.get())
+            .collect::<Vec<_>>();
+    assert_eq!(reranked[0], 1);
+    reranked.sort_unstable();
+    assert_eq!(reranked, expected);
+}
+
+#[test]
+fn test_appendable() {
+    use crate::operator::Op;
+    use crate::types::{DistanceKind, Structure, VchordrqIndexOptions, VectorKind, VectorOptions};
+    use index::fetch::Fetch;
+    use index::memory::MemoryRelation;
+    use index::prefetcher::{PlainPrefetcher, PrefetcherHeapFamily, Sequence};
+    use index_accessor::L2S;
+    use std::collections::BinaryHeap;
+    use vector::VectorOwned;
+    use vector::vect::VectOwned;
+
+    struct MakeH1PlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherHeapFamily<'b, R> for MakeH1PlainPrefetcher<'b, R> {
+        type P<T>
+            = PlainPrefetcher<'b, R, BinaryHeap<T>>
+        where
+            T: Ord + Fetch<'b>;
+
+        fn prefetch<T>(&mut self, seq: Vec<T>) -> Self::P<T>
+        where
+            T: Ord + Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, BinaryHeap::from(seq))
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    struct MakeH0PlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherSequenceFamily<'b, R> for MakeH0PlainPrefetcher<'b, R> {
+        type P<S: Sequence>
+            = PlainPrefetcher<'b, R, S>
+        where
+            S::Item: Fetch<'b>;
+
+        fn prefetch<S: Sequence>(&mut self, seq: S) -> Self::P<S>
+        where
+            S::Item: Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, seq)
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    struct IdChooser(usize);
+
+    impl crate::InsertChooser for IdChooser {
+        fn choose(&mut self, n: NonZero<usize>) -> usize {
+            self.0 % n.get()
+        }
+    }
+
+    struct ChooseAll;
+
+    impl MaintainChooser for ChooseAll {
+        fn choose(&mut self, _: usize) -> bool {
+            true
+        }
+    }
+
+    type O = Op<VectOwned<f32>, L2S>;
+    let dim = 16;
+    let projected = (0..200)
+        .map(|_| {
+            let vector = (0..dim).map(|_| rand::random::<f32>()).collect::<Vec<_>>();
+            VectOwned::new(rabitq::rotate::rotate(&vector))
+        })
+        .collect::<Vec<_>>();
+    let index = MemoryRelation::new();
+    let vector_options = VectorOptions {
+        dim: dim as u32,
+        v: VectorKind::Vecf32,
+        d: DistanceKind::L2S,
+    };
+    // two lists under the root
+    let structures = vec![
+        Structure {
+            centroids: projected[..2].to_vec(),
+            children: vec![Vec::new(); 2],
+        },
+        Structure {
+            centroids: projected[..1].to_vec(),
+            children: vec![vec![0, 1]],
+        },
+    ];
+    crate::build::<_, O>(
+        vector_options,
+        VchordrqIndexOptions::default(),
+        &index,
+        structures,
+    );
+    assert_eq!(appendable(&index, || ()), [0, 0]);
+    for (i, projected) in projected.iter().enumerate() {
+        let bump = bumpalo::Bump::new();
+        let payload = NonZero::new(i as u64 + 1).unwrap();
+        let key = crate::insert_vector::<_, O>(
+            &index,
+            payload,
+            projected.as_borrowed(),
+            &mut IdChooser(i),
+            true,
+        );
+        crate::insert::<_, O>(
+            &index,
+            payload,
+            projected.as_borrowed(),
+            key,
+            &bump,
+            MakeH1PlainPrefetcher(&index),
+            true,
+        );
+    }
+    let inserted = appendable(&index, || ());
+    assert_eq!(inserted.iter().sum::<u64>(), 200);
+    // deleted tuples are still in appendable tapes, but they are not counted
+    crate::bulkdelete::<_, O>(&index, || (), |payload| payload.get() % 4 == 0);
+    assert_eq!(appendable(&index, || ()).iter().sum::<u64>(), 150);
+    // tuples are frozen in groups of 32, and the rest stay appendable
+    maintain::<_, O>(&index, MakeH0PlainPrefetcher(&index), &mut ChooseAll, || ());
+    let maintained = appendable(&index, || ());
+    assert!(maintained.iter().all(|&x| x < 32), "{maintained:?}");
+}
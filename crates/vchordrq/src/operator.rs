@@ -14,17 +14,20 @@
 
 use distance::Distance;
 use index_accessor::{
-    Accessor1, Accessor2, ByteDistanceAccessor, DefaultWithDimension, DistanceAccessor, Dot,
-    HalfbyteDistanceAccessor, L2S, RAccess,
+    Accessor1, Accessor2, BitsDistanceAccessor, ByteDistanceAccessor, DefaultWithDimension,
+    DistanceAccessor, Dot, HalfbyteDistanceAccessor, L2S, RAccess,
 };
 use rabitq::bit::CodeMetadata;
 use rabitq::bit::binary::BinaryLut;
 use rabitq::bit::block::{BlockLut, STEP};
-use simd::{Floating, f16};
+use simd::{Floating, bf16, f16};
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use vector::rabitq1::{Rabitq1Borrowed, Rabitq1Owned};
+use vector::rabitq2::{Rabitq2Borrowed, Rabitq2Owned};
 use vector::rabitq4::{Rabitq4Borrowed, Rabitq4Owned};
 use vector::rabitq8::{Rabitq8Borrowed, Rabitq8Owned};
+use vector::veci8::{Veci8Borrowed, Veci8Owned};
 use vector::vect::{VectBorrowed, VectOwned};
 use vector::{VectorBorrowed, VectorOwned};
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
@@ -214,6 +217,58 @@ impl Vector for VectOwned<f16> {
     }
 }
 
+impl Vector for VectOwned<bf16> {
+    type Metadata = ();
+
+    type Element = bf16;
+
+    fn split(vector: Self::Borrowed<'_>) -> (Vec<&[bf16]>, ()) {
+        let vector = vector.slice();
+        (
+            match vector.len() {
+                0 => unreachable!(),
+                1..=1920 => vec![vector],
+                1921..=2560 => vec![&vector[..1280], &vector[1280..]],
+                2561.. => vector.chunks(3840).collect(),
+            },
+            (),
+        )
+    }
+
+    fn count(dim: u32) -> u32 {
+        match dim {
+            0 => unreachable!(),
+            1..=1920 => 1,
+            1921..=2560 => 2,
+            2561.. => dim.div_ceil(3840),
+        }
+    }
+
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (vector.slice(), ())
+    }
+
+    fn pack(_: u32, elements: Vec<Self::Element>, (): Self::Metadata) -> Self {
+        VectOwned::new(elements)
+    }
+
+    fn block_preprocess(vector: Self::Borrowed<'_>) -> BlockLut {
+        rabitq::bit::block::preprocess(&bf16::vector_to_f32(vector.slice()))
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> (BlockLut, BinaryLut) {
+        rabitq::bit::preprocess(&bf16::vector_to_f32(vector.slice()))
+    }
+
+    fn code(vector: Self::Borrowed<'_>) -> rabitq::bit::Code {
+        rabitq::bit::code(&bf16::vector_to_f32(vector.slice()))
+    }
+
+    fn squared_norm(vector: Self::Borrowed<'_>) -> f32 {
+        bf16::reduce_sum_of_x2(vector.slice())
+    }
+}
+
 impl Vector for Rabitq8Owned {
     type Metadata = [f32; 4];
 
@@ -418,69 +473,887 @@ impl Vector for Rabitq4Owned {
     }
 }
 
-pub trait Operator: 'static + Debug + Copy {
-    type Vector: Vector;
+impl Vector for Rabitq2Owned {
+    type Metadata = [f32; 4];
 
-    type DistanceAccessor: DefaultWithDimension
-        + Accessor2<
-            <Self::Vector as Vector>::Element,
-            <Self::Vector as Vector>::Element,
-            <Self::Vector as Vector>::Metadata,
-            <Self::Vector as Vector>::Metadata,
-            Output = Distance,
-        >;
+    type Element = u8;
 
-    fn block_access(
-        lut: &BlockLut,
-        is_residual: bool,
-        dis_f: f32,
-        norm: f32,
-    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>;
+    fn split(vector: Self::Borrowed<'_>) -> (Vec<&[u8]>, [f32; 4]) {
+        (
+            match vector.packed_code().len() {
+                0 => unreachable!(),
+                1..=3840 => vec![vector.packed_code()],
+                3841..=5120 => vec![&vector.packed_code()[..1280], &vector.packed_code()[1280..]],
+                5121.. => vector.packed_code().chunks(7680).collect(),
+            },
+            [
+                vector.sum_of_x2(),
+                vector.norm_of_lattice(),
+                vector.sum_of_code(),
+                vector.sum_of_abs_x(),
+            ],
+        )
+    }
 
-    fn binary_access(
-        lut: &BinaryLut,
-        is_residual: bool,
-        dis_f: f32,
-        norm: f32,
-    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32);
+    fn count(dim: u32) -> u32 {
+        match dim {
+            0 => unreachable!(),
+            1..=15360 => 1,
+            15361..=20480 => 2,
+            20481.. => dim.div_ceil(30720),
+        }
+    }
+
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (
+            vector.packed_code(),
+            [
+                vector.sum_of_x2(),
+                vector.norm_of_lattice(),
+                vector.sum_of_code(),
+                vector.sum_of_abs_x(),
+            ],
+        )
+    }
+
+    fn pack(dim: u32, elements: Vec<Self::Element>, [_0, _1, _2, _3]: Self::Metadata) -> Self {
+        Rabitq2Owned::new(dim, _0, _1, _2, _3, elements)
+    }
+
+    fn block_preprocess(vector: Self::Borrowed<'_>) -> BlockLut {
+        let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+        let mut result = Vec::with_capacity(vector.dim() as _);
+        for c in vector.unpacked_code() {
+            let base = -0.5 * ((1 << 2) - 1) as f32;
+            result.push((base + c as f32) * scale);
+        }
+        rabitq::bit::block::preprocess(&result)
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> (BlockLut, BinaryLut) {
+        let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+        let mut result = Vec::with_capacity(vector.dim() as _);
+        for c in vector.unpacked_code() {
+            let base = -0.5 * ((1 << 2) - 1) as f32;
+            result.push((base + c as f32) * scale);
+        }
+        rabitq::bit::preprocess(&result)
+    }
+
+    fn code(vector: Self::Borrowed<'_>) -> rabitq::bit::Code {
+        let n = vector.dim();
+        let sum_of_abs_x = vector.sum_of_abs_x();
+        let sum_of_x_2 = vector.sum_of_x2();
+        (
+            CodeMetadata {
+                dis_u_2: sum_of_x_2,
+                factor_cnt: {
+                    let cnt_pos = vector.unpacked_code().filter(|&x| x >= 2).count();
+                    let cnt_neg = vector.unpacked_code().filter(|&x| x <= 1).count();
+                    cnt_pos as f32 - cnt_neg as f32
+                },
+                factor_ip: sum_of_x_2 / sum_of_abs_x,
+                factor_err: {
+                    let dis_u = sum_of_x_2.sqrt();
+                    let x_0 = sum_of_abs_x / dis_u / (n as f32).sqrt();
+                    dis_u * (1.0 / (x_0 * x_0) - 1.0).sqrt() / (n as f32 - 1.0).sqrt()
+                },
+            },
+            {
+                let vector = vector.unpacked_code();
+                let mut signs = Vec::new();
+                for x in vector {
+                    signs.push(x >= 2);
+                }
+                signs
+            },
+        )
+    }
+
+    fn squared_norm(vector: Self::Borrowed<'_>) -> f32 {
+        vector.sum_of_x2()
+    }
+}
+
+impl Vector for Rabitq1Owned {
+    type Metadata = [f32; 4];
+
+    type Element = u8;
+
+    fn split(vector: Self::Borrowed<'_>) -> (Vec<&[u8]>, [f32; 4]) {
+        (
+            match vector.packed_code().len() {
+                0 => unreachable!(),
+                1..=3840 => vec![vector.packed_code()],
+                3841..=5120 => vec![&vector.packed_code()[..1280], &vector.packed_code()[1280..]],
+                5121.. => vector.packed_code().chunks(7680).collect(),
+            },
+            [
+                vector.sum_of_x2(),
+                vector.norm_of_lattice(),
+                vector.sum_of_code(),
+                vector.sum_of_abs_x(),
+            ],
+        )
+    }
+
+    fn count(dim: u32) -> u32 {
+        match dim {
+            0 => unreachable!(),
+            1..=30720 => 1,
+            30721..=40960 => 2,
+            40961.. => dim.div_ceil(61440),
+        }
+    }
+
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (
+            vector.packed_code(),
+            [
+                vector.sum_of_x2(),
+                vector.norm_of_lattice(),
+                vector.sum_of_code(),
+                vector.sum_of_abs_x(),
+            ],
+        )
+    }
+
+    fn pack(dim: u32, elements: Vec<Self::Element>, [_0, _1, _2, _3]: Self::Metadata) -> Self {
+        Rabitq1Owned::new(dim, _0, _1, _2, _3, elements)
+    }
+
+    fn block_preprocess(vector: Self::Borrowed<'_>) -> BlockLut {
+        let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+        let mut result = Vec::with_capacity(vector.dim() as _);
+        for c in vector.unpacked_code() {
+            let base = -0.5 * ((1 << 1) - 1) as f32;
+            result.push((base + c as f32) * scale);
+        }
+        rabitq::bit::block::preprocess(&result)
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> (BlockLut, BinaryLut) {
+        let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+        let mut result = Vec::with_capacity(vector.dim() as _);
+        for c in vector.unpacked_code() {
+            let base = -0.5 * ((1 << 1) - 1) as f32;
+            result.push((base + c as f32) * scale);
+        }
+        rabitq::bit::preprocess(&result)
+    }
+
+    fn code(vector: Self::Borrowed<'_>) -> rabitq::bit::Code {
+        let n = vector.dim();
+        let sum_of_abs_x = vector.sum_of_abs_x();
+        let sum_of_x_2 = vector.sum_of_x2();
+        (
+            CodeMetadata {
+                dis_u_2: sum_of_x_2,
+                factor_cnt: {
+                    let cnt_pos = vector.unpacked_code().filter(|&x| x == 1).count();
+                    let cnt_neg = vector.unpacked_code().filter(|&x| x == 0).count();
+                    cnt_pos as f32 - cnt_neg as f32
+                },
+                factor_ip: sum_of_x_2 / sum_of_abs_x,
+                factor_err: {
+                    let dis_u = sum_of_x_2.sqrt();
+                    let x_0 = sum_of_abs_x / dis_u / (n as f32).sqrt();
+                    dis_u * (1.0 / (x_0 * x_0) - 1.0).sqrt() / (n as f32 - 1.0).sqrt()
+                },
+            },
+            {
+                let vector = vector.unpacked_code();
+                let mut signs = Vec::new();
+                for x in vector {
+                    signs.push(x == 1);
+                }
+                signs
+            },
+        )
+    }
+
+    fn squared_norm(vector: Self::Borrowed<'_>) -> f32 {
+        vector.sum_of_x2()
+    }
+}
+
+impl Vector for Veci8Owned {
+    type Metadata = ();
+
+    type Element = i8;
+
+    fn split(vector: Self::Borrowed<'_>) -> (Vec<&[i8]>, ()) {
+        let vector = vector.slice();
+        (
+            match vector.len() {
+                0 => unreachable!(),
+                1..=3840 => vec![vector],
+                3841..=5120 => vec![&vector[..1280], &vector[1280..]],
+                5121.. => vector.chunks(7680).collect(),
+            },
+            (),
+        )
+    }
+
+    fn count(dim: u32) -> u32 {
+        match dim {
+            0 => unreachable!(),
+            1..=3840 => 1,
+            3841..=5120 => 2,
+            5121.. => dim.div_ceil(7680),
+        }
+    }
+
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (vector.slice(), ())
+    }
+
+    fn pack(_: u32, elements: Vec<Self::Element>, (): Self::Metadata) -> Self {
+        Veci8Owned::new(elements)
+    }
+
+    fn block_preprocess(vector: Self::Borrowed<'_>) -> BlockLut {
+        let vector = vector.slice().iter().map(|&x| x as f32).collect::<Vec<_>>();
+        rabitq::bit::block::preprocess(&vector)
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> (BlockLut, BinaryLut) {
+        let vector = vector.slice().iter().map(|&x| x as f32).collect::<Vec<_>>();
+        rabitq::bit::preprocess(&vector)
+    }
+
+    fn code(vector: Self::Borrowed<'_>) -> rabitq::bit::Code {
+        let vector = vector.slice().iter().map(|&x| x as f32).collect::<Vec<_>>();
+        rabitq::bit::code(&vector)
+    }
+
+    fn squared_norm(vector: Self::Borrowed<'_>) -> f32 {
+        simd::byte::reduce_sum_of_xy_i8(vector.slice(), vector.slice()) as f32
+    }
+}
+
+pub trait Operator: 'static + Debug + Copy {
+    type Vector: Vector;
+
+    type DistanceAccessor: DefaultWithDimension
+        + Accessor2<
+            <Self::Vector as Vector>::Element,
+            <Self::Vector as Vector>::Element,
+            <Self::Vector as Vector>::Metadata,
+            <Self::Vector as Vector>::Metadata,
+            Output = Distance,
+        >;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        dis_f: f32,
+        norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>;
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        dis_f: f32,
+        norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32);
+
+    fn build(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32);
+}
+
+#[derive(Debug)]
+pub struct Op<V, D>(PhantomData<fn(V) -> V>, PhantomData<fn(D) -> D>);
+
+impl<V, D> Clone for Op<V, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V, D> Copy for Op<V, D> {}
+
+impl Operator for Op<VectOwned<f32>, L2S> {
+    type Vector = VectOwned<f32>;
+
+    type DistanceAccessor = DistanceAccessor<VectOwned<f32>, L2S>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        dis_f: f32,
+        _norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |value, code, delta| {
+                if !is_residual {
+                    rabitq::bit::block::half_process_l2s(value, code, lut.0)
+                } else {
+                    rabitq::bit::block::half_process_l2s_residual(value, code, lut.0, dis_f, delta)
+                }
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        dis_f: f32,
+        _norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        move |metadata: [f32; 4], elements: &[u64], delta: f32| {
+            let value = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            if !is_residual {
+                rabitq::bit::binary::half_process_l2s(value, code, lut.0)
+            } else {
+                rabitq::bit::binary::half_process_l2s_residual(value, code, lut.0, dis_f, delta)
+            }
+        }
+    }
+
+    fn build(
+        vector: VectBorrowed<'_, f32>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if let Some(centroid) = centroid {
+            let residual = VectOwned::new(f32::vector_sub(vector.slice(), centroid.slice()));
+            let code = Self::Vector::code(residual.as_borrowed());
+            let delta = {
+                use std::iter::zip;
+                let dim = vector.dim();
+                let t = zip(&code.1, centroid.slice())
+                    .map(|(&sign, &num)| std::hint::select_unpredictable(sign, num, -num))
+                    .sum::<f32>()
+                    / (dim as f32).sqrt();
+                let sum_of_x_2 = code.0.dis_u_2;
+                let sum_of_abs_x = sum_of_x_2 / code.0.factor_ip;
+                let dis_u = sum_of_x_2.sqrt();
+                let x_0 = sum_of_abs_x / dis_u / (dim as f32).sqrt();
+                2.0 * dis_u * t / x_0
+            };
+            (code, delta)
+        } else {
+            let code = Self::Vector::code(vector);
+            let delta = 0.0;
+            (code, delta)
+        }
+    }
+}
+
+impl Operator for Op<VectOwned<f32>, Dot> {
+    type Vector = VectOwned<f32>;
+
+    type DistanceAccessor = DistanceAccessor<VectOwned<f32>, Dot>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        dis_f: f32,
+        norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |sum, code, delta| {
+                if !is_residual {
+                    rabitq::bit::block::half_process_dot(sum, code, lut.0)
+                } else {
+                    rabitq::bit::block::half_process_dot_residual(
+                        sum, code, lut.0, dis_f, delta, norm,
+                    )
+                }
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        dis_f: f32,
+        norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        move |metadata: [f32; 4], elements: &[u64], delta: f32| {
+            let sum = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            if !is_residual {
+                rabitq::bit::binary::half_process_dot(sum, code, lut.0)
+            } else {
+                rabitq::bit::binary::half_process_dot_residual(sum, code, lut.0, dis_f, delta, norm)
+            }
+        }
+    }
+
+    fn build(
+        vector: VectBorrowed<'_, f32>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if let Some(centroid) = centroid {
+            let residual = VectOwned::new(f32::vector_sub(vector.slice(), centroid.slice()));
+            let code = Self::Vector::code(residual.as_borrowed());
+            let delta = {
+                use std::iter::zip;
+                let dim = vector.dim();
+                let t = zip(&code.1, centroid.slice())
+                    .map(|(&sign, &num)| std::hint::select_unpredictable(sign, num, -num))
+                    .sum::<f32>()
+                    / (dim as f32).sqrt();
+                let sum_of_x_2 = code.0.dis_u_2;
+                let sum_of_abs_x = sum_of_x_2 / code.0.factor_ip;
+                let dis_u = sum_of_x_2.sqrt();
+                let x_0 = sum_of_abs_x / dis_u / (dim as f32).sqrt();
+                dis_u * t / x_0 - f32::reduce_sum_of_xy(residual.slice(), centroid.slice())
+            };
+            (code, delta)
+        } else {
+            let code = Self::Vector::code(vector);
+            let delta = 0.0;
+            (code, delta)
+        }
+    }
+}
+
+impl Operator for Op<VectOwned<f16>, L2S> {
+    type Vector = VectOwned<f16>;
+
+    type DistanceAccessor = DistanceAccessor<VectOwned<f16>, L2S>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        dis_f: f32,
+        _norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |value, code, delta| {
+                if !is_residual {
+                    rabitq::bit::block::half_process_l2s(value, code, lut.0)
+                } else {
+                    rabitq::bit::block::half_process_l2s_residual(value, code, lut.0, dis_f, delta)
+                }
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        dis_f: f32,
+        _norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        move |metadata: [f32; 4], elements: &[u64], delta: f32| {
+            let value = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            if !is_residual {
+                rabitq::bit::binary::half_process_l2s(value, code, lut.0)
+            } else {
+                rabitq::bit::binary::half_process_l2s_residual(value, code, lut.0, dis_f, delta)
+            }
+        }
+    }
+
+    fn build(
+        vector: VectBorrowed<'_, f16>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if let Some(centroid) = centroid {
+            let residual = VectOwned::new(f16::vector_sub(vector.slice(), centroid.slice()));
+            let code = Self::Vector::code(residual.as_borrowed());
+            let delta = {
+                use std::iter::zip;
+                let dim = vector.dim();
+                let t = zip(&code.1, centroid.slice())
+                    .map(|(&sign, &num)| std::hint::select_unpredictable(sign, num, -num))
+                    .map(simd::F16::_to_f32)
+                    .sum::<f32>()
+                    / (dim as f32).sqrt();
+                let sum_of_x_2 = code.0.dis_u_2;
+                let sum_of_abs_x = sum_of_x_2 / code.0.factor_ip;
+                let dis_u = sum_of_x_2.sqrt();
+                let x_0 = sum_of_abs_x / dis_u / (dim as f32).sqrt();
+                2.0 * dis_u * t / x_0
+            };
+            (code, delta)
+        } else {
+            let code = Self::Vector::code(vector);
+            let delta = 0.0;
+            (code, delta)
+        }
+    }
+}
+
+impl Operator for Op<VectOwned<f16>, Dot> {
+    type Vector = VectOwned<f16>;
+
+    type DistanceAccessor = DistanceAccessor<VectOwned<f16>, Dot>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        dis_f: f32,
+        norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |sum, code, delta| {
+                if !is_residual {
+                    rabitq::bit::block::half_process_dot(sum, code, lut.0)
+                } else {
+                    rabitq::bit::block::half_process_dot_residual(
+                        sum, code, lut.0, dis_f, delta, norm,
+                    )
+                }
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        dis_f: f32,
+        norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        move |metadata: [f32; 4], elements: &[u64], delta: f32| {
+            let sum = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            if !is_residual {
+                rabitq::bit::binary::half_process_dot(sum, code, lut.0)
+            } else {
+                rabitq::bit::binary::half_process_dot_residual(sum, code, lut.0, dis_f, delta, norm)
+            }
+        }
+    }
+
+    fn build(
+        vector: VectBorrowed<'_, f16>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if let Some(centroid) = centroid {
+            let residual = VectOwned::new(f16::vector_sub(vector.slice(), centroid.slice()));
+            let code = Self::Vector::code(residual.as_borrowed());
+            let delta = {
+                use std::iter::zip;
+                let dim = vector.dim();
+                let t = zip(&code.1, centroid.slice())
+                    .map(|(&sign, &num)| std::hint::select_unpredictable(sign, num, -num))
+                    .map(simd::F16::_to_f32)
+                    .sum::<f32>()
+                    / (dim as f32).sqrt();
+                let sum_of_x_2 = code.0.dis_u_2;
+                let sum_of_abs_x = sum_of_x_2 / code.0.factor_ip;
+                let dis_u = sum_of_x_2.sqrt();
+                let x_0 = sum_of_abs_x / dis_u / (dim as f32).sqrt();
+                dis_u * t / x_0 - f16::reduce_sum_of_xy(residual.slice(), centroid.slice())
+            };
+            (code, delta)
+        } else {
+            let code = Self::Vector::code(vector);
+            let delta = 0.0;
+            (code, delta)
+        }
+    }
+}
+
+impl Operator for Op<VectOwned<bf16>, L2S> {
+    type Vector = VectOwned<bf16>;
+
+    type DistanceAccessor = DistanceAccessor<VectOwned<bf16>, L2S>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        dis_f: f32,
+        _norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |value, code, delta| {
+                if !is_residual {
+                    rabitq::bit::block::half_process_l2s(value, code, lut.0)
+                } else {
+                    rabitq::bit::block::half_process_l2s_residual(value, code, lut.0, dis_f, delta)
+                }
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        dis_f: f32,
+        _norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        move |metadata: [f32; 4], elements: &[u64], delta: f32| {
+            let value = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            if !is_residual {
+                rabitq::bit::binary::half_process_l2s(value, code, lut.0)
+            } else {
+                rabitq::bit::binary::half_process_l2s_residual(value, code, lut.0, dis_f, delta)
+            }
+        }
+    }
+
+    fn build(
+        vector: VectBorrowed<'_, bf16>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if let Some(centroid) = centroid {
+            let residual = VectOwned::new(bf16::vector_sub(vector.slice(), centroid.slice()));
+            let code = Self::Vector::code(residual.as_borrowed());
+            let delta = {
+                use std::iter::zip;
+                let dim = vector.dim();
+                let t = zip(&code.1, centroid.slice())
+                    .map(|(&sign, &num)| std::hint::select_unpredictable(sign, num, -num))
+                    .map(bf16::to_f32)
+                    .sum::<f32>()
+                    / (dim as f32).sqrt();
+                let sum_of_x_2 = code.0.dis_u_2;
+                let sum_of_abs_x = sum_of_x_2 / code.0.factor_ip;
+                let dis_u = sum_of_x_2.sqrt();
+                let x_0 = sum_of_abs_x / dis_u / (dim as f32).sqrt();
+                2.0 * dis_u * t / x_0
+            };
+            (code, delta)
+        } else {
+            let code = Self::Vector::code(vector);
+            let delta = 0.0;
+            (code, delta)
+        }
+    }
+}
+
+impl Operator for Op<VectOwned<bf16>, Dot> {
+    type Vector = VectOwned<bf16>;
+
+    type DistanceAccessor = DistanceAccessor<VectOwned<bf16>, Dot>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        dis_f: f32,
+        norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |sum, code, delta| {
+                if !is_residual {
+                    rabitq::bit::block::half_process_dot(sum, code, lut.0)
+                } else {
+                    rabitq::bit::block::half_process_dot_residual(
+                        sum, code, lut.0, dis_f, delta, norm,
+                    )
+                }
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        dis_f: f32,
+        norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        move |metadata: [f32; 4], elements: &[u64], delta: f32| {
+            let sum = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            if !is_residual {
+                rabitq::bit::binary::half_process_dot(sum, code, lut.0)
+            } else {
+                rabitq::bit::binary::half_process_dot_residual(sum, code, lut.0, dis_f, delta, norm)
+            }
+        }
+    }
+
+    fn build(
+        vector: VectBorrowed<'_, bf16>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if let Some(centroid) = centroid {
+            let residual = VectOwned::new(bf16::vector_sub(vector.slice(), centroid.slice()));
+            let code = Self::Vector::code(residual.as_borrowed());
+            let delta = {
+                use std::iter::zip;
+                let dim = vector.dim();
+                let t = zip(&code.1, centroid.slice())
+                    .map(|(&sign, &num)| std::hint::select_unpredictable(sign, num, -num))
+                    .map(bf16::to_f32)
+                    .sum::<f32>()
+                    / (dim as f32).sqrt();
+                let sum_of_x_2 = code.0.dis_u_2;
+                let sum_of_abs_x = sum_of_x_2 / code.0.factor_ip;
+                let dis_u = sum_of_x_2.sqrt();
+                let x_0 = sum_of_abs_x / dis_u / (dim as f32).sqrt();
+                dis_u * t / x_0 - bf16::reduce_sum_of_xy(residual.slice(), centroid.slice())
+            };
+            (code, delta)
+        } else {
+            let code = Self::Vector::code(vector);
+            let delta = 0.0;
+            (code, delta)
+        }
+    }
+}
+
+impl Operator for Op<Rabitq8Owned, L2S> {
+    type Vector = Rabitq8Owned;
+
+    type DistanceAccessor = ByteDistanceAccessor<Rabitq8Owned, L2S>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        assert!(!is_residual);
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |value, code, _delta| {
+                rabitq::bit::block::half_process_l2s(value, code, lut.0)
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        assert!(!is_residual);
+        move |metadata: [f32; 4], elements: &[u64], _delta: f32| {
+            let value = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            rabitq::bit::binary::half_process_l2s(value, code, lut.0)
+        }
+    }
+
+    fn build(
+        vector: Rabitq8Borrowed<'_>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if centroid.is_some() {
+            unimplemented!();
+        }
+        (Self::Vector::code(vector), 0.0)
+    }
+}
+
+impl Operator for Op<Rabitq8Owned, Dot> {
+    type Vector = Rabitq8Owned;
+
+    type DistanceAccessor = ByteDistanceAccessor<Rabitq8Owned, Dot>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        assert!(!is_residual);
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |sum, code, _delta| {
+                rabitq::bit::block::half_process_dot(sum, code, lut.0)
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        assert!(!is_residual);
+        move |metadata: [f32; 4], elements: &[u64], _delta: f32| {
+            let sum = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            rabitq::bit::binary::half_process_dot(sum, code, lut.0)
+        }
+    }
 
     fn build(
-        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        vector: Rabitq8Borrowed<'_>,
         centroid: Option<Self::Vector>,
-    ) -> (rabitq::bit::Code, f32);
-}
-
-#[derive(Debug)]
-pub struct Op<V, D>(PhantomData<fn(V) -> V>, PhantomData<fn(D) -> D>);
-
-impl<V, D> Clone for Op<V, D> {
-    fn clone(&self) -> Self {
-        *self
+    ) -> (rabitq::bit::Code, f32) {
+        if centroid.is_some() {
+            unimplemented!();
+        }
+        (Self::Vector::code(vector), 0.0)
     }
 }
 
-impl<V, D> Copy for Op<V, D> {}
-
-impl Operator for Op<VectOwned<f32>, L2S> {
-    type Vector = VectOwned<f32>;
+impl Operator for Op<Rabitq4Owned, L2S> {
+    type Vector = Rabitq4Owned;
 
-    type DistanceAccessor = DistanceAccessor<VectOwned<f32>, L2S>;
+    type DistanceAccessor = HalfbyteDistanceAccessor<Rabitq4Owned, L2S>;
 
     fn block_access(
         lut: &BlockLut,
         is_residual: bool,
-        dis_f: f32,
+        _dis_f: f32,
         _norm: f32,
     ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
     {
+        assert!(!is_residual);
         RAccess::new(
             (&lut.1, ()),
-            BlockAccessor([0_u32; 32], move |value, code, delta| {
-                if !is_residual {
-                    rabitq::bit::block::half_process_l2s(value, code, lut.0)
-                } else {
-                    rabitq::bit::block::half_process_l2s_residual(value, code, lut.0, dis_f, delta)
-                }
+            BlockAccessor([0_u32; 32], move |value, code, _delta| {
+                rabitq::bit::block::half_process_l2s(value, code, lut.0)
             }),
         )
     }
@@ -488,10 +1361,11 @@ impl Operator for Op<VectOwned<f32>, L2S> {
     fn binary_access(
         lut: &BinaryLut,
         is_residual: bool,
-        dis_f: f32,
+        _dis_f: f32,
         _norm: f32,
     ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
-        move |metadata: [f32; 4], elements: &[u64], delta: f32| {
+        assert!(!is_residual);
+        move |metadata: [f32; 4], elements: &[u64], _delta: f32| {
             let value = rabitq::bit::binary::accumulate(elements, &lut.1);
             let code = CodeMetadata {
                 dis_u_2: metadata[0],
@@ -499,65 +1373,38 @@ impl Operator for Op<VectOwned<f32>, L2S> {
                 factor_ip: metadata[2],
                 factor_err: metadata[3],
             };
-            if !is_residual {
-                rabitq::bit::binary::half_process_l2s(value, code, lut.0)
-            } else {
-                rabitq::bit::binary::half_process_l2s_residual(value, code, lut.0, dis_f, delta)
-            }
+            rabitq::bit::binary::half_process_l2s(value, code, lut.0)
         }
     }
 
     fn build(
-        vector: VectBorrowed<'_, f32>,
+        vector: Rabitq4Borrowed<'_>,
         centroid: Option<Self::Vector>,
     ) -> (rabitq::bit::Code, f32) {
-        if let Some(centroid) = centroid {
-            let residual = VectOwned::new(f32::vector_sub(vector.slice(), centroid.slice()));
-            let code = Self::Vector::code(residual.as_borrowed());
-            let delta = {
-                use std::iter::zip;
-                let dim = vector.dim();
-                let t = zip(&code.1, centroid.slice())
-                    .map(|(&sign, &num)| std::hint::select_unpredictable(sign, num, -num))
-                    .sum::<f32>()
-                    / (dim as f32).sqrt();
-                let sum_of_x_2 = code.0.dis_u_2;
-                let sum_of_abs_x = sum_of_x_2 / code.0.factor_ip;
-                let dis_u = sum_of_x_2.sqrt();
-                let x_0 = sum_of_abs_x / dis_u / (dim as f32).sqrt();
-                2.0 * dis_u * t / x_0
-            };
-            (code, delta)
-        } else {
-            let code = Self::Vector::code(vector);
-            let delta = 0.0;
-            (code, delta)
+        if centroid.is_some() {
+            unimplemented!();
         }
+        (Self::Vector::code(vector), 0.0)
     }
 }
 
-impl Operator for Op<VectOwned<f32>, Dot> {
-    type Vector = VectOwned<f32>;
+impl Operator for Op<Rabitq4Owned, Dot> {
+    type Vector = Rabitq4Owned;
 
-    type DistanceAccessor = DistanceAccessor<VectOwned<f32>, Dot>;
+    type DistanceAccessor = HalfbyteDistanceAccessor<Rabitq4Owned, Dot>;
 
     fn block_access(
         lut: &BlockLut,
         is_residual: bool,
-        dis_f: f32,
-        norm: f32,
+        _dis_f: f32,
+        _norm: f32,
     ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
     {
+        assert!(!is_residual);
         RAccess::new(
             (&lut.1, ()),
-            BlockAccessor([0_u32; 32], move |sum, code, delta| {
-                if !is_residual {
-                    rabitq::bit::block::half_process_dot(sum, code, lut.0)
-                } else {
-                    rabitq::bit::block::half_process_dot_residual(
-                        sum, code, lut.0, dis_f, delta, norm,
-                    )
-                }
+            BlockAccessor([0_u32; 32], move |sum, code, _delta| {
+                rabitq::bit::block::half_process_dot(sum, code, lut.0)
             }),
         )
     }
@@ -565,10 +1412,11 @@ impl Operator for Op<VectOwned<f32>, Dot> {
     fn binary_access(
         lut: &BinaryLut,
         is_residual: bool,
-        dis_f: f32,
-        norm: f32,
+        _dis_f: f32,
+        _norm: f32,
     ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
-        move |metadata: [f32; 4], elements: &[u64], delta: f32| {
+        assert!(!is_residual);
+        move |metadata: [f32; 4], elements: &[u64], _delta: f32| {
             let sum = rabitq::bit::binary::accumulate(elements, &lut.1);
             let code = CodeMetadata {
                 dis_u_2: metadata[0],
@@ -576,63 +1424,38 @@ impl Operator for Op<VectOwned<f32>, Dot> {
                 factor_ip: metadata[2],
                 factor_err: metadata[3],
             };
-            if !is_residual {
-                rabitq::bit::binary::half_process_dot(sum, code, lut.0)
-            } else {
-                rabitq::bit::binary::half_process_dot_residual(sum, code, lut.0, dis_f, delta, norm)
-            }
+            rabitq::bit::binary::half_process_dot(sum, code, lut.0)
         }
     }
 
     fn build(
-        vector: VectBorrowed<'_, f32>,
+        vector: Rabitq4Borrowed<'_>,
         centroid: Option<Self::Vector>,
     ) -> (rabitq::bit::Code, f32) {
-        if let Some(centroid) = centroid {
-            let residual = VectOwned::new(f32::vector_sub(vector.slice(), centroid.slice()));
-            let code = Self::Vector::code(residual.as_borrowed());
-            let delta = {
-                use std::iter::zip;
-                let dim = vector.dim();
-                let t = zip(&code.1, centroid.slice())
-                    .map(|(&sign, &num)| std::hint::select_unpredictable(sign, num, -num))
-                    .sum::<f32>()
-                    / (dim as f32).sqrt();
-                let sum_of_x_2 = code.0.dis_u_2;
-                let sum_of_abs_x = sum_of_x_2 / code.0.factor_ip;
-                let dis_u = sum_of_x_2.sqrt();
-                let x_0 = sum_of_abs_x / dis_u / (dim as f32).sqrt();
-                dis_u * t / x_0 - f32::reduce_sum_of_xy(residual.slice(), centroid.slice())
-            };
-            (code, delta)
-        } else {
-            let code = Self::Vector::code(vector);
-            let delta = 0.0;
-            (code, delta)
+        if centroid.is_some() {
+            unimplemented!();
         }
+        (Self::Vector::code(vector), 0.0)
     }
 }
 
-impl Operator for Op<VectOwned<f16>, L2S> {
-    type Vector = VectOwned<f16>;
+impl Operator for Op<Rabitq2Owned, L2S> {
+    type Vector = Rabitq2Owned;
 
-    type DistanceAccessor = DistanceAccessor<VectOwned<f16>, L2S>;
+    type DistanceAccessor = BitsDistanceAccessor<Rabitq2Owned, L2S>;
 
     fn block_access(
         lut: &BlockLut,
         is_residual: bool,
-        dis_f: f32,
+        _dis_f: f32,
         _norm: f32,
     ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
     {
+        assert!(!is_residual);
         RAccess::new(
             (&lut.1, ()),
-            BlockAccessor([0_u32; 32], move |value, code, delta| {
-                if !is_residual {
-                    rabitq::bit::block::half_process_l2s(value, code, lut.0)
-                } else {
-                    rabitq::bit::block::half_process_l2s_residual(value, code, lut.0, dis_f, delta)
-                }
+            BlockAccessor([0_u32; 32], move |value, code, _delta| {
+                rabitq::bit::block::half_process_l2s(value, code, lut.0)
             }),
         )
     }
@@ -640,10 +1463,11 @@ impl Operator for Op<VectOwned<f16>, L2S> {
     fn binary_access(
         lut: &BinaryLut,
         is_residual: bool,
-        dis_f: f32,
+        _dis_f: f32,
         _norm: f32,
     ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
-        move |metadata: [f32; 4], elements: &[u64], delta: f32| {
+        assert!(!is_residual);
+        move |metadata: [f32; 4], elements: &[u64], _delta: f32| {
             let value = rabitq::bit::binary::accumulate(elements, &lut.1);
             let code = CodeMetadata {
                 dis_u_2: metadata[0],
@@ -651,66 +1475,38 @@ impl Operator for Op<VectOwned<f16>, L2S> {
                 factor_ip: metadata[2],
                 factor_err: metadata[3],
             };
-            if !is_residual {
-                rabitq::bit::binary::half_process_l2s(value, code, lut.0)
-            } else {
-                rabitq::bit::binary::half_process_l2s_residual(value, code, lut.0, dis_f, delta)
-            }
+            rabitq::bit::binary::half_process_l2s(value, code, lut.0)
         }
     }
 
     fn build(
-        vector: VectBorrowed<'_, f16>,
+        vector: Rabitq2Borrowed<'_>,
         centroid: Option<Self::Vector>,
     ) -> (rabitq::bit::Code, f32) {
-        if let Some(centroid) = centroid {
-            let residual = VectOwned::new(f16::vector_sub(vector.slice(), centroid.slice()));
-            let code = Self::Vector::code(residual.as_borrowed());
-            let delta = {
-                use std::iter::zip;
-                let dim = vector.dim();
-                let t = zip(&code.1, centroid.slice())
-                    .map(|(&sign, &num)| std::hint::select_unpredictable(sign, num, -num))
-                    .map(simd::F16::_to_f32)
-                    .sum::<f32>()
-                    / (dim as f32).sqrt();
-                let sum_of_x_2 = code.0.dis_u_2;
-                let sum_of_abs_x = sum_of_x_2 / code.0.factor_ip;
-                let dis_u = sum_of_x_2.sqrt();
-                let x_0 = sum_of_abs_x / dis_u / (dim as f32).sqrt();
-                2.0 * dis_u * t / x_0
-            };
-            (code, delta)
-        } else {
-            let code = Self::Vector::code(vector);
-            let delta = 0.0;
-            (code, delta)
+        if centroid.is_some() {
+            unimplemented!();
         }
+        (Self::Vector::code(vector), 0.0)
     }
 }
 
-impl Operator for Op<VectOwned<f16>, Dot> {
-    type Vector = VectOwned<f16>;
+impl Operator for Op<Rabitq2Owned, Dot> {
+    type Vector = Rabitq2Owned;
 
-    type DistanceAccessor = DistanceAccessor<VectOwned<f16>, Dot>;
+    type DistanceAccessor = BitsDistanceAccessor<Rabitq2Owned, Dot>;
 
     fn block_access(
         lut: &BlockLut,
         is_residual: bool,
-        dis_f: f32,
-        norm: f32,
+        _dis_f: f32,
+        _norm: f32,
     ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
     {
+        assert!(!is_residual);
         RAccess::new(
             (&lut.1, ()),
-            BlockAccessor([0_u32; 32], move |sum, code, delta| {
-                if !is_residual {
-                    rabitq::bit::block::half_process_dot(sum, code, lut.0)
-                } else {
-                    rabitq::bit::block::half_process_dot_residual(
-                        sum, code, lut.0, dis_f, delta, norm,
-                    )
-                }
+            BlockAccessor([0_u32; 32], move |sum, code, _delta| {
+                rabitq::bit::block::half_process_dot(sum, code, lut.0)
             }),
         )
     }
@@ -718,10 +1514,11 @@ impl Operator for Op<VectOwned<f16>, Dot> {
     fn binary_access(
         lut: &BinaryLut,
         is_residual: bool,
-        dis_f: f32,
-        norm: f32,
+        _dis_f: f32,
+        _norm: f32,
     ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
-        move |metadata: [f32; 4], elements: &[u64], delta: f32| {
+        assert!(!is_residual);
+        move |metadata: [f32; 4], elements: &[u64], _delta: f32| {
             let sum = rabitq::bit::binary::accumulate(elements, &lut.1);
             let code = CodeMetadata {
                 dis_u_2: metadata[0],
@@ -729,48 +1526,25 @@ impl Operator for Op<VectOwned<f16>, Dot> {
                 factor_ip: metadata[2],
                 factor_err: metadata[3],
             };
-            if !is_residual {
-                rabitq::bit::binary::half_process_dot(sum, code, lut.0)
-            } else {
-                rabitq::bit::binary::half_process_dot_residual(sum, code, lut.0, dis_f, delta, norm)
-            }
+            rabitq::bit::binary::half_process_dot(sum, code, lut.0)
         }
     }
 
     fn build(
-        vector: VectBorrowed<'_, f16>,
+        vector: Rabitq2Borrowed<'_>,
         centroid: Option<Self::Vector>,
     ) -> (rabitq::bit::Code, f32) {
-        if let Some(centroid) = centroid {
-            let residual = VectOwned::new(f16::vector_sub(vector.slice(), centroid.slice()));
-            let code = Self::Vector::code(residual.as_borrowed());
-            let delta = {
-                use std::iter::zip;
-                let dim = vector.dim();
-                let t = zip(&code.1, centroid.slice())
-                    .map(|(&sign, &num)| std::hint::select_unpredictable(sign, num, -num))
-                    .map(simd::F16::_to_f32)
-                    .sum::<f32>()
-                    / (dim as f32).sqrt();
-                let sum_of_x_2 = code.0.dis_u_2;
-                let sum_of_abs_x = sum_of_x_2 / code.0.factor_ip;
-                let dis_u = sum_of_x_2.sqrt();
-                let x_0 = sum_of_abs_x / dis_u / (dim as f32).sqrt();
-                dis_u * t / x_0 - f16::reduce_sum_of_xy(residual.slice(), centroid.slice())
-            };
-            (code, delta)
-        } else {
-            let code = Self::Vector::code(vector);
-            let delta = 0.0;
-            (code, delta)
+        if centroid.is_some() {
+            unimplemented!();
         }
+        (Self::Vector::code(vector), 0.0)
     }
 }
 
-impl Operator for Op<Rabitq8Owned, L2S> {
-    type Vector = Rabitq8Owned;
+impl Operator for Op<Rabitq1Owned, L2S> {
+    type Vector = Rabitq1Owned;
 
-    type DistanceAccessor = ByteDistanceAccessor<Rabitq8Owned, L2S>;
+    type DistanceAccessor = BitsDistanceAccessor<Rabitq1Owned, L2S>;
 
     fn block_access(
         lut: &BlockLut,
@@ -808,7 +1582,7 @@ impl Operator for Op<Rabitq8Owned, L2S> {
     }
 
     fn build(
-        vector: Rabitq8Borrowed<'_>,
+        vector: Rabitq1Borrowed<'_>,
         centroid: Option<Self::Vector>,
     ) -> (rabitq::bit::Code, f32) {
         if centroid.is_some() {
@@ -818,10 +1592,10 @@ impl Operator for Op<Rabitq8Owned, L2S> {
     }
 }
 
-impl Operator for Op<Rabitq8Owned, Dot> {
-    type Vector = Rabitq8Owned;
+impl Operator for Op<Rabitq1Owned, Dot> {
+    type Vector = Rabitq1Owned;
 
-    type DistanceAccessor = ByteDistanceAccessor<Rabitq8Owned, Dot>;
+    type DistanceAccessor = BitsDistanceAccessor<Rabitq1Owned, Dot>;
 
     fn block_access(
         lut: &BlockLut,
@@ -859,7 +1633,7 @@ impl Operator for Op<Rabitq8Owned, Dot> {
     }
 
     fn build(
-        vector: Rabitq8Borrowed<'_>,
+        vector: Rabitq1Borrowed<'_>,
         centroid: Option<Self::Vector>,
     ) -> (rabitq::bit::Code, f32) {
         if centroid.is_some() {
@@ -869,10 +1643,10 @@ impl Operator for Op<Rabitq8Owned, Dot> {
     }
 }
 
-impl Operator for Op<Rabitq4Owned, L2S> {
-    type Vector = Rabitq4Owned;
+impl Operator for Op<Veci8Owned, L2S> {
+    type Vector = Veci8Owned;
 
-    type DistanceAccessor = HalfbyteDistanceAccessor<Rabitq4Owned, L2S>;
+    type DistanceAccessor = DistanceAccessor<Veci8Owned, L2S>;
 
     fn block_access(
         lut: &BlockLut,
@@ -910,7 +1684,7 @@ impl Operator for Op<Rabitq4Owned, L2S> {
     }
 
     fn build(
-        vector: Rabitq4Borrowed<'_>,
+        vector: Veci8Borrowed<'_>,
         centroid: Option<Self::Vector>,
     ) -> (rabitq::bit::Code, f32) {
         if centroid.is_some() {
@@ -920,10 +1694,10 @@ impl Operator for Op<Rabitq4Owned, L2S> {
     }
 }
 
-impl Operator for Op<Rabitq4Owned, Dot> {
-    type Vector = Rabitq4Owned;
+impl Operator for Op<Veci8Owned, Dot> {
+    type Vector = Veci8Owned;
 
-    type DistanceAccessor = HalfbyteDistanceAccessor<Rabitq4Owned, Dot>;
+    type DistanceAccessor = DistanceAccessor<Veci8Owned, Dot>;
 
     fn block_access(
         lut: &BlockLut,
@@ -961,7 +1735,7 @@ impl Operator for Op<Rabitq4Owned, Dot> {
     }
 
     fn build(
-        vector: Rabitq4Borrowed<'_>,
+        vector: Veci8Borrowed<'_>,
         centroid: Option<Self::Vector>,
     ) -> (rabitq::bit::Code, f32) {
         if centroid.is_some() {
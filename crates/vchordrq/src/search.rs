@@ -17,6 +17,7 @@ use crate::linked_vec::LinkedVec;
 use crate::operator::*;
 use crate::tape::{by_directory, by_next};
 use crate::tuples::*;
+use crate::upper::Upper;
 use crate::{Opaque, centroids, tape};
 use always_equal::AlwaysEqual;
 use distance::Distance;
@@ -26,6 +27,8 @@ use index::packed::{PackedRefMut4, PackedRefMut8};
 use index::prefetcher::{Prefetcher, PrefetcherHeapFamily, PrefetcherSequenceFamily};
 use index::relation::{Page, RelationRead};
 use index_accessor::{DefaultWithDimension, FunctionalAccessor, LAccess};
+use rabitq::bit::binary::BinaryLut;
+use rabitq::bit::block::BlockLut;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::num::NonZero;
@@ -33,29 +36,69 @@ use vector::{VectorBorrowed, VectorOwned};
 
 type Extra1<'b> = &'b mut (u32, f32, u16, BorrowedIter<'b>);
 
-pub fn default_search<'b, R: RelationRead, O: Operator>(
+pub(crate) type State = Vec<(Reverse<Distance>, AlwaysEqual<f32>, AlwaysEqual<u32>)>;
+
+// Reads the meta tuple and returns it with the root, whose distance is computed
+// if the index is residual.
+fn root<'b, R: RelationRead, O: Operator>(
     index: &'b R,
+    upper: Option<Upper<'_>>,
     vector: <O::Vector as VectorOwned>::Borrowed<'_>,
-    probes: Vec<u32>,
-    epsilon: f32,
+    probes: &[u32],
     bump: &'b impl Bump,
-    mut prefetch_h1_vectors: impl PrefetcherHeapFamily<'b, R>,
-    mut prefetch_h0_tuples: impl PrefetcherSequenceFamily<'b, R>,
-) -> Vec<(
-    (Reverse<Distance>, AlwaysEqual<()>),
-    AlwaysEqual<PackedRefMut4<'b, (NonZero<u64>, u16, BorrowedIter<'b>)>>,
-)>
+) -> (u32, bool, u32, Vec<u32>, State)
 where
     R::Page: Page<Opaque = Opaque>,
 {
-    let meta_guard = index.read(0);
-    let meta_bytes = meta_guard.get(1).expect("data corruption");
-    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
-    let dim = meta_tuple.dim();
-    let is_residual = meta_tuple.is_residual();
-    let height_of_root = meta_tuple.height_of_root();
-    let cells = meta_tuple.cells().to_vec();
-    assert_eq!(dim, vector.dim(), "unmatched dimensions");
+    let (dim, is_residual, height_of_root, cells, norm, first, distance);
+    if let Some(upper) = upper {
+        dim = upper.dim();
+        is_residual = upper.is_residual();
+        height_of_root = upper.height_of_root();
+        cells = upper.cells().to_vec();
+        norm = upper.root_norm();
+        first = upper.root_first();
+        assert_eq!(dim, vector.dim(), "unmatched dimensions");
+        distance = if is_residual {
+            upper.read_centroid::<O::Vector, _>(
+                upper.root_centroid(),
+                LAccess::new(
+                    O::Vector::unpack(vector),
+                    O::DistanceAccessor::default_with_dimension(dim),
+                ),
+            )
+        } else {
+            // fast path
+            Distance::ZERO
+        };
+    } else {
+        let meta_guard = index.read(0);
+        let meta_bytes = meta_guard.get(1).expect("data corruption");
+        let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+        dim = meta_tuple.dim();
+        is_residual = meta_tuple.is_residual();
+        height_of_root = meta_tuple.height_of_root();
+        cells = meta_tuple.cells().to_vec();
+        norm = meta_tuple.centroid_norm();
+        first = meta_tuple.first();
+        assert_eq!(dim, vector.dim(), "unmatched dimensions");
+        distance = if is_residual {
+            let prefetch =
+                BorrowedIter::from_slice(meta_tuple.centroid_prefetch(), |x| bump.alloc_slice(x));
+            let head = meta_tuple.centroid_head();
+            centroids::read::<R, O, _>(
+                prefetch.map(|id| index.read(id)),
+                head,
+                LAccess::new(
+                    O::Vector::unpack(vector),
+                    O::DistanceAccessor::default_with_dimension(dim),
+                ),
+            )
+        } else {
+            // fast path
+            Distance::ZERO
+        };
+    }
     if height_of_root as usize != 1 + probes.len() {
         panic!(
             "usage: need {} probes, but {} probes provided",
@@ -64,35 +107,76 @@ where
         );
     }
     debug_assert_eq!(cells[(height_of_root - 1) as usize], 1);
+    let state = vec![(Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first))];
+    (dim, is_residual, height_of_root, cells, state)
+}
 
-    type State = Vec<(Reverse<Distance>, AlwaysEqual<f32>, AlwaysEqual<u32>)>;
-    let mut state: State = if is_residual {
-        let prefetch =
-            BorrowedIter::from_slice(meta_tuple.centroid_prefetch(), |x| bump.alloc_slice(x));
-        let head = meta_tuple.centroid_head();
-        let distance = centroids::read::<R, O, _>(
-            prefetch.map(|id| index.read(id)),
-            head,
-            LAccess::new(
-                O::Vector::unpack(vector),
-                O::DistanceAccessor::default_with_dimension(dim),
-            ),
-        );
-        let norm = meta_tuple.centroid_norm();
-        let first = meta_tuple.first();
-        vec![(Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first))]
-    } else {
-        // fast path
-        let distance = Distance::ZERO;
-        let norm = meta_tuple.centroid_norm();
-        let first = meta_tuple.first();
-        vec![(Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first))]
-    };
+enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
 
-    drop(meta_guard);
+impl<A: Iterator, B: Iterator<Item = A::Item>> Iterator for Either<A, B> {
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Either::Left(a) => a.next(),
+            Either::Right(b) => b.next(),
+        }
+    }
+}
+
+pub fn default_search<'b, R: RelationRead, O: Operator>(
+    index: &'b R,
+    upper: Option<Upper<'_>>,
+    vector: <O::Vector as VectorOwned>::Borrowed<'_>,
+    probes: Vec<u32>,
+    epsilon: f32,
+    bump: &'b impl Bump,
+    mut prefetch_h1_vectors: impl PrefetcherHeapFamily<'b, R>,
+    mut prefetch_h0_tuples: impl PrefetcherSequenceFamily<'b, R>,
+) -> Vec<(
+    (Reverse<Distance>, AlwaysEqual<()>),
+    AlwaysEqual<PackedRefMut4<'b, (NonZero<u64>, u16, BorrowedIter<'b>)>>,
+)>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let (dim, is_residual, height_of_root, cells, mut state) =
+        root::<R, O>(index, upper, vector, &probes, bump);
     let lut = O::Vector::preprocess(vector);
 
     let mut step = |state: State| {
+        if let Some(upper) = upper {
+            let mut results = Vec::new();
+            for (Reverse(dis_f), AlwaysEqual(norm), AlwaysEqual(first)) in state {
+                upper.read_h1_tape(
+                    first,
+                    || O::block_access(&lut.0, is_residual, dis_f.to_f32(), norm),
+                    |(rough, err), centroid, norm, first| {
+                        let lowerbound = Distance::from_f32(rough - err * epsilon);
+                        results.push((Reverse(lowerbound), AlwaysEqual((first, norm, centroid))));
+                    },
+                );
+            }
+            let mut heap = BinaryHeap::from(results);
+            let mut cache = BinaryHeap::<(_, _, _)>::new();
+            return Either::Right(std::iter::from_fn(move || {
+                while heap.peek().map(|(d, _)| *d) > cache.peek().map(|(d, ..)| *d) {
+                    let (_, AlwaysEqual((first, norm, centroid))) = heap.pop().unwrap();
+                    let distance = upper.read_centroid::<O::Vector, _>(
+                        centroid,
+                        LAccess::new(
+                            O::Vector::unpack(vector),
+                            O::DistanceAccessor::default_with_dimension(dim),
+                        ),
+                    );
+                    cache.push((Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first)));
+                }
+                cache.pop()
+            }));
+        }
         let mut results = LinkedVec::<(_, AlwaysEqual<Extra1<'b>>)>::new();
         for (Reverse(dis_f), AlwaysEqual(norm), AlwaysEqual(first)) in state {
             tape::read_h1_tape::<R, _, _>(
@@ -114,7 +198,7 @@ where
         }
         let mut heap = prefetch_h1_vectors.prefetch(results.into_vec());
         let mut cache = BinaryHeap::<(_, _, _)>::new();
-        std::iter::from_fn(move || {
+        Either::Left(std::iter::from_fn(move || {
             while let Some(((Reverse(_), AlwaysEqual(&mut (first, norm, head, ..))), prefetch)) =
                 heap.next_if(|(d, _)| Some(*d) > cache.peek().map(|(d, ..)| *d))
             {
@@ -129,7 +213,7 @@ where
                 cache.push((Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first)));
             }
             cache.pop()
-        })
+        }))
     };
 
     for i in 1..height_of_root {
@@ -140,6 +224,20 @@ where
             // fast path
             let mut results = LinkedVec::new();
             for (Reverse(_), AlwaysEqual(_), AlwaysEqual(first)) in state {
+                if let Some(upper) = upper {
+                    upper.read_h1_tape(
+                        first,
+                        || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                        |(), _, norm, first| {
+                            results.push((
+                                Reverse(Distance::ZERO),
+                                AlwaysEqual(norm),
+                                AlwaysEqual(first),
+                            ));
+                        },
+                    );
+                    continue;
+                }
                 tape::read_h1_tape::<R, _, _>(
                     by_next(index, first),
                     || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
@@ -198,6 +296,7 @@ where
 
 pub fn maxsim_search<'b, R: RelationRead, O: Operator>(
     index: &'b R,
+    upper: Option<Upper<'_>>,
     vector: <O::Vector as VectorOwned>::Borrowed<'_>,
     probes: Vec<u32>,
     epsilon: f32,
@@ -215,51 +314,40 @@ pub fn maxsim_search<'b, R: RelationRead, O: Operator>(
 where
     R::Page: Page<Opaque = Opaque>,
 {
-    let meta_guard = index.read(0);
-    let meta_bytes = meta_guard.get(1).expect("data corruption");
-    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
-    let dim = meta_tuple.dim();
-    let is_residual = meta_tuple.is_residual();
-    let height_of_root = meta_tuple.height_of_root();
-    let cells = meta_tuple.cells().to_vec();
-    assert_eq!(dim, vector.dim(), "unmatched dimensions");
-    if height_of_root as usize != 1 + probes.len() {
-        panic!(
-            "usage: need {} probes, but {} probes provided",
-            height_of_root - 1,
-            probes.len()
-        );
-    }
-    debug_assert_eq!(cells[(height_of_root - 1) as usize], 1);
-
-    type State = Vec<(Reverse<Distance>, AlwaysEqual<f32>, AlwaysEqual<u32>)>;
-    let mut state: State = if is_residual {
-        let prefetch =
-            BorrowedIter::from_slice(meta_tuple.centroid_prefetch(), |x| bump.alloc_slice(x));
-        let head = meta_tuple.centroid_head();
-        let distance = centroids::read::<R, O, _>(
-            prefetch.map(|id| index.read(id)),
-            head,
-            LAccess::new(
-                O::Vector::unpack(vector),
-                O::DistanceAccessor::default_with_dimension(dim),
-            ),
-        );
-        let norm = meta_tuple.centroid_norm();
-        let first = meta_tuple.first();
-        vec![(Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first))]
-    } else {
-        // fast path
-        let distance = Distance::ZERO;
-        let norm = meta_tuple.centroid_norm();
-        let first = meta_tuple.first();
-        vec![(Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first))]
-    };
-
-    drop(meta_guard);
+    let (dim, is_residual, height_of_root, cells, mut state) =
+        root::<R, O>(index, upper, vector, &probes, bump);
     let lut = O::Vector::preprocess(vector);
 
     let mut step = |state: State| {
+        if let Some(upper) = upper {
+            let mut results = Vec::new();
+            for (Reverse(dis_f), AlwaysEqual(norm), AlwaysEqual(first)) in state {
+                upper.read_h1_tape(
+                    first,
+                    || O::block_access(&lut.0, is_residual, dis_f.to_f32(), norm),
+                    |(rough, err), centroid, norm, first| {
+                        let lowerbound = Distance::from_f32(rough - err * epsilon);
+                        results.push((Reverse(lowerbound), AlwaysEqual((first, norm, centroid))));
+                    },
+                );
+            }
+            let mut heap = BinaryHeap::from(results);
+            let mut cache = BinaryHeap::<(_, _, _)>::new();
+            return Either::Right(std::iter::from_fn(move || {
+                while heap.peek().map(|(d, _)| *d) > cache.peek().map(|(d, ..)| *d) {
+                    let (_, AlwaysEqual((first, norm, centroid))) = heap.pop().unwrap();
+                    let distance = upper.read_centroid::<O::Vector, _>(
+                        centroid,
+                        LAccess::new(
+                            O::Vector::unpack(vector),
+                            O::DistanceAccessor::default_with_dimension(dim),
+                        ),
+                    );
+                    cache.push((Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first)));
+                }
+                cache.pop()
+            }));
+        }
         let mut results = LinkedVec::<(_, AlwaysEqual<Extra1<'b>>)>::new();
         for (Reverse(dis_f), AlwaysEqual(norm), AlwaysEqual(first)) in state {
             tape::read_h1_tape::<R, _, _>(
@@ -281,7 +369,7 @@ where
         }
         let mut heap = prefetch_h1_vectors.prefetch(results.into_vec());
         let mut cache = BinaryHeap::<(_, _, _)>::new();
-        std::iter::from_fn(move || {
+        Either::Left(std::iter::from_fn(move || {
             while let Some(((Reverse(_), AlwaysEqual(&mut (first, norm, head, ..))), prefetch)) =
                 heap.next_if(|(d, _)| Some(*d) > cache.peek().map(|(d, ..)| *d))
             {
@@ -296,7 +384,7 @@ where
                 cache.push((Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first)));
             }
             cache.pop()
-        })
+        }))
     };
 
     let mut it = None;
@@ -310,6 +398,20 @@ where
             // fast path
             let mut results = LinkedVec::new();
             for (Reverse(_), AlwaysEqual(_), AlwaysEqual(first)) in state {
+                if let Some(upper) = upper {
+                    upper.read_h1_tape(
+                        first,
+                        || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                        |(), _, norm, first| {
+                            results.push((
+                                Reverse(Distance::ZERO),
+                                AlwaysEqual(norm),
+                                AlwaysEqual(first),
+                            ));
+                        },
+                    );
+                    continue;
+                }
                 tape::read_h1_tape::<R, _, _>(
                     by_next(index, first),
                     || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
@@ -378,3 +480,432 @@ where
     }
     (results.into_vec(), estimation_by_threshold)
 }
+
+type Candidate<'b> = (
+    (Reverse<Distance>, AlwaysEqual<()>),
+    AlwaysEqual<PackedRefMut4<'b, (NonZero<u64>, u16, BorrowedIter<'b>)>>,
+);
+
+/// Searches many vectors at once. Each vector descends the tree on its own,
+/// and then every probed list is read only once for all vectors that probe it.
+pub fn batch_search<'b, R: RelationRead, O: Operator>(
+    index: &'b R,
+    vectors: &[<O::Vector as VectorOwned>::Borrowed<'_>],
+    probes: Vec<u32>,
+    epsilon: f32,
+    bump: &'b impl Bump,
+    prefetch_h1_vectors: impl PrefetcherHeapFamily<'b, R>,
+) -> Vec<
+    Vec<(
+        (Reverse<Distance>, AlwaysEqual<()>),
+        AlwaysEqual<PackedRefMut4<'b, (NonZero<u64>, u16, BorrowedIter<'b>)>>,
+    )>,
+>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    use std::collections::BTreeMap;
+
+    let luts = vectors
+        .iter()
+        .map(|&vector| O::Vector::preprocess(vector))
+        .collect::<Vec<_>>();
+    let (is_residual, states) = batch_probe::<R, O>(
+        index,
+        vectors,
+        &luts,
+        &probes,
+        epsilon,
+        bump,
+        prefetch_h1_vectors,
+    );
+
+    // probed lists, and for each of them, the vectors that probe it
+    let mut lists = BTreeMap::<u32, (f32, Vec<(usize, Distance)>)>::new();
+    for (i, state) in states.into_iter().enumerate() {
+        for (Reverse(dis_f), AlwaysEqual(norm), AlwaysEqual(first)) in state {
+            lists
+                .entry(first)
+                .or_insert_with(|| (norm, Vec::new()))
+                .1
+                .push((i, dis_f));
+        }
+    }
+
+    let mut results = vectors
+        .iter()
+        .map(|_| LinkedVec::<Candidate<'b>>::new())
+        .collect::<Vec<_>>();
+    for (first, (norm, probers)) in lists {
+        batch_scan::<R, O>(
+            index,
+            first,
+            norm,
+            is_residual,
+            &luts,
+            &probers,
+            epsilon,
+            bump,
+            |i, candidate| results[i].push(candidate),
+        );
+    }
+    results.into_iter().map(LinkedVec::into_vec).collect()
+}
+
+// Descends the tree for every vector, and returns the probed lists of every
+// vector, with the distances to their centroids and the norms of them.
+pub(crate) fn batch_probe<'b, R: RelationRead, O: Operator>(
+    index: &'b R,
+    vectors: &[<O::Vector as VectorOwned>::Borrowed<'_>],
+    luts: &[(BlockLut, BinaryLut)],
+    probes: &[u32],
+    epsilon: f32,
+    bump: &'b impl Bump,
+    mut prefetch_h1_vectors: impl PrefetcherHeapFamily<'b, R>,
+) -> (bool, Vec<State>)
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let dim = meta_tuple.dim();
+    let is_residual = meta_tuple.is_residual();
+    let height_of_root = meta_tuple.height_of_root();
+    let cells = meta_tuple.cells().to_vec();
+    for vector in vectors {
+        assert_eq!(dim, vector.dim(), "unmatched dimensions");
+    }
+    if height_of_root as usize != 1 + probes.len() {
+        panic!(
+            "usage: need {} probes, but {} probes provided",
+            height_of_root - 1,
+            probes.len()
+        );
+    }
+    debug_assert_eq!(cells[(height_of_root - 1) as usize], 1);
+    let root_prefetch = meta_tuple.centroid_prefetch().to_vec();
+    let root_head = meta_tuple.centroid_head();
+    let root_norm = meta_tuple.centroid_norm();
+    let root_first = meta_tuple.first();
+    drop(meta_guard);
+
+    let mut states = Vec::with_capacity(vectors.len());
+    for (i, &vector) in vectors.iter().enumerate() {
+        let lut = &luts[i];
+        let mut state: State = if is_residual {
+            let distance = centroids::read::<R, O, _>(
+                root_prefetch.iter().map(|&id| index.read(id)),
+                root_head,
+                LAccess::new(
+                    O::Vector::unpack(vector),
+                    O::DistanceAccessor::default_with_dimension(dim),
+                ),
+            );
+            vec![(
+                Reverse(distance),
+                AlwaysEqual(root_norm),
+                AlwaysEqual(root_first),
+            )]
+        } else {
+            // fast path
+            let distance = Distance::ZERO;
+            vec![(
+                Reverse(distance),
+                AlwaysEqual(root_norm),
+                AlwaysEqual(root_first),
+            )]
+        };
+
+        let mut step = |state: State| {
+            let mut results = LinkedVec::<(_, AlwaysEqual<Extra1<'b>>)>::new();
+            for (Reverse(dis_f), AlwaysEqual(norm), AlwaysEqual(first)) in state {
+                tape::read_h1_tape::<R, _, _>(
+                    by_next(index, first),
+                    || O::block_access(&lut.0, is_residual, dis_f.to_f32(), norm),
+                    |(rough, err), head, norm, first, prefetch| {
+                        let lowerbound = Distance::from_f32(rough - err * epsilon);
+                        results.push((
+                            Reverse(lowerbound),
+                            AlwaysEqual(bump.alloc((
+                                first,
+                                norm,
+                                head,
+                                BorrowedIter::from_slice(prefetch, |x| bump.alloc_slice(x)),
+                            ))),
+                        ));
+                    },
+                );
+            }
+            let mut heap = prefetch_h1_vectors.prefetch(results.into_vec());
+            let mut cache = BinaryHeap::<(_, _, _)>::new();
+            std::iter::from_fn(move || {
+                while let Some((
+                    (Reverse(_), AlwaysEqual(&mut (first, norm, head, ..))),
+                    prefetch,
+                )) = heap.next_if(|(d, _)| Some(*d) > cache.peek().map(|(d, ..)| *d))
+                {
+                    let distance = centroids::read::<R, O, _>(
+                        prefetch,
+                        head,
+                        LAccess::new(
+                            O::Vector::unpack(vector),
+                            O::DistanceAccessor::default_with_dimension(dim),
+                        ),
+                    );
+                    cache.push((Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first)));
+                }
+                cache.pop()
+            })
+        };
+
+        for i in 1..height_of_root {
+            let partial_scan = probes[i as usize - 1] < cells[(height_of_root - 1 - i) as usize];
+            if partial_scan || is_residual {
+                state = step(state).take(probes[i as usize - 1] as _).collect();
+            } else {
+                // fast path
+                let mut results = LinkedVec::new();
+                for (Reverse(_), AlwaysEqual(_), AlwaysEqual(first)) in state {
+                    tape::read_h1_tape::<R, _, _>(
+                        by_next(index, first),
+                        || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                        |(), _, norm, first, _| {
+                            results.push((
+                                Reverse(Distance::ZERO),
+                                AlwaysEqual(norm),
+                                AlwaysEqual(first),
+                            ));
+                        },
+                    );
+                }
+                state = results.into_vec();
+            }
+        }
+
+        states.push(state);
+    }
+    (is_residual, states)
+}
+
+// Reads the list starting at `first` once, and scores its vectors against
+// every prober, which is a vector and the distance to the centroid.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn batch_scan<'b, R: RelationRead, O: Operator>(
+    index: &'b R,
+    first: u32,
+    norm: f32,
+    is_residual: bool,
+    luts: &[(BlockLut, BinaryLut)],
+    probers: &[(usize, Distance)],
+    epsilon: f32,
+    bump: &'b impl Bump,
+    mut push: impl FnMut(usize, Candidate<'b>),
+) where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let jump_guard = index.read(first);
+    let jump_bytes = jump_guard.get(1).expect("data corruption");
+    let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+    let mut callback = |k: usize, (rough, err): (f32, f32), head, payload, prefetch: &[u32]| {
+        let lowerbound = Distance::from_f32(rough - err * epsilon);
+        push(
+            probers[k].0,
+            (
+                (Reverse(lowerbound), AlwaysEqual(())),
+                AlwaysEqual(PackedRefMut4(bump.alloc((
+                    payload,
+                    head,
+                    BorrowedIter::from_slice(prefetch, |x| bump.alloc_slice(x)),
+                )))),
+            ),
+        );
+    };
+    tape::read_frozen_tape_batch::<R, _, _>(
+        by_next(index, jump_tuple.frozen_first()),
+        probers.len(),
+        |k| {
+            let (i, dis_f) = probers[k];
+            O::block_access(&luts[i].0, is_residual, dis_f.to_f32(), norm)
+        },
+        &mut callback,
+    );
+    let mut accesses = probers
+        .iter()
+        .map(|&(i, dis_f)| O::binary_access(&luts[i].1, is_residual, dis_f.to_f32(), norm))
+        .collect::<Vec<_>>();
+    tape::read_appendable_tape::<R, _>(
+        by_next(index, jump_tuple.appendable_first()),
+        |metadata, elements, delta| {
+            accesses
+                .iter_mut()
+                .map(|access| access(metadata, elements, delta))
+                .collect::<Vec<_>>()
+        },
+        id_2(|values: Vec<_>, head, payload, prefetch| {
+            for (k, value) in values.into_iter().enumerate() {
+                callback(k, value, head, payload, prefetch);
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_search_in_memory() {
+    use crate::types::{DistanceKind, Structure, VchordrqIndexOptions, VectorKind, VectorOptions};
+    use crate::{InsertChooser, MaintainChooser};
+    use index::fetch::Fetch;
+    use index::memory::MemoryRelation;
+    use index::prefetcher::{PlainPrefetcher, Sequence};
+    use index_accessor::L2S;
+    use vector::vect::VectOwned;
+
+    struct MakeH1PlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherHeapFamily<'b, R> for MakeH1PlainPrefetcher<'b, R> {
+        type P<T>
+            = PlainPrefetcher<'b, R, BinaryHeap<T>>
+        where
+            T: Ord + Fetch<'b>;
+
+        fn prefetch<T>(&mut self, seq: Vec<T>) -> Self::P<T>
+        where
+            T: Ord + Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, BinaryHeap::from(seq))
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    struct MakeH0PlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherSequenceFamily<'b, R> for MakeH0PlainPrefetcher<'b, R> {
+        type P<S: Sequence>
+            = PlainPrefetcher<'b, R, S>
+        where
+            S::Item: Fetch<'b>;
+
+        fn prefetch<S: Sequence>(&mut self, seq: S) -> Self::P<S>
+        where
+            S::Item: Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, seq)
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    struct IdChooser(usize);
+
+    impl InsertChooser for IdChooser {
+        fn choose(&mut self, n: NonZero<usize>) -> usize {
+            self.0 % n.get()
+        }
+    }
+
+    struct ChooseAll;
+
+    impl MaintainChooser for ChooseAll {
+        fn choose(&mut self, _: usize) -> bool {
+            true
+        }
+    }
+
+    type O = Op<VectOwned<f32>, L2S>;
+    let dim = 16;
+    let vectors = (0..400)
+        .map(|_| (0..dim).map(|_| rand::random::<f32>()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let projected = vectors
+        .iter()
+        .map(|vector| VectOwned::new(rabitq::rotate::rotate(vector)))
+        .collect::<Vec<_>>();
+    let index = MemoryRelation::new();
+    let vector_options = VectorOptions {
+        dim: dim as u32,
+        v: VectorKind::Vecf32,
+        d: DistanceKind::L2S,
+    };
+    // four lists, whose centroids are the first four vectors, under the root
+    let mut root = vec![0.0_f32; dim];
+    for centroid in projected[..4].iter() {
+        for (x, y) in root.iter_mut().zip(centroid.slice()) {
+            *x += y / 4.0;
+        }
+    }
+    let structures = vec![
+        Structure {
+            centroids: projected[..4].to_vec(),
+            children: vec![Vec::new(); 4],
+        },
+        Structure {
+            centroids: vec![VectOwned::new(root)],
+            children: vec![vec![0, 1, 2, 3]],
+        },
+    ];
+    crate::build::<_, O>(
+        vector_options,
+        VchordrqIndexOptions::default(),
+        &index,
+        structures,
+    );
+    let insert = |i: usize| {
+        let bump = bumpalo::Bump::new();
+        let payload = NonZero::new(i as u64 + 1).unwrap();
+        let vector = VectOwned::new(vectors[i].clone());
+        let key = crate::insert_vector::<_, O>(
+            &index,
+            payload,
+            vector.as_borrowed(),
+            &mut IdChooser(i),
+            true,
+        );
+        crate::insert::<_, O>(
+            &index,
+            payload,
+            projected[i].as_borrowed(),
+            key,
+            &bump,
+            MakeH1PlainPrefetcher(&index),
+            true,
+        );
+    };
+    // vectors inserted before maintenance are moved to frozen tuples, and
+    // the others stay in appendable tuples
+    (0..300).for_each(insert);
+    crate::maintain::<_, O>(&index, MakeH0PlainPrefetcher(&index), &mut ChooseAll, || ());
+    (300..400).for_each(insert);
+    for i in (0..400).step_by(10) {
+        let bump = bumpalo::Bump::new();
+        let results = default_search::<_, O>(
+            &index,
+            None,
+            projected[i].as_borrowed(),
+            vec![4],
+            1.9,
+            &bump,
+            MakeH1PlainPrefetcher(&index),
+            MakeH0PlainPrefetcher(&index),
+        );
+        let prefetcher = PlainPrefetcher::new(&index, BinaryHeap::from(results));
+        let mut reranker =
+            crate::rerank_index::<O, _, _, _>(VectOwned::new(vectors[i].clone()), prefetcher);
+        let (_, payload) = reranker.next().expect("no results");
+        assert_eq!(payload.get(), i as u64 + 1);
+    }
+    let mut payloads = Vec::new();
+    let problems = crate::verify::<_, O>(
+        &index,
+        index.len(),
+        || (),
+        |payload| payloads.push(payload.get()),
+    );
+    assert!(problems.is_empty(), "{problems:?}");
+    payloads.sort_unstable();
+    assert_eq!(payloads, (1..=400).collect::<Vec<_>>());
+}
@@ -307,6 +307,60 @@ pub fn read_frozen_tape<'b, R, A, T>(
     }
 }
 
+/// Reads a frozen tape once for `n` queries, each of which has its own accessor.
+pub fn read_frozen_tape_batch<'b, R, A, T>(
+    iter: impl Iterator<Item = R::ReadGuard<'b>>,
+    n: usize,
+    accessor: impl Fn(usize) -> A,
+    mut callback: impl for<'a> FnMut(usize, T, u16, NonZero<u64>, &'a [u32]),
+) where
+    R: RelationRead + 'b,
+    A: for<'a> Accessor1<[u8; 16], (&'a [[f32; 32]; 4], &'a [f32; 32]), Output = [T; 32]>,
+    T: Copy,
+{
+    let mut x = None::<Vec<A>>;
+    for guard in iter {
+        for i in 1..=guard.len() {
+            let bytes = guard.get(i).expect("data corruption");
+            let tuple = FrozenTuple::deserialize_ref(bytes);
+            match tuple {
+                FrozenTupleReader::_0(tuple) => {
+                    let x = x.take().unwrap_or_else(|| (0..n).map(&accessor).collect());
+                    let values = x
+                        .into_iter()
+                        .map(|mut x| {
+                            x.push(tuple.elements());
+                            x.finish((tuple.metadata(), tuple.delta()))
+                        })
+                        .collect::<Vec<_>>();
+                    let prefetch = tuple.prefetch();
+                    let flattened = prefetch.as_flattened();
+                    let step = prefetch.len();
+                    for j in 0..32 {
+                        if let Some(payload) = tuple.payload()[j] {
+                            for (k, value) in values.iter().enumerate() {
+                                callback(
+                                    k,
+                                    value[j],
+                                    tuple.head()[j],
+                                    payload,
+                                    &flattened[j * step..][..step],
+                                );
+                            }
+                        }
+                    }
+                }
+                FrozenTupleReader::_1(tuple) => {
+                    let x = x.get_or_insert_with(|| (0..n).map(&accessor).collect());
+                    for x in x.iter_mut() {
+                        x.push(tuple.elements());
+                    }
+                }
+            }
+        }
+    }
+}
+
 pub fn read_appendable_tape<'b, R, T>(
     iter: impl Iterator<Item = R::ReadGuard<'b>>,
     mut access: impl for<'a> FnMut([f32; 4], &'a [u64], f32) -> T,
@@ -0,0 +1,123 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::closure_lifetime_binder::{id_0, id_1, id_2};
+use crate::operator::{Operator, Vector};
+use crate::tape::by_next;
+use crate::tuples::*;
+use crate::{Opaque, centroids, tape};
+use index::relation::{Page, RelationRead};
+use index_accessor::{Accessor1, FunctionalAccessor};
+
+#[derive(Debug, Clone)]
+pub struct Node<V> {
+    pub height: u32,
+    /// position of the parent in the returned nodes
+    pub parent: Option<u32>,
+    /// the centroid, which is projected
+    pub centroid: V,
+    /// number of children, or number of vectors if `height` is 1
+    pub size: u64,
+}
+
+struct CentroidAccessor<V: Vector> {
+    dim: u32,
+    elements: Vec<V::Element>,
+}
+
+impl<V: Vector> Accessor1<V::Element, V::Metadata> for CentroidAccessor<V> {
+    type Output = V;
+
+    fn push(&mut self, input: &[V::Element]) {
+        self.elements.extend(input);
+    }
+
+    fn finish(self, metadata: V::Metadata) -> Self::Output {
+        V::pack(self.dim, self.elements, metadata)
+    }
+}
+
+/// Returns all nodes of the centroid tree, parents before children.
+pub fn tree<R: RelationRead, O: Operator>(index: &R) -> Vec<Node<O::Vector>>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let dim = meta_tuple.dim();
+    let height_of_root = meta_tuple.height_of_root();
+
+    let centroid = |prefetch: &[u32], head: u16| {
+        let accessor = CentroidAccessor::<O::Vector> {
+            dim,
+            elements: Vec::new(),
+        };
+        centroids::read::<R, O, _>(prefetch.iter().map(|&id| index.read(id)), head, accessor)
+    };
+
+    let mut nodes = vec![Node {
+        height: height_of_root,
+        parent: None,
+        centroid: centroid(meta_tuple.centroid_prefetch(), meta_tuple.centroid_head()),
+        size: 0,
+    }];
+    let mut state = vec![(0_u32, meta_tuple.first())];
+    drop(meta_guard);
+
+    for height in (1..height_of_root).rev() {
+        let mut results = Vec::new();
+        for (parent, first) in state {
+            tape::read_h1_tape::<R, _, _>(
+                by_next(index, first),
+                || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                |(), head, _, first, prefetch| {
+                    results.push((nodes.len() as u32, first));
+                    nodes.push(Node {
+                        height,
+                        parent: Some(parent),
+                        centroid: centroid(prefetch, head),
+                        size: 0,
+                    });
+                    nodes[parent as usize].size += 1;
+                },
+            );
+        }
+        state = results;
+    }
+
+    for (id, first) in state {
+        let jump_guard = index.read(first);
+        let jump_bytes = jump_guard.get(1).expect("data corruption");
+        let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+        let mut size = 0_u64;
+        tape::read_frozen_tape::<R, _, _>(
+            by_next(index, jump_tuple.frozen_first()),
+            || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+            id_2(|_, _, _, _| {
+                size += 1;
+            }),
+        );
+        tape::read_appendable_tape::<R, _>(
+            by_next(index, jump_tuple.appendable_first()),
+            |_, _, _| (),
+            id_2(|_, _, _, _| {
+                size += 1;
+            }),
+        );
+        nodes[id as usize].size = size;
+    }
+
+    nodes
+}
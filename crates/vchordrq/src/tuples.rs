@@ -20,7 +20,20 @@ use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 pub const ALIGN: usize = 8;
 pub type Tag = u64;
 const MAGIC: Tag = Tag::from_ne_bytes(*b"vchordrq");
-const VERSION: u64 = 1001;
+pub const VERSION: u64 = 1001;
+// indexes of older versions can be read and written, and they are rewritten
+// to the current version by `upgrade`
+pub const OLDEST_VERSION: u64 = 1001;
+
+#[inline(always)]
+fn check_version(version: u64) {
+    if !(OLDEST_VERSION..=VERSION).contains(&version) {
+        panic!(
+            "deserialization: bad version number; {}",
+            "after upgrading VectorChord, please use REINDEX to rebuild the index."
+        );
+    }
+}
 
 #[inline(always)]
 fn tag(source: &[u8]) -> Tag {
@@ -163,12 +176,7 @@ impl WithReader for MetaTuple {
         match tag {
             MAGIC => {
                 let checker = RefChecker::new(source);
-                if VERSION != *checker.prefix::<u64>(size_of::<Tag>()) {
-                    panic!(
-                        "deserialization: bad version number; {}",
-                        "after upgrading VectorChord, please use REINDEX to rebuild the index."
-                    );
-                }
+                check_version(*checker.prefix::<u64>(size_of::<Tag>()));
                 let header: &MetaTupleHeader = checker.prefix(size_of::<Tag>());
                 let cells = checker.bytes(header.cells_s, header.cells_e);
                 let vectors_first = checker.bytes(header.vectors_first_s, header.vectors_first_e);
@@ -195,6 +203,9 @@ pub struct MetaTupleReader<'a> {
 }
 
 impl<'a> MetaTupleReader<'a> {
+    pub fn version(self) -> u64 {
+        self.header.version
+    }
     pub fn dim(self) -> u32 {
         self.header.dim
     }
@@ -233,6 +244,34 @@ impl<'a> MetaTupleReader<'a> {
     }
 }
 
+impl WithWriter for MetaTuple {
+    type Writer<'a> = MetaTupleWriter<'a>;
+    fn deserialize_mut(source: &mut [u8]) -> MetaTupleWriter<'_> {
+        let tag = tag(source);
+        match tag {
+            MAGIC => {
+                let checker = RefChecker::new(source);
+                check_version(*checker.prefix::<u64>(size_of::<Tag>()));
+                let mut checker = MutChecker::new(source);
+                let header: &mut MetaTupleHeader = checker.prefix(size_of::<Tag>());
+                MetaTupleWriter { header }
+            }
+            _ => panic!("deserialization: bad magic number"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MetaTupleWriter<'a> {
+    header: &'a mut MetaTupleHeader,
+}
+
+impl MetaTupleWriter<'_> {
+    pub fn version(&mut self) -> &mut u64 {
+        &mut self.header.version
+    }
+}
+
 #[repr(C, align(8))]
 #[derive(Debug, Clone, FromBytes, IntoBytes, Immutable, KnownLayout)]
 struct FreepagesTupleHeader {
@@ -254,6 +293,27 @@ impl Tuple for FreepagesTuple {
     }
 }
 
+impl WithReader for FreepagesTuple {
+    type Reader<'a> = FreepagesTupleReader<'a>;
+
+    fn deserialize_ref(source: &[u8]) -> FreepagesTupleReader<'_> {
+        let checker = RefChecker::new(source);
+        let header = checker.prefix(0_u16);
+        FreepagesTupleReader { header }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FreepagesTupleReader<'a> {
+    header: &'a FreepagesTupleHeader,
+}
+
+impl FreepagesTupleReader<'_> {
+    pub fn first(self) -> u32 {
+        self.header.first
+    }
+}
+
 impl WithWriter for FreepagesTuple {
     type Writer<'a> = FreepagesTupleWriter<'a>;
 
@@ -0,0 +1,97 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::Opaque;
+use crate::tuples::*;
+use index::relation::{Page, RelationRead, RelationWrite};
+
+/// Rewrites an index in place to the current version, one version at a time,
+/// and returns the version before the upgrade.
+///
+/// `report` is called with the versions before and after each step. The
+/// version number is written after all steps, so an interrupted upgrade is
+/// resumed by calling it again.
+pub fn upgrade<R: RelationRead + RelationWrite>(
+    index: &R,
+    check: impl Fn(),
+    mut report: impl FnMut(u64, u64),
+) -> u64
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let from = {
+        let meta_guard = index.read(0);
+        let meta_bytes = meta_guard.get(1).expect("data corruption");
+        MetaTuple::deserialize_ref(meta_bytes).version()
+    };
+    let mut version = from;
+    while version < VERSION {
+        check();
+        report(version, version + 1);
+        step(index, version);
+        version += 1;
+    }
+    if from != VERSION {
+        let mut meta_guard = index.write(0, false);
+        let meta_bytes = meta_guard.get_mut(1).expect("data corruption");
+        *MetaTuple::deserialize_mut(meta_bytes).version() = VERSION;
+    }
+    from
+}
+
+// rewrites pages of `version` to `version + 1`, except the meta tuple
+fn step<R: RelationRead + RelationWrite>(_: &R, version: u64)
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    #[allow(clippy::match_single_binding)]
+    match version {
+        // The format is unchanged since the oldest readable version.
+        _ => unreachable!("no upgrade from version {version}"),
+    }
+}
+
+#[test]
+fn test_upgrade_from_current_version() {
+    use crate::operator::Op;
+    use crate::types::{DistanceKind, Structure, VchordrqIndexOptions, VectorKind, VectorOptions};
+    use index::memory::MemoryRelation;
+    use index_accessor::L2S;
+    use vector::vect::VectOwned;
+
+    type O = Op<VectOwned<f32>, L2S>;
+    let index = MemoryRelation::new();
+    let vector_options = VectorOptions {
+        dim: 16,
+        v: VectorKind::Vecf32,
+        d: DistanceKind::L2S,
+    };
+    let structures = vec![Structure {
+        centroids: vec![VectOwned::new(vec![0.0; 16])],
+        children: vec![Vec::new()],
+    }];
+    crate::build::<_, O>(
+        vector_options,
+        VchordrqIndexOptions::default(),
+        &index,
+        structures,
+    );
+    assert_eq!(crate::dimension(&index), Ok(16));
+    let mut steps = Vec::new();
+    let from = upgrade(&index, || (), |from, to| steps.push((from, to)));
+    assert_eq!(from, OLDEST_VERSION);
+    assert_eq!(steps.len() as u64, VERSION - OLDEST_VERSION);
+    let from = upgrade(&index, || (), |_, _| panic!("the index is upgraded twice"));
+    assert_eq!(from, VERSION);
+}
@@ -0,0 +1,518 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::Opaque;
+use crate::operator::{Operator, Vector};
+use crate::tape::by_next;
+use crate::tuples::*;
+use index::relation::{Page, RelationRead};
+use index_accessor::Accessor1;
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+// Byte ranges of sections are stored as `[start, end]`.
+#[repr(C, align(8))]
+#[derive(Debug, Clone, Copy, FromBytes, IntoBytes, Immutable, KnownLayout)]
+struct Header {
+    dim: u32,
+    is_residual: u32,
+    height_of_root: u32,
+    root_norm: f32,
+    root_first: u32,
+    root_centroid: u32,
+    cells: [u32; 2],
+    tapes: [u32; 2],
+    blocks: [u32; 2],
+    elements: [u32; 2],
+    centroids: [u32; 2],
+    pieces: [u32; 2],
+    bytes: [u32; 2],
+}
+
+// An h1 tape, whose blocks are `blocks[blocks_s..blocks_e]`.
+#[repr(C, align(8))]
+#[derive(Debug, Clone, Copy, FromBytes, IntoBytes, Immutable, KnownLayout)]
+struct Tape {
+    first: u32,
+    blocks_s: u32,
+    blocks_e: u32,
+    _padding_0: [u8; 4],
+}
+
+// An h1 tuple, whose codes are `elements[elements_s..elements_e]`.
+#[repr(C, align(8))]
+#[derive(Debug, Clone, Copy, FromBytes, IntoBytes, Immutable, KnownLayout)]
+struct Block {
+    metadata: [[f32; 32]; 4],
+    delta: [f32; 32],
+    norm: [f32; 32],
+    first: [u32; 32],
+    centroid: [u32; 32],
+    len: u32,
+    elements_s: u32,
+    elements_e: u32,
+    _padding_0: [u8; 4],
+}
+
+// A centroid, whose elements are read piece by piece as they are stored in
+// centroid tuples, so distances are computed exactly as they are from pages.
+#[repr(C, align(8))]
+#[derive(Debug, Clone, Copy, FromBytes, IntoBytes, Immutable, KnownLayout)]
+struct Centroid {
+    pieces_s: u32,
+    pieces_e: u32,
+    metadata_s: u32,
+    _padding_0: [u8; 4],
+}
+
+#[repr(C, align(8))]
+#[derive(Debug, Clone, Copy, FromBytes, IntoBytes, Immutable, KnownLayout)]
+struct Piece {
+    bytes_s: u32,
+    bytes_e: u32,
+}
+
+/// Decodes the meta tuple, h1 tuples and centroids of an index, which are
+/// read by every search and are not changed by inserts or maintenance.
+///
+/// The result contains no pointers, so it could be copied anywhere, for
+/// example, to shared memory. It's returned as words so that it's aligned.
+pub fn decode_upper<R: RelationRead, O: Operator>(index: &R) -> Vec<u64>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let mut centroids = Vec::<Centroid>::new();
+    let mut pieces = Vec::<Piece>::new();
+    let mut bytes = Vec::<u8>::new();
+    let mut centroid = |prefetch: &[u32], head: u16| {
+        let pieces_s = pieces.len() as u32;
+        let mut prefetch = prefetch.iter().map(|&id| index.read(id));
+        let mut cursor = Err(head);
+        while let Err(head) = cursor {
+            let guard = prefetch.next().expect("data corruption");
+            let tuple_bytes = guard.get(head).expect("data corruption");
+            let tuple = CentroidTuple::<O::Vector>::deserialize_ref(tuple_bytes);
+            let bytes_s = bytes.len() as u32;
+            bytes.extend(tuple.elements().as_bytes());
+            pieces.push(Piece {
+                bytes_s,
+                bytes_e: bytes.len() as u32,
+            });
+            bytes.resize(bytes.len().next_multiple_of(8), 0);
+            cursor = tuple.metadata_or_head();
+        }
+        if prefetch.next().is_some() {
+            panic!("data corruption");
+        }
+        let metadata_s = bytes.len() as u32;
+        bytes.extend(cursor.expect("data corruption").as_bytes());
+        bytes.resize(bytes.len().next_multiple_of(8), 0);
+        centroids.push(Centroid {
+            pieces_s,
+            pieces_e: pieces.len() as u32,
+            metadata_s,
+            _padding_0: Default::default(),
+        });
+        centroids.len() as u32 - 1
+    };
+
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let dim = meta_tuple.dim();
+    let is_residual = meta_tuple.is_residual();
+    let height_of_root = meta_tuple.height_of_root();
+    let cells = meta_tuple.cells().to_vec();
+    let root_norm = meta_tuple.centroid_norm();
+    let root_first = meta_tuple.first();
+    let root_centroid = if is_residual {
+        centroid(meta_tuple.centroid_prefetch(), meta_tuple.centroid_head())
+    } else {
+        u32::MAX
+    };
+    drop(meta_guard);
+
+    let mut tapes = Vec::<Tape>::new();
+    let mut blocks = Vec::<Block>::new();
+    let mut elements = Vec::<[u8; 16]>::new();
+    let mut state = vec![root_first];
+    for _ in 1..height_of_root {
+        let mut next = Vec::new();
+        for first in state {
+            let blocks_s = blocks.len() as u32;
+            let mut elements_s = elements.len() as u32;
+            for guard in by_next(index, first) {
+                for i in 1..=guard.len() {
+                    let bytes = guard.get(i).expect("data corruption");
+                    match H1Tuple::deserialize_ref(bytes) {
+                        H1TupleReader::_0(tuple) => {
+                            elements.extend_from_slice(tuple.elements());
+                            let prefetch = tuple.prefetch();
+                            let flattened = prefetch.as_flattened();
+                            let step = prefetch.len();
+                            let mut block = Block {
+                                metadata: *tuple.metadata(),
+                                delta: *tuple.delta(),
+                                norm: *tuple.norm(),
+                                first: *tuple.first(),
+                                centroid: [u32::MAX; 32],
+                                len: tuple.len(),
+                                elements_s,
+                                elements_e: elements.len() as u32,
+                                _padding_0: Default::default(),
+                            };
+                            for j in 0..tuple.len() as usize {
+                                let prefetch = &flattened[j * step..][..step];
+                                block.centroid[j] = centroid(prefetch, tuple.head()[j]);
+                                next.push(tuple.first()[j]);
+                            }
+                            blocks.push(block);
+                            elements_s = elements.len() as u32;
+                        }
+                        H1TupleReader::_1(tuple) => {
+                            elements.extend_from_slice(tuple.elements());
+                        }
+                    }
+                }
+            }
+            tapes.push(Tape {
+                first,
+                blocks_s,
+                blocks_e: blocks.len() as u32,
+                _padding_0: Default::default(),
+            });
+        }
+        state = next;
+    }
+    tapes.sort_unstable_by_key(|tape| tape.first);
+
+    let mut buffer = vec![0_u8; size_of::<Header>()];
+    let mut section = |data: &[u8]| {
+        let start = buffer.len() as u32;
+        buffer.extend_from_slice(data);
+        buffer.resize(buffer.len().next_multiple_of(8), 0);
+        [start, start + data.len() as u32]
+    };
+    let header = Header {
+        dim,
+        is_residual: is_residual as u32,
+        height_of_root,
+        root_norm,
+        root_first,
+        root_centroid,
+        cells: section(cells.as_bytes()),
+        tapes: section(tapes.as_bytes()),
+        blocks: section(blocks.as_bytes()),
+        elements: section(elements.as_bytes()),
+        centroids: section(centroids.as_bytes()),
+        pieces: section(pieces.as_bytes()),
+        bytes: section(&bytes),
+    };
+    buffer[..size_of::<Header>()].copy_from_slice(header.as_bytes());
+    buffer
+        .chunks_exact(8)
+        .map(|chunk| u64::from_ne_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
+/// Decoded upper levels of an index, which are read in place.
+#[derive(Debug, Clone, Copy)]
+pub struct Upper<'a> {
+    header: &'a Header,
+    cells: &'a [u32],
+    tapes: &'a [Tape],
+    blocks: &'a [Block],
+    elements: &'a [[u8; 16]],
+    centroids: &'a [Centroid],
+    pieces: &'a [Piece],
+    bytes: &'a [u8],
+}
+
+impl<'a> Upper<'a> {
+    pub fn new(buffer: &'a [u64]) -> Self {
+        let buffer = buffer.as_bytes();
+        let header = Header::ref_from_prefix(buffer).expect("data corruption").0;
+        fn section<T: FromBytes + Immutable + KnownLayout>(
+            buffer: &[u8],
+            [start, end]: [u32; 2],
+        ) -> &[T] {
+            let bytes = buffer
+                .get(start as usize..end as usize)
+                .expect("data corruption");
+            <[T]>::ref_from_bytes(bytes).expect("data corruption")
+        }
+        Self {
+            header,
+            cells: section(buffer, header.cells),
+            tapes: section(buffer, header.tapes),
+            blocks: section(buffer, header.blocks),
+            elements: section(buffer, header.elements),
+            centroids: section(buffer, header.centroids),
+            pieces: section(buffer, header.pieces),
+            bytes: section(buffer, header.bytes),
+        }
+    }
+
+    pub fn dim(&self) -> u32 {
+        self.header.dim
+    }
+
+    pub fn is_residual(&self) -> bool {
+        self.header.is_residual != 0
+    }
+
+    pub fn height_of_root(&self) -> u32 {
+        self.header.height_of_root
+    }
+
+    pub fn cells(&self) -> &'a [u32] {
+        self.cells
+    }
+
+    pub(crate) fn root_norm(&self) -> f32 {
+        self.header.root_norm
+    }
+
+    pub(crate) fn root_first(&self) -> u32 {
+        self.header.root_first
+    }
+
+    pub(crate) fn root_centroid(&self) -> u32 {
+        self.header.root_centroid
+    }
+
+    /// Reads an h1 tape like `tape::read_h1_tape`, but centroids are given
+    /// as ids for `read_centroid`.
+    pub(crate) fn read_h1_tape<A, T>(
+        &self,
+        first: u32,
+        accessor: impl Fn() -> A,
+        mut callback: impl FnMut(T, u32, f32, u32),
+    ) where
+        A: for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [T; 32]>,
+    {
+        let i = self
+            .tapes
+            .binary_search_by_key(&first, |tape| tape.first)
+            .expect("data corruption");
+        let tape = &self.tapes[i];
+        for block in &self.blocks[tape.blocks_s as usize..tape.blocks_e as usize] {
+            let mut x = accessor();
+            x.push(&self.elements[block.elements_s as usize..block.elements_e as usize]);
+            let values = x.finish((&block.metadata, &block.delta));
+            for (j, value) in values.into_iter().enumerate() {
+                if j < block.len as usize {
+                    callback(value, block.centroid[j], block.norm[j], block.first[j]);
+                }
+            }
+        }
+    }
+
+    /// Reads a centroid like `centroids::read`.
+    pub(crate) fn read_centroid<
+        V: Vector,
+        A: Accessor1<<V as Vector>::Element, <V as Vector>::Metadata>,
+    >(
+        &self,
+        id: u32,
+        accessor: A,
+    ) -> A::Output {
+        let centroid = &self.centroids[id as usize];
+        let mut result = accessor;
+        for piece in &self.pieces[centroid.pieces_s as usize..centroid.pieces_e as usize] {
+            let bytes = &self.bytes[piece.bytes_s as usize..piece.bytes_e as usize];
+            result.push(<[V::Element]>::ref_from_bytes(bytes).expect("data corruption"));
+        }
+        let bytes = &self.bytes[centroid.metadata_s as usize..];
+        let (metadata, _) = V::Metadata::read_from_prefix(bytes).expect("data corruption");
+        result.finish(metadata)
+    }
+}
+
+#[test]
+fn test_upper() {
+    use crate::operator::Op;
+    use crate::types::{DistanceKind, Structure, VchordrqIndexOptions, VectorKind, VectorOptions};
+    use index::fetch::Fetch;
+    use index::memory::MemoryRelation;
+    use index::prefetcher::{
+        PlainPrefetcher, PrefetcherHeapFamily, PrefetcherSequenceFamily, Sequence,
+    };
+    use index_accessor::L2S;
+    use std::collections::BinaryHeap;
+    use std::num::NonZero;
+    use vector::VectorOwned;
+    use vector::vect::VectOwned;
+
+    struct MakeH1PlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherHeapFamily<'b, R> for MakeH1PlainPrefetcher<'b, R> {
+        type P<T>
+            = PlainPrefetcher<'b, R, BinaryHeap<T>>
+        where
+            T: Ord + Fetch<'b>;
+
+        fn prefetch<T>(&mut self, seq: Vec<T>) -> Self::P<T>
+        where
+            T: Ord + Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, BinaryHeap::from(seq))
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    struct MakeH0PlainPrefetcher<'b, R>(&'b R);
+
+    impl<'b, R: RelationRead> PrefetcherSequenceFamily<'b, R> for MakeH0PlainPrefetcher<'b, R> {
+        type P<S: Sequence>
+            = PlainPrefetcher<'b, R, S>
+        where
+            S::Item: Fetch<'b>;
+
+        fn prefetch<S: Sequence>(&mut self, seq: S) -> Self::P<S>
+        where
+            S::Item: Fetch<'b>,
+        {
+            PlainPrefetcher::new(self.0, seq)
+        }
+
+        fn is_not_plain(&self) -> bool {
+            false
+        }
+    }
+
+    struct IdChooser(usize);
+
+    impl crate::InsertChooser for IdChooser {
+        fn choose(&mut self, n: NonZero<usize>) -> usize {
+            self.0 % n.get()
+        }
+    }
+
+    type O = Op<VectOwned<f32>, L2S>;
+    let dim = 16;
+    let vectors = (0..400)
+        .map(|_| (0..dim).map(|_| rand::random::<f32>()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let projected = vectors
+        .iter()
+        .map(|vector| VectOwned::new(rabitq::rotate::rotate(vector)))
+        .collect::<Vec<_>>();
+    let index = MemoryRelation::new();
+    let vector_options = VectorOptions {
+        dim: dim as u32,
+        v: VectorKind::Vecf32,
+        d: DistanceKind::L2S,
+    };
+    // eight lists under two nodes under the root, so centroids are read at
+    // every level
+    let mut nodes = vec![vec![0.0_f32; dim]; 2];
+    for (i, centroid) in projected[..8].iter().enumerate() {
+        for (x, y) in nodes[i / 4].iter_mut().zip(centroid.slice()) {
+            *x += y / 4.0;
+        }
+    }
+    let mut root = vec![0.0_f32; dim];
+    for node in nodes.iter() {
+        for (x, y) in root.iter_mut().zip(node) {
+            *x += y / 2.0;
+        }
+    }
+    let structures = vec![
+        Structure {
+            centroids: projected[..8].to_vec(),
+            children: vec![Vec::new(); 8],
+        },
+        Structure {
+            centroids: nodes.into_iter().map(VectOwned::new).collect(),
+            children: vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]],
+        },
+        Structure {
+            centroids: vec![VectOwned::new(root)],
+            children: vec![vec![0, 1]],
+        },
+    ];
+    crate::build::<_, O>(
+        vector_options,
+        VchordrqIndexOptions {
+            residual_quantization: true,
+            ..Default::default()
+        },
+        &index,
+        structures,
+    );
+    for i in 0..400 {
+        let bump = bumpalo::Bump::new();
+        let payload = NonZero::new(i as u64 + 1).unwrap();
+        let vector = VectOwned::new(vectors[i].clone());
+        let key = crate::insert_vector::<_, O>(
+            &index,
+            payload,
+            vector.as_borrowed(),
+            &mut IdChooser(i),
+            true,
+        );
+        crate::insert::<_, O>(
+            &index,
+            payload,
+            projected[i].as_borrowed(),
+            key,
+            &bump,
+            MakeH1PlainPrefetcher(&index),
+            true,
+        );
+    }
+    let decoded = decode_upper::<_, O>(&index);
+    let upper = Upper::new(&decoded);
+    assert_eq!(upper.dim(), dim as u32);
+    assert!(upper.is_residual());
+    assert_eq!(upper.height_of_root(), 3);
+    // searches read the same candidates, whether upper levels are decoded or not
+    for i in (0..400).step_by(10) {
+        for probes in [vec![1, 2], vec![2, 8]] {
+            let bump = bumpalo::Bump::new();
+            let mut expected = crate::default_search::<_, O>(
+                &index,
+                None,
+                projected[i].as_borrowed(),
+                probes.clone(),
+                1.9,
+                &bump,
+                MakeH1PlainPrefetcher(&index),
+                MakeH0PlainPrefetcher(&index),
+            )
+            .into_iter()
+            .map(|((lowerbound, _), packed)| (lowerbound, packed.0.0.0))
+            .collect::<Vec<_>>();
+            let mut actual = crate::default_search::<_, O>(
+                &index,
+                Some(upper),
+                projected[i].as_borrowed(),
+                probes.clone(),
+                1.9,
+                &bump,
+                MakeH1PlainPrefetcher(&index),
+                MakeH0PlainPrefetcher(&index),
+            )
+            .into_iter()
+            .map(|((lowerbound, _), packed)| (lowerbound, packed.0.0.0))
+            .collect::<Vec<_>>();
+            expected.sort_unstable();
+            actual.sort_unstable();
+            assert_eq!(expected, actual);
+        }
+    }
+}
@@ -0,0 +1,420 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::Opaque;
+use crate::operator::Operator;
+use crate::tuples::*;
+use index::relation::{Page, RelationRead};
+use std::collections::HashSet;
+use std::num::NonZero;
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub id: Option<u32>,
+    pub message: String,
+}
+
+struct Verifier<'b, R, C> {
+    index: &'b R,
+    nblocks: u32,
+    check: C,
+    visited: HashSet<u32>,
+    problems: Vec<Problem>,
+}
+
+impl<'b, R, C> Verifier<'b, R, C>
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = Opaque>,
+    C: Fn(),
+{
+    fn report(&mut self, id: Option<u32>, message: String) {
+        self.problems.push(Problem { id, message });
+    }
+
+    fn in_range(&mut self, from: u32, id: u32, what: &str) -> bool {
+        if id < self.nblocks {
+            true
+        } else {
+            self.report(Some(from), format!("{what}: page {id} is out of range"));
+            false
+        }
+    }
+
+    // Walks a linked list of pages, stopping at the first link that is out of
+    // range or points to a page that is already reached, since every page
+    // belongs to at most one list.
+    fn chain(&mut self, from: u32, first: u32, what: &str) -> Vec<u32> {
+        let index = self.index;
+        let mut pages = Vec::new();
+        let (mut from, mut current) = (from, first);
+        while current != u32::MAX {
+            (self.check)();
+            if !self.in_range(from, current, what) {
+                break;
+            }
+            if !self.visited.insert(current) {
+                self.report(
+                    Some(from),
+                    format!("{what}: page {current} is reached more than once"),
+                );
+                break;
+            }
+            pages.push(current);
+            let guard = index.read(current);
+            (from, current) = (current, guard.get_opaque().next);
+        }
+        pages
+    }
+
+    // Calls `f` for every tuple of a page, reporting unused slots.
+    fn items(&mut self, id: u32, what: &str, mut f: impl FnMut(&mut Self, &[u8])) {
+        let index = self.index;
+        let guard = index.read(id);
+        for i in 1..=guard.len() {
+            if let Some(bytes) = guard.get(i) {
+                f(self, bytes);
+            } else {
+                self.report(Some(id), format!("{what}: item {i} is unused"));
+            }
+        }
+    }
+
+    fn prefetch(&mut self, id: u32, prefetch: &[u32], targets: &HashSet<u32>, what: &str) {
+        for &p in prefetch {
+            if !targets.contains(&p) {
+                self.report(
+                    Some(id),
+                    format!("{what}: prefetch id {p} does not refer to a page of the tape"),
+                );
+                break;
+            }
+        }
+    }
+}
+
+// Tuple readers panic on malformed bytes, so they are called with panics
+// caught. They never call into PostgreSQL, so this is safe to do.
+fn decode<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).map_err(|e| {
+        if let Some(message) = e.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = e.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "unknown error".to_string()
+        }
+    })
+}
+
+/// Returns the dimension recorded in the meta tuple, or why the meta tuple
+/// cannot be read, for example, that the index is built by another version.
+pub fn dimension<R: RelationRead>(index: &R) -> Result<u32, String>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let Some(meta_bytes) = meta_guard.get(1) else {
+        return Err("the meta tuple is missing".to_string());
+    };
+    decode(|| MetaTuple::deserialize_ref(meta_bytes).dim())
+}
+
+pub fn verify<R: RelationRead, O: Operator>(
+    index: &R,
+    nblocks: u32,
+    check: impl Fn(),
+    mut callback: impl FnMut(NonZero<u64>),
+) -> Vec<Problem>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let mut v = Verifier {
+        index,
+        nblocks,
+        check,
+        visited: HashSet::new(),
+        problems: Vec::new(),
+    };
+
+    if nblocks == 0 {
+        v.report(None, "meta tuple: the index is empty".to_string());
+        return v.problems;
+    }
+
+    let meta_guard = index.read(0);
+    let meta_tuple = match meta_guard
+        .get(1)
+        .map(|bytes| decode(|| MetaTuple::deserialize_ref(bytes)))
+    {
+        Some(Ok(meta_tuple)) => meta_tuple,
+        Some(Err(message)) => {
+            v.report(Some(0), format!("meta tuple: {message}"));
+            return v.problems;
+        }
+        None => {
+            v.report(Some(0), "meta tuple: item 1 is unused".to_string());
+            return v.problems;
+        }
+    };
+    let height_of_root = meta_tuple.height_of_root();
+    let cells = meta_tuple.cells().to_vec();
+    let centroids_first = meta_tuple.centroids_first();
+    let vectors_first = meta_tuple.vectors_first().to_vec();
+    let freepages_first = meta_tuple.freepages_first();
+    let centroid_prefetch = meta_tuple.centroid_prefetch().to_vec();
+    let first = meta_tuple.first();
+    drop(meta_guard);
+    v.visited.insert(0);
+
+    if height_of_root == 0 || cells.len() != height_of_root as usize {
+        v.report(
+            Some(0),
+            format!(
+                "meta tuple: height of root is {height_of_root}, but there are {} levels",
+                cells.len()
+            ),
+        );
+        return v.problems;
+    }
+
+    // centroids
+    let centroids = v.chain(0, centroids_first, "centroid tape");
+    for &id in centroids.iter() {
+        v.items(id, "centroid tape", |v, bytes| {
+            if let Err(message) = decode(|| {
+                CentroidTuple::<O::Vector>::deserialize_ref(bytes);
+            }) {
+                v.report(Some(id), format!("centroid tape: {message}"));
+            }
+        });
+    }
+    let centroids = centroids.into_iter().collect::<HashSet<_>>();
+    v.prefetch(0, &centroid_prefetch, &centroids, "meta tuple");
+
+    // vectors
+    let mut vectors = HashSet::new();
+    for first in vectors_first {
+        for id in v.chain(0, first, "vector tape") {
+            let guard = index.read(id);
+            for i in 1..=guard.len() {
+                if let Some(bytes) = guard.get(i)
+                    && let Err(message) =
+                        decode(|| VectorTuple::<O::Vector>::deserialize_ref(bytes).payload())
+                {
+                    v.report(Some(id), format!("vector tape: {message}"));
+                }
+            }
+            vectors.insert(id);
+        }
+    }
+
+    // freepages
+    if v.in_range(0, freepages_first, "freepages tuple") && v.visited.insert(freepages_first) {
+        let guard = index.read(freepages_first);
+        let first = guard
+            .get(1)
+            .map(|bytes| decode(|| FreepagesTuple::deserialize_ref(bytes).first()));
+        drop(guard);
+        match first {
+            Some(Ok(first)) => {
+                v.chain(freepages_first, first, "freepages");
+            }
+            Some(Err(message)) => {
+                v.report(Some(freepages_first), format!("freepages tuple: {message}"));
+            }
+            None => {
+                v.report(
+                    Some(freepages_first),
+                    "freepages tuple: item 1 is unused".to_string(),
+                );
+            }
+        }
+    }
+
+    // h1 tuples
+    let mut state = vec![(0, first)];
+    for level in (1..height_of_root).rev() {
+        let mut results = Vec::new();
+        for (from, first) in state {
+            for id in v.chain(from, first, "h1 tape") {
+                v.items(id, "h1 tape", |v, bytes| {
+                    match decode(|| match H1Tuple::deserialize_ref(bytes) {
+                        H1TupleReader::_0(tuple) => {
+                            let len = tuple.len() as usize;
+                            let step = tuple.prefetch().len();
+                            let prefetch = tuple.prefetch().as_flattened();
+                            let mut children = Vec::new();
+                            for j in 0..std::cmp::min(len, 32) {
+                                children.push((
+                                    tuple.first()[j],
+                                    prefetch[j * step..][..step].to_vec(),
+                                ));
+                            }
+                            (len, children)
+                        }
+                        H1TupleReader::_1(tuple) => {
+                            tuple.elements();
+                            (0, Vec::new())
+                        }
+                    }) {
+                        Ok((len, children)) => {
+                            if len > 32 {
+                                v.report(Some(id), format!("h1 tape: bad length {len}"));
+                            }
+                            for (first, prefetch) in children {
+                                v.prefetch(id, &prefetch, &centroids, "h1 tape");
+                                results.push((id, first));
+                            }
+                        }
+                        Err(message) => v.report(Some(id), format!("h1 tape: {message}")),
+                    }
+                });
+            }
+        }
+        if results.len() != cells[level as usize - 1] as usize {
+            v.report(
+                None,
+                format!(
+                    "h1 tape: expected {} nodes at level {level}, but found {}",
+                    cells[level as usize - 1],
+                    results.len()
+                ),
+            );
+        }
+        state = results;
+    }
+
+    // jump tuples
+    for (from, first) in state {
+        (v.check)();
+        if !v.in_range(from, first, "jump tuple") {
+            continue;
+        }
+        if !v.visited.insert(first) {
+            v.report(
+                Some(from),
+                format!("jump tuple: page {first} is reached more than once"),
+            );
+            continue;
+        }
+        let jump_guard = index.read(first);
+        let jump = match jump_guard.get(1).map(|bytes| {
+            decode(|| {
+                let tuple = JumpTuple::deserialize_ref(bytes);
+                (
+                    tuple.centroid_prefetch().to_vec(),
+                    tuple.directory_first(),
+                    tuple.frozen_first(),
+                    tuple.appendable_first(),
+                )
+            })
+        }) {
+            Some(Ok(jump)) => jump,
+            Some(Err(message)) => {
+                v.report(Some(first), format!("jump tuple: {message}"));
+                continue;
+            }
+            None => {
+                v.report(Some(first), "jump tuple: item 1 is unused".to_string());
+                continue;
+            }
+        };
+        drop(jump_guard);
+        let (prefetch, directory_first, frozen_first, appendable_first) = jump;
+        v.prefetch(first, &prefetch, &centroids, "jump tuple");
+
+        // directory tape
+        let mut directory = Vec::new();
+        for id in v.chain(first, directory_first, "directory tape") {
+            v.items(id, "directory tape", |v, bytes| {
+                match decode(|| match DirectoryTuple::deserialize_ref(bytes) {
+                    DirectoryTupleReader::_0(tuple) => tuple.elements().to_vec(),
+                    DirectoryTupleReader::_1(tuple) => tuple.elements().to_vec(),
+                }) {
+                    Ok(elements) => directory.extend(elements),
+                    Err(message) => v.report(Some(id), format!("directory tape: {message}")),
+                }
+            });
+        }
+
+        // frozen tape
+        let frozen = v.chain(first, frozen_first, "frozen tape");
+        if directory != frozen {
+            v.report(
+                Some(first),
+                "directory tape: the directory does not match the frozen tape".to_string(),
+            );
+        }
+        for &id in frozen.iter() {
+            v.items(id, "frozen tape", |v, bytes| {
+                match decode(|| match FrozenTuple::deserialize_ref(bytes) {
+                    FrozenTupleReader::_0(tuple) => {
+                        let step = tuple.prefetch().len();
+                        let prefetch = tuple.prefetch().as_flattened();
+                        let mut children = Vec::new();
+                        for (j, payload) in tuple.payload().iter().enumerate() {
+                            if let Some(payload) = *payload {
+                                children.push((payload, prefetch[j * step..][..step].to_vec()));
+                            }
+                        }
+                        children
+                    }
+                    FrozenTupleReader::_1(tuple) => {
+                        tuple.elements();
+                        Vec::new()
+                    }
+                }) {
+                    Ok(children) => {
+                        for (payload, prefetch) in children {
+                            v.prefetch(id, &prefetch, &vectors, "frozen tape");
+                            callback(payload);
+                        }
+                    }
+                    Err(message) => v.report(Some(id), format!("frozen tape: {message}")),
+                }
+            });
+        }
+
+        // appendable tape
+        let appendable = v.chain(first, appendable_first, "appendable tape");
+        if let Some(&head) = appendable.first() {
+            let skip = index.read(head).get_opaque().skip;
+            if !appendable.contains(&skip) {
+                v.report(
+                    Some(head),
+                    format!("appendable tape: skip {skip} does not refer to a page of the tape"),
+                );
+            }
+        }
+        for &id in appendable.iter() {
+            v.items(id, "appendable tape", |v, bytes| {
+                match decode(|| {
+                    let tuple = AppendableTuple::deserialize_ref(bytes);
+                    (tuple.payload(), tuple.prefetch().to_vec())
+                }) {
+                    Ok((payload, prefetch)) => {
+                        if let Some(payload) = payload {
+                            v.prefetch(id, &prefetch, &vectors, "appendable tape");
+                            callback(payload);
+                        }
+                    }
+                    Err(message) => v.report(Some(id), format!("appendable tape: {message}")),
+                }
+            });
+        }
+    }
+
+    v.problems
+}
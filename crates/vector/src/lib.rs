@@ -13,9 +13,12 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 pub mod bvect;
+pub mod rabitq1;
+pub mod rabitq2;
 pub mod rabitq4;
 pub mod rabitq8;
 pub mod svect;
+pub mod veci8;
 pub mod vect;
 
 pub trait VectorOwned: Clone + 'static {
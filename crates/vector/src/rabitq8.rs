@@ -14,6 +14,7 @@
 
 use crate::{VectorBorrowed, VectorOwned};
 use distance::Distance;
+use simd::Floating;
 
 #[derive(Debug, Clone)]
 pub struct Rabitq8Owned {
@@ -98,6 +99,19 @@ impl Rabitq8Owned {
             packed_code,
         }
     }
+
+    /// Quantizes a vector that is already in the rotated space of RaBitQ.
+    pub fn from_rotated(vector: &[f32]) -> Self {
+        let (metadata, elements) = rabitq::byte::ugly_code(vector);
+        Self::new(
+            vector.len() as u32,
+            metadata.dis_u_2,
+            metadata.norm_of_lattice,
+            metadata.sum_of_code,
+            f32::reduce_sum_of_abs_x(vector),
+            rabitq::byte::pack_code(&elements),
+        )
+    }
 }
 
 impl VectorOwned for Rabitq8Owned {
@@ -229,6 +243,15 @@ impl<'a> Rabitq8Borrowed<'a> {
     pub fn unpacked_code(&self) -> impl Iterator<Item = u8> {
         self.packed_code.iter().copied()
     }
+
+    /// Decodes the vector, leaving it in the rotated space of RaBitQ.
+    pub fn to_rotated(&self) -> Vec<f32> {
+        let base = -0.5 * ((1 << 8) - 1) as f32;
+        let scale = self.sum_of_x2.sqrt() / self.norm_of_lattice;
+        self.unpacked_code()
+            .map(|c| (base + c as f32) * scale)
+            .collect()
+    }
 }
 
 impl VectorBorrowed for Rabitq8Borrowed<'_> {
@@ -347,12 +370,14 @@ impl VectorBorrowed for Rabitq8Borrowed<'_> {
         }
     }
 
-    fn operator_add(&self, _: Self) -> Self::Owned {
-        unimplemented!()
+    #[inline(always)]
+    fn operator_add(&self, rhs: Self) -> Self::Owned {
+        Rabitq8Owned::from_rotated(&f32::vector_add(&self.to_rotated(), &rhs.to_rotated()))
     }
 
-    fn operator_sub(&self, _: Self) -> Self::Owned {
-        unimplemented!()
+    #[inline(always)]
+    fn operator_sub(&self, rhs: Self) -> Self::Owned {
+        Rabitq8Owned::from_rotated(&f32::vector_sub(&self.to_rotated(), &rhs.to_rotated()))
     }
 
     fn operator_mul(&self, _: Self) -> Self::Owned {
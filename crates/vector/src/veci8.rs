@@ -0,0 +1,199 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::{VectorBorrowed, VectorOwned};
+use distance::Distance;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone)]
+#[repr(transparent)]
+pub struct Veci8Owned(Vec<i8>);
+
+impl Veci8Owned {
+    #[inline(always)]
+    pub fn new(slice: Vec<i8>) -> Self {
+        Self::new_checked(slice).expect("invalid data")
+    }
+
+    #[inline(always)]
+    pub fn new_checked(slice: Vec<i8>) -> Option<Self> {
+        if !(1..=65535).contains(&slice.len()) {
+            return None;
+        }
+        #[allow(unsafe_code)]
+        Some(unsafe { Self::new_unchecked(slice) })
+    }
+
+    /// # Safety
+    ///
+    /// * `slice.len()` must not be zero.
+    /// * `slice.len()` must be less than 65536.
+    #[allow(unsafe_code)]
+    #[inline(always)]
+    pub unsafe fn new_unchecked(slice: Vec<i8>) -> Self {
+        Self(slice)
+    }
+
+    #[inline(always)]
+    pub fn slice(&self) -> &[i8] {
+        self.0.as_slice()
+    }
+
+    #[inline(always)]
+    pub fn slice_mut(&mut self) -> &mut [i8] {
+        self.0.as_mut_slice()
+    }
+
+    #[inline(always)]
+    pub fn into_vec(self) -> Vec<i8> {
+        self.0
+    }
+}
+
+impl VectorOwned for Veci8Owned {
+    type Borrowed<'a> = Veci8Borrowed<'a>;
+
+    #[inline(always)]
+    fn as_borrowed(&self) -> Veci8Borrowed<'_> {
+        Veci8Borrowed(self.0.as_slice())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct Veci8Borrowed<'a>(&'a [i8]);
+
+impl<'a> Veci8Borrowed<'a> {
+    #[inline(always)]
+    pub fn new(slice: &'a [i8]) -> Self {
+        Self::new_checked(slice).expect("invalid data")
+    }
+
+    #[inline(always)]
+    pub fn new_checked(slice: &'a [i8]) -> Option<Self> {
+        if !(1..=65535).contains(&slice.len()) {
+            return None;
+        }
+        #[allow(unsafe_code)]
+        Some(unsafe { Self::new_unchecked(slice) })
+    }
+
+    /// # Safety
+    ///
+    /// * `slice.len()` must not be zero.
+    /// * `slice.len()` must be less than 65536.
+    #[allow(unsafe_code)]
+    #[inline(always)]
+    pub unsafe fn new_unchecked(slice: &'a [i8]) -> Self {
+        Self(slice)
+    }
+
+    #[inline(always)]
+    pub fn slice(&self) -> &'a [i8] {
+        self.0
+    }
+}
+
+impl VectorBorrowed for Veci8Borrowed<'_> {
+    type Owned = Veci8Owned;
+
+    #[inline(always)]
+    fn dim(&self) -> u32 {
+        self.0.len() as u32
+    }
+
+    #[inline(always)]
+    fn own(&self) -> Veci8Owned {
+        Veci8Owned(self.0.to_vec())
+    }
+
+    #[inline(always)]
+    fn norm(&self) -> f32 {
+        (simd::byte::reduce_sum_of_xy_i8(self.0, self.0) as f32).sqrt()
+    }
+
+    #[inline(always)]
+    fn operator_dot(self, rhs: Self) -> Distance {
+        let xy = simd::byte::reduce_sum_of_xy_i8(self.slice(), rhs.slice());
+        Distance::from(-(xy as f32))
+    }
+
+    #[inline(always)]
+    fn operator_l2s(self, rhs: Self) -> Distance {
+        let d2 = simd::byte::reduce_sum_of_d2_i8(self.slice(), rhs.slice());
+        Distance::from(d2 as f32)
+    }
+
+    #[inline(always)]
+    fn operator_cos(self, rhs: Self) -> Distance {
+        let xy = simd::byte::reduce_sum_of_xy_i8(self.slice(), rhs.slice()) as f32;
+        let x2 = simd::byte::reduce_sum_of_xy_i8(self.0, self.0) as f32;
+        let y2 = simd::byte::reduce_sum_of_xy_i8(rhs.0, rhs.0) as f32;
+        Distance::from(1.0 - xy / (x2 * y2).sqrt())
+    }
+
+    #[inline(always)]
+    fn operator_hamming(self, _: Self) -> Distance {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn operator_jaccard(self, _: Self) -> Distance {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn function_normalize(&self) -> Veci8Owned {
+        unimplemented!()
+    }
+
+    fn operator_add(&self, _: Self) -> Self::Owned {
+        unimplemented!()
+    }
+
+    fn operator_sub(&self, _: Self) -> Self::Owned {
+        unimplemented!()
+    }
+
+    fn operator_mul(&self, _: Self) -> Self::Owned {
+        unimplemented!()
+    }
+
+    fn operator_and(&self, _: Self) -> Self::Owned {
+        unimplemented!()
+    }
+
+    fn operator_or(&self, _: Self) -> Self::Owned {
+        unimplemented!()
+    }
+
+    fn operator_xor(&self, _: Self) -> Self::Owned {
+        unimplemented!()
+    }
+}
+
+impl PartialEq for Veci8Borrowed<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl PartialOrd for Veci8Borrowed<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.0.len() != other.0.len() {
+            return None;
+        }
+        Some(Ord::cmp(self.0, other.0))
+    }
+}
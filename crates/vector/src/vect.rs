@@ -104,6 +104,18 @@ impl<'a, S: Floating> VectBorrowed<'a, S> {
     pub fn slice(&self) -> &'a [S] {
         self.0
     }
+
+    /// Computes the `@#` distance: for each vector of `rhs`, the best inner product
+    /// against `lhs`, summed and negated.
+    #[inline(always)]
+    pub fn operator_maxsim(lhs: &[Self], rhs: &[Self]) -> Distance {
+        if rhs.is_empty() {
+            return Distance::ZERO;
+        }
+        let lhs = lhs.iter().map(|x| x.0).collect::<Vec<_>>();
+        let rhs = rhs.iter().map(|x| x.0).collect::<Vec<_>>();
+        Distance::from(-S::reduce_sum_of_max_xy(&rhs, &lhs))
+    }
 }
 
 impl<S: Floating> VectorBorrowed for VectBorrowed<'_, S> {
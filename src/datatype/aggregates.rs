@@ -0,0 +1,83 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+// The state of `vchord_sum`, `vchord_avg` and `vchord_weighted_avg` is a `double precision[]`
+// holding the total weight followed by the weighted sum of vectors, so that the same
+// combine function serves all of them. An empty state is `{0}`.
+
+use pgrx::datum::Array;
+
+pub fn accumulate(state: Array<'_, f64>, vector: &[f32], weight: f32) -> Vec<f64> {
+    let mut state = state.iter_deny_null().collect::<Vec<_>>();
+    if state.len() == 1 {
+        state.resize(1 + vector.len(), 0.0);
+    }
+    if state.len() != 1 + vector.len() {
+        pgrx::error!("dimension is not matched");
+    }
+    state[0] += weight as f64;
+    for (s, &x) in state[1..].iter_mut().zip(vector) {
+        *s += weight as f64 * x as f64;
+    }
+    state
+}
+
+pub fn sum(state: Array<'_, f64>) -> Option<Vec<f32>> {
+    let state = state.iter_deny_null().collect::<Vec<_>>();
+    if state.len() == 1 {
+        return None;
+    }
+    Some(finish(&state[1..], 1.0))
+}
+
+pub fn average(state: Array<'_, f64>) -> Option<Vec<f32>> {
+    let state = state.iter_deny_null().collect::<Vec<_>>();
+    if state.len() == 1 || state[0] == 0.0 {
+        return None;
+    }
+    Some(finish(&state[1..], state[0]))
+}
+
+fn finish(sum: &[f64], weight: f64) -> Vec<f32> {
+    let mut result = Vec::with_capacity(sum.len());
+    for &x in sum {
+        let x = (x / weight) as f32;
+        if !x.is_finite() {
+            pgrx::error!("value out of range: overflow");
+        }
+        result.push(x);
+    }
+    result
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_accum_combine(lhs: Array<'_, f64>, rhs: Array<'_, f64>) -> Vec<f64> {
+    let mut lhs = lhs.iter_deny_null().collect::<Vec<_>>();
+    let mut rhs = rhs.iter_deny_null().collect::<Vec<_>>();
+    if lhs.len() == 1 {
+        rhs[0] += lhs[0];
+        return rhs;
+    }
+    if rhs.len() == 1 {
+        lhs[0] += rhs[0];
+        return lhs;
+    }
+    if lhs.len() != rhs.len() {
+        pgrx::error!("dimension is not matched");
+    }
+    for (l, r) in lhs.iter_mut().zip(rhs) {
+        *l += r;
+    }
+    lhs
+}
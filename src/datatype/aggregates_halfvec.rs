@@ -0,0 +1,59 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::datatype::aggregates;
+use crate::datatype::memory_halfvec::{HalfvecInput, HalfvecOutput};
+use pgrx::datum::Array;
+use simd::{Floating, f16};
+use vector::vect::VectBorrowed;
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_halfvec_accum(state: Array<'_, f64>, vector: HalfvecInput<'_>) -> Vec<f64> {
+    aggregates::accumulate(
+        state,
+        &f16::vector_to_f32(vector.as_borrowed().slice()),
+        1.0,
+    )
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_halfvec_weighted_accum(
+    state: Array<'_, f64>,
+    vector: HalfvecInput<'_>,
+    weight: f32,
+) -> Vec<f64> {
+    aggregates::accumulate(
+        state,
+        &f16::vector_to_f32(vector.as_borrowed().slice()),
+        weight,
+    )
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_halfvec_sum(state: Array<'_, f64>) -> Option<HalfvecOutput> {
+    let result = f16::vector_from_f32(&aggregates::sum(state)?);
+    if result.iter().any(|x| !x.is_finite()) {
+        pgrx::error!("value out of range: overflow");
+    }
+    Some(HalfvecOutput::new(VectBorrowed::new(&result)))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_halfvec_avg(state: Array<'_, f64>) -> Option<HalfvecOutput> {
+    let result = f16::vector_from_f32(&aggregates::average(state)?);
+    if result.iter().any(|x| !x.is_finite()) {
+        pgrx::error!("value out of range: overflow");
+    }
+    Some(HalfvecOutput::new(VectBorrowed::new(&result)))
+}
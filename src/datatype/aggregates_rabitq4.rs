@@ -0,0 +1,49 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::datatype::aggregates;
+use crate::datatype::memory_rabitq4::{Rabitq4Input, Rabitq4Output};
+use pgrx::datum::Array;
+use vector::VectorOwned;
+use vector::rabitq4::Rabitq4Owned;
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_rabitq4_accum(state: Array<'_, f64>, vector: Rabitq4Input<'_>) -> Vec<f64> {
+    aggregates::accumulate(state, &vector.as_borrowed().to_rotated(), 1.0)
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_rabitq4_weighted_accum(
+    state: Array<'_, f64>,
+    vector: Rabitq4Input<'_>,
+    weight: f32,
+) -> Vec<f64> {
+    aggregates::accumulate(state, &vector.as_borrowed().to_rotated(), weight)
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_rabitq4_sum(state: Array<'_, f64>) -> Option<Rabitq4Output> {
+    let result = aggregates::sum(state)?;
+    Some(Rabitq4Output::new(
+        Rabitq4Owned::from_rotated(&result).as_borrowed(),
+    ))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_rabitq4_avg(state: Array<'_, f64>) -> Option<Rabitq4Output> {
+    let result = aggregates::average(state)?;
+    Some(Rabitq4Output::new(
+        Rabitq4Owned::from_rotated(&result).as_borrowed(),
+    ))
+}
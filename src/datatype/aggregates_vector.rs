@@ -0,0 +1,44 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::datatype::aggregates;
+use crate::datatype::memory_vector::{VectorInput, VectorOutput};
+use pgrx::datum::Array;
+use vector::vect::VectBorrowed;
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_vector_accum(state: Array<'_, f64>, vector: VectorInput<'_>) -> Vec<f64> {
+    aggregates::accumulate(state, vector.as_borrowed().slice(), 1.0)
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_vector_weighted_accum(
+    state: Array<'_, f64>,
+    vector: VectorInput<'_>,
+    weight: f32,
+) -> Vec<f64> {
+    aggregates::accumulate(state, vector.as_borrowed().slice(), weight)
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_vector_sum(state: Array<'_, f64>) -> Option<VectorOutput> {
+    let result = aggregates::sum(state)?;
+    Some(VectorOutput::new(VectBorrowed::new(&result)))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_vector_avg(state: Array<'_, f64>) -> Option<VectorOutput> {
+    let result = aggregates::average(state)?;
+    Some(VectorOutput::new(VectBorrowed::new(&result)))
+}
@@ -0,0 +1,61 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::datatype::memory_int8vec::{Int8vecInput, Int8vecOutput};
+use pgrx::datum::Internal;
+use pgrx::pg_sys::Oid;
+use vector::VectorBorrowed;
+use vector::veci8::Veci8Borrowed;
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_int8vec_send(vector: Int8vecInput<'_>) -> Vec<u8> {
+    let vector = vector.as_borrowed();
+    let mut stream = Vec::<u8>::new();
+    stream.extend(vector.dim().to_be_bytes());
+    for &c in vector.slice() {
+        stream.extend(c.to_be_bytes());
+    }
+    stream
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_int8vec_recv(mut internal: Internal, oid: Oid, typmod: i32) -> Int8vecOutput {
+    let _ = (oid, typmod);
+    let buf = unsafe { internal.get_mut::<pgrx::pg_sys::StringInfoData>().unwrap() };
+
+    let dim = {
+        assert!(buf.cursor < i32::MAX - 4 && buf.cursor + 4 <= buf.len);
+        let raw = unsafe { buf.data.add(buf.cursor as _).cast::<[u8; 4]>().read() };
+        buf.cursor += 4;
+        u32::from_be_bytes(raw)
+    };
+    let slice = {
+        let mut result = Vec::new();
+        for _ in 0..dim {
+            result.push({
+                assert!(buf.cursor < i32::MAX - 1 && buf.cursor + 1 <= buf.len);
+                let raw = unsafe { buf.data.add(buf.cursor as _).cast::<[u8; 1]>().read() };
+                buf.cursor += 1;
+                i8::from_be_bytes(raw)
+            });
+        }
+        result
+    };
+
+    if let Some(x) = Veci8Borrowed::new_checked(&slice) {
+        Int8vecOutput::new(x)
+    } else {
+        pgrx::error!("detect data corruption");
+    }
+}
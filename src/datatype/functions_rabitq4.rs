@@ -16,9 +16,9 @@ use crate::datatype::memory_halfvec::{HalfvecInput, HalfvecOutput};
 use crate::datatype::memory_rabitq4::{Rabitq4Input, Rabitq4Output};
 use crate::datatype::memory_vector::{VectorInput, VectorOutput};
 use simd::{Floating, f16};
-use vector::VectorBorrowed;
 use vector::rabitq4::Rabitq4Borrowed;
 use vector::vect::VectBorrowed;
+use vector::{VectorBorrowed, VectorOwned};
 
 #[pgrx::pg_extern(sql = "")]
 fn _vchord_vector_quantize_to_rabitq4(vector: VectorInput) -> Rabitq4Output {
@@ -82,3 +82,12 @@ fn _vchord_rabitq4_dequantize_to_halfvec(vector: Rabitq4Input) -> HalfvecOutput
     let result = f16::vector_from_f32(&result);
     HalfvecOutput::new(VectBorrowed::new(&result))
 }
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_rabitq4_normalize(vector: Rabitq4Input) -> Rabitq4Output {
+    let vector = vector.as_borrowed();
+    if vector.sum_of_x2() == 0.0 {
+        return Rabitq4Output::new(vector);
+    }
+    Rabitq4Output::new(vector.function_normalize().as_borrowed())
+}
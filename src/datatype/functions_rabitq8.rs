@@ -16,9 +16,9 @@ use crate::datatype::memory_halfvec::{HalfvecInput, HalfvecOutput};
 use crate::datatype::memory_rabitq8::{Rabitq8Input, Rabitq8Output};
 use crate::datatype::memory_vector::{VectorInput, VectorOutput};
 use simd::{Floating, f16};
-use vector::VectorBorrowed;
 use vector::rabitq8::Rabitq8Borrowed;
 use vector::vect::VectBorrowed;
+use vector::{VectorBorrowed, VectorOwned};
 
 #[pgrx::pg_extern(sql = "")]
 fn _vchord_vector_quantize_to_rabitq8(vector: VectorInput) -> Rabitq8Output {
@@ -82,3 +82,12 @@ fn _vchord_rabitq8_dequantize_to_halfvec(vector: Rabitq8Input) -> HalfvecOutput
     let result = f16::vector_from_f32(&result);
     HalfvecOutput::new(VectBorrowed::new(&result))
 }
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_rabitq8_normalize(vector: Rabitq8Input) -> Rabitq8Output {
+    let vector = vector.as_borrowed();
+    if vector.sum_of_x2() == 0.0 {
+        return Rabitq8Output::new(vector);
+    }
+    Rabitq8Output::new(vector.function_normalize().as_borrowed())
+}
@@ -12,20 +12,47 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+mod aggregates;
+mod aggregates_halfvec;
+mod aggregates_rabitq4;
+mod aggregates_rabitq8;
+mod aggregates_vector;
+mod binary_bf16vec;
+mod binary_int8vec;
+mod binary_rabitq1;
+mod binary_rabitq2;
 mod binary_rabitq4;
 mod binary_rabitq8;
+mod functions_rabitq1;
+mod functions_rabitq2;
 mod functions_rabitq4;
 mod functions_rabitq8;
+pub mod memory_bf16vec;
 pub mod memory_halfvec;
+pub mod memory_int8vec;
+pub mod memory_rabitq1;
+pub mod memory_rabitq2;
 pub mod memory_rabitq4;
 pub mod memory_rabitq8;
 pub mod memory_vector;
+mod operators_bf16vec;
 mod operators_halfvec;
+mod operators_int8vec;
+mod operators_rabitq1;
+mod operators_rabitq2;
 mod operators_rabitq4;
 mod operators_rabitq8;
 mod operators_vector;
+mod text_bf16vec;
+mod text_int8vec;
+mod text_rabitq1;
+mod text_rabitq2;
 mod text_rabitq4;
 mod text_rabitq8;
 pub mod typmod;
+mod typmod_bf16vec;
+mod typmod_int8vec;
+mod typmod_rabitq1;
+mod typmod_rabitq2;
 mod typmod_rabitq4;
 mod typmod_rabitq8;
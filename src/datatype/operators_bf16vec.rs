@@ -0,0 +1,134 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::datatype::memory_bf16vec::{Bf16vecInput, Bf16vecOutput};
+use pgrx::datum::Array;
+use simd::bf16;
+use std::num::NonZero;
+use vector::VectorBorrowed;
+use vector::vect::VectBorrowed;
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_bf16vec_operator_l2(lhs: Bf16vecInput<'_>, rhs: Bf16vecInput<'_>) -> f32 {
+    let lhs = lhs.as_borrowed();
+    let rhs = rhs.as_borrowed();
+    if lhs.dim() != rhs.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    VectBorrowed::operator_l2s(lhs, rhs).to_f32().sqrt()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_bf16vec_operator_ip(lhs: Bf16vecInput<'_>, rhs: Bf16vecInput<'_>) -> f32 {
+    let lhs = lhs.as_borrowed();
+    let rhs = rhs.as_borrowed();
+    if lhs.dim() != rhs.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    VectBorrowed::operator_dot(lhs, rhs).to_f32()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_bf16vec_operator_cosine(lhs: Bf16vecInput<'_>, rhs: Bf16vecInput<'_>) -> f32 {
+    let lhs = lhs.as_borrowed();
+    let rhs = rhs.as_borrowed();
+    if lhs.dim() != rhs.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    VectBorrowed::operator_cos(lhs, rhs).to_f32()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_bf16vec_sphere_l2_in(
+    lhs: Bf16vecInput<'_>,
+    rhs: pgrx::composite_type!("sphere_bf16vec"),
+) -> bool {
+    let center: Bf16vecOutput = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let lhs = lhs.as_borrowed();
+    let center = center.as_borrowed();
+    if lhs.dim() != center.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    let d = VectBorrowed::operator_l2s(lhs, center).to_f32().sqrt();
+    d < radius
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_bf16vec_sphere_ip_in(
+    lhs: Bf16vecInput<'_>,
+    rhs: pgrx::composite_type!("sphere_bf16vec"),
+) -> bool {
+    let center: Bf16vecOutput = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let lhs = lhs.as_borrowed();
+    let center = center.as_borrowed();
+    if lhs.dim() != center.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    let d = VectBorrowed::operator_dot(lhs, center).to_f32();
+    d < radius
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_bf16vec_sphere_cosine_in(
+    lhs: Bf16vecInput<'_>,
+    rhs: pgrx::composite_type!("sphere_bf16vec"),
+) -> bool {
+    let center: Bf16vecOutput = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let lhs = lhs.as_borrowed();
+    let center = center.as_borrowed();
+    if lhs.dim() != center.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    let d = VectBorrowed::operator_cos(lhs, center).to_f32();
+    d < radius
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_bf16vec_operator_maxsim(
+    lhs: Array<'_, Bf16vecInput<'_>>,
+    rhs: Array<'_, Bf16vecInput<'_>>,
+) -> f32 {
+    let lhs = lhs.iter().flatten().collect::<Vec<_>>();
+    let rhs = rhs.iter().flatten().collect::<Vec<_>>();
+    let lhs = lhs.iter().map(|x| x.as_borrowed()).collect::<Vec<_>>();
+    let rhs = rhs.iter().map(|x| x.as_borrowed()).collect::<Vec<_>>();
+    VectBorrowed::operator_maxsim(&lhs, &rhs).to_f32()
+}
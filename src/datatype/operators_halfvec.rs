@@ -95,15 +95,9 @@ fn _vchord_halfvec_operator_maxsim(
     lhs: Array<'_, HalfvecInput<'_>>,
     rhs: Array<'_, HalfvecInput<'_>>,
 ) -> f32 {
-    let mut maxsim = 0.0f32;
-    for rhs in rhs.iter().flatten() {
-        let mut d = f32::INFINITY;
-        for lhs in lhs.iter().flatten() {
-            let lhs = lhs.as_borrowed();
-            let rhs = rhs.as_borrowed();
-            d = d.min(VectBorrowed::operator_dot(lhs, rhs).to_f32());
-        }
-        maxsim += d;
-    }
-    maxsim
+    let lhs = lhs.iter().flatten().collect::<Vec<_>>();
+    let rhs = rhs.iter().flatten().collect::<Vec<_>>();
+    let lhs = lhs.iter().map(|x| x.as_borrowed()).collect::<Vec<_>>();
+    let rhs = rhs.iter().map(|x| x.as_borrowed()).collect::<Vec<_>>();
+    VectBorrowed::operator_maxsim(&lhs, &rhs).to_f32()
 }
@@ -0,0 +1,139 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::datatype::memory_int8vec::{Int8vecInput, Int8vecOutput};
+use pgrx::datum::Array;
+use std::num::NonZero;
+use vector::VectorBorrowed;
+use vector::veci8::Veci8Borrowed;
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_int8vec_operator_l2(lhs: Int8vecInput<'_>, rhs: Int8vecInput<'_>) -> f32 {
+    let lhs = lhs.as_borrowed();
+    let rhs = rhs.as_borrowed();
+    if lhs.dim() != rhs.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    Veci8Borrowed::operator_l2s(lhs, rhs).to_f32().sqrt()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_int8vec_operator_ip(lhs: Int8vecInput<'_>, rhs: Int8vecInput<'_>) -> f32 {
+    let lhs = lhs.as_borrowed();
+    let rhs = rhs.as_borrowed();
+    if lhs.dim() != rhs.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    Veci8Borrowed::operator_dot(lhs, rhs).to_f32()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_int8vec_operator_cosine(lhs: Int8vecInput<'_>, rhs: Int8vecInput<'_>) -> f32 {
+    let lhs = lhs.as_borrowed();
+    let rhs = rhs.as_borrowed();
+    if lhs.dim() != rhs.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    Veci8Borrowed::operator_cos(lhs, rhs).to_f32()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_int8vec_sphere_l2_in(
+    lhs: Int8vecInput<'_>,
+    rhs: pgrx::composite_type!("sphere_int8vec"),
+) -> bool {
+    let center: Int8vecOutput = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let lhs = lhs.as_borrowed();
+    let center = center.as_borrowed();
+    if lhs.dim() != center.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    let d = Veci8Borrowed::operator_l2s(lhs, center).to_f32().sqrt();
+    d < radius
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_int8vec_sphere_ip_in(
+    lhs: Int8vecInput<'_>,
+    rhs: pgrx::composite_type!("sphere_int8vec"),
+) -> bool {
+    let center: Int8vecOutput = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let lhs = lhs.as_borrowed();
+    let center = center.as_borrowed();
+    if lhs.dim() != center.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    let d = Veci8Borrowed::operator_dot(lhs, center).to_f32();
+    d < radius
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_int8vec_sphere_cosine_in(
+    lhs: Int8vecInput<'_>,
+    rhs: pgrx::composite_type!("sphere_int8vec"),
+) -> bool {
+    let center: Int8vecOutput = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let lhs = lhs.as_borrowed();
+    let center = center.as_borrowed();
+    if lhs.dim() != center.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    let d = Veci8Borrowed::operator_cos(lhs, center).to_f32();
+    d < radius
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_int8vec_operator_maxsim(
+    lhs: Array<'_, Int8vecInput<'_>>,
+    rhs: Array<'_, Int8vecInput<'_>>,
+) -> f32 {
+    let mut maxsim = 0.0f32;
+    for rhs in rhs.iter().flatten() {
+        let mut d = f32::INFINITY;
+        for lhs in lhs.iter().flatten() {
+            let lhs = lhs.as_borrowed();
+            let rhs = rhs.as_borrowed();
+            d = d.min(Veci8Borrowed::operator_dot(lhs, rhs).to_f32());
+        }
+        maxsim += d;
+    }
+    maxsim
+}
@@ -15,8 +15,8 @@
 use crate::datatype::memory_rabitq4::{Rabitq4Input, Rabitq4Output};
 use pgrx::datum::Array;
 use std::num::NonZero;
-use vector::VectorBorrowed;
 use vector::rabitq4::Rabitq4Borrowed;
+use vector::{VectorBorrowed, VectorOwned};
 
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vchord_rabitq4_operator_l2(lhs: Rabitq4Input<'_>, rhs: Rabitq4Input<'_>) -> f32 {
@@ -48,6 +48,26 @@ fn _vchord_rabitq4_operator_cosine(lhs: Rabitq4Input<'_>, rhs: Rabitq4Input<'_>)
     Rabitq4Borrowed::operator_cos(lhs, rhs).to_f32()
 }
 
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_rabitq4_operator_add(lhs: Rabitq4Input<'_>, rhs: Rabitq4Input<'_>) -> Rabitq4Output {
+    let lhs = lhs.as_borrowed();
+    let rhs = rhs.as_borrowed();
+    if lhs.dim() != rhs.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    Rabitq4Output::new(Rabitq4Borrowed::operator_add(&lhs, rhs).as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_rabitq4_operator_sub(lhs: Rabitq4Input<'_>, rhs: Rabitq4Input<'_>) -> Rabitq4Output {
+    let lhs = lhs.as_borrowed();
+    let rhs = rhs.as_borrowed();
+    if lhs.dim() != rhs.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    Rabitq4Output::new(Rabitq4Borrowed::operator_sub(&lhs, rhs).as_borrowed())
+}
+
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vchord_rabitq4_sphere_l2_in(
     lhs: Rabitq4Input<'_>,
@@ -15,8 +15,8 @@
 use crate::datatype::memory_rabitq8::{Rabitq8Input, Rabitq8Output};
 use pgrx::datum::Array;
 use std::num::NonZero;
-use vector::VectorBorrowed;
 use vector::rabitq8::Rabitq8Borrowed;
+use vector::{VectorBorrowed, VectorOwned};
 
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vchord_rabitq8_operator_l2(lhs: Rabitq8Input<'_>, rhs: Rabitq8Input<'_>) -> f32 {
@@ -48,6 +48,26 @@ fn _vchord_rabitq8_operator_cosine(lhs: Rabitq8Input<'_>, rhs: Rabitq8Input<'_>)
     Rabitq8Borrowed::operator_cos(lhs, rhs).to_f32()
 }
 
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_rabitq8_operator_add(lhs: Rabitq8Input<'_>, rhs: Rabitq8Input<'_>) -> Rabitq8Output {
+    let lhs = lhs.as_borrowed();
+    let rhs = rhs.as_borrowed();
+    if lhs.dim() != rhs.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    Rabitq8Output::new(Rabitq8Borrowed::operator_add(&lhs, rhs).as_borrowed())
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_rabitq8_operator_sub(lhs: Rabitq8Input<'_>, rhs: Rabitq8Input<'_>) -> Rabitq8Output {
+    let lhs = lhs.as_borrowed();
+    let rhs = rhs.as_borrowed();
+    if lhs.dim() != rhs.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    Rabitq8Output::new(Rabitq8Borrowed::operator_sub(&lhs, rhs).as_borrowed())
+}
+
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vchord_rabitq8_sphere_l2_in(
     lhs: Rabitq8Input<'_>,
@@ -0,0 +1,91 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::datatype::memory_int8vec::{Int8vecInput, Int8vecOutput};
+use pgrx::pg_sys::Oid;
+use std::ffi::{CStr, CString};
+use vector::veci8::Veci8Borrowed;
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_int8vec_in(input: &CStr, oid: Oid, typmod: i32) -> Int8vecOutput {
+    let _ = (oid, typmod);
+    let mut input = input.to_bytes().iter();
+    let mut p = Vec::<i8>::new();
+    {
+        loop {
+            let Some(c) = input.next().copied() else {
+                pgrx::error!("incorrect vector")
+            };
+            match c {
+                b' ' => (),
+                b'[' => break,
+                _ => pgrx::error!("incorrect vector"),
+            }
+        }
+    }
+    {
+        let mut s = Option::<String>::None;
+        loop {
+            let Some(c) = input.next().copied() else {
+                pgrx::error!("incorrect vector")
+            };
+            s = match (s, c) {
+                (s, b' ') => s,
+                (None, c @ (b'0'..=b'9' | b'+' | b'-')) => Some(String::from(c as char)),
+                (Some(s), c @ (b'0'..=b'9' | b'+' | b'-')) => {
+                    let mut x = s;
+                    x.push(c as char);
+                    Some(x)
+                }
+                (Some(s), b',') => {
+                    p.push(s.parse().expect("failed to parse number"));
+                    None
+                }
+                (None, b',') => {
+                    pgrx::error!("incorrect vector")
+                }
+                (Some(s), b']') => {
+                    p.push(s.parse().expect("failed to parse number"));
+                    break;
+                }
+                (None, b']') => break,
+                _ => pgrx::error!("incorrect vector"),
+            };
+        }
+    }
+    if p.is_empty() {
+        pgrx::error!("vector must have at least 1 dimension");
+    }
+    if let Some(x) = Veci8Borrowed::new_checked(&p) {
+        Int8vecOutput::new(x)
+    } else {
+        pgrx::error!("incorrect vector");
+    }
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_int8vec_out(vector: Int8vecInput<'_>) -> CString {
+    let vector = vector.as_borrowed();
+    let mut buffer = String::new();
+    buffer.push('[');
+    let mut iter = vector.slice().iter();
+    if let Some(x) = iter.next() {
+        buffer.push_str(format!("{x}").as_str());
+    }
+    for x in iter {
+        buffer.push_str(format!(",{x}").as_str());
+    }
+    buffer.push(']');
+    CString::new(buffer).unwrap()
+}
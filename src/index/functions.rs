@@ -13,14 +13,334 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 use crate::index::storage::PostgresRelation;
-use crate::recorder::dump;
-use pgrx::iter::SetOfIterator;
+use crate::recorder::{Value, binary, dump};
+use pgrx::datum::{AnyArray, AnyElement};
+use pgrx::iter::{SetOfIterator, TableIterator};
 use pgrx::pg_sys::Oid;
 use pgrx_catalog::{PgAm, PgClass, PgClassRelkind};
 
 #[pgrx::pg_extern(sql = "")]
 fn _vchordg_prewarm(indexrelid: Oid) -> String {
-    let pg_am = PgAm::search_amname(c"vchordg").unwrap();
+    check_index(indexrelid, c"vchordg");
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let opfamily = unsafe { crate::index::vchordg::opclass::opfamily(relation.raw()) };
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    crate::index::vchordg::dispatch::prewarm(opfamily, &index)
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_prewarm(indexrelid: Oid, height: i32) -> String {
+    check_index(indexrelid, c"vchordrq");
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let opfamily = unsafe { crate::index::vchordrq::opclass::opfamily(relation.raw()) };
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    crate::index::vchordrq::dispatch::prewarm(opfamily, &index, height)
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_lists(indexrelid: Oid) -> Vec<i32> {
+    check_index(indexrelid, c"vchordrq");
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let index = unsafe { PostgresRelation::<vchordrq::Opaque>::new(relation.raw()) };
+    let cost = vchordrq::cost(&index);
+    // `cells` is ordered from leaves to the root, while `probes` is ordered from the root
+    cost.cells.iter().rev().skip(1).map(|&x| x as i32).collect()
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_assign(indexrelid: Oid, vector: AnyElement, depth: i32) -> Vec<i32> {
+    let relname = check_index(indexrelid, c"vchordrq");
+    if depth < 0 {
+        pgrx::error!("depth must be non-negative");
+    }
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    // For a multivector index, a vector of the element type is assigned.
+    let typid = unsafe {
+        let typid = pgrx::pg_sys::get_atttype(indexrelid, 1);
+        let element = pgrx::pg_sys::get_element_type(typid);
+        if element != pgrx::pg_sys::InvalidOid {
+            element
+        } else {
+            typid
+        }
+    };
+    if vector.oid() != typid {
+        pgrx::error!("the type of the vector does not match the index {relname}");
+    }
+    let opfamily = unsafe { crate::index::vchordrq::opclass::opfamily(relation.raw()) };
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    let vector = unsafe { opfamily.input_vector(vector.datum()) }.expect("the vector is NULL");
+    crate::index::vchordrq::dispatch::assign(opfamily, &index, vector, depth as u32)
+        .into_iter()
+        .map(|x| x as i32)
+        .collect()
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_list_members(
+    indexrelid: Oid,
+    list: i32,
+) -> SetOfIterator<'static, pgrx::pg_sys::ItemPointerData> {
+    use crate::index::fetcher::{key_to_ctid, pointer_to_kv};
+    check_index(indexrelid, c"vchordrq");
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    check_select(heaprelid);
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let index = unsafe { PostgresRelation::<vchordrq::Opaque>::new(relation.raw()) };
+    let members = u32::try_from(list)
+        .ok()
+        .and_then(|list| vchordrq::list_members(&index, list));
+    let Some(members) = members else {
+        pgrx::error!("the list {list} does not exist");
+    };
+    // A heap tuple of a multivector index is stored once for each vector.
+    let mut keys = members
+        .into_iter()
+        .map(|payload| pointer_to_kv(payload).0)
+        .collect::<Vec<_>>();
+    keys.sort_unstable();
+    keys.dedup();
+    SetOfIterator::new(keys.into_iter().map(key_to_ctid))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_appendable(
+    indexrelid: Oid,
+) -> TableIterator<'static, (pgrx::name!(list, i32), pgrx::name!(tuples, i64))> {
+    check_index(indexrelid, c"vchordrq");
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let index = unsafe { PostgresRelation::<vchordrq::Opaque>::new(relation.raw()) };
+    let check = || {
+        pgrx::check_for_interrupts!();
+    };
+    let appendable = vchordrq::appendable(&index, check);
+    TableIterator::new(
+        appendable
+            .into_iter()
+            .enumerate()
+            .map(|(list, tuples)| (list as i32, tuples as i64)),
+    )
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_shared_cache_stats()
+-> TableIterator<'static, (pgrx::name!(hits, i64), pgrx::name!(misses, i64))> {
+    let (hits, misses) = crate::index::vchordrq::cache::stats();
+    TableIterator::once((hits as i64, misses as i64))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_search_batch(
+    indexrelid: Oid,
+    queries: AnyArray,
+    k: i32,
+) -> TableIterator<
+    'static,
+    (
+        pgrx::name!(query_no, i32),
+        pgrx::name!(tid, pgrx::pg_sys::ItemPointerData),
+        pgrx::name!(distance, f32),
+    ),
+> {
+    use crate::index::fetcher::{key_to_ctid, pointer_to_kv};
+    use crate::index::vchordrq::scanners::Limit;
+    let relname = check_index(indexrelid, c"vchordrq");
+    if k < 0 {
+        pgrx::error!("k must be non-negative");
+    }
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    check_select(heaprelid);
+    let table = Table::open(heaprelid, pgrx::pg_sys::AccessShareLock as _);
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let opfamily = unsafe { crate::index::vchordrq::opclass::opfamily(relation.raw()) };
+    if opfamily.is_maxsim() {
+        pgrx::error!("batch search on multivector indexes is not supported");
+    }
+    let typid = unsafe { pgrx::pg_sys::get_atttype(indexrelid, 1) };
+    if unsafe { pgrx::pg_sys::get_element_type(queries.oid()) } != typid {
+        pgrx::error!("the type of queries does not match the index {relname}");
+    }
+    let vectors = unsafe { opfamily.input_vectors(queries.datum()) }.unwrap_or_default();
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    let probes = unsafe { crate::index::gucs::vchordrq_probes(relation.raw()) };
+    let epsilon = unsafe { crate::index::gucs::vchordrq_epsilon(relation.raw()) };
+    let results = unsafe {
+        with_heap_fetcher(relation.raw(), table.raw(), |fetcher| {
+            crate::index::vchordrq::scanners::search_batch(
+                opfamily,
+                &index,
+                vectors,
+                Limit::K(k as u32),
+                probes,
+                epsilon,
+                fetcher,
+            )
+        })
+    };
+    let mut rows = Vec::new();
+    for (i, result) in results.into_iter().enumerate() {
+        for (distance, payload) in result {
+            let (key, _) = pointer_to_kv(payload);
+            rows.push((i as i32 + 1, key_to_ctid(key), distance));
+        }
+    }
+    TableIterator::new(rows)
+}
+
+// Outer rows are streamed, but all result rows are held until the function
+// returns, since the SPI connection cannot outlive a call. So memory grows
+// with the number of outer rows times k.
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_knn_join(
+    outer_query: &str,
+    indexrelid: Oid,
+    k: i32,
+) -> TableIterator<
+    'static,
+    (
+        pgrx::name!(outer_key, String),
+        pgrx::name!(inner_tid, pgrx::pg_sys::ItemPointerData),
+        pgrx::name!(distance, f32),
+    ),
+> {
+    use crate::index::fetcher::{key_to_ctid, pointer_to_kv};
+    use crate::index::vchordrq::scanners::Limit;
+    use std::ffi::CStr;
+    // Outer rows are fetched from the cursor and searched this many at a time.
+    const BATCH_SIZE: i64 = 4096;
+    check_index(indexrelid, c"vchordrq");
+    if k < 0 {
+        pgrx::error!("k must be non-negative");
+    }
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    check_select(heaprelid);
+    let table = Table::open(heaprelid, pgrx::pg_sys::AccessShareLock as _);
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let opfamily = unsafe { crate::index::vchordrq::opclass::opfamily(relation.raw()) };
+    if opfamily.is_maxsim() {
+        pgrx::error!("k-nearest neighbor join on multivector indexes is not supported");
+    }
+    let typname = unsafe {
+        let typid = pgrx::pg_sys::get_atttype(indexrelid, 1);
+        let typname = pgrx::pg_sys::format_type_be(typid);
+        let result = CStr::from_ptr(typname).to_string_lossy().into_owned();
+        pgrx::pg_sys::pfree(typname.cast());
+        result
+    };
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    let probes = unsafe { crate::index::gucs::vchordrq_probes(relation.raw()) };
+    let epsilon = unsafe { crate::index::gucs::vchordrq_epsilon(relation.raw()) };
+    let query = format!("SELECT k::TEXT, v::{typname} FROM ({outer_query}) AS o(k, v)");
+    let mut rows = Vec::new();
+    pgrx::spi::Spi::connect(|client| {
+        use pgrx::pg_sys::panic::ErrorReportable;
+        let mut cursor = client.open_cursor(&query, &[]);
+        // Outer rows are streamed in batches. Every batch is sorted by the
+        // closest list and searched at once, so that outer vectors probing the
+        // same lists are searched together and each list is read once.
+        loop {
+            pgrx::check_for_interrupts!();
+            let tuptable = cursor.fetch(BATCH_SIZE).unwrap_or_report();
+            if tuptable.is_empty() {
+                break;
+            }
+            let mut batch = Vec::with_capacity(tuptable.len());
+            for row in tuptable {
+                let Some(key) = row.get::<String>(1).unwrap_or_report() else {
+                    continue;
+                };
+                let Some(vector) = row.get::<AnyElement>(2).unwrap_or_report() else {
+                    continue;
+                };
+                let Some(vector) = (unsafe { opfamily.input_vector(vector.datum()) }) else {
+                    continue;
+                };
+                let path = crate::index::vchordrq::dispatch::assign(
+                    opfamily,
+                    &index,
+                    vector.clone(),
+                    u32::MAX,
+                );
+                batch.push((path, key, vector));
+            }
+            batch.sort_unstable_by(|(a, ..), (b, ..)| a.cmp(b));
+            let (keys, vectors): (Vec<_>, Vec<_>) = batch
+                .into_iter()
+                .map(|(_, key, vector)| (key, vector))
+                .unzip();
+            let results = unsafe {
+                with_heap_fetcher(relation.raw(), table.raw(), |fetcher| {
+                    crate::index::vchordrq::scanners::search_batch(
+                        opfamily,
+                        &index,
+                        vectors,
+                        Limit::K(k as u32),
+                        probes.clone(),
+                        epsilon,
+                        fetcher,
+                    )
+                })
+            };
+            for (key, result) in keys.into_iter().zip(results) {
+                for (distance, payload) in result {
+                    let (payload, _) = pointer_to_kv(payload);
+                    rows.push((key.clone(), key_to_ctid(payload), distance));
+                }
+            }
+        }
+    });
+    TableIterator::new(rows)
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_near_duplicates(
+    indexrelid: Oid,
+    radius: f32,
+) -> TableIterator<
+    'static,
+    (
+        pgrx::name!(tid_a, pgrx::pg_sys::ItemPointerData),
+        pgrx::name!(tid_b, pgrx::pg_sys::ItemPointerData),
+        pgrx::name!(distance, f32),
+    ),
+> {
+    use crate::index::fetcher::key_to_ctid;
+    check_index(indexrelid, c"vchordrq");
+    if radius.is_nan() || radius < 0.0 {
+        pgrx::error!("radius must be non-negative");
+    }
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    check_select(heaprelid);
+    let table = Table::open(heaprelid, pgrx::pg_sys::AccessShareLock as _);
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let opfamily = unsafe { crate::index::vchordrq::opclass::opfamily(relation.raw()) };
+    if opfamily.is_maxsim() {
+        pgrx::error!("near-duplicate detection on multivector indexes is not supported");
+    }
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    let probes = unsafe { crate::index::gucs::vchordrq_probes(relation.raw()) };
+    let epsilon = unsafe { crate::index::gucs::vchordrq_epsilon(relation.raw()) };
+    let mut rows = Vec::new();
+    unsafe {
+        with_heap_fetcher(relation.raw(), table.raw(), |fetcher| {
+            crate::index::vchordrq::scanners::near_duplicates(
+                opfamily,
+                &index,
+                radius,
+                probes,
+                epsilon,
+                fetcher,
+                |a, b, distance| rows.push((key_to_ctid(a), key_to_ctid(b), distance)),
+            )
+        })
+    };
+    TableIterator::new(rows)
+}
+
+// Checks that the relation is an index of the access method, and returns its
+// name quoted for messages.
+fn check_index(indexrelid: Oid, amname: &std::ffi::CStr) -> String {
+    let pg_am = PgAm::search_amname(amname).unwrap();
     let Some(pg_am) = pg_am.get() else {
         pgrx::error!("vchord is not installed");
     };
@@ -32,20 +352,245 @@ fn _vchordg_prewarm(indexrelid: Oid) -> String {
         pgrx::error!("the relation {:?} is not an index", pg_class.relname());
     }
     if pg_class.relam() != pg_am.oid() {
-        pgrx::error!("the index {:?} is not a vchordg index", pg_class.relname());
+        pgrx::error!(
+            "the index {:?} is not a {} index",
+            pg_class.relname(),
+            amname.to_string_lossy()
+        );
+    }
+    format!("{:?}", pg_class.relname())
+}
+
+fn check_select(heaprelid: Oid) {
+    unsafe {
+        let result = pgrx::pg_sys::pg_class_aclcheck(
+            heaprelid,
+            pgrx::pg_sys::GetUserId(),
+            pgrx::pg_sys::ACL_SELECT as _,
+        );
+        if result != pgrx::pg_sys::AclResult::ACLCHECK_OK {
+            pgrx::pg_sys::aclcheck_error(
+                result,
+                pgrx::pg_sys::ObjectType::OBJECT_TABLE,
+                pgrx::pg_sys::get_rel_name(heaprelid),
+            );
+        }
+    }
+}
+
+unsafe fn with_heap_fetcher<T>(
+    index_relation: pgrx::pg_sys::Relation,
+    heap_relation: pgrx::pg_sys::Relation,
+    f: impl FnOnce(crate::index::fetcher::HeapFetcher) -> T,
+) -> T {
+    use pgrx::pg_sys::ffi::pg_guard_ffi_boundary;
+    unsafe {
+        let table_am = (*heap_relation).rd_tableam;
+        if table_am.is_null() {
+            panic!("unknown heap access method");
+        }
+        let index_fetch_begin = (*table_am)
+            .index_fetch_begin
+            .expect("unsupported heap access method");
+        let index_fetch_end = (*table_am)
+            .index_fetch_end
+            .expect("unsupported heap access method");
+        #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
+        let heapfetch = pg_guard_ffi_boundary(|| index_fetch_begin(heap_relation));
+        let fetcher = crate::index::fetcher::HeapFetcher::new(
+            index_relation,
+            heap_relation,
+            pgrx::pg_sys::GetActiveSnapshot(),
+            heapfetch,
+            std::ptr::null_mut(),
+        );
+        let result = f(fetcher);
+        #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
+        pg_guard_ffi_boundary(|| index_fetch_end(heapfetch));
+        result
     }
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_verify(
+    indexrelid: Oid,
+    heapallindexed: bool,
+) -> TableIterator<
+    'static,
+    (
+        pgrx::name!(block, Option<i64>),
+        pgrx::name!(problem, String),
+    ),
+> {
+    use crate::index::fetcher::{ctid_to_key, kv_to_pointer, pointer_to_kv};
+    use crate::index::traverse::{HeapTraverser, Traverser, Tuple};
+    use std::collections::HashMap;
+    check_index(indexrelid, c"vchordrq");
+    // Writes to the table move pages between tapes and the free list, so they are
+    // blocked while the index is verified.
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    let table = Table::open(heaprelid, pgrx::pg_sys::ShareLock as _);
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let opfamily = unsafe { crate::index::vchordrq::opclass::opfamily(relation.raw()) };
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    let nblocks = unsafe {
+        pgrx::pg_sys::RelationGetNumberOfBlocksInFork(
+            relation.raw(),
+            pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
+        )
+    };
+    let check = || {
+        pgrx::check_for_interrupts!();
+    };
+    let mut payloads = HashMap::<_, u32>::new();
+    let mut problems =
+        crate::index::vchordrq::dispatch::verify(opfamily, &index, nblocks, check, |payload| {
+            *payloads.entry(payload).or_default() += 1
+        })
+        .into_iter()
+        .map(|problem| (problem.id.map(i64::from), problem.message))
+        .collect::<Vec<_>>();
+    let describe = |[bi_hi, bi_lo, ip_posid]: [u16; 3]| {
+        format!("({},{})", (bi_hi as u32) << 16 | bi_lo as u32, ip_posid)
+    };
+    let mut duplicates = payloads
+        .iter()
+        .filter(|(_, count)| **count > 1)
+        .map(|(payload, count)| (*payload, *count))
+        .collect::<Vec<_>>();
+    duplicates.sort();
+    for (payload, count) in duplicates {
+        let (key, _) = pointer_to_kv(payload);
+        problems.push((
+            None,
+            format!("heap tuple {} is indexed {count} times", describe(key)),
+        ));
+    }
+    if heapallindexed {
+        let index_info = unsafe { pgrx::pg_sys::BuildIndexInfo(relation.raw()) };
+        // Only tuples visible to a fresh snapshot are checked.
+        unsafe {
+            (*index_info).ii_Concurrent = true;
+        }
+        let traverser = unsafe {
+            HeapTraverser::new(
+                table.raw(),
+                relation.raw(),
+                index_info,
+                std::ptr::null_mut(),
+            )
+        };
+        traverser.traverse(false, |tuple: &mut dyn Tuple| {
+            check();
+            let key = ctid_to_key(tuple.id());
+            let (values, is_nulls) = tuple.build();
+            let value = unsafe { (!is_nulls.add(0).read()).then_some(values.add(0).read()) };
+            let store = value
+                .and_then(|x| unsafe { opfamily.store(x) })
+                .unwrap_or_default();
+            for (_, extra) in store {
+                if !payloads.contains_key(&kv_to_pointer((key, extra))) {
+                    problems.push((None, format!("heap tuple {} is not indexed", describe(key))));
+                    break;
+                }
+            }
+        });
+    }
+    TableIterator::new(problems)
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_compact(indexrelid: Oid) -> i64 {
+    let relname = check_index(indexrelid, c"vchordrq");
+    // The lock on the table conflicts with vacuum, which also rewrites tapes,
+    // but not with writes to the table.
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    let _table = Table::open(heaprelid, pgrx::pg_sys::ShareUpdateExclusiveLock as _);
+    let relation = Index::open(indexrelid, pgrx::pg_sys::RowExclusiveLock as _);
+    let opfamily = unsafe { crate::index::vchordrq::opclass::opfamily(relation.raw()) };
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    let check = || {
+        pgrx::check_for_interrupts!();
+    };
+    let _invalidation = unsafe { crate::index::vchordrq::cache::Invalidation::new(relation.raw()) };
+    match crate::index::vchordrq::dispatch::compact(opfamily, &index, check) {
+        Some(truncated) => truncated as i64,
+        None => {
+            pgrx::warning!("the index {relname} is in use, so it is compacted but not truncated");
+            0
+        }
+    }
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordg_verify(
+    indexrelid: Oid,
+) -> TableIterator<
+    'static,
+    (
+        pgrx::name!(block, Option<i64>),
+        pgrx::name!(problem, String),
+    ),
+> {
+    check_index(indexrelid, c"vchordg");
+    // A vertex is unreachable for a moment while it is inserted, so writes to
+    // the table are blocked while the index is verified.
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    let _table = Table::open(heaprelid, pgrx::pg_sys::ShareLock as _);
     let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
     let opfamily = unsafe { crate::index::vchordg::opclass::opfamily(relation.raw()) };
     let index = unsafe { PostgresRelation::new(relation.raw()) };
-    crate::index::vchordg::dispatch::prewarm(opfamily, &index)
+    let nblocks = unsafe {
+        pgrx::pg_sys::RelationGetNumberOfBlocksInFork(
+            relation.raw(),
+            pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
+        )
+    };
+    let check = || {
+        pgrx::check_for_interrupts!();
+    };
+    let verification =
+        crate::index::vchordg::dispatch::verify(opfamily, &index, nblocks, check, |_| ());
+    let mut problems = verification
+        .problems
+        .into_iter()
+        .map(|problem| (problem.id.map(i64::from), problem.message))
+        .collect::<Vec<_>>();
+    if !verification.unreachable.is_empty() {
+        problems.push((
+            None,
+            format!(
+                "{} of {} live vertices are unreachable from the start vertices",
+                verification.unreachable.len(),
+                verification.live
+            ),
+        ));
+    }
+    TableIterator::new(problems)
 }
 
 #[pgrx::pg_extern(sql = "")]
-fn _vchordrq_prewarm(indexrelid: Oid, height: i32) -> String {
-    let pg_am = PgAm::search_amname(c"vchordrq").unwrap();
-    let Some(pg_am) = pg_am.get() else {
-        pgrx::error!("vchord is not installed");
+fn _vchordg_repair(indexrelid: Oid) -> i64 {
+    check_index(indexrelid, c"vchordg");
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    let _table = Table::open(heaprelid, pgrx::pg_sys::ShareLock as _);
+    let relation = Index::open(indexrelid, pgrx::pg_sys::RowExclusiveLock as _);
+    let opfamily = unsafe { crate::index::vchordg::opclass::opfamily(relation.raw()) };
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    let nblocks = unsafe {
+        pgrx::pg_sys::RelationGetNumberOfBlocksInFork(
+            relation.raw(),
+            pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
+        )
+    };
+    let check = || {
+        pgrx::check_for_interrupts!();
     };
+    crate::index::vchordg::dispatch::repair(opfamily, &index, nblocks, check) as i64
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_upgrade_index(indexrelid: Oid) -> i64 {
     let pg_class = PgClass::search_reloid(indexrelid).unwrap();
     let Some(pg_class) = pg_class.get() else {
         pgrx::error!("the relation does not exist");
@@ -53,13 +598,235 @@ fn _vchordrq_prewarm(indexrelid: Oid, height: i32) -> String {
     if pg_class.relkind() != PgClassRelkind::Index {
         pgrx::error!("the relation {:?} is not an index", pg_class.relname());
     }
-    if pg_class.relam() != pg_am.oid() {
-        pgrx::error!("the index {:?} is not a vchordrq index", pg_class.relname());
+    let is = |amname: &std::ffi::CStr| {
+        let pg_am = PgAm::search_amname(amname).unwrap();
+        pg_am
+            .get()
+            .is_some_and(|pg_am| pg_class.relam() == pg_am.oid())
+    };
+    let (is_vchordrq, is_vchordg) = (is(c"vchordrq"), is(c"vchordg"));
+    if !is_vchordrq && !is_vchordg {
+        pgrx::error!(
+            "the index {:?} is not a vchordrq or vchordg index",
+            pg_class.relname()
+        );
     }
-    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    // The lock on the table conflicts with vacuum and other upgrades, but not
+    // with reads and writes, which accept all readable versions.
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    let _table = Table::open(heaprelid, pgrx::pg_sys::ShareUpdateExclusiveLock as _);
+    let relation = Index::open(indexrelid, pgrx::pg_sys::RowExclusiveLock as _);
+    let check = || {
+        pgrx::check_for_interrupts!();
+    };
+    let report = |from: u64, to: u64| {
+        pgrx::notice!(
+            "upgrading the index {:?} from version {from} to version {to}",
+            pg_class.relname()
+        );
+    };
+    let from = if is_vchordrq {
+        let index = unsafe { PostgresRelation::<vchordrq::Opaque>::new(relation.raw()) };
+        let _invalidation =
+            unsafe { crate::index::vchordrq::cache::Invalidation::new(relation.raw()) };
+        vchordrq::upgrade(&index, check, report)
+    } else {
+        let index = unsafe { PostgresRelation::<vchordg::Opaque>::new(relation.raw()) };
+        vchordg::upgrade(&index, check, report)
+    };
+    from as i64
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_import(indexrelid: Oid, path: &str) -> i64 {
+    let relation = import_open(indexrelid, c"vchordrq");
+    let name = unsafe { crate::index::vchordrq::opclass::opfamily_name(relation.raw()) };
+    let file = import_map::<vchordrq::Opaque>(path, &name);
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    import_check(vchordrq::dimension(&file), vchordrq::dimension(&index));
     let opfamily = unsafe { crate::index::vchordrq::opclass::opfamily(relation.raw()) };
+    let check = || {
+        pgrx::check_for_interrupts!();
+    };
+    let mut payloads = Vec::new();
+    let problems =
+        crate::index::vchordrq::dispatch::verify(opfamily, &file, file.len(), check, |payload| {
+            payloads.push(payload)
+        });
+    import_validate(
+        indexrelid,
+        problems.into_iter().map(|problem| problem.message),
+        payloads,
+    );
+    let imported = unsafe { import_copy(&relation, &index, &file) };
+    // The new relfilenode may be used by a dropped index before.
+    unsafe { crate::index::vchordrq::cache::invalidate(relation.raw()) };
+    imported
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordg_import(indexrelid: Oid, path: &str) -> i64 {
+    let relation = import_open(indexrelid, c"vchordg");
+    let name = unsafe { crate::index::vchordg::opclass::opfamily_name(relation.raw()) };
+    let file = import_map::<vchordg::Opaque>(path, &name);
     let index = unsafe { PostgresRelation::new(relation.raw()) };
-    crate::index::vchordrq::dispatch::prewarm(opfamily, &index, height)
+    import_check(vchordg::dimension(&file), vchordg::dimension(&index));
+    let opfamily = unsafe { crate::index::vchordg::opclass::opfamily(relation.raw()) };
+    let check = || {
+        pgrx::check_for_interrupts!();
+    };
+    let mut payloads = Vec::new();
+    let verification =
+        crate::index::vchordg::dispatch::verify(opfamily, &file, file.len(), check, |payload| {
+            payloads.push(payload)
+        });
+    let unreachable = (!verification.unreachable.is_empty()).then(|| {
+        format!(
+            "{} of {} live vertices are unreachable from the start vertices",
+            verification.unreachable.len(),
+            verification.live
+        )
+    });
+    import_validate(
+        indexrelid,
+        verification
+            .problems
+            .into_iter()
+            .map(|problem| problem.message)
+            .chain(unreachable),
+        payloads,
+    );
+    unsafe { import_copy(&relation, &index, &file) }
+}
+
+fn import_open(indexrelid: Oid, amname: &std::ffi::CStr) -> Index {
+    if !unsafe { pgrx::pg_sys::superuser() } {
+        pgrx::error!("must be superuser to import an index from a file");
+    }
+    check_index(indexrelid, amname);
+    Index::open(indexrelid, pgrx::pg_sys::AccessExclusiveLock as _)
+}
+
+fn import_map<O: index::relation::Opaque>(
+    path: &str,
+    name: &str,
+) -> index::file::MappedRelation<O> {
+    let file = match index::file::MappedRelation::<O>::open(path) {
+        Ok(file) => file,
+        Err(e) => pgrx::error!("failed to open {path:?}: {e}"),
+    };
+    if file.kind() != name {
+        pgrx::error!(
+            "the file is built for {:?}, but the index uses {:?}",
+            file.kind(),
+            name
+        );
+    }
+    if file.is_empty() {
+        pgrx::error!("the file contains no pages");
+    }
+    file
+}
+
+fn import_check(file: Result<u32, String>, index: Result<u32, String>) {
+    let file = match file {
+        Ok(dim) => dim,
+        Err(e) => pgrx::error!("the file is not a valid index: {e}"),
+    };
+    let index = match index {
+        Ok(dim) => dim,
+        Err(e) => pgrx::error!("the index is not valid: {e}"),
+    };
+    if file != index {
+        pgrx::error!("the file is built for dimension {file}, but the index has dimension {index}");
+    }
+}
+
+// Payloads in the file are heap TIDs and are copied as they are, so the file
+// MUST be built from the table of the target index, for example, exported from
+// an index on the same table or built by `vchord-cli` with its ctids. A file
+// built from another table returns wrong rows silently. Only TIDs that cannot
+// exist in the table are rejected here.
+fn import_validate(
+    indexrelid: Oid,
+    mut problems: impl Iterator<Item = String>,
+    payloads: Vec<std::num::NonZero<u64>>,
+) {
+    use crate::index::fetcher::pointer_to_kv;
+    // Scans panic on damaged indexes, so damaged files are never imported.
+    if let Some(message) = problems.next() {
+        pgrx::error!(
+            "the file is damaged, and {} problems are found, starting with: {message}",
+            1 + problems.count()
+        );
+    }
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    let table = Table::open(heaprelid, pgrx::pg_sys::AccessShareLock as _);
+    let nblocks = unsafe {
+        pgrx::pg_sys::RelationGetNumberOfBlocksInFork(
+            table.raw(),
+            pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
+        )
+    };
+    for payload in payloads {
+        let ([bi_hi, bi_lo, ip_posid], _) = pointer_to_kv(payload);
+        let block = (bi_hi as u32) << 16 | bi_lo as u32;
+        if block >= nblocks || ip_posid == 0 {
+            pgrx::error!(
+                "the file refers to the heap tuple ({block},{ip_posid}), which is not in the table; the file must be built from the table of the index"
+            );
+        }
+    }
+}
+
+// The index gets a new relfilenode, like `TRUNCATE`, so that the old contents
+// are kept if the transaction aborts.
+unsafe fn import_copy<O: index::relation::Opaque>(
+    relation: &Index,
+    index: &PostgresRelation<O>,
+    file: &index::file::MappedRelation<O>,
+) -> i64 {
+    use index::relation::{Page, PageGuard, RelationRead, RelationWrite};
+    unsafe {
+        let persistence = (*(*relation.raw()).rd_rel).relpersistence;
+        #[cfg(any(feature = "pg14", feature = "pg15"))]
+        pgrx::pg_sys::RelationSetNewRelfilenode(relation.raw(), persistence);
+        #[cfg(any(feature = "pg16", feature = "pg17", feature = "pg18"))]
+        pgrx::pg_sys::RelationSetNewRelfilenumber(relation.raw(), persistence);
+    }
+    for id in 0..file.len() {
+        pgrx::check_for_interrupts!();
+        let source = file.read(id);
+        let mut target = index.extend(*source.get_opaque(), false);
+        assert_eq!(target.id(), id, "the index is extended concurrently");
+        target.copy_from_memory(&source);
+    }
+    file.len() as i64
+}
+
+struct Table {
+    raw: *mut pgrx::pg_sys::RelationData,
+    lockmode: pgrx::pg_sys::LOCKMODE,
+}
+
+impl Table {
+    fn open(relid: Oid, lockmode: pgrx::pg_sys::LOCKMASK) -> Self {
+        Self {
+            raw: unsafe { pgrx::pg_sys::table_open(relid, lockmode) },
+            lockmode,
+        }
+    }
+    fn raw(&self) -> *mut pgrx::pg_sys::RelationData {
+        self.raw
+    }
+}
+
+impl Drop for Table {
+    fn drop(&mut self) {
+        unsafe {
+            pgrx::pg_sys::table_close(self.raw, self.lockmode);
+        }
+    }
 }
 
 struct Index {
@@ -88,23 +855,69 @@ impl Drop for Index {
 }
 
 #[pgrx::pg_extern(sql = "")]
-fn _vchordrq_sampled_values(indexrelid: Oid) -> SetOfIterator<'static, String> {
-    let pg_am = PgAm::search_amname(c"vchordrq").unwrap();
-    let Some(pg_am) = pg_am.get() else {
-        pgrx::error!("vchord is not installed");
-    };
-    let pg_class = PgClass::search_reloid(indexrelid).unwrap();
-    let Some(pg_class) = pg_class.get() else {
-        pgrx::error!("the relation does not exist");
-    };
-    if pg_class.relkind() != PgClassRelkind::Index {
-        pgrx::error!("the relation {:?} is not an index", pg_class.relname());
-    }
-    if pg_class.relam() != pg_am.oid() {
-        pgrx::error!("the index {:?} is not a vchordrq index", pg_class.relname());
-    }
+fn _vchordrq_sampled_values(
+    indexrelid: Oid,
+) -> TableIterator<
+    'static,
+    (
+        pgrx::name!(value, String),
+        pgrx::name!(options, Option<String>),
+        pgrx::name!(elapsed_ms, Option<f64>),
+        pgrx::name!(rows, Option<i64>),
+        pgrx::name!(scanned, Option<i64>),
+        pgrx::name!(reranked, Option<i64>),
+    ),
+> {
+    check_index(indexrelid, c"vchordrq");
+    // The user must have access to the index, if not, raise an error from Postgres.
+    let _relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let records = dump(indexrelid.to_u32());
+    TableIterator::new(records.into_iter().map(|record| {
+        let value = match record.value {
+            Value::Text(text) => text,
+            Value::Binary { typid, data } => binary::output(typid, &data),
+        };
+        (
+            value,
+            record.options,
+            record.elapsed,
+            record.rows,
+            record.scanned,
+            record.reranked,
+        )
+    }))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordg_sampled_values(
+    indexrelid: Oid,
+) -> TableIterator<
+    'static,
+    (
+        pgrx::name!(value, String),
+        pgrx::name!(options, Option<String>),
+        pgrx::name!(elapsed_ms, Option<f64>),
+        pgrx::name!(rows, Option<i64>),
+        pgrx::name!(scanned, Option<i64>),
+        pgrx::name!(reranked, Option<i64>),
+    ),
+> {
+    check_index(indexrelid, c"vchordg");
     // The user must have access to the index, if not, raise an error from Postgres.
     let _relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
-    let queries = dump(indexrelid.to_u32());
-    SetOfIterator::new(queries)
+    let records = dump(indexrelid.to_u32());
+    TableIterator::new(records.into_iter().map(|record| {
+        let value = match record.value {
+            Value::Text(text) => text,
+            Value::Binary { typid, data } => binary::output(typid, &data),
+        };
+        (
+            value,
+            record.options,
+            record.elapsed,
+            record.rows,
+            record.scanned,
+            record.reranked,
+        )
+    }))
 }
@@ -27,12 +27,35 @@ pub enum PostgresIo {
     ReadStream,
 }
 
+#[cfg(not(feature = "pg14"))]
+static VCHORD_CUSTOM_WAL: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+static VCHORDRQ_VACUUM_TRUNCATE: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+static VCHORDRQ_MAINTENANCE_DATABASES: GucSetting<Option<CString>> =
+    GucSetting::<Option<CString>>::new(Some(c""));
+
+static VCHORDRQ_MAINTENANCE_THRESHOLD: GucSetting<i32> = GucSetting::<i32>::new(10000);
+
+static VCHORDRQ_MAINTENANCE_NAPTIME: GucSetting<i32> = GucSetting::<i32>::new(60);
+
+static VCHORDRQ_MAINTENANCE_DELAY: GucSetting<i32> = GucSetting::<i32>::new(0);
+static VCHORDRQ_SHARED_CACHE_SIZE: GucSetting<i32> = GucSetting::<i32>::new(0);
+
+static VCHORDG_VACUUM_TRUNCATE: GucSetting<bool> = GucSetting::<bool>::new(false);
+
 static VCHORDRQ_QUERY_SAMPLING_ENABLE: GucSetting<bool> = GucSetting::<bool>::new(false);
 
 static VCHORDRQ_QUERY_SAMPLING_MAX_RECORDS: GucSetting<i32> = GucSetting::<i32>::new(0);
 
 static VCHORDRQ_QUERY_SAMPLING_RATE: GucSetting<f64> = GucSetting::<f64>::new(0.0);
 
+static VCHORDG_QUERY_SAMPLING_ENABLE: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+static VCHORDG_QUERY_SAMPLING_MAX_RECORDS: GucSetting<i32> = GucSetting::<i32>::new(0);
+
+static VCHORDG_QUERY_SAMPLING_RATE: GucSetting<f64> = GucSetting::<f64>::new(0.0);
+
 static VCHORDG_ENABLE_SCAN: GucSetting<bool> = GucSetting::<bool>::new(true);
 
 static VCHORDG_EF_SEARCH: GucSetting<i32> = GucSetting::<i32>::new(64);
@@ -175,6 +198,62 @@ pub fn init() {
         GucContext::Userset,
         GucFlags::default(),
     );
+    GucRegistry::define_bool_guc(
+        c"vchordrq.vacuum_truncate",
+        c"Whether vacuum compacts vchordrq indexes and truncates free pages at the end.",
+        c"Whether vacuum compacts vchordrq indexes and truncates free pages at the end.",
+        &VCHORDRQ_VACUUM_TRUNCATE,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_string_guc(
+        c"vchordrq.maintenance_databases",
+        c"Comma-separated list of databases that maintenance workers of vchordrq connect to.",
+        c"A maintenance worker is started for each database, and none is started if it's empty.",
+        &VCHORDRQ_MAINTENANCE_DATABASES,
+        GucContext::Postmaster,
+        GucFlags::default(),
+    );
+    GucRegistry::define_int_guc(
+        c"vchordrq.maintenance_threshold",
+        c"The number of appendable tuples in a list, above which the list is maintained by the maintenance worker.",
+        c"The number of appendable tuples in a list, above which the list is maintained by the maintenance worker.",
+        &VCHORDRQ_MAINTENANCE_THRESHOLD,
+        1,
+        i32::MAX,
+        GucContext::Sighup,
+        GucFlags::default(),
+    );
+    GucRegistry::define_int_guc(
+        c"vchordrq.maintenance_naptime",
+        c"Sleep time between runs of the maintenance worker of vchordrq.",
+        c"Sleep time between runs of the maintenance worker of vchordrq.",
+        &VCHORDRQ_MAINTENANCE_NAPTIME,
+        1,
+        86400,
+        GucContext::Sighup,
+        GucFlags::UNIT_S,
+    );
+    GucRegistry::define_int_guc(
+        c"vchordrq.maintenance_delay",
+        c"Sleep time of the maintenance worker of vchordrq after maintaining a list.",
+        c"Sleep time of the maintenance worker of vchordrq after maintaining a list.",
+        &VCHORDRQ_MAINTENANCE_DELAY,
+        0,
+        10000,
+        GucContext::Sighup,
+        GucFlags::UNIT_MS,
+    );
+    GucRegistry::define_int_guc(
+        c"vchordrq.shared_cache_size",
+        c"The size of the shared memory that caches upper levels of vchordrq indexes.",
+        c"The shared memory is not allocated if it's zero.",
+        &VCHORDRQ_SHARED_CACHE_SIZE,
+        0,
+        i32::MAX / 2,
+        GucContext::Postmaster,
+        GucFlags::UNIT_BLOCKS,
+    );
     GucRegistry::define_bool_guc(
         c"vchordrq.query_sampling_enable",
         c"`query_sampling_enable` argument of vchordrq.",
@@ -263,12 +342,62 @@ pub fn init() {
         GucContext::Userset,
         GucFlags::default(),
     );
+    GucRegistry::define_bool_guc(
+        c"vchordg.vacuum_truncate",
+        c"Whether vacuum truncates empty pages at the end of vchordg indexes.",
+        c"Whether vacuum truncates empty pages at the end of vchordg indexes.",
+        &VCHORDG_VACUUM_TRUNCATE,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_bool_guc(
+        c"vchordg.query_sampling_enable",
+        c"`query_sampling_enable` argument of vchordg.",
+        c"`query_sampling_enable` argument of vchordg.",
+        &VCHORDG_QUERY_SAMPLING_ENABLE,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_int_guc(
+        c"vchordg.query_sampling_max_records",
+        c"`query_sampling_max_records` argument of vchordg.",
+        c"`query_sampling_max_records` argument of vchordg.",
+        &VCHORDG_QUERY_SAMPLING_MAX_RECORDS,
+        0,
+        10000,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_float_guc(
+        c"vchordg.query_sampling_rate",
+        c"`query_sampling_rate` argument of vchordg.",
+        c"`query_sampling_rate` argument of vchordg.",
+        &VCHORDG_QUERY_SAMPLING_RATE,
+        0.0,
+        1.0,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
     unsafe {
         #[cfg(feature = "pg14")]
         pgrx::pg_sys::EmitWarningsOnPlaceholders(c"vchordg".as_ptr());
         #[cfg(any(feature = "pg15", feature = "pg16", feature = "pg17", feature = "pg18"))]
         pgrx::pg_sys::MarkGUCPrefixReserved(c"vchordg".as_ptr());
     }
+    #[cfg(not(feature = "pg14"))]
+    {
+        GucRegistry::define_bool_guc(
+            c"vchord.custom_wal",
+            c"Whether index pages are logged with the custom WAL resource manager of vchord.",
+            c"The resource manager uses the experimental ID 128, so it must not be turned on if another extension uses the ID. It must stay on while WAL written with it may be replayed.",
+            &VCHORD_CUSTOM_WAL,
+            GucContext::Postmaster,
+            GucFlags::default(),
+        );
+        unsafe {
+            pgrx::pg_sys::MarkGUCPrefixReserved(c"vchord".as_ptr());
+        }
+    }
     assert!(crate::is_main());
     let targets = vec![
         (c"vchordg.ef_search", &raw mut VCHORDG_EF_SEARCH_CONFIG),
@@ -330,6 +459,11 @@ unsafe fn check(p: *mut pgrx::pg_sys::config_generic, name: &CStr) -> bool {
     true
 }
 
+#[cfg(not(feature = "pg14"))]
+pub fn vchord_custom_wal() -> bool {
+    VCHORD_CUSTOM_WAL.get()
+}
+
 pub fn vchordg_enable_scan() -> bool {
     VCHORDG_ENABLE_SCAN.get()
 }
@@ -377,6 +511,22 @@ pub fn vchordg_io_rerank() -> Io {
     }
 }
 
+pub fn vchordg_vacuum_truncate() -> bool {
+    VCHORDG_VACUUM_TRUNCATE.get()
+}
+
+pub fn vchordg_query_sampling_enable() -> bool {
+    VCHORDG_QUERY_SAMPLING_ENABLE.get()
+}
+
+pub fn vchordg_query_sampling_max_records() -> u32 {
+    VCHORDG_QUERY_SAMPLING_MAX_RECORDS.get() as u32
+}
+
+pub fn vchordg_query_sampling_rate() -> f64 {
+    VCHORDG_QUERY_SAMPLING_RATE.get()
+}
+
 pub fn vchordrq_enable_scan() -> bool {
     VCHORDRQ_ENABLE_SCAN.get()
 }
@@ -494,6 +644,38 @@ pub fn vchordrq_io_rerank() -> Io {
     }
 }
 
+pub fn vchordrq_vacuum_truncate() -> bool {
+    VCHORDRQ_VACUUM_TRUNCATE.get()
+}
+
+pub fn vchordrq_maintenance_databases() -> Vec<String> {
+    let Some(databases) = VCHORDRQ_MAINTENANCE_DATABASES.get() else {
+        return Vec::new();
+    };
+    databases
+        .to_string_lossy()
+        .split(',')
+        .map(|database| database.trim().to_string())
+        .filter(|database| !database.is_empty())
+        .collect()
+}
+
+pub fn vchordrq_maintenance_threshold() -> u64 {
+    VCHORDRQ_MAINTENANCE_THRESHOLD.get() as u64
+}
+
+pub fn vchordrq_maintenance_naptime() -> std::time::Duration {
+    std::time::Duration::from_secs(VCHORDRQ_MAINTENANCE_NAPTIME.get() as u64)
+}
+
+pub fn vchordrq_maintenance_delay() -> std::time::Duration {
+    std::time::Duration::from_millis(VCHORDRQ_MAINTENANCE_DELAY.get() as u64)
+}
+
+pub fn vchordrq_shared_cache_size() -> usize {
+    VCHORDRQ_SHARED_CACHE_SIZE.get() as usize
+}
+
 pub fn vchordrq_query_sampling_enable() -> bool {
     VCHORDRQ_QUERY_SAMPLING_ENABLE.get()
 }
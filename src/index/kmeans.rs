@@ -0,0 +1,166 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::datatype::memory_vector::{VectorInput, VectorOutput};
+use k_means::square::Square;
+use pgrx::datum::Array;
+use pgrx::iter::TableIterator;
+use vchordrq::types::{KMeansAlgorithm, VchordrqInternalBuildOptions};
+use vector::VectorBorrowed;
+use vector::vect::VectBorrowed;
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_kmeans(
+    query: &str,
+    k: i32,
+    iterations: i32,
+    spherical: bool,
+    algorithm: &str,
+    threads: i32,
+) -> TableIterator<'static, (pgrx::name!(id, i32), pgrx::name!(centroid, VectorOutput))> {
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+    let algorithm = match algorithm {
+        "lloyd" => KMeansAlgorithm::Lloyd {},
+        "hierarchical" => KMeansAlgorithm::Hierarchical {},
+        _ => pgrx::error!("unknown k-means algorithm {algorithm:?}"),
+    };
+    if !(1..=1 << 24).contains(&k) {
+        pgrx::error!("k must be between 1 and {}", 1 << 24);
+    }
+    if !(0..=1024).contains(&iterations) {
+        pgrx::error!("iterations must be between 0 and 1024");
+    }
+    if !(1..=255).contains(&threads) {
+        pgrx::error!("threads must be between 1 and 255");
+    }
+    // Sampling follows the defaults of internal build.
+    let defaults = VchordrqInternalBuildOptions::default();
+    let max_number_of_samples = (k as u32).saturating_mul(defaults.sampling_factor) as usize;
+    let mut samples = None::<Square>;
+    let mut rng = StdRng::from_seed([7; 32]);
+    pgrx::spi::Spi::connect(|client| {
+        use pgrx::pg_sys::panic::ErrorReportable;
+        let schema_query = "SELECT n.nspname::TEXT
+            FROM pg_catalog.pg_extension e
+            LEFT JOIN pg_catalog.pg_namespace n ON n.oid = e.extnamespace
+            WHERE e.extname = 'vector';";
+        let pgvector_schema: String = client
+            .select(schema_query, None, &[])
+            .unwrap_or_report()
+            .first()
+            .get_by_name("nspname")
+            .expect("k-means: cannot get schema of pgvector")
+            .expect("k-means: cannot get schema of pgvector");
+        let sample_query =
+            format!("SELECT v::{pgvector_schema}.vector AS v FROM ({query}) AS q(v);");
+        let rows = client.select(&sample_query, None, &[]).unwrap_or_report();
+        // Reservoir sampling keeps the samples uniform over the whole result.
+        let mut seen = 0_usize;
+        for row in rows {
+            pgrx::check_for_interrupts!();
+            let vector: Option<VectorOutput> = row.get_by_name("v").unwrap();
+            let Some(vector) = vector else {
+                continue;
+            };
+            let slice = vector.as_borrowed().slice();
+            let samples = samples
+                .get_or_insert_with(|| Square::with_capacity(slice.len(), max_number_of_samples));
+            if samples.d() != slice.len() {
+                pgrx::error!("k-means: vectors have different dimensions");
+            }
+            if samples.len() < max_number_of_samples {
+                samples.push_slice(slice);
+            } else {
+                let j = rng.random_range(0..=seen);
+                if j < max_number_of_samples {
+                    samples[j].copy_from_slice(slice);
+                }
+            }
+            seen += 1;
+        }
+    });
+    let Some(mut samples) = samples else {
+        pgrx::error!("k-means: the query returns no vectors");
+    };
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads as usize)
+        .build()
+        .expect("failed to build thread pool");
+    let num_dim = samples.d();
+    let mut f = match algorithm {
+        KMeansAlgorithm::Lloyd {} => k_means::lloyd_k_means(
+            &pool,
+            num_dim,
+            samples.as_mut_view(),
+            k as usize,
+            [7; 32],
+            spherical,
+        ),
+        KMeansAlgorithm::Hierarchical {} => k_means::hierarchical_k_means(
+            &pool,
+            num_dim,
+            samples.as_mut_view(),
+            k as usize,
+            [7; 32],
+            spherical,
+        ),
+    };
+    for _ in 0..iterations {
+        pgrx::check_for_interrupts!();
+        f.assign();
+        f.update();
+    }
+    let centroids = f.finish();
+    let result = centroids
+        .into_iter()
+        .enumerate()
+        .map(|(id, centroid)| {
+            let centroid = VectorOutput::new(VectBorrowed::new(centroid));
+            (id as i32, centroid)
+        })
+        .collect::<Vec<_>>();
+    TableIterator::new(result)
+}
+
+// With `spherical`, the nearest centroid is the one with the largest inner
+// product, which suits the unit centroids of spherical k-means.
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_kmeans_assign(
+    vector: VectorInput<'_>,
+    centroids: Array<'_, VectorInput<'_>>,
+    spherical: bool,
+) -> Option<i32> {
+    use simd::Floating;
+    let vector = vector.as_borrowed();
+    let mut result = None::<(f32, i32)>;
+    for (id, centroid) in centroids.iter().enumerate() {
+        let Some(centroid) = centroid else {
+            continue;
+        };
+        let centroid = centroid.as_borrowed();
+        if vector.dim() != centroid.dim() {
+            pgrx::error!("dimension is not matched");
+        }
+        let dis = if spherical {
+            -f32::reduce_sum_of_xy(vector.slice(), centroid.slice())
+        } else {
+            f32::reduce_sum_of_d2(vector.slice(), centroid.slice())
+        };
+        if result.is_none_or(|(x, _)| dis < x) {
+            result = Some((dis, id as i32));
+        }
+    }
+    result.map(|(_, id)| id)
+}
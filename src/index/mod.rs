@@ -16,6 +16,7 @@ mod fetcher;
 mod functions;
 mod gucs;
 mod hook;
+mod kmeans;
 mod opclass;
 mod sample;
 mod scanners;
@@ -27,6 +28,9 @@ mod vchordrq;
 pub fn init() {
     gucs::init();
     hook::init();
+    storage::wal::init();
     vchordrq::am::init();
+    vchordrq::cache::init();
     vchordg::am::init();
+    vchordrq::worker::init();
 }
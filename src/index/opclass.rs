@@ -72,6 +72,66 @@ fn _vchordg_support_rabitq4_ip_ops() -> String {
     "vchordg_rabitq4_ip_ops".to_string()
 }
 
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_rabitq2_l2_ops() -> String {
+    "vchordg_rabitq2_l2_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_rabitq2_cosine_ops() -> String {
+    "vchordg_rabitq2_cosine_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_rabitq2_ip_ops() -> String {
+    "vchordg_rabitq2_ip_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_rabitq1_l2_ops() -> String {
+    "vchordg_rabitq1_l2_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_rabitq1_cosine_ops() -> String {
+    "vchordg_rabitq1_cosine_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_rabitq1_ip_ops() -> String {
+    "vchordg_rabitq1_ip_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_int8vec_l2_ops() -> String {
+    "vchordg_int8vec_l2_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_int8vec_ip_ops() -> String {
+    "vchordg_int8vec_ip_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_bf16vec_l2_ops() -> String {
+    "vchordg_bf16vec_l2_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_bf16vec_cosine_ops() -> String {
+    "vchordg_bf16vec_cosine_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_bf16vec_ip_ops() -> String {
+    "vchordg_bf16vec_ip_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_int4_array_ops() -> String {
+    "vchordg_int4_array_ops".to_string()
+}
+
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vchordrq_support_vector_l2_ops() -> String {
     "vchordrq_vector_l2_ops".to_string()
@@ -132,6 +192,61 @@ fn _vchordrq_support_rabitq4_cosine_ops() -> String {
     "vchordrq_rabitq4_cosine_ops".to_string()
 }
 
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_rabitq2_l2_ops() -> String {
+    "vchordrq_rabitq2_l2_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_rabitq2_ip_ops() -> String {
+    "vchordrq_rabitq2_ip_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_rabitq2_cosine_ops() -> String {
+    "vchordrq_rabitq2_cosine_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_rabitq1_l2_ops() -> String {
+    "vchordrq_rabitq1_l2_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_rabitq1_ip_ops() -> String {
+    "vchordrq_rabitq1_ip_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_rabitq1_cosine_ops() -> String {
+    "vchordrq_rabitq1_cosine_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_int8vec_l2_ops() -> String {
+    "vchordrq_int8vec_l2_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_int8vec_ip_ops() -> String {
+    "vchordrq_int8vec_ip_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_bf16vec_l2_ops() -> String {
+    "vchordrq_bf16vec_l2_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_bf16vec_ip_ops() -> String {
+    "vchordrq_bf16vec_ip_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_bf16vec_cosine_ops() -> String {
+    "vchordrq_bf16vec_cosine_ops".to_string()
+}
+
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vchordrq_support_vector_maxsim_ops() -> String {
     "vchordrq_vector_maxsim_ops".to_string()
@@ -152,6 +267,16 @@ fn _vchordrq_support_rabitq4_maxsim_ops() -> String {
     "vchordrq_rabitq4_maxsim_ops".to_string()
 }
 
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_rabitq2_maxsim_ops() -> String {
+    "vchordrq_rabitq2_maxsim_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_rabitq1_maxsim_ops() -> String {
+    "vchordrq_rabitq1_maxsim_ops".to_string()
+}
+
 pub struct Sphere<T> {
     pub center: T,
     pub radius: f32,
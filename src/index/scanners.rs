@@ -46,7 +46,7 @@ pub trait SearchBuilder: 'static {
         options: Self::Options,
         fetcher: impl Fetcher + 'b,
         bump: &'b impl Bump,
-        recorder: impl Recorder,
+        recorder: impl Recorder + 'b,
     ) -> Box<dyn Iterator<Item = (f32, [u16; 3], bool)> + 'b>
     where
         R: RelationRead + RelationPrefetch + RelationReadStream,
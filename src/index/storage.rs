@@ -13,17 +13,19 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 pub mod buffered;
+pub mod wal;
 
 use index::fetch::Fetch;
+use index::memory::MemoryPage;
 use index::relation::{
     Hints, Opaque, Page, PageGuard, ReadStream, Relation, RelationPrefetch, RelationRead,
-    RelationReadStream, RelationReadStreamTypes, RelationReadTypes, RelationWrite,
-    RelationWriteTypes,
+    RelationReadStream, RelationReadStreamTypes, RelationReadTypes, RelationTruncate,
+    RelationWrite, RelationWriteTypes,
 };
 use std::collections::VecDeque;
 use std::iter::{Chain, Flatten};
 use std::marker::PhantomData;
-use std::mem::{MaybeUninit, offset_of};
+use std::mem::offset_of;
 use std::ops::{Deref, DerefMut};
 use std::ptr::NonNull;
 
@@ -52,6 +54,7 @@ const _: () = {
 
 const _: () = assert!(align_of::<PostgresPage<()>>() == pgrx::pg_sys::MAXIMUM_ALIGNOF as usize);
 const _: () = assert!(size_of::<PostgresPage<()>>() == pgrx::pg_sys::BLCKSZ as usize);
+const _: () = assert!(size_of::<PostgresPage<()>>() == index::memory::PAGE_SIZE);
 
 impl<O: Opaque> PostgresPage<O> {
     pub fn clone_into_boxed(&self) -> Box<Self> {
@@ -61,6 +64,20 @@ impl<O: Opaque> PostgresPage<O> {
             result.assume_init()
         }
     }
+    /// Overwrites the page with a page of the same layout, built outside
+    /// PostgreSQL.
+    pub fn copy_from_memory(&mut self, page: &MemoryPage<O>) {
+        let bytes = page.as_bytes();
+        unsafe {
+            std::ptr::copy(
+                bytes.as_ptr(),
+                (self as *mut Self).cast::<u8>(),
+                bytes.len(),
+            );
+        }
+        assert!(self.header.pd_special as usize + size_of::<O>() == size_of::<Self>());
+        wal::record(self, |recorder, _| recorder.image());
+    }
 }
 
 impl<O: Opaque> Page for PostgresPage<O> {
@@ -71,6 +88,7 @@ impl<O: Opaque> Page for PostgresPage<O> {
     }
     fn get_opaque_mut(&mut self) -> &mut O {
         assert!(self.header.pd_special as usize + size_of::<O>() == size_of::<Self>());
+        wal::record(self, |recorder, page| recorder.get_opaque_mut(page));
         unsafe { &mut *((self as *mut _ as *mut O).byte_add(self.header.pd_special as _)) }
     }
     fn len(&self) -> u16 {
@@ -139,6 +157,7 @@ impl<O: Opaque> Page for PostgresPage<O> {
         assert!(lp_off <= size_of::<Self>());
         assert!(lp_len <= size_of::<Self>());
         assert!(lp_off + lp_len <= size_of::<Self>());
+        wal::record(self, |recorder, page| recorder.get_mut(page, i));
         unsafe {
             let ptr = (self as *mut Self).cast::<u8>().add(lp_off as _);
             Some(std::slice::from_raw_parts_mut(ptr, lp_len as _))
@@ -153,10 +172,15 @@ impl<O: Opaque> Page for PostgresPage<O> {
                 0,
                 0,
             );
-            if i == 0 { None } else { Some(i) }
+            if i == 0 {
+                return None;
+            }
+            wal::record(self, |recorder, page| recorder.alloc(page, i));
+            Some(i)
         }
     }
     fn free(&mut self, i: u16) {
+        wal::record(self, |recorder, page| recorder.free(page, i));
         unsafe {
             pgrx::pg_sys::PageIndexTupleDeleteNoCompact((self as *mut Self).cast(), i);
         }
@@ -182,6 +206,7 @@ unsafe fn page_init<O: Opaque>(this: *mut PostgresPage<O>, opaque: O) {
         this.cast::<O>()
             .byte_add(size_of::<PostgresPage<O>>() - size_of::<O>())
             .write(opaque);
+        wal::record(&*this, |recorder, page| recorder.clear(page));
     }
 }
 
@@ -217,7 +242,7 @@ pub struct PostgresBufferWriteGuard<O: Opaque> {
     raw: pgrx::pg_sys::Relation,
     buf: i32,
     page: NonNull<PostgresPage<O>>,
-    state: *mut pgrx::pg_sys::GenericXLogState,
+    state: wal::State<O>,
     id: u32,
     tracking_freespace: bool,
 }
@@ -246,13 +271,13 @@ impl<O: Opaque> Drop for PostgresBufferWriteGuard<O> {
     fn drop(&mut self) {
         unsafe {
             if std::thread::panicking() {
-                pgrx::pg_sys::GenericXLogAbort(self.state);
+                self.state.abort();
             } else {
                 if self.tracking_freespace {
                     pgrx::pg_sys::RecordPageWithFreeSpace(self.raw, self.id, self.freespace() as _);
                     pgrx::pg_sys::FreeSpaceMapVacuumRange(self.raw, self.id, self.id + 1);
                 }
-                pgrx::pg_sys::GenericXLogFinish(self.state);
+                self.state.finish();
             }
             pgrx::pg_sys::UnlockReleaseBuffer(self.buf);
         }
@@ -313,8 +338,7 @@ impl<O: Opaque> RelationWrite for PostgresRelation<O> {
         assert!(id != u32::MAX, "no such page");
         unsafe {
             use pgrx::pg_sys::{
-                BUFFER_LOCK_EXCLUSIVE, ForkNumber, GenericXLogRegisterBuffer, GenericXLogStart,
-                LockBuffer, ReadBufferExtended, ReadBufferMode,
+                BUFFER_LOCK_EXCLUSIVE, ForkNumber, LockBuffer, ReadBufferExtended, ReadBufferMode,
             };
             let buf = ReadBufferExtended(
                 self.raw,
@@ -324,11 +348,8 @@ impl<O: Opaque> RelationWrite for PostgresRelation<O> {
                 std::ptr::null_mut(),
             );
             LockBuffer(buf, BUFFER_LOCK_EXCLUSIVE as _);
-            let state = GenericXLogStart(self.raw);
-            let page = NonNull::new(
-                GenericXLogRegisterBuffer(state, buf, 0).cast::<MaybeUninit<PostgresPage<O>>>(),
-            )
-            .expect("failed to get page");
+            let state = wal::State::start(self.raw, buf, false);
+            let page = state.page();
             PostgresBufferWriteGuard {
                 raw: self.raw,
                 buf,
@@ -345,9 +366,6 @@ impl<O: Opaque> RelationWrite for PostgresRelation<O> {
         tracking_freespace: bool,
     ) -> PostgresBufferWriteGuard<O> {
         unsafe {
-            use pgrx::pg_sys::{
-                GENERIC_XLOG_FULL_IMAGE, GenericXLogRegisterBuffer, GenericXLogStart,
-            };
             let buf;
             #[cfg(any(feature = "pg14", feature = "pg15"))]
             {
@@ -384,12 +402,8 @@ impl<O: Opaque> RelationWrite for PostgresRelation<O> {
                     ExtendBufferedFlags::EB_LOCK_FIRST as _,
                 );
             }
-            let state = GenericXLogStart(self.raw);
-            let mut page = NonNull::new(
-                GenericXLogRegisterBuffer(state, buf, GENERIC_XLOG_FULL_IMAGE as _)
-                    .cast::<MaybeUninit<PostgresPage<O>>>(),
-            )
-            .expect("failed to get page");
+            let state = wal::State::start(self.raw, buf, true);
+            let mut page = state.page();
             page_init(page.as_mut().as_mut_ptr(), opaque);
             PostgresBufferWriteGuard {
                 raw: self.raw,
@@ -431,6 +445,34 @@ impl<O: Opaque> RelationPrefetch for PostgresRelation<O> {
     }
 }
 
+impl<O: Opaque> RelationTruncate for PostgresRelation<O> {
+    fn truncate(&self, f: impl FnOnce(u32) -> u32) -> Option<u32> {
+        unsafe {
+            use pgrx::pg_sys::{
+                AccessExclusiveLock, ConditionalLockRelation, ForkNumber, IsInParallelMode,
+                RelationGetNumberOfBlocksInFork, UnlockRelation,
+            };
+            // Like the truncation of tables in vacuum, give up if anyone else
+            // is using the relation, since no one should wait for vacuum. Locks
+            // are shared by parallel workers, so it's skipped in parallel mode.
+            if IsInParallelMode() || !ConditionalLockRelation(self.raw, AccessExclusiveLock as _) {
+                return None;
+            }
+            let nblocks = RelationGetNumberOfBlocksInFork(self.raw, ForkNumber::MAIN_FORKNUM);
+            let target = f(nblocks);
+            assert!(
+                target <= nblocks,
+                "the relation cannot be extended by truncation"
+            );
+            if target < nblocks {
+                pgrx::pg_sys::RelationTruncate(self.raw, target);
+            }
+            UnlockRelation(self.raw, AccessExclusiveLock as _);
+            Some(target)
+        }
+    }
+}
+
 pub struct Cache<'b, I: Iterator> {
     window: VecDeque<I::Item>,
     tail: VecDeque<u32>,
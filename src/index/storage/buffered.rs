@@ -83,12 +83,8 @@ impl<O: Opaque> RelationWrite for BufferedPostgresRelation<O> {
     #[cfg(any(feature = "pg16", feature = "pg17", feature = "pg18"))]
     fn extend(&self, opaque: O, tracking_freespace: bool) -> Self::WriteGuard<'_> {
         unsafe {
-            use pgrx::pg_sys::{
-                BUFFER_LOCK_EXCLUSIVE, GENERIC_XLOG_FULL_IMAGE, GenericXLogRegisterBuffer,
-                GenericXLogStart, LockBuffer,
-            };
-            use std::mem::MaybeUninit;
-            use std::ptr::NonNull;
+            use crate::index::storage::wal;
+            use pgrx::pg_sys::{BUFFER_LOCK_EXCLUSIVE, LockBuffer};
             let buf = {
                 let mut list = self.list.borrow_mut();
                 if list.is_empty() {
@@ -115,12 +111,8 @@ impl<O: Opaque> RelationWrite for BufferedPostgresRelation<O> {
                 list.pop().expect("number of allocated pages is zero")
             };
             LockBuffer(buf, BUFFER_LOCK_EXCLUSIVE as _);
-            let state = GenericXLogStart(self.postgres.raw);
-            let mut page = NonNull::new(
-                GenericXLogRegisterBuffer(state, buf, GENERIC_XLOG_FULL_IMAGE as _)
-                    .cast::<MaybeUninit<PostgresPage<O>>>(),
-            )
-            .expect("failed to get page");
+            let state = wal::State::start(self.postgres.raw, buf, true);
+            let mut page = state.page();
             crate::index::storage::page_init(page.as_mut().as_mut_ptr(), opaque);
             PostgresBufferWriteGuard {
                 raw: self.postgres.raw,
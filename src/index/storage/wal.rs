@@ -0,0 +1,407 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+//! WAL logging of index pages.
+//!
+//! By default, generic WAL records are used, which log the modified bytes of
+//! a page.
+//!
+//! On PostgreSQL 15 and later, if `vchord.custom_wal` is on, a modified page is
+//! instead logged as the logical operations performed on it (`clear`, `free`,
+//! `alloc`, in-place updates and opaque changes), which are replayed through
+//! the `Page` trait by a custom resource manager. `PostgresPage` reports each
+//! operation to the recorder of the page, see `index::wal`. The resource
+//! manager is registered only if the setting is on, so it must stay on while
+//! WAL written with it may be replayed.
+
+use crate::index::storage::PostgresPage;
+use index::relation::Opaque;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+#[cfg(not(feature = "pg14"))]
+pub use custom::{init, record};
+
+#[cfg(feature = "pg14")]
+pub fn init() {}
+
+/// Reports an operation on a page to its recorder, if the page is being
+/// modified and logged.
+#[cfg(feature = "pg14")]
+pub fn record<O: Opaque>(
+    _page: &PostgresPage<O>,
+    _f: impl FnOnce(&mut index::wal::Recorder, &PostgresPage<O>),
+) {
+}
+
+pub struct State<O> {
+    inner: Inner,
+    page: NonNull<MaybeUninit<PostgresPage<O>>>,
+}
+
+enum Inner {
+    Generic(*mut pgrx::pg_sys::GenericXLogState),
+    #[cfg(not(feature = "pg14"))]
+    Custom(pgrx::pg_sys::Relation, i32),
+}
+
+impl<O: Opaque> State<O> {
+    /// Starts modifying a locked buffer. The returned page is the one to be
+    /// modified. If `will_init` is set, the page must be initialized before
+    /// it is read.
+    pub unsafe fn start(raw: pgrx::pg_sys::Relation, buf: i32, will_init: bool) -> Self {
+        #[cfg(not(feature = "pg14"))]
+        if crate::index::gucs::vchord_custom_wal() {
+            unsafe {
+                let page = NonNull::from(Box::leak(Box::<PostgresPage<O>>::new_uninit()));
+                if !will_init {
+                    let source = pgrx::pg_sys::BufferGetPage(buf).cast::<PostgresPage<O>>();
+                    std::ptr::copy_nonoverlapping(source, page.as_ptr().cast(), 1);
+                }
+                if custom::needs_wal(raw) {
+                    custom::register(page.cast());
+                }
+                return Self {
+                    inner: Inner::Custom(raw, buf),
+                    page,
+                };
+            }
+        }
+        unsafe {
+            use pgrx::pg_sys::{
+                GENERIC_XLOG_FULL_IMAGE, GenericXLogRegisterBuffer, GenericXLogStart,
+            };
+            let flags = if will_init {
+                GENERIC_XLOG_FULL_IMAGE
+            } else {
+                0
+            };
+            let state = GenericXLogStart(raw);
+            let page = NonNull::new(GenericXLogRegisterBuffer(state, buf, flags as _).cast())
+                .expect("failed to get page");
+            Self {
+                inner: Inner::Generic(state),
+                page,
+            }
+        }
+    }
+    pub fn page(&self) -> NonNull<MaybeUninit<PostgresPage<O>>> {
+        self.page
+    }
+    /// Applies the modification to the buffer and logs it. The buffer is
+    /// still locked afterwards.
+    pub unsafe fn finish(&mut self) {
+        match self.inner {
+            Inner::Generic(state) => unsafe {
+                pgrx::pg_sys::GenericXLogFinish(state);
+            },
+            #[cfg(not(feature = "pg14"))]
+            Inner::Custom(raw, buf) => unsafe {
+                custom::finish(raw, buf, self.page.cast());
+                drop(Box::from_raw(self.page.as_ptr()));
+            },
+        }
+    }
+    /// Discards the modification.
+    pub unsafe fn abort(&mut self) {
+        match self.inner {
+            Inner::Generic(state) => unsafe {
+                pgrx::pg_sys::GenericXLogAbort(state);
+            },
+            #[cfg(not(feature = "pg14"))]
+            Inner::Custom(_, _) => unsafe {
+                custom::unregister(self.page.cast());
+                drop(Box::from_raw(self.page.as_ptr()));
+            },
+        }
+    }
+}
+
+#[cfg(not(feature = "pg14"))]
+mod custom {
+    use crate::index::storage::PostgresPage;
+    use index::relation::Opaque;
+    use index::wal::{Record, Recorder, apply, describe};
+    use std::cell::RefCell;
+    use std::ffi::CString;
+    use std::ptr::NonNull;
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    // No ID is reserved at
+    // https://wiki.postgresql.org/wiki/CustomWALResourceManagers yet, so the ID
+    // for experiments is used, and the resource manager is opt-in.
+    const RM_VCHORD_ID: u8 = pgrx::pg_sys::RM_EXPERIMENTAL_ID as u8;
+
+    // The page is modified by the operations.
+    const XLOG_VCHORD_MODIFY: u8 = 0x00;
+    // The page is initialized by the operations.
+    const XLOG_VCHORD_INIT: u8 = 0x10;
+    // The page is restored from the full page image.
+    const XLOG_VCHORD_IMAGE: u8 = 0x20;
+
+    // Redo functions do not know the access method of the page, but opaques of
+    // all access methods have the same size.
+    #[repr(C, align(8))]
+    #[derive(Debug, Clone, Copy, FromBytes, IntoBytes, Immutable, KnownLayout)]
+    struct WalOpaque([u8; 8]);
+
+    unsafe impl Opaque for WalOpaque {}
+
+    const _: () = assert!(size_of::<vchordrq::Opaque>() == size_of::<WalOpaque>());
+    const _: () = assert!(size_of::<vchordg::Opaque>() == size_of::<WalOpaque>());
+
+    thread_local! {
+        // Recorders of pages being modified, keyed by addresses of the pages.
+        static RECORDERS: RefCell<Vec<(usize, Recorder)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    pub fn init() {
+        if !crate::index::gucs::vchord_custom_wal() {
+            return;
+        }
+        unsafe {
+            use pgrx::pg_sys::RmgrData;
+            let rmgr = Box::leak(Box::new(RmgrData {
+                rm_name: c"vchord".as_ptr(),
+                rm_redo: Some(redo),
+                rm_desc: Some(desc),
+                rm_identify: Some(identify),
+                ..std::mem::zeroed()
+            }));
+            pgrx::pg_sys::RegisterCustomRmgr(RM_VCHORD_ID, rmgr);
+        }
+    }
+
+    pub fn register<O>(page: NonNull<PostgresPage<O>>) {
+        let key = page.as_ptr() as usize;
+        RECORDERS.with_borrow_mut(|recorders| {
+            // A recorder is left behind if its modification is interrupted.
+            recorders.retain(|(k, _)| *k != key);
+            recorders.push((key, Recorder::new()));
+        });
+    }
+
+    pub fn unregister<O>(page: NonNull<PostgresPage<O>>) -> Option<Recorder> {
+        let key = page.as_ptr() as usize;
+        RECORDERS.with_borrow_mut(|recorders| {
+            let index = recorders.iter().position(|(k, _)| *k == key)?;
+            Some(recorders.swap_remove(index).1)
+        })
+    }
+
+    /// Reports an operation on a page to its recorder, if the page is being
+    /// modified and logged.
+    pub fn record<O: Opaque>(
+        page: &PostgresPage<O>,
+        f: impl FnOnce(&mut Recorder, &PostgresPage<O>),
+    ) {
+        let key = page as *const PostgresPage<O> as usize;
+        RECORDERS.with_borrow_mut(|recorders| {
+            if let Some((_, recorder)) = recorders.iter_mut().find(|(k, _)| *k == key) {
+                f(recorder, page);
+            }
+        });
+    }
+
+    pub unsafe fn needs_wal(raw: pgrx::pg_sys::Relation) -> bool {
+        unsafe {
+            use pgrx::pg_sys::{InvalidSubTransactionId, WalLevel, wal_level};
+            if (*(*raw).rd_rel).relpersistence as u8 != b'p' {
+                return false;
+            }
+            if wal_level >= WalLevel::WAL_LEVEL_REPLICA as i32 {
+                return true;
+            }
+            #[cfg(feature = "pg15")]
+            let first = (*raw).rd_firstRelfilenodeSubid;
+            #[cfg(any(feature = "pg16", feature = "pg17", feature = "pg18"))]
+            let first = (*raw).rd_firstRelfilelocatorSubid;
+            (*raw).rd_createSubid == InvalidSubTransactionId && first == InvalidSubTransactionId
+        }
+    }
+
+    fn set_lsn<O>(page: &mut PostgresPage<O>, lsn: u64) {
+        page.header.pd_lsn.xlogid = (lsn >> 32) as u32;
+        page.header.pd_lsn.xrecoff = lsn as u32;
+    }
+
+    // Checks that the record reproduces the page, ignoring the LSN, the
+    // checksum and the unused space.
+    #[cfg(debug_assertions)]
+    fn check<O: Opaque>(before: &PostgresPage<O>, after: &PostgresPage<O>, data: &[u8]) {
+        use pgrx::pg_sys::PageHeaderData;
+        use std::mem::offset_of;
+        let mut page = before.clone_into_boxed();
+        assert!(
+            apply(&mut *page, data),
+            "failed to replay vchord WAL record"
+        );
+        let (a, b) = (&page.header, &after.header);
+        assert_eq!(
+            (a.pd_flags, a.pd_lower, a.pd_upper, a.pd_special),
+            (b.pd_flags, b.pd_lower, b.pd_upper, b.pd_special)
+        );
+        let bytes = |x: &PostgresPage<O>| unsafe {
+            std::slice::from_raw_parts(
+                (x as *const PostgresPage<O>).cast::<u8>(),
+                size_of::<PostgresPage<O>>(),
+            )
+            .to_vec()
+        };
+        let (x, y) = (bytes(&page), bytes(after));
+        let linp = offset_of!(PageHeaderData, pd_linp);
+        let (lower, upper) = (a.pd_lower as usize, a.pd_upper as usize);
+        assert!(x[linp..lower] == y[linp..lower] && x[upper..] == y[upper..]);
+    }
+
+    pub unsafe fn finish<O: Opaque>(
+        raw: pgrx::pg_sys::Relation,
+        buf: i32,
+        page: NonNull<PostgresPage<O>>,
+    ) {
+        unsafe {
+            use pgrx::pg_sys::{
+                BufferGetPage, CritSectionCount, MarkBufferDirty, REGBUF_FORCE_IMAGE,
+                REGBUF_STANDARD, REGBUF_WILL_INIT, XLogBeginInsert, XLogInsert,
+                XLogRegisterBufData, XLogRegisterBuffer,
+            };
+            let target = BufferGetPage(buf).cast::<PostgresPage<O>>();
+            let record = match unregister(page) {
+                Some(recorder) => match recorder.finish(page.as_ref()) {
+                    Some(record) => Some(record),
+                    // The page is not modified.
+                    None => return,
+                },
+                None => None,
+            };
+            #[cfg(debug_assertions)]
+            match &record {
+                Some(Record::Modify(data)) => check(&*target, page.as_ref(), data),
+                Some(Record::Init(data)) => check(page.as_ref(), page.as_ref(), data),
+                _ => (),
+            }
+            let (info, flags, data) = match record {
+                None => (0, 0, None),
+                Some(Record::Modify(data)) => (XLOG_VCHORD_MODIFY, REGBUF_STANDARD, Some(data)),
+                Some(Record::Init(data)) => (
+                    XLOG_VCHORD_INIT,
+                    REGBUF_STANDARD | REGBUF_WILL_INIT,
+                    Some(data),
+                ),
+                Some(Record::Image) => (
+                    XLOG_VCHORD_IMAGE,
+                    REGBUF_STANDARD | REGBUF_FORCE_IMAGE,
+                    Some(Vec::new()),
+                ),
+            };
+            CritSectionCount += 1;
+            std::ptr::copy_nonoverlapping(page.as_ptr(), target, 1);
+            MarkBufferDirty(buf);
+            if let Some(data) = data {
+                XLogBeginInsert();
+                XLogRegisterBuffer(0, buf, flags as _);
+                if !data.is_empty() {
+                    XLogRegisterBufData(0, data.as_ptr().cast_mut().cast(), data.len() as _);
+                }
+                let lsn = XLogInsert(RM_VCHORD_ID, info);
+                set_lsn(&mut *target, lsn);
+            }
+            CritSectionCount -= 1;
+        }
+    }
+
+    #[pgrx::pg_guard]
+    unsafe extern "C-unwind" fn redo(record: *mut pgrx::pg_sys::XLogReaderState) {
+        unsafe {
+            use pgrx::pg_sys::{
+                BufferGetPage, MarkBufferDirty, UnlockReleaseBuffer, XLR_INFO_MASK,
+                XLogInitBufferForRedo, XLogReadBufferForRedo, XLogRecGetBlockData,
+                XLogRecGetBlockTag, XLogRedoAction,
+            };
+            let info = (*(*record).record).header.xl_info & !(XLR_INFO_MASK as u8);
+            let mut buf = 0_i32;
+            let action = if info == XLOG_VCHORD_INIT {
+                buf = XLogInitBufferForRedo(record, 0);
+                XLogRedoAction::BLK_NEEDS_REDO
+            } else {
+                XLogReadBufferForRedo(record, 0, &raw mut buf)
+            };
+            if action == XLogRedoAction::BLK_NEEDS_REDO {
+                let mut len = 0_usize;
+                let ptr = XLogRecGetBlockData(record, 0, &raw mut len);
+                let data = if ptr.is_null() {
+                    &[][..]
+                } else {
+                    std::slice::from_raw_parts(ptr.cast::<u8>(), len)
+                };
+                let page = &mut *BufferGetPage(buf).cast::<PostgresPage<WalOpaque>>();
+                // An image is always restored, so it never needs redo.
+                if info == XLOG_VCHORD_IMAGE || !apply(page, data) {
+                    let mut block = 0_u32;
+                    XLogRecGetBlockTag(
+                        record,
+                        0,
+                        std::ptr::null_mut(),
+                        std::ptr::null_mut(),
+                        &raw mut block,
+                    );
+                    let lsn = (*record).ReadRecPtr;
+                    pgrx::PANIC!(
+                        "vchord: failed to replay WAL record at {:X}/{:X}: info {:#04x}, block {}, {}",
+                        lsn >> 32,
+                        lsn as u32,
+                        info,
+                        block,
+                        describe(data, size_of::<WalOpaque>())
+                    );
+                }
+                set_lsn(page, (*record).EndRecPtr);
+                MarkBufferDirty(buf);
+            }
+            if buf != 0 {
+                UnlockReleaseBuffer(buf);
+            }
+        }
+    }
+
+    #[pgrx::pg_guard]
+    unsafe extern "C-unwind" fn desc(
+        buf: pgrx::pg_sys::StringInfo,
+        record: *mut pgrx::pg_sys::XLogReaderState,
+    ) {
+        unsafe {
+            use pgrx::pg_sys::{XLogRecGetBlockData, appendStringInfoString};
+            let mut len = 0_usize;
+            let ptr = XLogRecGetBlockData(record, 0, &raw mut len);
+            let data = if ptr.is_null() {
+                &[][..]
+            } else {
+                std::slice::from_raw_parts(ptr.cast::<u8>(), len)
+            };
+            if let Ok(text) = CString::new(describe(data, size_of::<WalOpaque>())) {
+                appendStringInfoString(buf, text.as_ptr());
+            }
+        }
+    }
+
+    #[pgrx::pg_guard]
+    unsafe extern "C-unwind" fn identify(info: u8) -> *const std::ffi::c_char {
+        match info & !(pgrx::pg_sys::XLR_INFO_MASK as u8) {
+            XLOG_VCHORD_MODIFY => c"MODIFY".as_ptr(),
+            XLOG_VCHORD_INIT => c"INIT".as_ptr(),
+            XLOG_VCHORD_IMAGE => c"IMAGE".as_ptr(),
+            _ => std::ptr::null(),
+        }
+    }
+}
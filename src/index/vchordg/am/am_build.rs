@@ -16,8 +16,7 @@ use crate::datatype::typmod::Typmod;
 use crate::index::storage::PostgresRelation;
 use crate::index::traverse::{HeapTraverser, Traverser};
 use crate::index::vchordg::am::{Reloption, ctid_to_key, kv_to_pointer};
-use crate::index::vchordg::opclass::opfamily;
-use crate::index::vchordg::types::VchordgIndexingOptions;
+use crate::index::vchordg::opclass::{opfamily, store_labels};
 use std::ffi::CStr;
 use std::marker::PhantomData;
 use vchordg::types::*;
@@ -481,10 +480,13 @@ unsafe fn parallel_build(
                 let store = value
                     .and_then(|x| unsafe { opfamily.store(x) })
                     .unwrap_or_default();
+                let labels = unsafe { store_labels(index_relation, values, is_nulls) };
                 for (vector, extra) in store {
                     let key = ctid_to_key(ctid);
                     let payload = kv_to_pointer((key, extra));
-                    crate::index::vchordg::dispatch::insert(opfamily, &index, payload, vector);
+                    crate::index::vchordg::dispatch::insert(
+                        opfamily, &index, payload, vector, &labels,
+                    );
                 }
                 unsafe {
                     let indtuples;
@@ -538,10 +540,13 @@ unsafe fn sequential_build(
                 let store = value
                     .and_then(|x| unsafe { opfamily.store(x) })
                     .unwrap_or_default();
+                let labels = unsafe { store_labels(index_relation, values, is_nulls) };
                 for (vector, extra) in store {
                     let key = ctid_to_key(ctid);
                     let payload = kv_to_pointer((key, extra));
-                    crate::index::vchordg::dispatch::insert(opfamily, &index, payload, vector);
+                    crate::index::vchordg::dispatch::insert(
+                        opfamily, &index, payload, vector, &labels,
+                    );
                 }
                 indtuples += 1;
                 callback(indtuples);
@@ -574,9 +579,12 @@ unsafe fn options(
     if atts.is_empty() {
         pgrx::error!("indexing on no columns is not supported");
     }
-    if atts.len() != 1 {
+    if atts.len() > 2 {
         pgrx::error!("multicolumn index is not supported");
     }
+    if atts.len() == 2 && atts[1].atttypid != pgrx::pg_sys::INT4ARRAYOID {
+        pgrx::error!("the second column of the index must be an integer array of labels");
+    }
     // get dim
     let typmod = Typmod::new(atts[0].atttypmod).unwrap();
     let dim = if let Some(dim) = typmod.dim() {
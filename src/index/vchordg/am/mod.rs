@@ -18,7 +18,7 @@ use crate::index::fetcher::*;
 use crate::index::gucs;
 use crate::index::scanners::SearchBuilder;
 use crate::index::storage::PostgresRelation;
-use crate::index::vchordg::opclass::opfamily;
+use crate::index::vchordg::opclass::{opfamily, store_labels};
 use crate::index::vchordg::scanners::*;
 use crate::recorder::DefaultRecorder;
 use pgrx::datum::Internal;
@@ -124,6 +124,7 @@ const AM_HANDLER: pgrx::pg_sys::IndexAmRoutine = const {
 
     am_routine.amsupport = 1;
     am_routine.amcanorderbyop = true;
+    am_routine.amcanmulticol = true;
 
     #[cfg(any(feature = "pg17", feature = "pg18"))]
     {
@@ -188,7 +189,7 @@ pub unsafe extern "C-unwind" fn amoptions(
 #[pgrx::pg_guard]
 pub unsafe extern "C-unwind" fn amcostestimate(
     _root: *mut pgrx::pg_sys::PlannerInfo,
-    _path: *mut pgrx::pg_sys::IndexPath,
+    path: *mut pgrx::pg_sys::IndexPath,
     _loop_count: f64,
     index_startup_cost: *mut pgrx::pg_sys::Cost,
     index_total_cost: *mut pgrx::pg_sys::Cost,
@@ -198,7 +199,15 @@ pub unsafe extern "C-unwind" fn amcostestimate(
 ) {
     unsafe {
         use pgrx::pg_sys::disable_cost;
-        if !gucs::vchordg_enable_scan() {
+        // do not use index, if there are no orderbys or clauses on the vector column
+        let clauses = (*path).indexclauses;
+        let vector_clauses = !clauses.is_null()
+            && (0..(*clauses).length).any(|i| {
+                let cell = (*clauses).elements.add(i as _);
+                let clause = (*cell).ptr_value.cast::<pgrx::pg_sys::IndexClause>();
+                (*clause).indexcol == 0
+            });
+        if ((*path).indexorderbys.is_null() && !vector_clauses) || !gucs::vchordg_enable_scan() {
             *index_startup_cost = disable_cost;
             *index_total_cost = disable_cost;
             *index_selectivity = 0.0;
@@ -230,10 +239,11 @@ pub unsafe extern "C-unwind" fn aminsert(
     let datum = unsafe { (!is_null.add(0).read()).then_some(values.add(0).read()) };
     let ctid = unsafe { heap_tid.read() };
     if let Some(store) = unsafe { datum.and_then(|x| opfamily.store(x)) } {
+        let labels = unsafe { store_labels(index_relation, values, is_null) };
         for (vector, extra) in store {
             let key = ctid_to_key(ctid);
             let payload = kv_to_pointer((key, extra));
-            crate::index::vchordg::dispatch::insert(opfamily, &index, payload, vector);
+            crate::index::vchordg::dispatch::insert(opfamily, &index, payload, vector, &labels);
         }
     }
     false
@@ -293,7 +303,11 @@ pub unsafe extern "C-unwind" fn amvacuumcleanup(
         #[cfg(feature = "pg18")]
         pgrx::pg_sys::vacuum_delay_point(false);
     };
-    crate::index::vchordg::dispatch::maintain(opfamily, &index, check);
+    crate::index::vchordg::dispatch::maintain(opfamily, &index, &check);
+    if crate::index::gucs::vchordg_vacuum_truncate() {
+        let truncated = vchordg::truncate(&index, check);
+        pgrx::debug1!("truncate: number_of_truncated_pages = {truncated:?}");
+    }
     stats
 }
 
@@ -366,12 +380,16 @@ pub unsafe extern "C-unwind" fn amrescan(
                 )
             })
         };
-        // Query recorde is disable for vchordg indexes for now.
+        let rate = match gucs::vchordg_query_sampling_rate() {
+            0.0 => None,
+            rate => Some(rate),
+        };
         let recorder = DefaultRecorder {
-            enable: false,
-            rate: None,
-            max_records: 0,
+            enable: gucs::vchordg_query_sampling_enable(),
+            rate,
+            max_records: gucs::vchordg_query_sampling_max_records(),
             index: (*(*scan).indexRelation).rd_id.to_u32(),
+            typid: (*(*(*scan).indexRelation).rd_opcintype).to_u32(),
         };
         // PAY ATTENTATION: `scanning` references `bump`, so `scanning` must be dropped before `bump`.
         let bump = scanner.bump.as_ref();
@@ -387,7 +405,18 @@ pub unsafe extern "C-unwind" fn amrescan(
             | Opfamily::Rabitq8Ip
             | Opfamily::Rabitq4L2
             | Opfamily::Rabitq4Cosine
-            | Opfamily::Rabitq4Ip => {
+            | Opfamily::Rabitq4Ip
+            | Opfamily::Rabitq2L2
+            | Opfamily::Rabitq2Cosine
+            | Opfamily::Rabitq2Ip
+            | Opfamily::Rabitq1L2
+            | Opfamily::Rabitq1Cosine
+            | Opfamily::Rabitq1Ip
+            | Opfamily::Int8vecL2
+            | Opfamily::Int8vecIp
+            | Opfamily::Bf16vecL2
+            | Opfamily::Bf16vecCosine
+            | Opfamily::Bf16vecIp => {
                 let mut builder = DefaultBuilder::new(opfamily);
                 for i in 0..(*scan).numberOfOrderBys {
                     let data = (*scan).orderByData.add(i as usize);
@@ -19,11 +19,13 @@ use index::relation::{
     Hints, Page, RelationPrefetch, RelationRead, RelationReadStream, RelationWrite,
 };
 use index_accessor::{Dot, L2S};
-use simd::f16;
+use simd::{bf16, f16};
 use std::num::NonZero;
 use vchordg::operator::Op;
 use vchordg::types::*;
 use vector::VectorOwned;
+use vector::rabitq1::Rabitq1Owned;
+use vector::rabitq2::Rabitq2Owned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
 use vector::vect::{VectBorrowed, VectOwned};
@@ -46,6 +48,12 @@ where
         (VectorKind::Vecf16, DistanceKind::Dot) => {
             vchordg::prewarm::<_, Op<VectOwned<f16>, Dot>>(index)
         }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordg::prewarm::<_, Op<VectOwned<bf16>, L2S>>(index)
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordg::prewarm::<_, Op<VectOwned<bf16>, Dot>>(index)
+        }
         (VectorKind::Rabitq8, DistanceKind::L2S) => {
             vchordg::prewarm::<_, Op<Rabitq8Owned, L2S>>(index)
         }
@@ -58,6 +66,20 @@ where
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
             vchordg::prewarm::<_, Op<Rabitq4Owned, Dot>>(index)
         }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            vchordg::prewarm::<_, Op<Rabitq2Owned, L2S>>(index)
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            vchordg::prewarm::<_, Op<Rabitq2Owned, Dot>>(index)
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            vchordg::prewarm::<_, Op<Rabitq1Owned, L2S>>(index)
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            vchordg::prewarm::<_, Op<Rabitq1Owned, Dot>>(index)
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => vchordg::prewarm::<_, Op<Veci8Owned, L2S>>(index),
+        (VectorKind::Veci8, DistanceKind::Dot) => vchordg::prewarm::<_, Op<Veci8Owned, Dot>>(index),
     }
 }
 
@@ -83,6 +105,12 @@ pub fn bulkdelete<R>(
         (VectorKind::Vecf16, DistanceKind::Dot) => {
             vchordg::bulkdelete::<_, Op<VectOwned<f16>, Dot>>(index, &check, &callback);
         }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordg::bulkdelete::<_, Op<VectOwned<bf16>, L2S>>(index, &check, &callback);
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordg::bulkdelete::<_, Op<VectOwned<bf16>, Dot>>(index, &check, &callback);
+        }
         (VectorKind::Rabitq8, DistanceKind::L2S) => {
             vchordg::bulkdelete::<_, Op<Rabitq8Owned, L2S>>(index, &check, &callback);
         }
@@ -95,6 +123,24 @@ pub fn bulkdelete<R>(
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
             vchordg::bulkdelete::<_, Op<Rabitq4Owned, Dot>>(index, &check, &callback);
         }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            vchordg::bulkdelete::<_, Op<Rabitq2Owned, L2S>>(index, &check, &callback);
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            vchordg::bulkdelete::<_, Op<Rabitq2Owned, Dot>>(index, &check, &callback);
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            vchordg::bulkdelete::<_, Op<Rabitq1Owned, L2S>>(index, &check, &callback);
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            vchordg::bulkdelete::<_, Op<Rabitq1Owned, Dot>>(index, &check, &callback);
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => {
+            vchordg::bulkdelete::<_, Op<Veci8Owned, L2S>>(index, &check, &callback);
+        }
+        (VectorKind::Veci8, DistanceKind::Dot) => {
+            vchordg::bulkdelete::<_, Op<Veci8Owned, Dot>>(index, &check, &callback);
+        }
     }
 }
 
@@ -116,6 +162,12 @@ where
         (VectorKind::Vecf16, DistanceKind::Dot) => {
             vchordg::maintain::<_, Op<VectOwned<f16>, Dot>>(index, &check);
         }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordg::maintain::<_, Op<VectOwned<bf16>, L2S>>(index, &check);
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordg::maintain::<_, Op<VectOwned<bf16>, Dot>>(index, &check);
+        }
         (VectorKind::Rabitq8, DistanceKind::L2S) => {
             vchordg::maintain::<_, Op<Rabitq8Owned, L2S>>(index, &check);
         }
@@ -128,7 +180,173 @@ where
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
             vchordg::maintain::<_, Op<Rabitq4Owned, Dot>>(index, &check);
         }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            vchordg::maintain::<_, Op<Rabitq2Owned, L2S>>(index, &check);
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            vchordg::maintain::<_, Op<Rabitq2Owned, Dot>>(index, &check);
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            vchordg::maintain::<_, Op<Rabitq1Owned, L2S>>(index, &check);
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            vchordg::maintain::<_, Op<Rabitq1Owned, Dot>>(index, &check);
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => {
+            vchordg::maintain::<_, Op<Veci8Owned, L2S>>(index, &check);
+        }
+        (VectorKind::Veci8, DistanceKind::Dot) => {
+            vchordg::maintain::<_, Op<Veci8Owned, Dot>>(index, &check);
+        }
+    }
+}
+
+pub fn verify<R>(
+    opfamily: Opfamily,
+    index: &R,
+    nblocks: u32,
+    check: impl Fn(),
+    callback: impl FnMut(NonZero<u64>),
+) -> vchordg::Verification
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordg::Opaque>,
+{
+    match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<VectOwned<f32>, L2S>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Vecf32, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<VectOwned<f32>, Dot>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Vecf16, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<VectOwned<f16>, L2S>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Vecf16, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<VectOwned<f16>, Dot>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<VectOwned<bf16>, L2S>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<VectOwned<bf16>, Dot>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Rabitq8, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<Rabitq8Owned, L2S>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Rabitq8, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<Rabitq8Owned, Dot>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Rabitq4, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<Rabitq4Owned, L2S>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Rabitq4, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<Rabitq4Owned, Dot>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<Rabitq2Owned, L2S>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<Rabitq2Owned, Dot>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<Rabitq1Owned, L2S>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<Rabitq1Owned, Dot>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<Veci8Owned, L2S>>(index, nblocks, &check, callback)
+        }
+        (VectorKind::Veci8, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<Veci8Owned, Dot>>(index, nblocks, &check, callback)
+        }
+    }
+}
+
+pub fn repair<R>(opfamily: Opfamily, index: &R, nblocks: u32, check: impl Fn()) -> u64
+where
+    R: RelationRead + RelationWrite,
+    R::Page: Page<Opaque = vchordg::Opaque>,
+{
+    match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => {
+            repair_by::<_, Op<VectOwned<f32>, L2S>>(index, nblocks, &check)
+        }
+        (VectorKind::Vecf32, DistanceKind::Dot) => {
+            repair_by::<_, Op<VectOwned<f32>, Dot>>(index, nblocks, &check)
+        }
+        (VectorKind::Vecf16, DistanceKind::L2S) => {
+            repair_by::<_, Op<VectOwned<f16>, L2S>>(index, nblocks, &check)
+        }
+        (VectorKind::Vecf16, DistanceKind::Dot) => {
+            repair_by::<_, Op<VectOwned<f16>, Dot>>(index, nblocks, &check)
+        }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            repair_by::<_, Op<VectOwned<bf16>, L2S>>(index, nblocks, &check)
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            repair_by::<_, Op<VectOwned<bf16>, Dot>>(index, nblocks, &check)
+        }
+        (VectorKind::Rabitq8, DistanceKind::L2S) => {
+            repair_by::<_, Op<Rabitq8Owned, L2S>>(index, nblocks, &check)
+        }
+        (VectorKind::Rabitq8, DistanceKind::Dot) => {
+            repair_by::<_, Op<Rabitq8Owned, Dot>>(index, nblocks, &check)
+        }
+        (VectorKind::Rabitq4, DistanceKind::L2S) => {
+            repair_by::<_, Op<Rabitq4Owned, L2S>>(index, nblocks, &check)
+        }
+        (VectorKind::Rabitq4, DistanceKind::Dot) => {
+            repair_by::<_, Op<Rabitq4Owned, Dot>>(index, nblocks, &check)
+        }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            repair_by::<_, Op<Rabitq2Owned, L2S>>(index, nblocks, &check)
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            repair_by::<_, Op<Rabitq2Owned, Dot>>(index, nblocks, &check)
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            repair_by::<_, Op<Rabitq1Owned, L2S>>(index, nblocks, &check)
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            repair_by::<_, Op<Rabitq1Owned, Dot>>(index, nblocks, &check)
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => {
+            repair_by::<_, Op<Veci8Owned, L2S>>(index, nblocks, &check)
+        }
+        (VectorKind::Veci8, DistanceKind::Dot) => {
+            repair_by::<_, Op<Veci8Owned, Dot>>(index, nblocks, &check)
+        }
+    }
+}
+
+// Vectors are read back as stored, so they are already projected and are
+// inserted again without projection.
+fn repair_by<R, O: vchordg::operator::Operator>(index: &R, nblocks: u32, check: impl Fn()) -> u64
+where
+    R: RelationRead + RelationWrite,
+    R::Page: Page<Opaque = vchordg::Opaque>,
+{
+    let verification = vchordg::verify::<R, O>(index, nblocks, &check, |_| ());
+    let mut repaired = 0_u64;
+    for u in verification.unreachable {
+        check();
+        let reinserted = vchordg::reinsert::<R, O>(index, u, |vector, payload, labels| {
+            let bump = bumpalo::Bump::new();
+            vchordg::insert::<_, O>(
+                index,
+                vector.as_borrowed(),
+                payload,
+                labels,
+                &bump,
+                MakePlainPrefetcher { index },
+                MakePlainPrefetcher { index },
+            );
+        });
+        repaired += reinserted as u64;
     }
+    repaired
 }
 
 pub fn build<R>(vector_options: VectorOptions, vchordg_options: VchordgIndexOptions, index: &R)
@@ -149,6 +367,12 @@ where
         (VectorKind::Vecf16, DistanceKind::Dot) => {
             vchordg::build::<_, Op<VectOwned<f16>, Dot>>(vector_options, vchordg_options, index)
         }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordg::build::<_, Op<VectOwned<bf16>, L2S>>(vector_options, vchordg_options, index)
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordg::build::<_, Op<VectOwned<bf16>, Dot>>(vector_options, vchordg_options, index)
+        }
         (VectorKind::Rabitq8, DistanceKind::L2S) => {
             vchordg::build::<_, Op<Rabitq8Owned, L2S>>(vector_options, vchordg_options, index)
         }
@@ -161,11 +385,34 @@ where
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
             vchordg::build::<_, Op<Rabitq4Owned, Dot>>(vector_options, vchordg_options, index)
         }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            vchordg::build::<_, Op<Rabitq2Owned, L2S>>(vector_options, vchordg_options, index)
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            vchordg::build::<_, Op<Rabitq2Owned, Dot>>(vector_options, vchordg_options, index)
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            vchordg::build::<_, Op<Rabitq1Owned, L2S>>(vector_options, vchordg_options, index)
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            vchordg::build::<_, Op<Rabitq1Owned, Dot>>(vector_options, vchordg_options, index)
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => {
+            vchordg::build::<_, Op<Veci8Owned, L2S>>(vector_options, vchordg_options, index)
+        }
+        (VectorKind::Veci8, DistanceKind::Dot) => {
+            vchordg::build::<_, Op<Veci8Owned, Dot>>(vector_options, vchordg_options, index)
+        }
     }
 }
 
-pub fn insert<R>(opfamily: Opfamily, index: &R, payload: NonZero<u64>, vector: OwnedVector)
-where
+pub fn insert<R>(
+    opfamily: Opfamily,
+    index: &R,
+    payload: NonZero<u64>,
+    vector: OwnedVector,
+    labels: &[i32],
+) where
     R: RelationRead + RelationWrite + RelationReadStream,
     R::Page: Page<Opaque = vchordg::Opaque>,
 {
@@ -180,6 +427,7 @@ where
                 index,
                 projected.as_borrowed(),
                 payload,
+                labels,
                 &bump,
                 make_vertex_plain_prefetcher,
                 make_vector_plain_prefetcher,
@@ -192,6 +440,7 @@ where
                 index,
                 projected.as_borrowed(),
                 payload,
+                labels,
                 &bump,
                 make_vertex_plain_prefetcher,
                 make_vector_plain_prefetcher,
@@ -204,6 +453,7 @@ where
                 index,
                 projected.as_borrowed(),
                 payload,
+                labels,
                 &bump,
                 make_vertex_plain_prefetcher,
                 make_vector_plain_prefetcher,
@@ -216,6 +466,33 @@ where
                 index,
                 projected.as_borrowed(),
                 payload,
+                labels,
+                &bump,
+                make_vertex_plain_prefetcher,
+                make_vector_plain_prefetcher,
+            )
+        }
+        (OwnedVector::Vecbf16(unprojected), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Vecbf16);
+            let projected = RandomProject::project(unprojected.as_borrowed());
+            vchordg::insert::<_, Op<VectOwned<bf16>, L2S>>(
+                index,
+                projected.as_borrowed(),
+                payload,
+                labels,
+                &bump,
+                make_vertex_plain_prefetcher,
+                make_vector_plain_prefetcher,
+            )
+        }
+        (OwnedVector::Vecbf16(unprojected), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Vecbf16);
+            let projected = RandomProject::project(unprojected.as_borrowed());
+            vchordg::insert::<_, Op<VectOwned<bf16>, Dot>>(
+                index,
+                projected.as_borrowed(),
+                payload,
+                labels,
                 &bump,
                 make_vertex_plain_prefetcher,
                 make_vector_plain_prefetcher,
@@ -227,6 +504,7 @@ where
                 index,
                 unprojected.as_borrowed(),
                 payload,
+                labels,
                 &bump,
                 make_vertex_plain_prefetcher,
                 make_vector_plain_prefetcher,
@@ -238,6 +516,7 @@ where
                 index,
                 unprojected.as_borrowed(),
                 payload,
+                labels,
                 &bump,
                 make_vertex_plain_prefetcher,
                 make_vector_plain_prefetcher,
@@ -249,6 +528,7 @@ where
                 index,
                 unprojected.as_borrowed(),
                 payload,
+                labels,
                 &bump,
                 make_vertex_plain_prefetcher,
                 make_vector_plain_prefetcher,
@@ -260,6 +540,79 @@ where
                 index,
                 unprojected.as_borrowed(),
                 payload,
+                labels,
+                &bump,
+                make_vertex_plain_prefetcher,
+                make_vector_plain_prefetcher,
+            )
+        }
+        (OwnedVector::Rabitq2(unprojected), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq2);
+            vchordg::insert::<_, Op<Rabitq2Owned, L2S>>(
+                index,
+                unprojected.as_borrowed(),
+                payload,
+                labels,
+                &bump,
+                make_vertex_plain_prefetcher,
+                make_vector_plain_prefetcher,
+            )
+        }
+        (OwnedVector::Rabitq2(unprojected), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq2);
+            vchordg::insert::<_, Op<Rabitq2Owned, Dot>>(
+                index,
+                unprojected.as_borrowed(),
+                payload,
+                labels,
+                &bump,
+                make_vertex_plain_prefetcher,
+                make_vector_plain_prefetcher,
+            )
+        }
+        (OwnedVector::Rabitq1(unprojected), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq1);
+            vchordg::insert::<_, Op<Rabitq1Owned, L2S>>(
+                index,
+                unprojected.as_borrowed(),
+                payload,
+                labels,
+                &bump,
+                make_vertex_plain_prefetcher,
+                make_vector_plain_prefetcher,
+            )
+        }
+        (OwnedVector::Rabitq1(unprojected), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq1);
+            vchordg::insert::<_, Op<Rabitq1Owned, Dot>>(
+                index,
+                unprojected.as_borrowed(),
+                payload,
+                labels,
+                &bump,
+                make_vertex_plain_prefetcher,
+                make_vector_plain_prefetcher,
+            )
+        }
+        (OwnedVector::Veci8(unprojected), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Veci8);
+            vchordg::insert::<_, Op<Veci8Owned, L2S>>(
+                index,
+                unprojected.as_borrowed(),
+                payload,
+                labels,
+                &bump,
+                make_vertex_plain_prefetcher,
+                make_vector_plain_prefetcher,
+            )
+        }
+        (OwnedVector::Veci8(unprojected), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Veci8);
+            vchordg::insert::<_, Op<Veci8Owned, Dot>>(
+                index,
+                unprojected.as_borrowed(),
+                payload,
+                labels,
                 &bump,
                 make_vertex_plain_prefetcher,
                 make_vector_plain_prefetcher,
@@ -292,6 +645,16 @@ impl RandomProject for VectBorrowed<'_, f16> {
     }
 }
 
+impl RandomProject for VectBorrowed<'_, bf16> {
+    type Output = VectOwned<bf16>;
+    fn project(self) -> VectOwned<bf16> {
+        use rabitq::rotate::rotate;
+        use simd::Floating;
+        let input = bf16::vector_to_f32(self.slice());
+        VectOwned::new(bf16::vector_from_f32(&rotate(&input)))
+    }
+}
+
 #[derive(Debug)]
 pub struct MakePlainPrefetcher<'b, R> {
     pub index: &'b R,
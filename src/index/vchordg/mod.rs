@@ -16,4 +16,3 @@ pub mod am;
 pub mod dispatch;
 pub mod opclass;
 mod scanners;
-pub mod types;
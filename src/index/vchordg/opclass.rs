@@ -12,7 +12,11 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+use crate::datatype::memory_bf16vec::{Bf16vecInput, Bf16vecOutput};
 use crate::datatype::memory_halfvec::{HalfvecInput, HalfvecOutput};
+use crate::datatype::memory_int8vec::{Int8vecInput, Int8vecOutput};
+use crate::datatype::memory_rabitq1::{Rabitq1Input, Rabitq1Output};
+use crate::datatype::memory_rabitq2::{Rabitq2Input, Rabitq2Output};
 use crate::datatype::memory_rabitq4::{Rabitq4Input, Rabitq4Output};
 use crate::datatype::memory_rabitq8::{Rabitq8Input, Rabitq8Output};
 use crate::datatype::memory_vector::{VectorInput, VectorOutput};
@@ -39,6 +43,17 @@ pub enum Opfamily {
     Rabitq4L2,
     Rabitq4Cosine,
     Rabitq4Ip,
+    Rabitq2L2,
+    Rabitq2Cosine,
+    Rabitq2Ip,
+    Rabitq1L2,
+    Rabitq1Cosine,
+    Rabitq1Ip,
+    Int8vecL2,
+    Int8vecIp,
+    Bf16vecL2,
+    Bf16vecCosine,
+    Bf16vecIp,
 }
 
 impl Opfamily {
@@ -70,6 +85,28 @@ impl Opfamily {
             (Self::Rabitq4Cosine, _) => unreachable!(),
             (Self::Rabitq4Ip, B::Rabitq4(x)) => O::Rabitq4(x.own()),
             (Self::Rabitq4Ip, _) => unreachable!(),
+            (Self::Rabitq2L2, B::Rabitq2(x)) => O::Rabitq2(x.own()),
+            (Self::Rabitq2L2, _) => unreachable!(),
+            (Self::Rabitq2Cosine, B::Rabitq2(x)) => O::Rabitq2(x.function_normalize()),
+            (Self::Rabitq2Cosine, _) => unreachable!(),
+            (Self::Rabitq2Ip, B::Rabitq2(x)) => O::Rabitq2(x.own()),
+            (Self::Rabitq2Ip, _) => unreachable!(),
+            (Self::Rabitq1L2, B::Rabitq1(x)) => O::Rabitq1(x.own()),
+            (Self::Rabitq1L2, _) => unreachable!(),
+            (Self::Rabitq1Cosine, B::Rabitq1(x)) => O::Rabitq1(x.function_normalize()),
+            (Self::Rabitq1Cosine, _) => unreachable!(),
+            (Self::Rabitq1Ip, B::Rabitq1(x)) => O::Rabitq1(x.own()),
+            (Self::Rabitq1Ip, _) => unreachable!(),
+            (Self::Int8vecL2, B::Veci8(x)) => O::Veci8(x.own()),
+            (Self::Int8vecL2, _) => unreachable!(),
+            (Self::Int8vecIp, B::Veci8(x)) => O::Veci8(x.own()),
+            (Self::Int8vecIp, _) => unreachable!(),
+            (Self::Bf16vecL2, B::Vecbf16(x)) => O::Vecbf16(x.own()),
+            (Self::Bf16vecL2, _) => unreachable!(),
+            (Self::Bf16vecCosine, B::Vecbf16(x)) => O::Vecbf16(x.function_normalize()),
+            (Self::Bf16vecCosine, _) => unreachable!(),
+            (Self::Bf16vecIp, B::Vecbf16(x)) => O::Vecbf16(x.own()),
+            (Self::Bf16vecIp, _) => unreachable!(),
         }
     }
     pub unsafe fn store(self, datum: Datum) -> Option<Vec<(OwnedVector, u16)>> {
@@ -93,6 +130,22 @@ impl Opfamily {
                 let vector = unsafe { Rabitq4Input::from_datum(datum, false).unwrap() };
                 vec![(self.input(BorrowedVector::Rabitq4(vector.as_borrowed())), 0)]
             }
+            Self::Rabitq2L2 | Self::Rabitq2Cosine | Self::Rabitq2Ip => {
+                let vector = unsafe { Rabitq2Input::from_datum(datum, false).unwrap() };
+                vec![(self.input(BorrowedVector::Rabitq2(vector.as_borrowed())), 0)]
+            }
+            Self::Rabitq1L2 | Self::Rabitq1Cosine | Self::Rabitq1Ip => {
+                let vector = unsafe { Rabitq1Input::from_datum(datum, false).unwrap() };
+                vec![(self.input(BorrowedVector::Rabitq1(vector.as_borrowed())), 0)]
+            }
+            Self::Int8vecL2 | Self::Int8vecIp => {
+                let vector = unsafe { Int8vecInput::from_datum(datum, false).unwrap() };
+                vec![(self.input(BorrowedVector::Veci8(vector.as_borrowed())), 0)]
+            }
+            Self::Bf16vecL2 | Self::Bf16vecCosine | Self::Bf16vecIp => {
+                let vector = unsafe { Bf16vecInput::from_datum(datum, false).unwrap() };
+                vec![(self.input(BorrowedVector::Vecbf16(vector.as_borrowed())), 0)]
+            }
         };
         Some(store)
     }
@@ -120,6 +173,22 @@ impl Opfamily {
                 let vector = tuple.get_by_index::<Rabitq4Output>(attno_1).unwrap()?;
                 self.input(BorrowedVector::Rabitq4(vector.as_borrowed()))
             }
+            Self::Rabitq2L2 | Self::Rabitq2Cosine | Self::Rabitq2Ip => {
+                let vector = tuple.get_by_index::<Rabitq2Output>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Rabitq2(vector.as_borrowed()))
+            }
+            Self::Rabitq1L2 | Self::Rabitq1Cosine | Self::Rabitq1Ip => {
+                let vector = tuple.get_by_index::<Rabitq1Output>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Rabitq1(vector.as_borrowed()))
+            }
+            Self::Int8vecL2 | Self::Int8vecIp => {
+                let vector = tuple.get_by_index::<Int8vecOutput>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Veci8(vector.as_borrowed()))
+            }
+            Self::Bf16vecL2 | Self::Bf16vecCosine | Self::Bf16vecIp => {
+                let vector = tuple.get_by_index::<Bf16vecOutput>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Vecbf16(vector.as_borrowed()))
+            }
         };
         let radius = tuple.get_by_index::<f32>(attno_2).unwrap()?;
         Some(Sphere { center, radius })
@@ -145,6 +214,22 @@ impl Opfamily {
                 let vector = unsafe { Rabitq4Input::from_datum(datum, false).unwrap() };
                 self.input(BorrowedVector::Rabitq4(vector.as_borrowed()))
             }
+            Self::Rabitq2L2 | Self::Rabitq2Cosine | Self::Rabitq2Ip => {
+                let vector = unsafe { Rabitq2Input::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Rabitq2(vector.as_borrowed()))
+            }
+            Self::Rabitq1L2 | Self::Rabitq1Cosine | Self::Rabitq1Ip => {
+                let vector = unsafe { Rabitq1Input::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Rabitq1(vector.as_borrowed()))
+            }
+            Self::Int8vecL2 | Self::Int8vecIp => {
+                let vector = unsafe { Int8vecInput::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Veci8(vector.as_borrowed()))
+            }
+            Self::Bf16vecL2 | Self::Bf16vecCosine | Self::Bf16vecIp => {
+                let vector = unsafe { Bf16vecInput::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Vecbf16(vector.as_borrowed()))
+            }
         };
         Some(vector)
     }
@@ -153,25 +238,53 @@ impl Opfamily {
             Self::VectorCosine
             | Self::HalfvecCosine
             | Self::Rabitq8Cosine
-            | Self::Rabitq4Cosine => x.to_f32() * 0.5,
-            Self::VectorL2 | Self::HalfvecL2 | Self::Rabitq8L2 | Self::Rabitq4L2 => {
-                x.to_f32().sqrt()
-            }
-            Self::VectorIp | Self::HalfvecIp | Self::Rabitq8Ip | Self::Rabitq4Ip => x.to_f32(),
+            | Self::Rabitq4Cosine
+            | Self::Rabitq2Cosine
+            | Self::Rabitq1Cosine
+            | Self::Bf16vecCosine => x.to_f32() * 0.5,
+            Self::VectorL2
+            | Self::HalfvecL2
+            | Self::Rabitq8L2
+            | Self::Rabitq4L2
+            | Self::Rabitq2L2
+            | Self::Rabitq1L2
+            | Self::Int8vecL2
+            | Self::Bf16vecL2 => x.to_f32().sqrt(),
+            Self::VectorIp
+            | Self::HalfvecIp
+            | Self::Rabitq8Ip
+            | Self::Rabitq4Ip
+            | Self::Rabitq2Ip
+            | Self::Rabitq1Ip
+            | Self::Int8vecIp
+            | Self::Bf16vecIp => x.to_f32(),
         }
     }
     pub const fn distance_kind(self) -> DistanceKind {
         match self {
-            Self::VectorL2 | Self::HalfvecL2 | Self::Rabitq8L2 | Self::Rabitq4L2 => {
-                DistanceKind::L2S
-            }
+            Self::VectorL2
+            | Self::HalfvecL2
+            | Self::Rabitq8L2
+            | Self::Rabitq4L2
+            | Self::Rabitq2L2
+            | Self::Rabitq1L2
+            | Self::Int8vecL2
+            | Self::Bf16vecL2 => DistanceKind::L2S,
             Self::VectorCosine
             | Self::HalfvecCosine
             | Self::Rabitq8Cosine
-            | Self::Rabitq4Cosine => DistanceKind::L2S,
-            Self::VectorIp | Self::HalfvecIp | Self::Rabitq8Ip | Self::Rabitq4Ip => {
-                DistanceKind::Dot
-            }
+            | Self::Rabitq4Cosine
+            | Self::Rabitq2Cosine
+            | Self::Rabitq1Cosine
+            | Self::Bf16vecCosine => DistanceKind::L2S,
+            Self::VectorIp
+            | Self::HalfvecIp
+            | Self::Rabitq8Ip
+            | Self::Rabitq4Ip
+            | Self::Rabitq2Ip
+            | Self::Rabitq1Ip
+            | Self::Int8vecIp
+            | Self::Bf16vecIp => DistanceKind::Dot,
         }
     }
     pub const fn vector_kind(self) -> VectorKind {
@@ -180,11 +293,17 @@ impl Opfamily {
             Self::HalfvecL2 | Self::HalfvecCosine | Self::HalfvecIp => VectorKind::Vecf16,
             Self::Rabitq8L2 | Self::Rabitq8Cosine | Self::Rabitq8Ip => VectorKind::Rabitq8,
             Self::Rabitq4L2 | Self::Rabitq4Cosine | Self::Rabitq4Ip => VectorKind::Rabitq4,
+            Self::Rabitq2L2 | Self::Rabitq2Cosine | Self::Rabitq2Ip => VectorKind::Rabitq2,
+            Self::Rabitq1L2 | Self::Rabitq1Cosine | Self::Rabitq1Ip => VectorKind::Rabitq1,
+            Self::Int8vecL2 | Self::Int8vecIp => VectorKind::Veci8,
+            Self::Bf16vecL2 | Self::Bf16vecCosine | Self::Bf16vecIp => VectorKind::Vecbf16,
         }
     }
 }
 
-pub unsafe fn opfamily(index_relation: pgrx::pg_sys::Relation) -> Opfamily {
+/// Returns the name of the operator class, which is returned by support
+/// function 1.
+pub unsafe fn opfamily_name(index_relation: pgrx::pg_sys::Relation) -> String {
     use pgrx::pg_sys::Oid;
 
     let proc = unsafe { pgrx::pg_sys::index_getprocid(index_relation, 1, 1) };
@@ -218,7 +337,17 @@ pub unsafe fn opfamily(index_relation: pgrx::pg_sys::Relation) -> Opfamily {
 
     let result_string = result_option.expect("null return value");
 
-    let result = match result_string.as_str() {
+    unsafe {
+        pgrx::pg_sys::pfree(result_datum.cast_mut_ptr());
+    }
+
+    result_string
+}
+
+pub unsafe fn opfamily(index_relation: pgrx::pg_sys::Relation) -> Opfamily {
+    let result_string = unsafe { opfamily_name(index_relation) };
+
+    match result_string.as_str() {
         "vchordg_vector_l2_ops" => Opfamily::VectorL2,
         "vchordg_vector_ip_ops" => Opfamily::VectorIp,
         "vchordg_vector_cosine_ops" => Opfamily::VectorCosine,
@@ -231,12 +360,44 @@ pub unsafe fn opfamily(index_relation: pgrx::pg_sys::Relation) -> Opfamily {
         "vchordg_rabitq4_l2_ops" => Opfamily::Rabitq4L2,
         "vchordg_rabitq4_ip_ops" => Opfamily::Rabitq4Ip,
         "vchordg_rabitq4_cosine_ops" => Opfamily::Rabitq4Cosine,
+        "vchordg_rabitq2_l2_ops" => Opfamily::Rabitq2L2,
+        "vchordg_rabitq2_ip_ops" => Opfamily::Rabitq2Ip,
+        "vchordg_rabitq2_cosine_ops" => Opfamily::Rabitq2Cosine,
+        "vchordg_rabitq1_l2_ops" => Opfamily::Rabitq1L2,
+        "vchordg_rabitq1_ip_ops" => Opfamily::Rabitq1Ip,
+        "vchordg_rabitq1_cosine_ops" => Opfamily::Rabitq1Cosine,
+        "vchordg_int8vec_l2_ops" => Opfamily::Int8vecL2,
+        "vchordg_int8vec_ip_ops" => Opfamily::Int8vecIp,
+        "vchordg_bf16vec_l2_ops" => Opfamily::Bf16vecL2,
+        "vchordg_bf16vec_ip_ops" => Opfamily::Bf16vecIp,
+        "vchordg_bf16vec_cosine_ops" => Opfamily::Bf16vecCosine,
         _ => pgrx::error!("unknown operator class"),
-    };
+    }
+}
 
-    unsafe {
-        pgrx::pg_sys::pfree(result_datum.cast_mut_ptr());
+pub unsafe fn input_labels(datum: Datum) -> Option<Vec<i32>> {
+    if datum.is_null() {
+        return None;
     }
+    let array = unsafe { pgrx::datum::Array::<i32>::from_datum(datum, false).unwrap() };
+    let mut labels = array.iter().flatten().collect::<Vec<_>>();
+    labels.sort_unstable();
+    labels.dedup();
+    Some(labels)
+}
 
-    result
+pub unsafe fn store_labels(
+    index_relation: pgrx::pg_sys::Relation,
+    values: *const Datum,
+    is_nulls: *const bool,
+) -> Vec<i32> {
+    if unsafe { (*(*index_relation).rd_att).natts } < 2 {
+        return Vec::new();
+    }
+    let datum = unsafe { (!is_nulls.add(1).read()).then_some(values.add(1).read()) };
+    let labels = unsafe { datum.and_then(|x| input_labels(x)) }.unwrap_or_default();
+    if labels.len() > 1024 {
+        pgrx::error!("indexing more than 1024 labels for a row is not supported");
+    }
+    labels
 }
@@ -16,20 +16,23 @@ use crate::index::fetcher::{Fetcher, pointer_to_kv};
 use crate::index::opclass::Sphere;
 use crate::index::scanners::{Io, SearchBuilder};
 use crate::index::vchordg::dispatch::*;
-use crate::index::vchordg::opclass::Opfamily;
+use crate::index::vchordg::opclass::{Opfamily, input_labels};
 use crate::index::vchordg::scanners::SearchOptions;
-use crate::recorder::{Recorder, text};
+use crate::recorder::{Recorder, Recording, binary};
 use distance::Distance;
 use index::bump::Bump;
 use index::relation::{Hints, Page, RelationPrefetch, RelationRead, RelationReadStream};
 use index_accessor::{Dot, L2S};
-use simd::f16;
+use simd::{bf16, f16};
 use std::num::NonZero;
 use vchordg::operator::{self};
 use vchordg::search;
 use vchordg::types::{DistanceKind, OwnedVector, VectorKind};
+use vector::rabitq1::{Rabitq1Borrowed, Rabitq1Owned};
+use vector::rabitq2::{Rabitq2Borrowed, Rabitq2Owned};
 use vector::rabitq4::{Rabitq4Borrowed, Rabitq4Owned};
 use vector::rabitq8::{Rabitq8Borrowed, Rabitq8Owned};
+use vector::veci8::{Veci8Borrowed, Veci8Owned};
 use vector::vect::{VectBorrowed, VectOwned};
 use vector::{VectorBorrowed, VectorOwned};
 
@@ -37,6 +40,7 @@ pub struct DefaultBuilder {
     opfamily: Opfamily,
     orderbys: Vec<Option<OwnedVector>>,
     spheres: Vec<Option<Sphere<OwnedVector>>>,
+    labels: Vec<Option<Vec<i32>>>,
 }
 
 impl SearchBuilder for DefaultBuilder {
@@ -61,11 +65,23 @@ impl SearchBuilder for DefaultBuilder {
                 | Opfamily::Rabitq4Cosine
                 | Opfamily::Rabitq4L2
                 | Opfamily::Rabitq4Ip
+                | Opfamily::Rabitq2Cosine
+                | Opfamily::Rabitq2L2
+                | Opfamily::Rabitq2Ip
+                | Opfamily::Rabitq1Cosine
+                | Opfamily::Rabitq1L2
+                | Opfamily::Rabitq1Ip
+                | Opfamily::Int8vecL2
+                | Opfamily::Int8vecIp
+                | Opfamily::Bf16vecCosine
+                | Opfamily::Bf16vecL2
+                | Opfamily::Bf16vecIp
         ));
         Self {
             opfamily,
             orderbys: Vec::new(),
             spheres: Vec::new(),
+            labels: Vec::new(),
         }
     }
 
@@ -79,6 +95,10 @@ impl SearchBuilder for DefaultBuilder {
                 let x = unsafe { datum.and_then(|x| self.opfamily.input_sphere(x)) };
                 self.spheres.push(x);
             }
+            4 => {
+                let x = unsafe { datum.and_then(|x| input_labels(x)) };
+                self.labels.push(x);
+            }
             _ => unreachable!(),
         }
     }
@@ -89,12 +109,15 @@ impl SearchBuilder for DefaultBuilder {
         options: SearchOptions,
         _fetcher: impl Fetcher + 'b,
         bump: &'b impl Bump,
-        recorder: impl Recorder,
+        recorder: impl Recorder + 'b,
     ) -> Box<dyn Iterator<Item = (f32, [u16; 3], bool)> + 'b>
     where
         R: RelationRead + RelationPrefetch + RelationReadStream,
         R::Page: Page<Opaque = vchordg::Opaque>,
     {
+        if self.orderbys.is_empty() && self.spheres.is_empty() {
+            pgrx::error!("vector search with only label filters is not supported");
+        }
         let mut vector = None;
         let mut threshold = None;
         let mut recheck = false;
@@ -112,6 +135,17 @@ impl SearchBuilder for DefaultBuilder {
                 recheck = true;
             }
         }
+        let mut labels = None;
+        for x in self.labels {
+            let Some(x) = x else {
+                return Box::new(std::iter::empty());
+            };
+            if labels.is_none() {
+                labels = Some(&*bump.alloc_slice(&x));
+            } else {
+                recheck = true;
+            }
+        }
         let opfamily = self.opfamily;
         let Some(vector) = vector else {
             return Box::new(std::iter::empty()) as Box<dyn Iterator<Item = (f32, [u16; 3], bool)>>;
@@ -130,6 +164,27 @@ impl SearchBuilder for DefaultBuilder {
             index,
             hints: vector_hints,
         };
+        let sample = recorder.is_enabled().then(|| {
+            let value = match &vector {
+                OwnedVector::Vecf32(v) => binary::vector_send(v.as_borrowed()),
+                OwnedVector::Vecf16(v) => binary::halfvec_send(v.as_borrowed()),
+                OwnedVector::Rabitq8(v) => binary::rabitq8_send(v.as_borrowed()),
+                OwnedVector::Rabitq4(v) => binary::rabitq4_send(v.as_borrowed()),
+                OwnedVector::Rabitq2(v) => binary::rabitq2_send(v.as_borrowed()),
+                OwnedVector::Rabitq1(v) => binary::rabitq1_send(v.as_borrowed()),
+                OwnedVector::Veci8(v) => binary::int8vec_send(v.as_borrowed()),
+                OwnedVector::Vecbf16(v) => binary::bf16vec_send(v.as_borrowed()),
+            };
+            let settings = format!(
+                "{{\"ef_search\":{},\"beam_search\":{},\"max_scan_tuples\":{}}}",
+                options.ef_search,
+                options.beam_search,
+                options
+                    .max_scan_tuples
+                    .map_or_else(|| "null".to_string(), |x| x.to_string()),
+            );
+            (value, settings)
+        });
         let iter: Box<dyn Iterator<Item = (Distance, NonZero<u64>)>> =
             match (opfamily.vector_kind(), opfamily.distance_kind()) {
                 (VectorKind::Vecf32, DistanceKind::L2S) => {
@@ -149,6 +204,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
@@ -158,6 +214,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
@@ -167,6 +224,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
@@ -176,6 +234,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
@@ -185,6 +244,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
@@ -194,6 +254,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
@@ -203,6 +264,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
@@ -212,6 +274,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
@@ -221,6 +284,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
@@ -244,6 +308,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
@@ -253,6 +318,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
@@ -262,6 +328,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
@@ -271,6 +338,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
@@ -280,6 +348,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
@@ -289,6 +358,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
@@ -298,6 +368,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
@@ -307,6 +378,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
@@ -316,6 +388,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
@@ -339,6 +412,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
@@ -348,6 +422,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
@@ -357,6 +432,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
@@ -366,6 +442,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
@@ -375,6 +452,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
@@ -384,6 +462,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
@@ -393,6 +472,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
@@ -402,6 +482,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
@@ -411,6 +492,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
@@ -434,6 +516,215 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Simple) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Stream) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Simple, Io::Plain) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Simple) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Stream) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Stream, Io::Plain) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Simple) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Stream) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                    }
+                }
+                (VectorKind::Vecbf16, DistanceKind::L2S) => {
+                    type Op = operator::Op<VectOwned<bf16>, L2S>;
+                    let unprojected = if let OwnedVector::Vecbf16(vector) = vector.clone() {
+                        VectBorrowed::new(bump.alloc_slice(vector.slice()))
+                    } else {
+                        unreachable!()
+                    };
+                    let projected = {
+                        let projected = RandomProject::project(unprojected);
+                        VectBorrowed::new(bump.alloc_slice(projected.slice()))
+                    };
+                    match (options.io_search, options.io_rerank) {
+                        (Io::Plain, Io::Plain) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Simple) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Stream) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Simple, Io::Plain) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Simple) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Stream) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Stream, Io::Plain) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Simple) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Stream) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                    }
+                }
+                (VectorKind::Vecbf16, DistanceKind::Dot) => {
+                    type Op = operator::Op<VectOwned<bf16>, Dot>;
+                    let unprojected = if let OwnedVector::Vecbf16(vector) = vector.clone() {
+                        VectBorrowed::new(bump.alloc_slice(vector.slice()))
+                    } else {
+                        unreachable!()
+                    };
+                    let projected = {
+                        let projected = RandomProject::project(unprojected);
+                        VectBorrowed::new(bump.alloc_slice(projected.slice()))
+                    };
+                    match (options.io_search, options.io_rerank) {
+                        (Io::Plain, Io::Plain) => search::<_, Op>(
+                            index,
+                            projected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
@@ -443,6 +734,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
@@ -452,6 +744,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
@@ -461,6 +754,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
@@ -470,6 +764,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
@@ -479,6 +774,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
@@ -488,6 +784,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
@@ -497,6 +794,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
@@ -506,6 +804,7 @@ impl SearchBuilder for DefaultBuilder {
                             projected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
@@ -533,6 +832,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
@@ -542,6 +842,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
@@ -551,6 +852,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
@@ -560,6 +862,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
@@ -569,6 +872,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
@@ -578,6 +882,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
@@ -587,6 +892,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
@@ -596,6 +902,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
@@ -605,6 +912,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
@@ -632,6 +940,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
@@ -641,6 +950,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
@@ -650,6 +960,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
@@ -659,6 +970,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
@@ -668,6 +980,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
@@ -677,6 +990,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
@@ -686,6 +1000,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
@@ -695,6 +1010,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
@@ -704,6 +1020,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
@@ -731,6 +1048,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
@@ -740,6 +1058,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
@@ -749,6 +1068,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
@@ -758,6 +1078,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
@@ -767,6 +1088,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
@@ -776,6 +1098,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
@@ -785,6 +1108,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
@@ -794,6 +1118,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
@@ -803,6 +1128,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
@@ -830,6 +1156,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
@@ -839,6 +1166,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
@@ -848,6 +1176,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
@@ -857,6 +1186,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
@@ -866,6 +1196,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
@@ -875,6 +1206,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
@@ -884,6 +1216,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
@@ -893,6 +1226,7 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
@@ -902,42 +1236,665 @@ impl SearchBuilder for DefaultBuilder {
                             unprojected,
                             options.ef_search,
                             options.beam_search,
+                            labels,
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
                         ),
                     }
                 }
-            };
-        let iter = if let Some(threshold) = threshold {
-            Box::new(iter.take_while(move |(distance, _)| distance.to_f32() < threshold))
-        } else {
-            iter
-        };
-        let iter = if let Some(max_scan_tuples) = options.max_scan_tuples {
-            Box::new(iter.take(max_scan_tuples as _))
-        } else {
-            iter
-        };
-        if recorder.is_enabled() {
-            match &vector {
-                OwnedVector::Vecf32(v) => {
-                    recorder.send(&text::vector_out(v.as_borrowed()));
-                }
-                OwnedVector::Vecf16(v) => {
-                    recorder.send(&text::halfvec_out(v.as_borrowed()));
-                }
-                OwnedVector::Rabitq8(v) => {
-                    recorder.send(&text::rabitq8_out(v.as_borrowed()));
-                }
-                OwnedVector::Rabitq4(v) => {
-                    recorder.send(&text::rabitq4_out(v.as_borrowed()));
-                }
-            }
+                (VectorKind::Rabitq2, DistanceKind::L2S) => {
+                    type Op = operator::Op<Rabitq2Owned, L2S>;
+                    let unprojected = if let OwnedVector::Rabitq2(vector) = vector.clone() {
+                        let vector = vector.as_borrowed();
+                        Rabitq2Borrowed::new(
+                            vector.dim(),
+                            vector.sum_of_x2(),
+                            vector.norm_of_lattice(),
+                            vector.sum_of_code(),
+                            vector.sum_of_abs_x(),
+                            bump.alloc_slice(vector.packed_code()),
+                        )
+                    } else {
+                        unreachable!()
+                    };
+                    match (options.io_search, options.io_rerank) {
+                        (Io::Plain, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Simple, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Stream, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                    }
+                }
+                (VectorKind::Rabitq2, DistanceKind::Dot) => {
+                    type Op = operator::Op<Rabitq2Owned, Dot>;
+                    let unprojected = if let OwnedVector::Rabitq2(vector) = vector.clone() {
+                        let vector = vector.as_borrowed();
+                        Rabitq2Borrowed::new(
+                            vector.dim(),
+                            vector.sum_of_x2(),
+                            vector.norm_of_lattice(),
+                            vector.sum_of_code(),
+                            vector.sum_of_abs_x(),
+                            bump.alloc_slice(vector.packed_code()),
+                        )
+                    } else {
+                        unreachable!()
+                    };
+                    match (options.io_search, options.io_rerank) {
+                        (Io::Plain, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Simple, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Stream, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                    }
+                }
+                (VectorKind::Rabitq1, DistanceKind::L2S) => {
+                    type Op = operator::Op<Rabitq1Owned, L2S>;
+                    let unprojected = if let OwnedVector::Rabitq1(vector) = vector.clone() {
+                        let vector = vector.as_borrowed();
+                        Rabitq1Borrowed::new(
+                            vector.dim(),
+                            vector.sum_of_x2(),
+                            vector.norm_of_lattice(),
+                            vector.sum_of_code(),
+                            vector.sum_of_abs_x(),
+                            bump.alloc_slice(vector.packed_code()),
+                        )
+                    } else {
+                        unreachable!()
+                    };
+                    match (options.io_search, options.io_rerank) {
+                        (Io::Plain, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Simple, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Stream, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                    }
+                }
+                (VectorKind::Rabitq1, DistanceKind::Dot) => {
+                    type Op = operator::Op<Rabitq1Owned, Dot>;
+                    let unprojected = if let OwnedVector::Rabitq1(vector) = vector.clone() {
+                        let vector = vector.as_borrowed();
+                        Rabitq1Borrowed::new(
+                            vector.dim(),
+                            vector.sum_of_x2(),
+                            vector.norm_of_lattice(),
+                            vector.sum_of_code(),
+                            vector.sum_of_abs_x(),
+                            bump.alloc_slice(vector.packed_code()),
+                        )
+                    } else {
+                        unreachable!()
+                    };
+                    match (options.io_search, options.io_rerank) {
+                        (Io::Plain, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Simple, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Stream, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                    }
+                }
+                (VectorKind::Veci8, DistanceKind::L2S) => {
+                    type Op = operator::Op<Veci8Owned, L2S>;
+                    let unprojected = if let OwnedVector::Veci8(vector) = vector.clone() {
+                        Veci8Borrowed::new(bump.alloc_slice(vector.slice()))
+                    } else {
+                        unreachable!()
+                    };
+                    match (options.io_search, options.io_rerank) {
+                        (Io::Plain, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Simple, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Stream, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                    }
+                }
+                (VectorKind::Veci8, DistanceKind::Dot) => {
+                    type Op = operator::Op<Veci8Owned, Dot>;
+                    let unprojected = if let OwnedVector::Veci8(vector) = vector.clone() {
+                        Veci8Borrowed::new(bump.alloc_slice(vector.slice()))
+                    } else {
+                        unreachable!()
+                    };
+                    match (options.io_search, options.io_rerank) {
+                        (Io::Plain, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Plain, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_plain_prefetcher,
+                        ),
+                        (Io::Simple, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Simple, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_simple_prefetcher,
+                        ),
+                        (Io::Stream, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                        (Io::Stream, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            labels,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_stream_prefetcher,
+                        ),
+                    }
+                }
+            };
+        let iter = if let Some(threshold) = threshold {
+            Box::new(iter.take_while(move |(distance, _)| distance.to_f32() < threshold))
+        } else {
+            iter
+        };
+        let iter = if let Some(max_scan_tuples) = options.max_scan_tuples {
+            Box::new(iter.take(max_scan_tuples as _))
+        } else {
+            iter
+        };
+        let iter: Box<dyn Iterator<Item = (f32, [u16; 3], bool)> + 'b> =
+            Box::new(iter.map(move |(distance, pointer)| {
+                let (key, _) = pointer_to_kv(pointer);
+                (opfamily.output(distance), key, recheck)
+            }));
+        if let Some((value, settings)) = sample {
+            Box::new(Recording::new(iter, recorder, value, settings, None, None))
+        } else {
+            iter
         }
-        Box::new(iter.map(move |(distance, pointer)| {
-            let (key, _) = pointer_to_kv(pointer);
-            (opfamily.output(distance), key, recheck)
-        }))
     }
 }
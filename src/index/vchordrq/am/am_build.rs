@@ -21,7 +21,6 @@ use crate::index::traverse::{HeapTraverser, Traverser};
 use crate::index::vchordrq::am::Reloption;
 use crate::index::vchordrq::build::{Normalize, Normalized};
 use crate::index::vchordrq::opclass::{Opfamily, opfamily};
-use crate::index::vchordrq::types::*;
 use index::relation::{
     Page, PageGuard, Relation, RelationRead, RelationReadTypes, RelationWrite, RelationWriteTypes,
 };
@@ -34,8 +33,11 @@ use std::num::NonZero;
 use std::ops::Deref;
 use vchordrq::types::*;
 use vchordrq::{InsertChooser, MaintainChooser};
+use vector::rabitq1::Rabitq1Owned;
+use vector::rabitq2::Rabitq2Owned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
+use vector::veci8::Veci8Owned;
 use vector::vect::VectOwned;
 
 #[derive(Debug, Clone, Copy)]
@@ -226,6 +228,25 @@ pub unsafe extern "C-unwind" fn ambuild(
         let errors = "residual_quantization is not supported for rabitq4 type";
         pgrx::error!("error while validating options: {errors}");
     }
+    if vector_options.v == VectorKind::Rabitq2 && vchordrq_options.index.residual_quantization {
+        let errors = "residual_quantization is not supported for rabitq2 type";
+        pgrx::error!("error while validating options: {errors}");
+    }
+    if vector_options.v == VectorKind::Rabitq1 && vchordrq_options.index.residual_quantization {
+        let errors = "residual_quantization is not supported for rabitq1 type";
+        pgrx::error!("error while validating options: {errors}");
+    }
+    if vector_options.v == VectorKind::Veci8 && vchordrq_options.index.residual_quantization {
+        let errors = "residual_quantization is not supported for int8vec type";
+        pgrx::error!("error while validating options: {errors}");
+    }
+    if vector_options.v == VectorKind::Veci8
+        && let VchordrqBuildSourceOptions::Internal(internal_build) = &vchordrq_options.build.source
+        && internal_build.spherical_centroids
+    {
+        let errors = "spherical_centroids is not supported for int8vec type";
+        pgrx::error!("error while validating options: {errors}");
+    }
     let opfamily = unsafe { opfamily(index_relation) };
     let reporter = PostgresReporter {
         _phantom: PhantomData,
@@ -258,9 +279,12 @@ pub unsafe extern "C-unwind" fn ambuild(
             make_external_build(vector_options, opfamily, external_build)
         }
     };
-    for structure in structures.iter_mut() {
-        for centroid in structure.centroids.iter_mut() {
-            rabitq::rotate::rotate_inplace(centroid);
+    // int8vec is indexed without the random rotation, so are its centroids
+    if vector_options.v != VectorKind::Veci8 {
+        for structure in structures.iter_mut() {
+            for centroid in structure.centroids.iter_mut() {
+                rabitq::rotate::rotate_inplace(centroid);
+            }
         }
     }
     reporter.phase(BuildPhase::from_code(BuildPhaseCode::Build));
@@ -271,6 +295,8 @@ pub unsafe extern "C-unwind" fn ambuild(
         &index,
         structures,
     );
+    // The relfilenode may be used by a dropped index before.
+    unsafe { crate::index::vchordrq::cache::invalidate(index_relation) };
     let cached = if vchordrq_options.build.pin >= 0 {
         let mut trace = vchordrq::cache(&index, vchordrq_options.build.pin);
         trace.sort();
@@ -1326,6 +1352,10 @@ fn make_internal_build(
                             OwnedVector::Vecf16(x) => VectOwned::normalize(x),
                             OwnedVector::Rabitq8(x) => Rabitq8Owned::normalize(x),
                             OwnedVector::Rabitq4(x) => Rabitq4Owned::normalize(x),
+                            OwnedVector::Rabitq2(x) => Rabitq2Owned::normalize(x),
+                            OwnedVector::Rabitq1(x) => Rabitq1Owned::normalize(x),
+                            OwnedVector::Veci8(x) => Veci8Owned::normalize(x),
+                            OwnedVector::Vecbf16(x) => VectOwned::normalize(x),
                         };
                         assert_eq!(
                             vector_options.dim,
@@ -1487,6 +1517,14 @@ fn make_internal_build(
                                                 OwnedVector::Rabitq4(x) => {
                                                     Rabitq4Owned::normalize(x)
                                                 }
+                                                OwnedVector::Rabitq2(x) => {
+                                                    Rabitq2Owned::normalize(x)
+                                                }
+                                                OwnedVector::Rabitq1(x) => {
+                                                    Rabitq1Owned::normalize(x)
+                                                }
+                                                OwnedVector::Veci8(x) => Veci8Owned::normalize(x),
+                                                OwnedVector::Vecbf16(x) => VectOwned::normalize(x),
                                             };
                                             assert_eq!(
                                                 vector_options.dim,
@@ -13,6 +13,7 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 use crate::index::vchordrq::am::PostgresRelation;
+use crate::index::vchordrq::cache;
 use crate::index::vchordrq::opclass::opfamily;
 use vchordrq::MaintainChooser;
 
@@ -57,8 +58,14 @@ unsafe fn sequential_vacuumcleanup(
 
     sync_0();
 
-    let mut chooser = ChooseAll;
-    crate::index::vchordrq::dispatch::maintain(opfamily, &index, &mut chooser, check);
+    let invalidation = unsafe { cache::Invalidation::new(index_relation) };
+    if crate::index::gucs::vchordrq_vacuum_truncate() {
+        crate::index::vchordrq::dispatch::compact(opfamily, &index, check);
+    } else {
+        let mut chooser = ChooseAll;
+        crate::index::vchordrq::dispatch::maintain(opfamily, &index, &mut chooser, check);
+    }
+    drop(invalidation);
 
     sync_1();
 }
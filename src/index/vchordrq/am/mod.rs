@@ -19,6 +19,7 @@ use crate::index::fetcher::*;
 use crate::index::gucs;
 use crate::index::scanners::SearchBuilder;
 use crate::index::storage::PostgresRelation;
+use crate::index::vchordrq::cache;
 use crate::index::vchordrq::opclass::{Opfamily, opfamily};
 use crate::index::vchordrq::scanners::*;
 use crate::recorder::DefaultRecorder;
@@ -325,6 +326,17 @@ pub unsafe extern "C-unwind" fn amcostestimate(
                     | Opfamily::Rabitq4Cosine
                     | Opfamily::Rabitq4Ip
                     | Opfamily::Rabitq4L2
+                    | Opfamily::Rabitq2Cosine
+                    | Opfamily::Rabitq2Ip
+                    | Opfamily::Rabitq2L2
+                    | Opfamily::Rabitq1Cosine
+                    | Opfamily::Rabitq1Ip
+                    | Opfamily::Rabitq1L2
+                    | Opfamily::Int8vecIp
+                    | Opfamily::Int8vecL2
+                    | Opfamily::Bf16vecCosine
+                    | Opfamily::Bf16vecIp
+                    | Opfamily::Bf16vecL2
             ) {
                 *index_startup_cost = 0.0;
                 *index_total_cost = 0.0;
@@ -475,6 +487,7 @@ pub unsafe extern "C-unwind" fn ambeginscan(
         hack: None,
         scanning: LazyCell::new(Box::new(|| Box::new(std::iter::empty()))),
         bump: Box::new(bumpalo::Bump::new()),
+        pinned: None,
     };
     unsafe {
         (*scan).opaque = CurrentMemoryContext.leak_and_drop_on_delete(scanner).cast();
@@ -506,6 +519,7 @@ pub unsafe extern "C-unwind" fn amrescan(
         let scanner = &mut *(*scan).opaque.cast::<Scanner>();
         scanner.scanning = LazyCell::new(Box::new(|| Box::new(std::iter::empty())));
         scanner.bump.reset();
+        scanner.pinned = cache::pin((*scan).indexRelation);
         let opfamily = opfamily((*scan).indexRelation);
         let index = PostgresRelation::new((*scan).indexRelation);
         let options = SearchOptions {
@@ -517,6 +531,8 @@ pub unsafe extern "C-unwind" fn amrescan(
             io_search: gucs::vchordrq_io_search(),
             io_rerank: gucs::vchordrq_io_rerank(),
             prefilter: gucs::vchordrq_prefilter(),
+            // `pinned` outlives `scanning`, which is the only user of `upper`
+            upper: scanner.pinned.as_ref().map(|pinned| pinned.upper()),
         };
         let fetcher = {
             let hack = scanner.hack;
@@ -543,8 +559,9 @@ pub unsafe extern "C-unwind" fn amrescan(
             rate,
             max_records: gucs::vchordrq_query_sampling_max_records(),
             index: (*(*scan).indexRelation).rd_id.to_u32(),
+            typid: (*(*(*scan).indexRelation).rd_opcintype).to_u32(),
         };
-        // PAY ATTENTATION: `scanning` references `bump`, so `scanning` must be dropped before `bump`.
+        // PAY ATTENTATION: `scanning` references `bump` and `pinned`, so `scanning` must be dropped first.
         let bump = scanner.bump.as_ref();
         scanner.scanning = match opfamily {
             Opfamily::VectorL2
@@ -558,7 +575,18 @@ pub unsafe extern "C-unwind" fn amrescan(
             | Opfamily::Rabitq8Cosine
             | Opfamily::Rabitq4L2
             | Opfamily::Rabitq4Ip
-            | Opfamily::Rabitq4Cosine => {
+            | Opfamily::Rabitq4Cosine
+            | Opfamily::Rabitq2L2
+            | Opfamily::Rabitq2Ip
+            | Opfamily::Rabitq2Cosine
+            | Opfamily::Rabitq1L2
+            | Opfamily::Rabitq1Ip
+            | Opfamily::Rabitq1Cosine
+            | Opfamily::Int8vecL2
+            | Opfamily::Int8vecIp
+            | Opfamily::Bf16vecL2
+            | Opfamily::Bf16vecIp
+            | Opfamily::Bf16vecCosine => {
                 let mut builder = DefaultBuilder::new(opfamily);
                 for i in 0..(*scan).numberOfOrderBys {
                     let data = (*scan).orderByData.add(i as usize);
@@ -581,7 +609,9 @@ pub unsafe extern "C-unwind" fn amrescan(
             Opfamily::VectorMaxsim
             | Opfamily::HalfvecMaxsim
             | Opfamily::Rabitq8Maxsim
-            | Opfamily::Rabitq4Maxsim => {
+            | Opfamily::Rabitq4Maxsim
+            | Opfamily::Rabitq2Maxsim
+            | Opfamily::Rabitq1Maxsim => {
                 let mut builder = MaxsimBuilder::new(opfamily);
                 for i in 0..(*scan).numberOfOrderBys {
                     let data = (*scan).orderByData.add(i as usize);
@@ -639,6 +669,7 @@ pub unsafe extern "C-unwind" fn amendscan(scan: pgrx::pg_sys::IndexScanDesc) {
     let scanner = unsafe { &mut *(*scan).opaque.cast::<Scanner>() };
     scanner.scanning = LazyCell::new(Box::new(|| Box::new(std::iter::empty())));
     scanner.bump.reset();
+    scanner.pinned = None;
 }
 
 type Iter = Box<dyn Iterator<Item = (f32, [u16; 3], bool)>>;
@@ -647,6 +678,7 @@ pub struct Scanner {
     pub hack: Option<NonNull<pgrx::pg_sys::IndexScanState>>,
     scanning: LazyCell<Iter, Box<dyn FnOnce() -> Iter>>,
     bump: Box<bumpalo::Bump>,
+    pinned: Option<cache::Pinned>,
 }
 
 struct Index {
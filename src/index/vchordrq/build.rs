@@ -12,9 +12,13 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
-use simd::{Floating, f16};
+use rabitq::bits::Bits;
+use simd::{Floating, bf16, f16};
+use vector::rabitq1::Rabitq1Owned;
+use vector::rabitq2::Rabitq2Owned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
+use vector::veci8::Veci8Owned;
 use vector::vect::VectOwned;
 use vector::{VectorBorrowed, VectorOwned};
 
@@ -45,6 +49,16 @@ impl Normalize for VectOwned<f16> {
     }
 }
 
+impl Normalize for VectOwned<bf16> {
+    fn normalize(vector: Self) -> Normalized {
+        bf16::vector_to_f32(vector.slice())
+    }
+
+    fn denormalize(x: Normalized) -> Self {
+        Self::new(bf16::vector_from_f32(&x))
+    }
+}
+
 impl Normalize for Rabitq8Owned {
     fn normalize(vector: Self) -> Normalized {
         let vector = vector.as_borrowed();
@@ -100,3 +114,69 @@ impl Normalize for Rabitq4Owned {
         )
     }
 }
+
+impl Normalize for Rabitq2Owned {
+    fn normalize(vector: Self) -> Normalized {
+        let vector = vector.as_borrowed();
+        let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+        let mut result = Vec::with_capacity(vector.dim() as _);
+        for c in vector.unpacked_code() {
+            let base = -0.5 * ((1 << 2) - 1) as f32;
+            result.push((base + c as f32) * scale);
+        }
+        rabitq::rotate::rotate_reversed_inplace(&mut result);
+        result
+    }
+
+    fn denormalize(vector: Normalized) -> Self {
+        let dim = vector.len() as u32;
+        let (metadata, elements) = rabitq::bits::ugly_code(Bits::_2, &vector);
+        let elements = rabitq::bits::packed::pack_code(Bits::_2, &elements);
+        Rabitq2Owned::new(
+            dim,
+            metadata.dis_u_2,
+            metadata.norm_of_lattice,
+            metadata.sum_of_code,
+            f32::reduce_sum_of_abs_x(&vector),
+            elements,
+        )
+    }
+}
+
+impl Normalize for Rabitq1Owned {
+    fn normalize(vector: Self) -> Normalized {
+        let vector = vector.as_borrowed();
+        let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+        let mut result = Vec::with_capacity(vector.dim() as _);
+        for c in vector.unpacked_code() {
+            let base = -0.5 * ((1 << 1) - 1) as f32;
+            result.push((base + c as f32) * scale);
+        }
+        rabitq::rotate::rotate_reversed_inplace(&mut result);
+        result
+    }
+
+    fn denormalize(vector: Normalized) -> Self {
+        let dim = vector.len() as u32;
+        let (metadata, elements) = rabitq::bits::ugly_code(Bits::_1, &vector);
+        let elements = rabitq::bits::packed::pack_code(Bits::_1, &elements);
+        Rabitq1Owned::new(
+            dim,
+            metadata.dis_u_2,
+            metadata.norm_of_lattice,
+            metadata.sum_of_code,
+            f32::reduce_sum_of_abs_x(&vector),
+            elements,
+        )
+    }
+}
+
+impl Normalize for Veci8Owned {
+    fn normalize(vector: Self) -> Normalized {
+        vector.slice().iter().map(|&x| x as f32).collect()
+    }
+
+    fn denormalize(vector: Normalized) -> Self {
+        Self::new(vector.iter().map(|&x| x.round() as i8).collect())
+    }
+}
@@ -0,0 +1,533 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+//! A cache of upper levels of indexes in shared memory.
+//!
+//! The meta tuple, h1 tuples and centroids of an index are decoded by the
+//! first scan and copied into shared memory, so later scans of all backends
+//! read them in place without reading and parsing pages. Entries are keyed by
+//! relfilenode, so they are not used after `REINDEX`, and they are looked up
+//! in a shared hash table under a named LWLock. They are invalidated if lists
+//! are maintained or a relfilenode is built again.
+
+use crate::index::gucs;
+use crate::index::storage::PostgresRelation;
+use crate::index::vchordrq::opclass::opfamily;
+use std::cell::{Cell, RefCell};
+use std::ffi::CStr;
+
+const ENTRIES: usize = 256;
+
+const NAME: &CStr = c"vchordrq shared cache";
+
+const BLCKSZ: usize = pgrx::pg_sys::BLCKSZ as usize;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Key {
+    spc: u32,
+    db: u32,
+    rel: u32,
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    // it's being copied by the backend that pins it
+    Loading,
+    Valid,
+    // it's invalidated, but it's still pinned
+    Dead,
+}
+
+// An entry of the hash table, whose key must be the first field.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    key: Key,
+    state: State,
+    pins: u32,
+    used: u64,
+    // the first word in the arena
+    start: u64,
+    // the number of words
+    len: u64,
+}
+
+#[repr(C)]
+struct Shared {
+    clock: u64,
+    // the number of words in the arena
+    words: u64,
+    hits: u64,
+    misses: u64,
+    entries: u32,
+    // ranges of the arena that are used by entries, sorted by start
+    n: u32,
+    ranges: [(u64, u64); ENTRIES],
+    // the arena follows
+}
+
+const _: () = assert!(size_of::<Shared>() % size_of::<u64>() == 0);
+
+static mut SHARED: *mut Shared = std::ptr::null_mut();
+
+static mut TABLE: *mut pgrx::pg_sys::HTAB = std::ptr::null_mut();
+
+static mut LOCK: *mut pgrx::pg_sys::LWLock = std::ptr::null_mut();
+
+fn size() -> usize {
+    size_of::<Shared>() + gucs::vchordrq_shared_cache_size() * BLCKSZ
+}
+
+fn table_size() -> usize {
+    unsafe { pgrx::pg_sys::hash_estimate_size(ENTRIES as _, size_of::<Entry>()) }
+}
+
+struct Inner<'a> {
+    shared: &'a mut Shared,
+    table: *mut pgrx::pg_sys::HTAB,
+}
+
+impl Inner<'_> {
+    fn find(&mut self, key: Key) -> Option<&mut Entry> {
+        unsafe {
+            let entry = pgrx::pg_sys::hash_search(
+                self.table,
+                (&raw const key).cast(),
+                pgrx::pg_sys::HASHACTION::HASH_FIND,
+                std::ptr::null_mut(),
+            );
+            entry.cast::<Entry>().as_mut()
+        }
+    }
+
+    fn remove(&mut self, key: Key) {
+        let Some(&mut Entry { start, .. }) = self.find(key) else {
+            return;
+        };
+        let n = self.shared.n as usize;
+        let ranges = &mut self.shared.ranges[..n];
+        let i = ranges
+            .binary_search_by_key(&start, |&(start, _)| start)
+            .expect("corrupted shared cache");
+        ranges.copy_within(i + 1.., i);
+        self.shared.n -= 1;
+        self.shared.entries -= 1;
+        unsafe {
+            pgrx::pg_sys::hash_search(
+                self.table,
+                (&raw const key).cast(),
+                pgrx::pg_sys::HASHACTION::HASH_REMOVE,
+                std::ptr::null_mut(),
+            );
+        }
+    }
+
+    fn unpin(&mut self, key: Key, n: u32) {
+        let Some(entry) = self.find(key) else {
+            return;
+        };
+        entry.pins -= n;
+        if entry.pins == 0 && entry.state != State::Valid {
+            self.remove(key);
+        }
+    }
+
+    fn invalidate(&mut self, key: Key) {
+        let Some(entry) = self.find(key) else {
+            return;
+        };
+        if entry.pins == 0 {
+            self.remove(key);
+        } else {
+            entry.state = State::Dead;
+        }
+    }
+
+    // returns the index of the first range after the gap and the start of the gap
+    fn fit(&self, len: u64) -> Option<(usize, u64)> {
+        let ranges = &self.shared.ranges[..self.shared.n as usize];
+        let mut cursor = 0_u64;
+        for (i, &(start, used)) in ranges.iter().enumerate() {
+            if start - cursor >= len {
+                return Some((i, cursor));
+            }
+            cursor = start + used;
+        }
+        (self.shared.words - cursor >= len).then_some((ranges.len(), cursor))
+    }
+
+    fn evict(&mut self) -> bool {
+        let mut victim = None::<(u64, Key)>;
+        unsafe {
+            let mut status = std::mem::zeroed::<pgrx::pg_sys::HASH_SEQ_STATUS>();
+            pgrx::pg_sys::hash_seq_init(&mut status, self.table);
+            loop {
+                let entry = pgrx::pg_sys::hash_seq_search(&mut status).cast::<Entry>();
+                let Some(entry) = entry.as_ref() else {
+                    break;
+                };
+                if entry.state == State::Valid
+                    && entry.pins == 0
+                    && victim.is_none_or(|(used, _)| entry.used < used)
+                {
+                    victim = Some((entry.used, entry.key));
+                }
+            }
+        }
+        if let Some((_, key)) = victim {
+            self.remove(key);
+        }
+        victim.is_some()
+    }
+
+    fn reserve(&mut self, key: Key, len: u64) -> Option<Entry> {
+        loop {
+            if (self.shared.entries as usize) < ENTRIES
+                && let Some((i, start)) = self.fit(len)
+            {
+                let n = self.shared.n as usize;
+                self.shared.ranges.copy_within(i..n, i + 1);
+                self.shared.ranges[i] = (start, len);
+                self.shared.n += 1;
+                self.shared.entries += 1;
+                self.shared.clock += 1;
+                let entry = Entry {
+                    key,
+                    state: State::Loading,
+                    pins: 1,
+                    used: self.shared.clock,
+                    start,
+                    len,
+                };
+                unsafe {
+                    let mut found = false;
+                    let slot = pgrx::pg_sys::hash_search(
+                        self.table,
+                        (&raw const key).cast(),
+                        pgrx::pg_sys::HASHACTION::HASH_ENTER,
+                        &mut found,
+                    );
+                    assert!(!found, "corrupted shared cache");
+                    slot.cast::<Entry>().write(entry);
+                }
+                return Some(entry);
+            }
+            // evict the least recently used entry
+            if !self.evict() {
+                return None;
+            }
+        }
+    }
+}
+
+fn locked<T>(f: impl FnOnce(&mut Inner) -> T) -> Option<T> {
+    use pgrx::pg_sys::{LWLockAcquire, LWLockMode, LWLockRelease};
+    unsafe {
+        if SHARED.is_null() {
+            return None;
+        }
+        LWLockAcquire(LOCK, LWLockMode::LW_EXCLUSIVE);
+        let result = f(&mut Inner {
+            shared: &mut *SHARED,
+            table: TABLE,
+        });
+        LWLockRelease(LOCK);
+        Some(result)
+    }
+}
+
+unsafe fn view(entry: &Entry) -> &'static [u64] {
+    unsafe { std::slice::from_raw_parts(arena().add(entry.start as _), entry.len as _) }
+}
+
+unsafe fn arena() -> *mut u64 {
+    unsafe { SHARED.add(1).cast() }
+}
+
+unsafe fn key(index_relation: pgrx::pg_sys::Relation) -> Key {
+    unsafe {
+        #[cfg(any(feature = "pg14", feature = "pg15"))]
+        let key = {
+            let node = (*index_relation).rd_node;
+            Key {
+                spc: node.spcNode.to_u32(),
+                db: node.dbNode.to_u32(),
+                rel: node.relNode.to_u32(),
+            }
+        };
+        #[cfg(any(feature = "pg16", feature = "pg17", feature = "pg18"))]
+        let key = {
+            let locator = (*index_relation).rd_locator;
+            Key {
+                spc: locator.spcOid.to_u32(),
+                db: locator.dbOid.to_u32(),
+                rel: locator.relNumber.to_u32(),
+            }
+        };
+        key
+    }
+}
+
+std::thread_local! {
+    // pins of this backend, which are released at exit if they are leaked
+    static PINS: RefCell<Vec<(Key, u32)>> = const { RefCell::new(Vec::new()) };
+    static REGISTERED: Cell<bool> = const { Cell::new(false) };
+    // indexes that are too large to be cached, so they are not decoded again
+    static OVERSIZED: RefCell<Vec<Key>> = const { RefCell::new(Vec::new()) };
+}
+
+#[pgrx::pg_guard]
+unsafe extern "C-unwind" fn release(_code: std::os::raw::c_int, _arg: pgrx::pg_sys::Datum) {
+    let pins = PINS.take();
+    locked(|inner| {
+        for (key, n) in pins {
+            inner.unpin(key, n);
+        }
+    });
+}
+
+/// A pinned entry, which is not evicted until it's dropped.
+pub struct Pinned {
+    key: Key,
+    upper: Option<vchordrq::Upper<'static>>,
+}
+
+impl Pinned {
+    fn new(key: Key) -> Self {
+        if !REGISTERED.replace(true) {
+            unsafe {
+                pgrx::pg_sys::before_shmem_exit(Some(release), pgrx::pg_sys::Datum::from(0));
+            }
+        }
+        PINS.with_borrow_mut(|pins| match pins.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, n)) => *n += 1,
+            None => pins.push((key, 1)),
+        });
+        Self { key, upper: None }
+    }
+
+    /// Returns the decoded upper levels.
+    ///
+    /// # Safety
+    ///
+    /// The result must not be used after the pin is dropped.
+    pub unsafe fn upper(&self) -> vchordrq::Upper<'static> {
+        self.upper.expect("the entry is not loaded")
+    }
+}
+
+impl Drop for Pinned {
+    fn drop(&mut self) {
+        // the pin is already released if the backend is exiting
+        let held = PINS.with_borrow_mut(|pins| {
+            let Some(i) = pins.iter().position(|(k, _)| *k == self.key) else {
+                return false;
+            };
+            pins[i].1 -= 1;
+            if pins[i].1 == 0 {
+                pins.swap_remove(i);
+            }
+            true
+        });
+        if held {
+            locked(|inner| inner.unpin(self.key, 1));
+        }
+    }
+}
+
+/// Pins the cached upper levels of an index, and caches them if they are not
+/// cached.
+///
+/// Returns `None` if the cache is disabled or the index cannot be cached now.
+pub unsafe fn pin(index_relation: pgrx::pg_sys::Relation) -> Option<Pinned> {
+    if unsafe { SHARED }.is_null() {
+        return None;
+    }
+    unsafe {
+        // The cache is invalidated on the primary, so standbys never use it.
+        if pgrx::pg_sys::RecoveryInProgress() {
+            return None;
+        }
+        // Relfilenodes of temporary relations are not unique across backends.
+        if (*(*index_relation).rd_rel).relpersistence as u8 == b't' {
+            return None;
+        }
+    }
+    let key = unsafe { key(index_relation) };
+    let hit = |inner: &mut Inner| {
+        inner.shared.clock += 1;
+        let clock = inner.shared.clock;
+        let entry = inner.find(key).filter(|entry| entry.state == State::Valid);
+        let Some(entry) = entry else {
+            inner.shared.misses += 1;
+            return None;
+        };
+        entry.pins += 1;
+        entry.used = clock;
+        let entry = *entry;
+        inner.shared.hits += 1;
+        Some(entry)
+    };
+    if let Some(entry) = locked(hit)? {
+        let mut pinned = Pinned::new(key);
+        pinned.upper = Some(vchordrq::Upper::new(unsafe { view(&entry) }));
+        return Some(pinned);
+    }
+    if OVERSIZED.with_borrow(|oversized| oversized.contains(&key)) {
+        return None;
+    }
+    let index = unsafe { PostgresRelation::<vchordrq::Opaque>::new(index_relation) };
+    let opfamily = unsafe { opfamily(index_relation) };
+    let upper = crate::index::vchordrq::dispatch::decode_upper(opfamily, &index);
+    if upper.len() * size_of::<u64>() > gucs::vchordrq_shared_cache_size() * BLCKSZ {
+        OVERSIZED.with_borrow_mut(|oversized| oversized.push(key));
+        return None;
+    }
+    let entry = locked(|inner| {
+        if inner.find(key).is_some() {
+            return None;
+        }
+        inner.reserve(key, upper.len() as u64)
+    })??;
+    // It's unpinned and freed if an error is raised while loading.
+    let mut pinned = Pinned::new(key);
+    unsafe {
+        std::ptr::copy_nonoverlapping(upper.as_ptr(), arena().add(entry.start as _), upper.len());
+    }
+    let valid = locked(|inner| {
+        let entry = inner.find(key).expect("corrupted shared cache");
+        if entry.state == State::Loading {
+            entry.state = State::Valid;
+        }
+        entry.state == State::Valid
+    })?;
+    if !valid {
+        return None;
+    }
+    pinned.upper = Some(vchordrq::Upper::new(unsafe { view(&entry) }));
+    Some(pinned)
+}
+
+/// Returns the number of hits and misses of the cache.
+pub fn stats() -> (u64, u64) {
+    locked(|inner| (inner.shared.hits, inner.shared.misses)).unwrap_or((0, 0))
+}
+
+/// Invalidates the cached upper levels of an index.
+pub unsafe fn invalidate(index_relation: pgrx::pg_sys::Relation) {
+    let key = unsafe { key(index_relation) };
+    locked(|inner| inner.invalidate(key));
+}
+
+/// Invalidates the cached upper levels of an index when it's created and
+/// when it's dropped, so upper levels cached while lists are rewritten are
+/// not used.
+pub struct Invalidation(Key);
+
+impl Invalidation {
+    pub unsafe fn new(index_relation: pgrx::pg_sys::Relation) -> Self {
+        unsafe {
+            invalidate(index_relation);
+            Self(key(index_relation))
+        }
+    }
+}
+
+impl Drop for Invalidation {
+    fn drop(&mut self) {
+        locked(|inner| inner.invalidate(self.0));
+    }
+}
+
+#[cfg(any(feature = "pg15", feature = "pg16", feature = "pg17", feature = "pg18"))]
+static mut PREV_SHMEM_REQUEST: pgrx::pg_sys::shmem_request_hook_type = None;
+
+static mut PREV_SHMEM_STARTUP: pgrx::pg_sys::shmem_startup_hook_type = None;
+
+#[cfg(any(feature = "pg15", feature = "pg16", feature = "pg17", feature = "pg18"))]
+#[pgrx::pg_guard]
+unsafe extern "C-unwind" fn shmem_request() {
+    unsafe {
+        use pgrx::pg_sys::submodules::ffi::pg_guard_ffi_boundary;
+        if let Some(prev_shmem_request) = PREV_SHMEM_REQUEST {
+            #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
+            pg_guard_ffi_boundary(|| prev_shmem_request());
+        }
+        pgrx::pg_sys::RequestAddinShmemSpace(size() + table_size());
+        pgrx::pg_sys::RequestNamedLWLockTranche(NAME.as_ptr(), 1);
+    }
+}
+
+#[pgrx::pg_guard]
+unsafe extern "C-unwind" fn shmem_startup() {
+    unsafe {
+        use pgrx::pg_sys::submodules::ffi::pg_guard_ffi_boundary;
+        if let Some(prev_shmem_startup) = PREV_SHMEM_STARTUP {
+            #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
+            pg_guard_ffi_boundary(|| prev_shmem_startup());
+        }
+        use pgrx::pg_sys::{LWLockAcquire, LWLockMode, LWLockRelease};
+        let lock = &raw mut (*pgrx::pg_sys::GetNamedLWLockTranche(NAME.as_ptr())).lock;
+        LWLockAcquire(lock, LWLockMode::LW_EXCLUSIVE);
+        let mut found = false;
+        let shared =
+            pgrx::pg_sys::ShmemInitStruct(NAME.as_ptr(), size(), &mut found).cast::<Shared>();
+        if !found {
+            (&raw mut (*shared).clock).write(0);
+            (&raw mut (*shared).words)
+                .write(((gucs::vchordrq_shared_cache_size() * BLCKSZ) / size_of::<u64>()) as u64);
+            (&raw mut (*shared).hits).write(0);
+            (&raw mut (*shared).misses).write(0);
+            (&raw mut (*shared).entries).write(0);
+            (&raw mut (*shared).n).write(0);
+        }
+        let mut info = std::mem::zeroed::<pgrx::pg_sys::HASHCTL>();
+        info.keysize = size_of::<Key>();
+        info.entrysize = size_of::<Entry>();
+        let table = pgrx::pg_sys::ShmemInitHash(
+            c"vchordrq shared cache table".as_ptr(),
+            ENTRIES as _,
+            ENTRIES as _,
+            &mut info,
+            (pgrx::pg_sys::HASH_ELEM | pgrx::pg_sys::HASH_BLOBS) as _,
+        );
+        SHARED = shared;
+        TABLE = table;
+        LOCK = lock;
+        LWLockRelease(lock);
+    }
+}
+
+pub fn init() {
+    assert!(crate::is_main());
+    if gucs::vchordrq_shared_cache_size() == 0 {
+        return;
+    }
+    unsafe {
+        #[cfg(feature = "pg14")]
+        {
+            pgrx::pg_sys::RequestAddinShmemSpace(size() + table_size());
+            pgrx::pg_sys::RequestNamedLWLockTranche(NAME.as_ptr(), 1);
+        }
+        #[cfg(any(feature = "pg15", feature = "pg16", feature = "pg17", feature = "pg18"))]
+        {
+            PREV_SHMEM_REQUEST = pgrx::pg_sys::shmem_request_hook;
+            pgrx::pg_sys::shmem_request_hook = Some(shmem_request);
+        }
+        PREV_SHMEM_STARTUP = pgrx::pg_sys::shmem_startup_hook;
+        pgrx::pg_sys::shmem_startup_hook = Some(shmem_startup);
+    }
+}
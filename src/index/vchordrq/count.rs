@@ -0,0 +1,51 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::recorder::Counter;
+use index::prefetcher::Sequence;
+
+pub struct Count<S> {
+    sequence: S,
+    counter: Counter,
+}
+
+impl<S> Sequence for Count<S>
+where
+    S: Sequence,
+{
+    type Item = S::Item;
+
+    type Inner = S::Inner;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.sequence.next()?;
+        self.counter.add(1);
+        Some(item)
+    }
+
+    fn peek(&mut self) -> Option<&Self::Item> {
+        self.sequence.peek()
+    }
+
+    fn into_inner(self) -> Self::Inner {
+        self.sequence.into_inner()
+    }
+}
+
+pub fn count<S>(sequence: S, counter: &Counter) -> Count<S> {
+    Count {
+        sequence,
+        counter: counter.clone(),
+    }
+}
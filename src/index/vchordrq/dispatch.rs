@@ -18,16 +18,19 @@ use index::bump::Bump;
 use index::fetch::Fetch;
 use index::prefetcher::*;
 use index::relation::{
-    Hints, Page, RelationPrefetch, RelationRead, RelationReadStream, RelationWrite,
+    Hints, Page, RelationPrefetch, RelationRead, RelationReadStream, RelationTruncate,
+    RelationWrite,
 };
 use index_accessor::{Dot, L2S};
-use simd::f16;
+use simd::{bf16, f16};
 use std::collections::BinaryHeap;
 use std::num::NonZero;
 use vchordrq::operator::Op;
 use vchordrq::types::*;
 use vchordrq::{FastHeap, InsertChooser, MaintainChooser};
 use vector::VectorOwned;
+use vector::rabitq1::Rabitq1Owned;
+use vector::rabitq2::Rabitq2Owned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
 use vector::vect::{VectBorrowed, VectOwned};
@@ -51,6 +54,20 @@ where
         (VectorKind::Vecf16, DistanceKind::Dot) => {
             vchordrq::prewarm::<_, Op<VectOwned<f16>, Dot>>(index, height, make_h0_plain_prefetcher)
         }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordrq::prewarm::<_, Op<VectOwned<bf16>, L2S>>(
+                index,
+                height,
+                make_h0_plain_prefetcher,
+            )
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordrq::prewarm::<_, Op<VectOwned<bf16>, Dot>>(
+                index,
+                height,
+                make_h0_plain_prefetcher,
+            )
+        }
         (VectorKind::Rabitq8, DistanceKind::L2S) => {
             vchordrq::prewarm::<_, Op<Rabitq8Owned, L2S>>(index, height, make_h0_plain_prefetcher)
         }
@@ -63,6 +80,144 @@ where
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
             vchordrq::prewarm::<_, Op<Rabitq4Owned, Dot>>(index, height, make_h0_plain_prefetcher)
         }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            vchordrq::prewarm::<_, Op<Rabitq2Owned, L2S>>(index, height, make_h0_plain_prefetcher)
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            vchordrq::prewarm::<_, Op<Rabitq2Owned, Dot>>(index, height, make_h0_plain_prefetcher)
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            vchordrq::prewarm::<_, Op<Rabitq1Owned, L2S>>(index, height, make_h0_plain_prefetcher)
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            vchordrq::prewarm::<_, Op<Rabitq1Owned, Dot>>(index, height, make_h0_plain_prefetcher)
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => {
+            vchordrq::prewarm::<_, Op<Veci8Owned, L2S>>(index, height, make_h0_plain_prefetcher)
+        }
+        (VectorKind::Veci8, DistanceKind::Dot) => {
+            vchordrq::prewarm::<_, Op<Veci8Owned, Dot>>(index, height, make_h0_plain_prefetcher)
+        }
+    }
+}
+
+pub fn decode_upper<R>(opfamily: Opfamily, index: &R) -> Vec<u64>
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => {
+            vchordrq::decode_upper::<_, Op<VectOwned<f32>, L2S>>(index)
+        }
+        (VectorKind::Vecf32, DistanceKind::Dot) => {
+            vchordrq::decode_upper::<_, Op<VectOwned<f32>, Dot>>(index)
+        }
+        (VectorKind::Vecf16, DistanceKind::L2S) => {
+            vchordrq::decode_upper::<_, Op<VectOwned<f16>, L2S>>(index)
+        }
+        (VectorKind::Vecf16, DistanceKind::Dot) => {
+            vchordrq::decode_upper::<_, Op<VectOwned<f16>, Dot>>(index)
+        }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordrq::decode_upper::<_, Op<VectOwned<bf16>, L2S>>(index)
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordrq::decode_upper::<_, Op<VectOwned<bf16>, Dot>>(index)
+        }
+        (VectorKind::Rabitq8, DistanceKind::L2S) => {
+            vchordrq::decode_upper::<_, Op<Rabitq8Owned, L2S>>(index)
+        }
+        (VectorKind::Rabitq8, DistanceKind::Dot) => {
+            vchordrq::decode_upper::<_, Op<Rabitq8Owned, Dot>>(index)
+        }
+        (VectorKind::Rabitq4, DistanceKind::L2S) => {
+            vchordrq::decode_upper::<_, Op<Rabitq4Owned, L2S>>(index)
+        }
+        (VectorKind::Rabitq4, DistanceKind::Dot) => {
+            vchordrq::decode_upper::<_, Op<Rabitq4Owned, Dot>>(index)
+        }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            vchordrq::decode_upper::<_, Op<Rabitq2Owned, L2S>>(index)
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            vchordrq::decode_upper::<_, Op<Rabitq2Owned, Dot>>(index)
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            vchordrq::decode_upper::<_, Op<Rabitq1Owned, L2S>>(index)
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            vchordrq::decode_upper::<_, Op<Rabitq1Owned, Dot>>(index)
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => {
+            vchordrq::decode_upper::<_, Op<Veci8Owned, L2S>>(index)
+        }
+        (VectorKind::Veci8, DistanceKind::Dot) => {
+            vchordrq::decode_upper::<_, Op<Veci8Owned, Dot>>(index)
+        }
+    }
+}
+
+pub fn verify<R>(
+    opfamily: Opfamily,
+    index: &R,
+    nblocks: u32,
+    check: impl Fn(),
+    callback: impl FnMut(NonZero<u64>),
+) -> Vec<vchordrq::Problem>
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<VectOwned<f32>, L2S>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Vecf32, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<VectOwned<f32>, Dot>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Vecf16, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<VectOwned<f16>, L2S>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Vecf16, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<VectOwned<f16>, Dot>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<VectOwned<bf16>, L2S>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<VectOwned<bf16>, Dot>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Rabitq8, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<Rabitq8Owned, L2S>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Rabitq8, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<Rabitq8Owned, Dot>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Rabitq4, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<Rabitq4Owned, L2S>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Rabitq4, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<Rabitq4Owned, Dot>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<Rabitq2Owned, L2S>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<Rabitq2Owned, Dot>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<Rabitq1Owned, L2S>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<Rabitq1Owned, Dot>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<Veci8Owned, L2S>>(index, nblocks, check, callback)
+        }
+        (VectorKind::Veci8, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<Veci8Owned, Dot>>(index, nblocks, check, callback)
+        }
     }
 }
 
@@ -92,6 +247,14 @@ pub fn bulkdelete<R>(
             vchordrq::bulkdelete::<_, Op<VectOwned<f16>, Dot>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<VectOwned<f16>, Dot>>(index, &check, &callback);
         }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordrq::bulkdelete::<_, Op<VectOwned<bf16>, L2S>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<VectOwned<bf16>, L2S>>(index, &check, &callback);
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordrq::bulkdelete::<_, Op<VectOwned<bf16>, Dot>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<VectOwned<bf16>, Dot>>(index, &check, &callback);
+        }
         (VectorKind::Rabitq8, DistanceKind::L2S) => {
             vchordrq::bulkdelete::<_, Op<Rabitq8Owned, L2S>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<Rabitq8Owned, L2S>>(index, &check, &callback);
@@ -108,6 +271,30 @@ pub fn bulkdelete<R>(
             vchordrq::bulkdelete::<_, Op<Rabitq4Owned, Dot>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<Rabitq4Owned, Dot>>(index, &check, &callback);
         }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            vchordrq::bulkdelete::<_, Op<Rabitq2Owned, L2S>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<Rabitq2Owned, L2S>>(index, &check, &callback);
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            vchordrq::bulkdelete::<_, Op<Rabitq2Owned, Dot>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<Rabitq2Owned, Dot>>(index, &check, &callback);
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            vchordrq::bulkdelete::<_, Op<Rabitq1Owned, L2S>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<Rabitq1Owned, L2S>>(index, &check, &callback);
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            vchordrq::bulkdelete::<_, Op<Rabitq1Owned, Dot>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<Rabitq1Owned, Dot>>(index, &check, &callback);
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => {
+            vchordrq::bulkdelete::<_, Op<Veci8Owned, L2S>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<Veci8Owned, L2S>>(index, &check, &callback);
+        }
+        (VectorKind::Veci8, DistanceKind::Dot) => {
+            vchordrq::bulkdelete::<_, Op<Veci8Owned, Dot>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<Veci8Owned, Dot>>(index, &check, &callback);
+        }
     }
 }
 
@@ -154,6 +341,22 @@ pub fn maintain<R>(
                 check,
             )
         }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordrq::maintain::<_, Op<VectOwned<bf16>, L2S>>(
+                index,
+                make_h0_plain_prefetcher,
+                chooser,
+                check,
+            )
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordrq::maintain::<_, Op<VectOwned<bf16>, Dot>>(
+                index,
+                make_h0_plain_prefetcher,
+                chooser,
+                check,
+            )
+        }
         (VectorKind::Rabitq8, DistanceKind::L2S) => vchordrq::maintain::<_, Op<Rabitq8Owned, L2S>>(
             index,
             make_h0_plain_prefetcher,
@@ -178,6 +381,42 @@ pub fn maintain<R>(
             chooser,
             check,
         ),
+        (VectorKind::Rabitq2, DistanceKind::L2S) => vchordrq::maintain::<_, Op<Rabitq2Owned, L2S>>(
+            index,
+            make_h0_plain_prefetcher,
+            chooser,
+            check,
+        ),
+        (VectorKind::Rabitq2, DistanceKind::Dot) => vchordrq::maintain::<_, Op<Rabitq2Owned, Dot>>(
+            index,
+            make_h0_plain_prefetcher,
+            chooser,
+            check,
+        ),
+        (VectorKind::Rabitq1, DistanceKind::L2S) => vchordrq::maintain::<_, Op<Rabitq1Owned, L2S>>(
+            index,
+            make_h0_plain_prefetcher,
+            chooser,
+            check,
+        ),
+        (VectorKind::Rabitq1, DistanceKind::Dot) => vchordrq::maintain::<_, Op<Rabitq1Owned, Dot>>(
+            index,
+            make_h0_plain_prefetcher,
+            chooser,
+            check,
+        ),
+        (VectorKind::Veci8, DistanceKind::L2S) => vchordrq::maintain::<_, Op<Veci8Owned, L2S>>(
+            index,
+            make_h0_plain_prefetcher,
+            chooser,
+            check,
+        ),
+        (VectorKind::Veci8, DistanceKind::Dot) => vchordrq::maintain::<_, Op<Veci8Owned, Dot>>(
+            index,
+            make_h0_plain_prefetcher,
+            chooser,
+            check,
+        ),
     };
     pgrx::debug1!(
         "maintain: number_of_formerly_allocated_pages = {}",
@@ -193,6 +432,81 @@ pub fn maintain<R>(
     );
 }
 
+pub fn compact<R>(opfamily: Opfamily, index: &R, check: impl Fn()) -> Option<usize>
+where
+    R: RelationRead + RelationWrite + RelationTruncate,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    let make_h0_plain_prefetcher = MakeH0PlainPrefetcher { index };
+    let compact = match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => {
+            vchordrq::compact::<_, Op<VectOwned<f32>, L2S>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Vecf32, DistanceKind::Dot) => {
+            vchordrq::compact::<_, Op<VectOwned<f32>, Dot>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Vecf16, DistanceKind::L2S) => {
+            vchordrq::compact::<_, Op<VectOwned<f16>, L2S>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Vecf16, DistanceKind::Dot) => {
+            vchordrq::compact::<_, Op<VectOwned<f16>, Dot>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Vecbf16, DistanceKind::L2S) => {
+            vchordrq::compact::<_, Op<VectOwned<bf16>, L2S>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Vecbf16, DistanceKind::Dot) => {
+            vchordrq::compact::<_, Op<VectOwned<bf16>, Dot>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Rabitq8, DistanceKind::L2S) => {
+            vchordrq::compact::<_, Op<Rabitq8Owned, L2S>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Rabitq8, DistanceKind::Dot) => {
+            vchordrq::compact::<_, Op<Rabitq8Owned, Dot>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Rabitq4, DistanceKind::L2S) => {
+            vchordrq::compact::<_, Op<Rabitq4Owned, L2S>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Rabitq4, DistanceKind::Dot) => {
+            vchordrq::compact::<_, Op<Rabitq4Owned, Dot>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Rabitq2, DistanceKind::L2S) => {
+            vchordrq::compact::<_, Op<Rabitq2Owned, L2S>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Rabitq2, DistanceKind::Dot) => {
+            vchordrq::compact::<_, Op<Rabitq2Owned, Dot>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Rabitq1, DistanceKind::L2S) => {
+            vchordrq::compact::<_, Op<Rabitq1Owned, L2S>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Rabitq1, DistanceKind::Dot) => {
+            vchordrq::compact::<_, Op<Rabitq1Owned, Dot>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Veci8, DistanceKind::L2S) => {
+            vchordrq::compact::<_, Op<Veci8Owned, L2S>>(index, make_h0_plain_prefetcher, check)
+        }
+        (VectorKind::Veci8, DistanceKind::Dot) => {
+            vchordrq::compact::<_, Op<Veci8Owned, Dot>>(index, make_h0_plain_prefetcher, check)
+        }
+    };
+    pgrx::debug1!(
+        "compact: number_of_freshly_allocated_pages = {}",
+        compact.number_of_freshly_allocated_pages
+    );
+    pgrx::debug1!(
+        "compact: number_of_moved_vectors = {}",
+        compact.number_of_moved_vectors
+    );
+    pgrx::debug1!(
+        "compact: number_of_free_pages = {}",
+        compact.number_of_free_pages
+    );
+    pgrx::debug1!(
+        "compact: number_of_truncated_pages = {:?}",
+        compact.number_of_truncated_pages
+    );
+    compact.number_of_truncated_pages
+}
+
 pub fn build<R>(
     vector_options: VectorOptions,
     vchordrq_options: VchordrqIndexOptions,
@@ -227,6 +541,18 @@ pub fn build<R>(
             index,
             map_structures(structures, Normalize::denormalize),
         ),
+        (VectorKind::Vecbf16, DistanceKind::L2S) => vchordrq::build::<_, Op<VectOwned<bf16>, L2S>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
+        (VectorKind::Vecbf16, DistanceKind::Dot) => vchordrq::build::<_, Op<VectOwned<bf16>, Dot>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
         (VectorKind::Rabitq8, DistanceKind::L2S) => vchordrq::build::<_, Op<Rabitq8Owned, L2S>>(
             vector_options,
             vchordrq_options,
@@ -251,6 +577,42 @@ pub fn build<R>(
             index,
             map_structures(structures, Normalize::denormalize),
         ),
+        (VectorKind::Rabitq2, DistanceKind::L2S) => vchordrq::build::<_, Op<Rabitq2Owned, L2S>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
+        (VectorKind::Rabitq2, DistanceKind::Dot) => vchordrq::build::<_, Op<Rabitq2Owned, Dot>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
+        (VectorKind::Rabitq1, DistanceKind::L2S) => vchordrq::build::<_, Op<Rabitq1Owned, L2S>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
+        (VectorKind::Rabitq1, DistanceKind::Dot) => vchordrq::build::<_, Op<Rabitq1Owned, Dot>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
+        (VectorKind::Veci8, DistanceKind::L2S) => vchordrq::build::<_, Op<Veci8Owned, L2S>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
+        (VectorKind::Veci8, DistanceKind::Dot) => vchordrq::build::<_, Op<Veci8Owned, Dot>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
     }
 }
 
@@ -349,6 +711,46 @@ pub fn insert<R>(
                 skip_freespaces,
             )
         }
+        (OwnedVector::Vecbf16(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Vecbf16);
+            let projected = RandomProject::project(vector.as_borrowed());
+            let key = vchordrq::insert_vector::<_, Op<VectOwned<bf16>, L2S>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<VectOwned<bf16>, L2S>>(
+                index,
+                payload,
+                projected.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+        (OwnedVector::Vecbf16(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Vecbf16);
+            let projected = RandomProject::project(vector.as_borrowed());
+            let key = vchordrq::insert_vector::<_, Op<VectOwned<bf16>, Dot>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<VectOwned<bf16>, Dot>>(
+                index,
+                payload,
+                projected.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
         (OwnedVector::Rabitq8(vector), DistanceKind::Dot) => {
             assert!(opfamily.vector_kind() == VectorKind::Rabitq8);
             let key = vchordrq::insert_vector::<_, Op<Rabitq8Owned, Dot>>(
@@ -425,6 +827,199 @@ pub fn insert<R>(
                 skip_freespaces,
             )
         }
+        (OwnedVector::Rabitq2(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq2);
+            let key = vchordrq::insert_vector::<_, Op<Rabitq2Owned, Dot>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<Rabitq2Owned, Dot>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+        (OwnedVector::Rabitq2(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq2);
+            let key = vchordrq::insert_vector::<_, Op<Rabitq2Owned, L2S>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<Rabitq2Owned, L2S>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+        (OwnedVector::Rabitq1(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq1);
+            let key = vchordrq::insert_vector::<_, Op<Rabitq1Owned, Dot>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<Rabitq1Owned, Dot>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+        (OwnedVector::Rabitq1(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq1);
+            let key = vchordrq::insert_vector::<_, Op<Rabitq1Owned, L2S>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<Rabitq1Owned, L2S>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+        (OwnedVector::Veci8(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Veci8);
+            let key = vchordrq::insert_vector::<_, Op<Veci8Owned, Dot>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<Veci8Owned, Dot>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+        (OwnedVector::Veci8(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Veci8);
+            let key = vchordrq::insert_vector::<_, Op<Veci8Owned, L2S>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<Veci8Owned, L2S>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+    }
+}
+
+pub fn assign<R>(opfamily: Opfamily, index: &R, vector: OwnedVector, depth: u32) -> Vec<u32>
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    match (vector, opfamily.distance_kind()) {
+        (OwnedVector::Vecf32(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Vecf32);
+            let projected = RandomProject::project(vector.as_borrowed());
+            vchordrq::assign::<_, Op<VectOwned<f32>, L2S>>(index, projected.as_borrowed(), depth)
+        }
+        (OwnedVector::Vecf32(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Vecf32);
+            let projected = RandomProject::project(vector.as_borrowed());
+            vchordrq::assign::<_, Op<VectOwned<f32>, Dot>>(index, projected.as_borrowed(), depth)
+        }
+        (OwnedVector::Vecf16(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Vecf16);
+            let projected = RandomProject::project(vector.as_borrowed());
+            vchordrq::assign::<_, Op<VectOwned<f16>, L2S>>(index, projected.as_borrowed(), depth)
+        }
+        (OwnedVector::Vecf16(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Vecf16);
+            let projected = RandomProject::project(vector.as_borrowed());
+            vchordrq::assign::<_, Op<VectOwned<f16>, Dot>>(index, projected.as_borrowed(), depth)
+        }
+        (OwnedVector::Vecbf16(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Vecbf16);
+            let projected = RandomProject::project(vector.as_borrowed());
+            vchordrq::assign::<_, Op<VectOwned<bf16>, L2S>>(index, projected.as_borrowed(), depth)
+        }
+        (OwnedVector::Vecbf16(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Vecbf16);
+            let projected = RandomProject::project(vector.as_borrowed());
+            vchordrq::assign::<_, Op<VectOwned<bf16>, Dot>>(index, projected.as_borrowed(), depth)
+        }
+        (OwnedVector::Rabitq8(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq8);
+            vchordrq::assign::<_, Op<Rabitq8Owned, L2S>>(index, vector.as_borrowed(), depth)
+        }
+        (OwnedVector::Rabitq8(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq8);
+            vchordrq::assign::<_, Op<Rabitq8Owned, Dot>>(index, vector.as_borrowed(), depth)
+        }
+        (OwnedVector::Rabitq4(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq4);
+            vchordrq::assign::<_, Op<Rabitq4Owned, L2S>>(index, vector.as_borrowed(), depth)
+        }
+        (OwnedVector::Rabitq4(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq4);
+            vchordrq::assign::<_, Op<Rabitq4Owned, Dot>>(index, vector.as_borrowed(), depth)
+        }
+        (OwnedVector::Rabitq2(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq2);
+            vchordrq::assign::<_, Op<Rabitq2Owned, L2S>>(index, vector.as_borrowed(), depth)
+        }
+        (OwnedVector::Rabitq2(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq2);
+            vchordrq::assign::<_, Op<Rabitq2Owned, Dot>>(index, vector.as_borrowed(), depth)
+        }
+        (OwnedVector::Rabitq1(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq1);
+            vchordrq::assign::<_, Op<Rabitq1Owned, L2S>>(index, vector.as_borrowed(), depth)
+        }
+        (OwnedVector::Rabitq1(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Rabitq1);
+            vchordrq::assign::<_, Op<Rabitq1Owned, Dot>>(index, vector.as_borrowed(), depth)
+        }
+        (OwnedVector::Veci8(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Veci8);
+            vchordrq::assign::<_, Op<Veci8Owned, L2S>>(index, vector.as_borrowed(), depth)
+        }
+        (OwnedVector::Veci8(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Veci8);
+            vchordrq::assign::<_, Op<Veci8Owned, Dot>>(index, vector.as_borrowed(), depth)
+        }
     }
 }
 
@@ -466,6 +1061,16 @@ impl RandomProject for VectBorrowed<'_, f16> {
     }
 }
 
+impl RandomProject for VectBorrowed<'_, bf16> {
+    type Output = VectOwned<bf16>;
+    fn project(self) -> VectOwned<bf16> {
+        use rabitq::rotate::rotate;
+        use simd::Floating;
+        let input = bf16::vector_to_f32(self.slice());
+        VectOwned::new(bf16::vector_from_f32(&rotate(&input)))
+    }
+}
+
 #[derive(Debug)]
 pub struct MakeH1PlainPrefetcherForInsertion<'b, R> {
     pub index: &'b R,
@@ -14,8 +14,10 @@
 
 pub mod am;
 mod build;
+pub mod cache;
+mod count;
 pub mod dispatch;
 mod filter;
 pub mod opclass;
-mod scanners;
-pub mod types;
+pub mod scanners;
+pub mod worker;
@@ -12,7 +12,11 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+use crate::datatype::memory_bf16vec::{Bf16vecInput, Bf16vecOutput};
 use crate::datatype::memory_halfvec::{HalfvecInput, HalfvecOutput};
+use crate::datatype::memory_int8vec::{Int8vecInput, Int8vecOutput};
+use crate::datatype::memory_rabitq1::{Rabitq1Input, Rabitq1Output};
+use crate::datatype::memory_rabitq2::{Rabitq2Input, Rabitq2Output};
 use crate::datatype::memory_rabitq4::{Rabitq4Input, Rabitq4Output};
 use crate::datatype::memory_rabitq8::{Rabitq8Input, Rabitq8Output};
 use crate::datatype::memory_vector::{VectorInput, VectorOutput};
@@ -39,10 +43,23 @@ pub enum Opfamily {
     Rabitq4L2,
     Rabitq4Ip,
     Rabitq4Cosine,
+    Rabitq2L2,
+    Rabitq2Ip,
+    Rabitq2Cosine,
+    Rabitq1L2,
+    Rabitq1Ip,
+    Rabitq1Cosine,
+    Int8vecL2,
+    Int8vecIp,
+    Bf16vecL2,
+    Bf16vecIp,
+    Bf16vecCosine,
     VectorMaxsim,
     HalfvecMaxsim,
     Rabitq8Maxsim,
     Rabitq4Maxsim,
+    Rabitq2Maxsim,
+    Rabitq1Maxsim,
 }
 
 impl Opfamily {
@@ -66,6 +83,19 @@ impl Opfamily {
             (B::Rabitq4(x), Self::Rabitq4Ip | Self::Rabitq4Maxsim) => O::Rabitq4(x.own()),
             (B::Rabitq4(x), Self::Rabitq4Cosine) => O::Rabitq4(x.function_normalize()),
             (B::Rabitq4(_), _) => unreachable!(),
+            (B::Rabitq2(x), Self::Rabitq2L2) => O::Rabitq2(x.own()),
+            (B::Rabitq2(x), Self::Rabitq2Ip | Self::Rabitq2Maxsim) => O::Rabitq2(x.own()),
+            (B::Rabitq2(x), Self::Rabitq2Cosine) => O::Rabitq2(x.function_normalize()),
+            (B::Rabitq2(_), _) => unreachable!(),
+            (B::Rabitq1(x), Self::Rabitq1L2) => O::Rabitq1(x.own()),
+            (B::Rabitq1(x), Self::Rabitq1Ip | Self::Rabitq1Maxsim) => O::Rabitq1(x.own()),
+            (B::Rabitq1(x), Self::Rabitq1Cosine) => O::Rabitq1(x.function_normalize()),
+            (B::Rabitq1(_), _) => unreachable!(),
+            (B::Veci8(x), Self::Int8vecL2 | Self::Int8vecIp) => O::Veci8(x.own()),
+            (B::Veci8(_), _) => unreachable!(),
+            (B::Vecbf16(x), Self::Bf16vecL2 | Self::Bf16vecIp) => O::Vecbf16(x.own()),
+            (B::Vecbf16(x), Self::Bf16vecCosine) => O::Vecbf16(x.function_normalize()),
+            (B::Vecbf16(_), _) => unreachable!(),
         }
     }
     pub unsafe fn store(self, datum: Datum) -> Option<Vec<(OwnedVector, u16)>> {
@@ -89,6 +119,22 @@ impl Opfamily {
                 let vector = unsafe { Rabitq4Input::from_datum(datum, false).unwrap() };
                 vec![(self.input(BorrowedVector::Rabitq4(vector.as_borrowed())), 0)]
             }
+            Self::Rabitq2L2 | Self::Rabitq2Ip | Self::Rabitq2Cosine => {
+                let vector = unsafe { Rabitq2Input::from_datum(datum, false).unwrap() };
+                vec![(self.input(BorrowedVector::Rabitq2(vector.as_borrowed())), 0)]
+            }
+            Self::Rabitq1L2 | Self::Rabitq1Ip | Self::Rabitq1Cosine => {
+                let vector = unsafe { Rabitq1Input::from_datum(datum, false).unwrap() };
+                vec![(self.input(BorrowedVector::Rabitq1(vector.as_borrowed())), 0)]
+            }
+            Self::Int8vecL2 | Self::Int8vecIp => {
+                let vector = unsafe { Int8vecInput::from_datum(datum, false).unwrap() };
+                vec![(self.input(BorrowedVector::Veci8(vector.as_borrowed())), 0)]
+            }
+            Self::Bf16vecL2 | Self::Bf16vecIp | Self::Bf16vecCosine => {
+                let vector = unsafe { Bf16vecInput::from_datum(datum, false).unwrap() };
+                vec![(self.input(BorrowedVector::Vecbf16(vector.as_borrowed())), 0)]
+            }
             Self::VectorMaxsim => {
                 let vectors =
                     unsafe { pgrx::datum::Array::<VectorInput>::from_datum(datum, false).unwrap() };
@@ -140,6 +186,32 @@ impl Opfamily {
                 }
                 result
             }
+            Self::Rabitq2Maxsim => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<Rabitq2Input>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for (i, vector) in vectors.iter_deny_null().enumerate() {
+                    result.push((
+                        self.input(BorrowedVector::Rabitq2(vector.as_borrowed())),
+                        i as u16,
+                    ));
+                }
+                result
+            }
+            Self::Rabitq1Maxsim => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<Rabitq1Input>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for (i, vector) in vectors.iter_deny_null().enumerate() {
+                    result.push((
+                        self.input(BorrowedVector::Rabitq1(vector.as_borrowed())),
+                        i as u16,
+                    ));
+                }
+                result
+            }
         };
         Some(store)
     }
@@ -167,6 +239,22 @@ impl Opfamily {
                 let vector = tuple.get_by_index::<Rabitq4Output>(attno_1).unwrap()?;
                 self.input(BorrowedVector::Rabitq4(vector.as_borrowed()))
             }
+            Self::Rabitq2L2 | Self::Rabitq2Ip | Self::Rabitq2Cosine | Self::Rabitq2Maxsim => {
+                let vector = tuple.get_by_index::<Rabitq2Output>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Rabitq2(vector.as_borrowed()))
+            }
+            Self::Rabitq1L2 | Self::Rabitq1Ip | Self::Rabitq1Cosine | Self::Rabitq1Maxsim => {
+                let vector = tuple.get_by_index::<Rabitq1Output>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Rabitq1(vector.as_borrowed()))
+            }
+            Self::Int8vecL2 | Self::Int8vecIp => {
+                let vector = tuple.get_by_index::<Int8vecOutput>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Veci8(vector.as_borrowed()))
+            }
+            Self::Bf16vecL2 | Self::Bf16vecIp | Self::Bf16vecCosine => {
+                let vector = tuple.get_by_index::<Bf16vecOutput>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Vecbf16(vector.as_borrowed()))
+            }
         };
         let radius = tuple.get_by_index::<f32>(attno_2).unwrap()?;
         Some(Sphere { center, radius })
@@ -192,6 +280,22 @@ impl Opfamily {
                 let vector = unsafe { Rabitq4Input::from_datum(datum, false).unwrap() };
                 self.input(BorrowedVector::Rabitq4(vector.as_borrowed()))
             }
+            Self::Rabitq2L2 | Self::Rabitq2Ip | Self::Rabitq2Cosine | Self::Rabitq2Maxsim => {
+                let vector = unsafe { Rabitq2Input::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Rabitq2(vector.as_borrowed()))
+            }
+            Self::Rabitq1L2 | Self::Rabitq1Ip | Self::Rabitq1Cosine | Self::Rabitq1Maxsim => {
+                let vector = unsafe { Rabitq1Input::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Rabitq1(vector.as_borrowed()))
+            }
+            Self::Int8vecL2 | Self::Int8vecIp => {
+                let vector = unsafe { Int8vecInput::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Veci8(vector.as_borrowed()))
+            }
+            Self::Bf16vecL2 | Self::Bf16vecIp | Self::Bf16vecCosine => {
+                let vector = unsafe { Bf16vecInput::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Vecbf16(vector.as_borrowed()))
+            }
         };
         Some(vector)
     }
@@ -239,45 +343,124 @@ impl Opfamily {
                 }
                 result
             }
+            Self::Rabitq2L2 | Self::Rabitq2Ip | Self::Rabitq2Cosine | Self::Rabitq2Maxsim => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<Rabitq2Input>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for vector in vectors.iter_deny_null() {
+                    result.push(self.input(BorrowedVector::Rabitq2(vector.as_borrowed())));
+                }
+                result
+            }
+            Self::Rabitq1L2 | Self::Rabitq1Ip | Self::Rabitq1Cosine | Self::Rabitq1Maxsim => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<Rabitq1Input>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for vector in vectors.iter_deny_null() {
+                    result.push(self.input(BorrowedVector::Rabitq1(vector.as_borrowed())));
+                }
+                result
+            }
+            Self::Int8vecL2 | Self::Int8vecIp => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<Int8vecInput>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for vector in vectors.iter_deny_null() {
+                    result.push(self.input(BorrowedVector::Veci8(vector.as_borrowed())));
+                }
+                result
+            }
+            Self::Bf16vecL2 | Self::Bf16vecIp | Self::Bf16vecCosine => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<Bf16vecInput>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for vector in vectors.iter_deny_null() {
+                    result.push(self.input(BorrowedVector::Vecbf16(vector.as_borrowed())));
+                }
+                result
+            }
         };
         Some(vectors)
     }
+    pub fn is_maxsim(self) -> bool {
+        matches!(
+            self,
+            Self::VectorMaxsim
+                | Self::HalfvecMaxsim
+                | Self::Rabitq8Maxsim
+                | Self::Rabitq4Maxsim
+                | Self::Rabitq2Maxsim
+                | Self::Rabitq1Maxsim
+        )
+    }
     pub fn output(self, x: Distance) -> f32 {
         match self {
             Self::VectorCosine
             | Self::HalfvecCosine
             | Self::Rabitq8Cosine
-            | Self::Rabitq4Cosine => x.to_f32() + 1.0f32,
-            Self::VectorL2 | Self::HalfvecL2 | Self::Rabitq8L2 | Self::Rabitq4L2 => {
-                x.to_f32().sqrt()
-            }
+            | Self::Rabitq4Cosine
+            | Self::Rabitq2Cosine
+            | Self::Rabitq1Cosine
+            | Self::Bf16vecCosine => x.to_f32() + 1.0f32,
+            Self::VectorL2
+            | Self::HalfvecL2
+            | Self::Rabitq8L2
+            | Self::Rabitq4L2
+            | Self::Rabitq2L2
+            | Self::Rabitq1L2
+            | Self::Int8vecL2
+            | Self::Bf16vecL2 => x.to_f32().sqrt(),
             Self::VectorIp
             | Self::HalfvecIp
             | Self::Rabitq8Ip
             | Self::Rabitq4Ip
+            | Self::Rabitq2Ip
+            | Self::Rabitq1Ip
+            | Self::Int8vecIp
+            | Self::Bf16vecIp
             | Self::VectorMaxsim
             | Self::HalfvecMaxsim
             | Self::Rabitq8Maxsim
-            | Self::Rabitq4Maxsim => x.to_f32(),
+            | Self::Rabitq4Maxsim
+            | Self::Rabitq2Maxsim
+            | Self::Rabitq1Maxsim => x.to_f32(),
         }
     }
     pub const fn distance_kind(self) -> DistanceKind {
         match self {
-            Self::VectorL2 | Self::HalfvecL2 | Self::Rabitq8L2 | Self::Rabitq4L2 => {
-                DistanceKind::L2S
-            }
+            Self::VectorL2
+            | Self::HalfvecL2
+            | Self::Rabitq8L2
+            | Self::Rabitq4L2
+            | Self::Rabitq2L2
+            | Self::Rabitq1L2
+            | Self::Int8vecL2
+            | Self::Bf16vecL2 => DistanceKind::L2S,
             Self::VectorIp
             | Self::HalfvecIp
             | Self::Rabitq8Ip
             | Self::Rabitq4Ip
+            | Self::Rabitq2Ip
+            | Self::Rabitq1Ip
+            | Self::Int8vecIp
+            | Self::Bf16vecIp
             | Self::VectorCosine
             | Self::HalfvecCosine
             | Self::Rabitq8Cosine
             | Self::Rabitq4Cosine
+            | Self::Rabitq2Cosine
+            | Self::Rabitq1Cosine
+            | Self::Bf16vecCosine
             | Self::VectorMaxsim
             | Self::HalfvecMaxsim
             | Self::Rabitq8Maxsim
-            | Self::Rabitq4Maxsim => DistanceKind::Dot,
+            | Self::Rabitq4Maxsim
+            | Self::Rabitq2Maxsim
+            | Self::Rabitq1Maxsim => DistanceKind::Dot,
         }
     }
     pub const fn vector_kind(self) -> VectorKind {
@@ -294,11 +477,21 @@ impl Opfamily {
             Self::Rabitq4L2 | Self::Rabitq4Ip | Self::Rabitq4Cosine | Self::Rabitq4Maxsim => {
                 VectorKind::Rabitq4
             }
+            Self::Rabitq2L2 | Self::Rabitq2Ip | Self::Rabitq2Cosine | Self::Rabitq2Maxsim => {
+                VectorKind::Rabitq2
+            }
+            Self::Rabitq1L2 | Self::Rabitq1Ip | Self::Rabitq1Cosine | Self::Rabitq1Maxsim => {
+                VectorKind::Rabitq1
+            }
+            Self::Int8vecL2 | Self::Int8vecIp => VectorKind::Veci8,
+            Self::Bf16vecL2 | Self::Bf16vecIp | Self::Bf16vecCosine => VectorKind::Vecbf16,
         }
     }
 }
 
-pub unsafe fn opfamily(index_relation: pgrx::pg_sys::Relation) -> Opfamily {
+/// Returns the name of the operator class, which is returned by support
+/// function 1.
+pub unsafe fn opfamily_name(index_relation: pgrx::pg_sys::Relation) -> String {
     use pgrx::pg_sys::Oid;
 
     let proc = unsafe { pgrx::pg_sys::index_getprocid(index_relation, 1, 1) };
@@ -332,7 +525,17 @@ pub unsafe fn opfamily(index_relation: pgrx::pg_sys::Relation) -> Opfamily {
 
     let result_string = result_option.expect("null return value");
 
-    let result = match result_string.as_str() {
+    unsafe {
+        pgrx::pg_sys::pfree(result_datum.cast_mut_ptr());
+    }
+
+    result_string
+}
+
+pub unsafe fn opfamily(index_relation: pgrx::pg_sys::Relation) -> Opfamily {
+    let result_string = unsafe { opfamily_name(index_relation) };
+
+    match result_string.as_str() {
         "vchordrq_vector_l2_ops" => Opfamily::VectorL2,
         "vchordrq_vector_ip_ops" => Opfamily::VectorIp,
         "vchordrq_vector_cosine_ops" => Opfamily::VectorCosine,
@@ -345,16 +548,23 @@ pub unsafe fn opfamily(index_relation: pgrx::pg_sys::Relation) -> Opfamily {
         "vchordrq_rabitq4_l2_ops" => Opfamily::Rabitq4L2,
         "vchordrq_rabitq4_ip_ops" => Opfamily::Rabitq4Ip,
         "vchordrq_rabitq4_cosine_ops" => Opfamily::Rabitq4Cosine,
+        "vchordrq_rabitq2_l2_ops" => Opfamily::Rabitq2L2,
+        "vchordrq_rabitq2_ip_ops" => Opfamily::Rabitq2Ip,
+        "vchordrq_rabitq2_cosine_ops" => Opfamily::Rabitq2Cosine,
+        "vchordrq_rabitq1_l2_ops" => Opfamily::Rabitq1L2,
+        "vchordrq_rabitq1_ip_ops" => Opfamily::Rabitq1Ip,
+        "vchordrq_rabitq1_cosine_ops" => Opfamily::Rabitq1Cosine,
+        "vchordrq_int8vec_l2_ops" => Opfamily::Int8vecL2,
+        "vchordrq_int8vec_ip_ops" => Opfamily::Int8vecIp,
+        "vchordrq_bf16vec_l2_ops" => Opfamily::Bf16vecL2,
+        "vchordrq_bf16vec_ip_ops" => Opfamily::Bf16vecIp,
+        "vchordrq_bf16vec_cosine_ops" => Opfamily::Bf16vecCosine,
         "vchordrq_vector_maxsim_ops" => Opfamily::VectorMaxsim,
         "vchordrq_halfvec_maxsim_ops" => Opfamily::HalfvecMaxsim,
         "vchordrq_rabitq8_maxsim_ops" => Opfamily::Rabitq8Maxsim,
         "vchordrq_rabitq4_maxsim_ops" => Opfamily::Rabitq4Maxsim,
+        "vchordrq_rabitq2_maxsim_ops" => Opfamily::Rabitq2Maxsim,
+        "vchordrq_rabitq1_maxsim_ops" => Opfamily::Rabitq1Maxsim,
         _ => pgrx::error!("unknown operator class"),
-    };
-
-    unsafe {
-        pgrx::pg_sys::pfree(result_datum.cast_mut_ptr());
     }
-
-    result
 }
@@ -0,0 +1,656 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::index::fetcher::*;
+use crate::index::vchordrq::dispatch::*;
+use crate::index::vchordrq::opclass::Opfamily;
+use always_equal::AlwaysEqual;
+use dary_heap::QuaternaryHeap as Heap;
+use distance::Distance;
+use index::fetch::BorrowedIter;
+use index::packed::PackedRefMut4;
+use index::prefetcher::*;
+use index::relation::{Page, RelationRead};
+use index_accessor::{Dot, L2S};
+use simd::{bf16, f16};
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::num::NonZero;
+use vchordrq::operator::{Op, Operator};
+use vchordrq::types::{DistanceKind, OwnedVector, VectorKind};
+use vchordrq::{
+    RerankMethod, batch_search, how, neighbour_lists, read_list, rerank_heap, rerank_index,
+    scan_lists,
+};
+use vector::VectorOwned;
+use vector::rabitq1::Rabitq1Owned;
+use vector::rabitq2::Rabitq2Owned;
+use vector::rabitq4::Rabitq4Owned;
+use vector::rabitq8::Rabitq8Owned;
+use vector::veci8::Veci8Owned;
+use vector::vect::VectOwned;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Limit {
+    /// Returns the `k` nearest heap tuples.
+    K(u32),
+    /// Returns heap tuples whose distances are not greater than the radius.
+    Radius(f32),
+}
+
+impl Limit {
+    fn apply(self, iter: impl Iterator<Item = (f32, NonZero<u64>)>) -> Vec<(f32, NonZero<u64>)> {
+        match self {
+            Limit::K(k) => iter.take(k as _).collect(),
+            Limit::Radius(radius) => iter
+                .take_while(|&(distance, _)| distance <= radius)
+                .collect(),
+        }
+    }
+}
+
+/// Returns heap tuples that are visible for each vector, nearest first.
+pub fn search_batch<R>(
+    opfamily: Opfamily,
+    index: &R,
+    vectors: Vec<OwnedVector>,
+    limit: Limit,
+    probes: Vec<u32>,
+    epsilon: f32,
+    fetcher: impl Fetcher,
+) -> Vec<Vec<(f32, NonZero<u64>)>>
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    let parameters = Parameters {
+        opfamily,
+        limit,
+        probes,
+        epsilon,
+    };
+    match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => search::<_, Op<VectOwned<f32>, L2S>>(
+            index,
+            vectors,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecf32(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Vecf32, DistanceKind::Dot) => search::<_, Op<VectOwned<f32>, Dot>>(
+            index,
+            vectors,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecf32(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Vecf16, DistanceKind::L2S) => search::<_, Op<VectOwned<f16>, L2S>>(
+            index,
+            vectors,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecf16(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Vecf16, DistanceKind::Dot) => search::<_, Op<VectOwned<f16>, Dot>>(
+            index,
+            vectors,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecf16(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Vecbf16, DistanceKind::L2S) => search::<_, Op<VectOwned<bf16>, L2S>>(
+            index,
+            vectors,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecbf16(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Vecbf16, DistanceKind::Dot) => search::<_, Op<VectOwned<bf16>, Dot>>(
+            index,
+            vectors,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecbf16(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Rabitq8, DistanceKind::L2S) => search::<_, Op<Rabitq8Owned, L2S>>(
+            index,
+            vectors,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq8(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Rabitq8, DistanceKind::Dot) => search::<_, Op<Rabitq8Owned, Dot>>(
+            index,
+            vectors,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq8(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Rabitq4, DistanceKind::L2S) => search::<_, Op<Rabitq4Owned, L2S>>(
+            index,
+            vectors,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq4(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Rabitq4, DistanceKind::Dot) => search::<_, Op<Rabitq4Owned, Dot>>(
+            index,
+            vectors,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq4(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Rabitq2, DistanceKind::L2S) => search::<_, Op<Rabitq2Owned, L2S>>(
+            index,
+            vectors,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq2(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Rabitq2, DistanceKind::Dot) => search::<_, Op<Rabitq2Owned, Dot>>(
+            index,
+            vectors,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq2(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Rabitq1, DistanceKind::L2S) => search::<_, Op<Rabitq1Owned, L2S>>(
+            index,
+            vectors,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq1(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Rabitq1, DistanceKind::Dot) => search::<_, Op<Rabitq1Owned, Dot>>(
+            index,
+            vectors,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq1(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Veci8, DistanceKind::L2S) => search::<_, Op<Veci8Owned, L2S>>(
+            index,
+            vectors,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Veci8(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+        (VectorKind::Veci8, DistanceKind::Dot) => search::<_, Op<Veci8Owned, Dot>>(
+            index,
+            vectors,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Veci8(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+        ),
+    }
+}
+
+/// Calls `callback` on every pair of visible heap tuples whose distance is not
+/// greater than the radius, once for each pair.
+pub fn near_duplicates<R>(
+    opfamily: Opfamily,
+    index: &R,
+    radius: f32,
+    probes: Vec<u32>,
+    epsilon: f32,
+    fetcher: impl Fetcher,
+    callback: impl FnMut([u16; 3], [u16; 3], f32),
+) where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    let parameters = Parameters {
+        opfamily,
+        limit: Limit::Radius(radius),
+        probes,
+        epsilon,
+    };
+    match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => near::<_, Op<VectOwned<f32>, L2S>>(
+            index,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecf32(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Vecf32, DistanceKind::Dot) => near::<_, Op<VectOwned<f32>, Dot>>(
+            index,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecf32(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Vecf16, DistanceKind::L2S) => near::<_, Op<VectOwned<f16>, L2S>>(
+            index,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecf16(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Vecf16, DistanceKind::Dot) => near::<_, Op<VectOwned<f16>, Dot>>(
+            index,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecf16(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Vecbf16, DistanceKind::L2S) => near::<_, Op<VectOwned<bf16>, L2S>>(
+            index,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecbf16(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Vecbf16, DistanceKind::Dot) => near::<_, Op<VectOwned<bf16>, Dot>>(
+            index,
+            parameters,
+            |x| RandomProject::project(x.as_borrowed()),
+            |x| match x {
+                OwnedVector::Vecbf16(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Rabitq8, DistanceKind::L2S) => near::<_, Op<Rabitq8Owned, L2S>>(
+            index,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq8(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Rabitq8, DistanceKind::Dot) => near::<_, Op<Rabitq8Owned, Dot>>(
+            index,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq8(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Rabitq4, DistanceKind::L2S) => near::<_, Op<Rabitq4Owned, L2S>>(
+            index,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq4(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Rabitq4, DistanceKind::Dot) => near::<_, Op<Rabitq4Owned, Dot>>(
+            index,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq4(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Rabitq2, DistanceKind::L2S) => near::<_, Op<Rabitq2Owned, L2S>>(
+            index,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq2(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Rabitq2, DistanceKind::Dot) => near::<_, Op<Rabitq2Owned, Dot>>(
+            index,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq2(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Rabitq1, DistanceKind::L2S) => near::<_, Op<Rabitq1Owned, L2S>>(
+            index,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq1(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Rabitq1, DistanceKind::Dot) => near::<_, Op<Rabitq1Owned, Dot>>(
+            index,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Rabitq1(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Veci8, DistanceKind::L2S) => near::<_, Op<Veci8Owned, L2S>>(
+            index,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Veci8(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+        (VectorKind::Veci8, DistanceKind::Dot) => near::<_, Op<Veci8Owned, Dot>>(
+            index,
+            parameters,
+            Clone::clone,
+            |x| match x {
+                OwnedVector::Veci8(x) => x,
+                _ => unreachable!(),
+            },
+            fetcher,
+            callback,
+        ),
+    }
+}
+
+struct Parameters {
+    opfamily: Opfamily,
+    limit: Limit,
+    probes: Vec<u32>,
+    epsilon: f32,
+}
+
+fn search<R, O: Operator>(
+    index: &R,
+    vectors: Vec<OwnedVector>,
+    parameters: Parameters,
+    project: impl Fn(&O::Vector) -> O::Vector,
+    extract: impl Fn(OwnedVector) -> O::Vector + Copy,
+    fetcher: impl Fetcher,
+) -> Vec<Vec<(f32, NonZero<u64>)>>
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    let Parameters {
+        opfamily,
+        limit,
+        probes,
+        epsilon,
+    } = parameters;
+    // Queries are searched this many at a time, so that candidates and
+    // lookup tables are kept for one chunk only.
+    const CHUNK: usize = 256;
+    let fetcher = RefCell::new(fetcher);
+    let method = how(index);
+    let mut unprojected = vectors.into_iter().map(extract).collect::<Vec<_>>();
+    let mut results = Vec::with_capacity(unprojected.len());
+    while !unprojected.is_empty() {
+        let rest = unprojected.split_off(unprojected.len().min(CHUNK));
+        let chunk = std::mem::replace(&mut unprojected, rest);
+        let bump = bumpalo::Bump::new();
+        let projected = chunk.iter().map(&project).collect::<Vec<_>>();
+        let borrowed = projected
+            .iter()
+            .map(|x| x.as_borrowed())
+            .collect::<Vec<_>>();
+        let make_h1_plain_prefetcher = MakeH1PlainPrefetcher { index };
+        let candidates = batch_search::<_, O>(
+            index,
+            &borrowed,
+            probes.clone(),
+            epsilon,
+            &bump,
+            make_h1_plain_prefetcher,
+        );
+        for (unprojected, candidates) in chunk.into_iter().zip(candidates) {
+            pgrx::check_for_interrupts!();
+            results.push(rerank::<_, O>(
+                index,
+                opfamily,
+                method,
+                limit,
+                unprojected,
+                candidates,
+                &fetcher,
+                extract,
+            ));
+        }
+    }
+    results
+}
+
+#[allow(clippy::too_many_arguments)]
+fn near<R, O: Operator>(
+    index: &R,
+    parameters: Parameters,
+    project: impl Fn(&O::Vector) -> O::Vector,
+    extract: impl Fn(OwnedVector) -> O::Vector + Copy,
+    fetcher: impl Fetcher,
+    mut callback: impl FnMut([u16; 3], [u16; 3], f32),
+) where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    let Parameters {
+        opfamily,
+        limit,
+        probes,
+        epsilon,
+    } = parameters;
+    let fetcher = RefCell::new(fetcher);
+    let method = how(index);
+    let neighbours = {
+        let bump = bumpalo::Bump::new();
+        let make_h1_plain_prefetcher = MakeH1PlainPrefetcher { index };
+        neighbour_lists::<_, O>(index, probes, epsilon, &bump, make_h1_plain_prefetcher)
+    };
+    // Members of a list are compared with the list and its neighbours after
+    // it, so a pair in two lists is found once, and a pair in one list is found
+    // from both sides and kept once.
+    for lists in neighbours {
+        pgrx::check_for_interrupts!();
+        let mut keys = read_list(index, lists[0].0)
+            .into_iter()
+            .map(|payload| pointer_to_kv(payload).0)
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.dedup();
+        let mut members = Vec::with_capacity(keys.len());
+        let mut unprojected = Vec::with_capacity(keys.len());
+        for &key in keys.iter() {
+            let mut fetcher = fetcher.borrow_mut();
+            let Some(mut tuple) = fetcher.fetch(key) else {
+                continue;
+            };
+            let (datums, is_nulls) = tuple.build();
+            let datum = (!is_nulls[0]).then_some(datums[0]);
+            let Some(vector) = (unsafe { datum.and_then(|x| opfamily.input_vector(x)) }) else {
+                continue;
+            };
+            members.push(key);
+            unprojected.push(extract(vector));
+        }
+        let bump = bumpalo::Bump::new();
+        let projected = unprojected.iter().map(&project).collect::<Vec<_>>();
+        let borrowed = projected
+            .iter()
+            .map(|x| x.as_borrowed())
+            .collect::<Vec<_>>();
+        let candidates = scan_lists::<_, O>(index, &borrowed, &lists, epsilon, &bump);
+        for ((a, unprojected), candidates) in members.into_iter().zip(unprojected).zip(candidates) {
+            pgrx::check_for_interrupts!();
+            let result = rerank::<_, O>(
+                index,
+                opfamily,
+                method,
+                limit,
+                unprojected,
+                candidates,
+                &fetcher,
+                extract,
+            );
+            for (distance, payload) in result {
+                let (b, _) = pointer_to_kv(payload);
+                if a != b && (keys.binary_search(&b).is_err() || a < b) {
+                    callback(a.min(b), a.max(b), distance);
+                }
+            }
+        }
+    }
+}
+
+type Candidate<'b> = (
+    (Reverse<Distance>, AlwaysEqual<()>),
+    AlwaysEqual<PackedRefMut4<'b, (NonZero<u64>, u16, BorrowedIter<'b>)>>,
+);
+
+#[allow(clippy::too_many_arguments)]
+fn rerank<'b, R, O: Operator>(
+    index: &'b R,
+    opfamily: Opfamily,
+    method: RerankMethod,
+    limit: Limit,
+    unprojected: O::Vector,
+    candidates: Vec<Candidate<'b>>,
+    fetcher: &'b RefCell<impl Fetcher>,
+    extract: impl Fn(OwnedVector) -> O::Vector + 'b,
+) -> Vec<(f32, NonZero<u64>)>
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    let f = |(distance, payload)| (opfamily.output(distance), payload);
+    let prefetcher = PlainPrefetcher::new(index, Heap::from(candidates));
+    match method {
+        // The index may return dead tuples, and they are skipped here
+        // since there is no executor to recheck visibility.
+        RerankMethod::Index => limit.apply(
+            rerank_index::<O, _, _, _>(unprojected, prefetcher)
+                .filter(|&(_, payload)| {
+                    let (key, _) = pointer_to_kv(payload);
+                    fetcher.borrow_mut().fetch(key).is_some()
+                })
+                .map(f),
+        ),
+        RerankMethod::Heap => {
+            let fetch = move |payload| {
+                let (key, _) = pointer_to_kv(payload);
+                let mut fetcher = fetcher.borrow_mut();
+                let mut tuple = fetcher.fetch(key)?;
+                let (datums, is_nulls) = tuple.build();
+                let datum = (!is_nulls[0]).then_some(datums[0]);
+                let maybe_vector = unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                Some(extract(maybe_vector?))
+            };
+            limit.apply(rerank_heap::<O, _, _, _>(unprojected, prefetcher, fetch).map(f))
+        }
+    }
+}
@@ -15,11 +15,12 @@
 use crate::index::fetcher::*;
 use crate::index::opclass::Sphere;
 use crate::index::scanners::{Io, SearchBuilder};
+use crate::index::vchordrq::count::count;
 use crate::index::vchordrq::dispatch::*;
 use crate::index::vchordrq::filter::filter;
 use crate::index::vchordrq::opclass::Opfamily;
 use crate::index::vchordrq::scanners::SearchOptions;
-use crate::recorder::{Recorder, text};
+use crate::recorder::{Counter, Recorder, Recording, binary};
 use always_equal::AlwaysEqual;
 use dary_heap::QuaternaryHeap as Heap;
 use index::bump::Bump;
@@ -27,13 +28,16 @@ use index::packed::PackedRefMut4;
 use index::prefetcher::*;
 use index::relation::{Hints, Page, RelationPrefetch, RelationRead, RelationReadStream};
 use index_accessor::{Dot, L2S};
-use simd::f16;
+use simd::{bf16, f16};
 use std::num::NonZero;
 use vchordrq::types::{DistanceKind, OwnedVector, VectorKind};
 use vchordrq::{RerankMethod, default_search, how, rerank_heap, rerank_index};
 use vector::VectorOwned;
+use vector::rabitq1::Rabitq1Owned;
+use vector::rabitq2::Rabitq2Owned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
+use vector::veci8::Veci8Owned;
 use vector::vect::VectOwned;
 
 pub struct DefaultBuilder {
@@ -64,6 +68,17 @@ impl SearchBuilder for DefaultBuilder {
                 | Opfamily::Rabitq4Cosine
                 | Opfamily::Rabitq4Ip
                 | Opfamily::Rabitq4L2
+                | Opfamily::Rabitq2Cosine
+                | Opfamily::Rabitq2Ip
+                | Opfamily::Rabitq2L2
+                | Opfamily::Rabitq1Cosine
+                | Opfamily::Rabitq1Ip
+                | Opfamily::Rabitq1L2
+                | Opfamily::Int8vecIp
+                | Opfamily::Int8vecL2
+                | Opfamily::Bf16vecCosine
+                | Opfamily::Bf16vecL2
+                | Opfamily::Bf16vecIp
         ));
         Self {
             opfamily,
@@ -92,7 +107,7 @@ impl SearchBuilder for DefaultBuilder {
         options: SearchOptions,
         mut fetcher: impl Fetcher + 'b,
         bump: &'b impl Bump,
-        recorder: impl Recorder,
+        recorder: impl Recorder + 'b,
     ) -> Box<dyn Iterator<Item = (f32, [u16; 3], bool)> + 'b>
     where
         R: RelationRead + RelationPrefetch + RelationReadStream,
@@ -128,6 +143,29 @@ impl SearchBuilder for DefaultBuilder {
             index,
             hints: search_hints,
         };
+        let sample = recorder.is_enabled().then(|| {
+            let value = match &vector {
+                OwnedVector::Vecf32(v) => binary::vector_send(v.as_borrowed()),
+                OwnedVector::Vecf16(v) => binary::halfvec_send(v.as_borrowed()),
+                OwnedVector::Rabitq8(v) => binary::rabitq8_send(v.as_borrowed()),
+                OwnedVector::Rabitq4(v) => binary::rabitq4_send(v.as_borrowed()),
+                OwnedVector::Rabitq2(v) => binary::rabitq2_send(v.as_borrowed()),
+                OwnedVector::Rabitq1(v) => binary::rabitq1_send(v.as_borrowed()),
+                OwnedVector::Veci8(v) => binary::int8vec_send(v.as_borrowed()),
+                OwnedVector::Vecbf16(v) => binary::bf16vec_send(v.as_borrowed()),
+            };
+            let settings = format!(
+                "{{\"probes\":{:?},\"epsilon\":{},\"max_scan_tuples\":{}}}",
+                options.probes,
+                options.epsilon,
+                options
+                    .max_scan_tuples
+                    .map_or_else(|| "null".to_string(), |x| x.to_string()),
+            );
+            (value, settings)
+        });
+        let scanned = Counter::default();
+        let reranked = Counter::default();
         let f = move |(distance, payload)| (opfamily.output(distance), payload);
         let iter: Box<dyn Iterator<Item = (f32, NonZero<u64>)>> =
             match (opfamily.vector_kind(), opfamily.distance_kind()) {
@@ -142,6 +180,7 @@ impl SearchBuilder for DefaultBuilder {
                     let results = match options.io_search {
                         Io::Plain => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -151,6 +190,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Simple => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -160,6 +200,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Stream => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -169,10 +210,12 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                     };
                     let method = how(index);
+                    scanned.add(results.len() as u64);
                     let sequence = Heap::from(results);
                     match (method, options.io_rerank, options.prefilter) {
                         (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Plain, true) => {
@@ -185,11 +228,13 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, true) => {
@@ -202,11 +247,16 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, true) => {
@@ -219,7 +269,11 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Heap, _, false) => {
@@ -238,7 +292,8 @@ impl SearchBuilder for DefaultBuilder {
                                 };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -262,7 +317,8 @@ impl SearchBuilder for DefaultBuilder {
                                 };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -280,6 +336,7 @@ impl SearchBuilder for DefaultBuilder {
                     let results = match options.io_search {
                         Io::Plain => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -289,6 +346,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Simple => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -298,6 +356,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Stream => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -307,10 +366,12 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                     };
                     let method = how(index);
+                    scanned.add(results.len() as u64);
                     let sequence = Heap::from(results);
                     match (method, options.io_rerank, options.prefilter) {
                         (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Plain, true) => {
@@ -323,11 +384,13 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, true) => {
@@ -340,11 +403,16 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, true) => {
@@ -357,7 +425,11 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Heap, _, false) => {
@@ -376,7 +448,8 @@ impl SearchBuilder for DefaultBuilder {
                                 };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -400,7 +473,8 @@ impl SearchBuilder for DefaultBuilder {
                                 };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -418,6 +492,7 @@ impl SearchBuilder for DefaultBuilder {
                     let results = match options.io_search {
                         Io::Plain => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -427,6 +502,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Simple => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -436,6 +512,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Stream => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -445,10 +522,12 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                     };
                     let method = how(index);
+                    scanned.add(results.len() as u64);
                     let sequence = Heap::from(results);
                     match (method, options.io_rerank, options.prefilter) {
                         (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Plain, true) => {
@@ -461,11 +540,13 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, true) => {
@@ -478,11 +559,16 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, true) => {
@@ -495,7 +581,11 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Heap, _, false) => {
@@ -514,7 +604,8 @@ impl SearchBuilder for DefaultBuilder {
                                 };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -538,7 +629,8 @@ impl SearchBuilder for DefaultBuilder {
                                 };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -556,6 +648,7 @@ impl SearchBuilder for DefaultBuilder {
                     let results = match options.io_search {
                         Io::Plain => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -565,6 +658,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Simple => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -574,6 +668,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Stream => default_search::<_, Op>(
                             index,
+                            options.upper,
                             projected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -583,10 +678,12 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                     };
                     let method = how(index);
+                    scanned.add(results.len() as u64);
                     let sequence = Heap::from(results);
                     match (method, options.io_rerank, options.prefilter) {
                         (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Plain, true) => {
@@ -599,11 +696,13 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, true) => {
@@ -616,11 +715,16 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, true) => {
@@ -633,7 +737,11 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Heap, _, false) => {
@@ -652,7 +760,8 @@ impl SearchBuilder for DefaultBuilder {
                                 };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -676,7 +785,320 @@ impl SearchBuilder for DefaultBuilder {
                                 };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                    }
+                }
+                (VectorKind::Vecbf16, DistanceKind::L2S) => {
+                    type Op = vchordrq::operator::Op<VectOwned<bf16>, L2S>;
+                    let unprojected = if let OwnedVector::Vecbf16(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let projected = RandomProject::project(unprojected.as_borrowed());
+                    let results = match options.io_search {
+                        Io::Plain => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            projected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_plain_prefetcher,
+                        ),
+                        Io::Simple => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            projected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_simple_prefetcher,
+                        ),
+                        Io::Stream => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            projected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_stream_prefetcher,
+                        ),
+                    };
+                    let method = how(index);
+                    scanned.add(results.len() as u64);
+                    let sequence = Heap::from(results);
+                    match (method, options.io_rerank, options.prefilter) {
+                        (RerankMethod::Index, Io::Plain, false) => {
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Plain, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, false) => {
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, false) => {
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Heap, _, false) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Vecbf16(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                        (RerankMethod::Heap, _, true) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                if !tuple.filter() {
+                                    return None;
+                                }
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Vecbf16(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                    }
+                }
+                (VectorKind::Vecbf16, DistanceKind::Dot) => {
+                    type Op = vchordrq::operator::Op<VectOwned<bf16>, Dot>;
+                    let unprojected = if let OwnedVector::Vecbf16(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let projected = RandomProject::project(unprojected.as_borrowed());
+                    let results = match options.io_search {
+                        Io::Plain => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            projected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_plain_prefetcher,
+                        ),
+                        Io::Simple => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            projected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_simple_prefetcher,
+                        ),
+                        Io::Stream => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            projected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_stream_prefetcher,
+                        ),
+                    };
+                    let method = how(index);
+                    scanned.add(results.len() as u64);
+                    let sequence = Heap::from(results);
+                    match (method, options.io_rerank, options.prefilter) {
+                        (RerankMethod::Index, Io::Plain, false) => {
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Plain, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, false) => {
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, false) => {
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Heap, _, false) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Vecbf16(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                        (RerankMethod::Heap, _, true) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                if !tuple.filter() {
+                                    return None;
+                                }
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Vecbf16(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -693,6 +1115,7 @@ impl SearchBuilder for DefaultBuilder {
                     let results = match options.io_search {
                         Io::Plain => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -702,6 +1125,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Simple => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -711,6 +1135,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Stream => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -720,10 +1145,12 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                     };
                     let method = how(index);
+                    scanned.add(results.len() as u64);
                     let sequence = Heap::from(results);
                     match (method, options.io_rerank, options.prefilter) {
                         (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Plain, true) => {
@@ -736,11 +1163,13 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, true) => {
@@ -753,11 +1182,16 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, true) => {
@@ -770,7 +1204,11 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Heap, _, false) => {
@@ -789,7 +1227,8 @@ impl SearchBuilder for DefaultBuilder {
                                     };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -813,7 +1252,8 @@ impl SearchBuilder for DefaultBuilder {
                                     };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -830,6 +1270,7 @@ impl SearchBuilder for DefaultBuilder {
                     let results = match options.io_search {
                         Io::Plain => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -839,6 +1280,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Simple => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -848,6 +1290,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Stream => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -857,10 +1300,12 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                     };
                     let method = how(index);
+                    scanned.add(results.len() as u64);
                     let sequence = Heap::from(results);
                     match (method, options.io_rerank, options.prefilter) {
                         (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Plain, true) => {
@@ -873,11 +1318,13 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, true) => {
@@ -890,11 +1337,16 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, true) => {
@@ -907,7 +1359,11 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Heap, _, false) => {
@@ -926,7 +1382,8 @@ impl SearchBuilder for DefaultBuilder {
                                     };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -950,7 +1407,8 @@ impl SearchBuilder for DefaultBuilder {
                                     };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -967,6 +1425,7 @@ impl SearchBuilder for DefaultBuilder {
                     let results = match options.io_search {
                         Io::Plain => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -976,6 +1435,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Simple => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -985,6 +1445,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Stream => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -994,10 +1455,12 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                     };
                     let method = how(index);
+                    scanned.add(results.len() as u64);
                     let sequence = Heap::from(results);
                     match (method, options.io_rerank, options.prefilter) {
                         (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Plain, true) => {
@@ -1010,11 +1473,13 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, true) => {
@@ -1027,11 +1492,16 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, true) => {
@@ -1044,7 +1514,11 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Heap, _, false) => {
@@ -1063,7 +1537,8 @@ impl SearchBuilder for DefaultBuilder {
                                     };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -1087,7 +1562,8 @@ impl SearchBuilder for DefaultBuilder {
                                     };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -1104,6 +1580,7 @@ impl SearchBuilder for DefaultBuilder {
                     let results = match options.io_search {
                         Io::Plain => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -1113,6 +1590,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Simple => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -1122,6 +1600,7 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                         Io::Stream => default_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected.as_borrowed(),
                             options.probes,
                             options.epsilon,
@@ -1131,10 +1610,12 @@ impl SearchBuilder for DefaultBuilder {
                         ),
                     };
                     let method = how(index);
+                    scanned.add(results.len() as u64);
                     let sequence = Heap::from(results);
                     match (method, options.io_rerank, options.prefilter) {
                         (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Plain, true) => {
@@ -1147,11 +1628,13 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Simple, true) => {
@@ -1164,11 +1647,16 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Index, Io::Stream, true) => {
@@ -1181,7 +1669,11 @@ impl SearchBuilder for DefaultBuilder {
                                     tuple.filter()
                                 });
                             let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
                             Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
                         }
                         (RerankMethod::Heap, _, false) => {
@@ -1200,7 +1692,8 @@ impl SearchBuilder for DefaultBuilder {
                                     };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
@@ -1224,44 +1717,972 @@ impl SearchBuilder for DefaultBuilder {
                                     };
                                 Some(raw)
                             };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
                             Box::new(
                                 rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
                             )
                         }
                     }
                 }
-            };
-        let iter = if let Some(threshold) = threshold {
-            Box::new(iter.take_while(move |(x, _)| *x < threshold))
-        } else {
-            iter
+                (VectorKind::Rabitq2, DistanceKind::L2S) => {
+                    type Op = vchordrq::operator::Op<Rabitq2Owned, L2S>;
+                    let unprojected = if let OwnedVector::Rabitq2(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let results = match options.io_search {
+                        Io::Plain => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_plain_prefetcher,
+                        ),
+                        Io::Simple => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_simple_prefetcher,
+                        ),
+                        Io::Stream => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_stream_prefetcher,
+                        ),
+                    };
+                    let method = how(index);
+                    scanned.add(results.len() as u64);
+                    let sequence = Heap::from(results);
+                    match (method, options.io_rerank, options.prefilter) {
+                        (RerankMethod::Index, Io::Plain, false) => {
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Plain, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, false) => {
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, false) => {
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Heap, _, false) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Rabitq2(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                        (RerankMethod::Heap, _, true) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                if !tuple.filter() {
+                                    return None;
+                                }
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Rabitq2(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                    }
+                }
+                (VectorKind::Rabitq2, DistanceKind::Dot) => {
+                    type Op = vchordrq::operator::Op<Rabitq2Owned, Dot>;
+                    let unprojected = if let OwnedVector::Rabitq2(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let results = match options.io_search {
+                        Io::Plain => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_plain_prefetcher,
+                        ),
+                        Io::Simple => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_simple_prefetcher,
+                        ),
+                        Io::Stream => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_stream_prefetcher,
+                        ),
+                    };
+                    let method = how(index);
+                    scanned.add(results.len() as u64);
+                    let sequence = Heap::from(results);
+                    match (method, options.io_rerank, options.prefilter) {
+                        (RerankMethod::Index, Io::Plain, false) => {
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Plain, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, false) => {
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, false) => {
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Heap, _, false) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Rabitq2(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                        (RerankMethod::Heap, _, true) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                if !tuple.filter() {
+                                    return None;
+                                }
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Rabitq2(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                    }
+                }
+                (VectorKind::Rabitq1, DistanceKind::L2S) => {
+                    type Op = vchordrq::operator::Op<Rabitq1Owned, L2S>;
+                    let unprojected = if let OwnedVector::Rabitq1(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let results = match options.io_search {
+                        Io::Plain => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_plain_prefetcher,
+                        ),
+                        Io::Simple => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_simple_prefetcher,
+                        ),
+                        Io::Stream => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_stream_prefetcher,
+                        ),
+                    };
+                    let method = how(index);
+                    scanned.add(results.len() as u64);
+                    let sequence = Heap::from(results);
+                    match (method, options.io_rerank, options.prefilter) {
+                        (RerankMethod::Index, Io::Plain, false) => {
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Plain, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, false) => {
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, false) => {
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Heap, _, false) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Rabitq1(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                        (RerankMethod::Heap, _, true) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                if !tuple.filter() {
+                                    return None;
+                                }
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Rabitq1(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                    }
+                }
+                (VectorKind::Rabitq1, DistanceKind::Dot) => {
+                    type Op = vchordrq::operator::Op<Rabitq1Owned, Dot>;
+                    let unprojected = if let OwnedVector::Rabitq1(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let results = match options.io_search {
+                        Io::Plain => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_plain_prefetcher,
+                        ),
+                        Io::Simple => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_simple_prefetcher,
+                        ),
+                        Io::Stream => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_stream_prefetcher,
+                        ),
+                    };
+                    let method = how(index);
+                    scanned.add(results.len() as u64);
+                    let sequence = Heap::from(results);
+                    match (method, options.io_rerank, options.prefilter) {
+                        (RerankMethod::Index, Io::Plain, false) => {
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Plain, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, false) => {
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, false) => {
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Heap, _, false) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Rabitq1(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                        (RerankMethod::Heap, _, true) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                if !tuple.filter() {
+                                    return None;
+                                }
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw =
+                                    if let OwnedVector::Rabitq1(vector) = maybe_vector.unwrap() {
+                                        vector
+                                    } else {
+                                        unreachable!()
+                                    };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                    }
+                }
+                (VectorKind::Veci8, DistanceKind::L2S) => {
+                    type Op = vchordrq::operator::Op<Veci8Owned, L2S>;
+                    let unprojected = if let OwnedVector::Veci8(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let results = match options.io_search {
+                        Io::Plain => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_plain_prefetcher,
+                        ),
+                        Io::Simple => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_simple_prefetcher,
+                        ),
+                        Io::Stream => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_stream_prefetcher,
+                        ),
+                    };
+                    let method = how(index);
+                    scanned.add(results.len() as u64);
+                    let sequence = Heap::from(results);
+                    match (method, options.io_rerank, options.prefilter) {
+                        (RerankMethod::Index, Io::Plain, false) => {
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Plain, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, false) => {
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, false) => {
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Heap, _, false) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw = if let OwnedVector::Veci8(vector) = maybe_vector.unwrap()
+                                {
+                                    vector
+                                } else {
+                                    unreachable!()
+                                };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                        (RerankMethod::Heap, _, true) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                if !tuple.filter() {
+                                    return None;
+                                }
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw = if let OwnedVector::Veci8(vector) = maybe_vector.unwrap()
+                                {
+                                    vector
+                                } else {
+                                    unreachable!()
+                                };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                    }
+                }
+                (VectorKind::Veci8, DistanceKind::Dot) => {
+                    type Op = vchordrq::operator::Op<Veci8Owned, Dot>;
+                    let unprojected = if let OwnedVector::Veci8(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let results = match options.io_search {
+                        Io::Plain => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_plain_prefetcher,
+                        ),
+                        Io::Simple => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_simple_prefetcher,
+                        ),
+                        Io::Stream => default_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected.as_borrowed(),
+                            options.probes,
+                            options.epsilon,
+                            bump,
+                            make_h1_plain_prefetcher,
+                            make_h0_stream_prefetcher,
+                        ),
+                    };
+                    let method = how(index);
+                    scanned.add(results.len() as u64);
+                    let sequence = Heap::from(results);
+                    match (method, options.io_rerank, options.prefilter) {
+                        (RerankMethod::Index, Io::Plain, false) => {
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Plain, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, false) => {
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Simple, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher =
+                                SimplePrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, false) => {
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Index, Io::Stream, true) => {
+                            let predicate =
+                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                                    let (key, _) = pointer_to_kv(*pointer);
+                                    let Some(mut tuple) = fetcher.fetch(key) else {
+                                        return false;
+                                    };
+                                    tuple.filter()
+                                });
+                            let sequence = filter(sequence, predicate);
+                            let prefetcher = StreamPrefetcher::new(
+                                index,
+                                count(sequence, &reranked),
+                                rerank_hints,
+                            );
+                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
+                        }
+                        (RerankMethod::Heap, _, false) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw = if let OwnedVector::Veci8(vector) = maybe_vector.unwrap()
+                                {
+                                    vector
+                                } else {
+                                    unreachable!()
+                                };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                        (RerankMethod::Heap, _, true) => {
+                            let fetch = move |payload| {
+                                let (key, _) = pointer_to_kv(payload);
+                                let mut tuple = fetcher.fetch(key)?;
+                                if !tuple.filter() {
+                                    return None;
+                                }
+                                let (datums, is_nulls) = tuple.build();
+                                let datum = (!is_nulls[0]).then_some(datums[0]);
+                                let maybe_vector =
+                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
+                                let raw = if let OwnedVector::Veci8(vector) = maybe_vector.unwrap()
+                                {
+                                    vector
+                                } else {
+                                    unreachable!()
+                                };
+                                Some(raw)
+                            };
+                            let prefetcher =
+                                PlainPrefetcher::new(index, count(sequence, &reranked));
+                            Box::new(
+                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
+                            )
+                        }
+                    }
+                }
+            };
+        let iter = if let Some(threshold) = threshold {
+            Box::new(iter.take_while(move |(x, _)| *x < threshold))
+        } else {
+            iter
         };
         let iter = if let Some(max_scan_tuples) = options.max_scan_tuples {
             Box::new(iter.take(max_scan_tuples as _))
         } else {
             iter
         };
-        if recorder.is_enabled() {
-            match &vector {
-                OwnedVector::Vecf32(v) => {
-                    recorder.send(&text::vector_out(v.as_borrowed()));
-                }
-                OwnedVector::Vecf16(v) => {
-                    recorder.send(&text::halfvec_out(v.as_borrowed()));
-                }
-                OwnedVector::Rabitq8(v) => {
-                    recorder.send(&text::rabitq8_out(v.as_borrowed()));
-                }
-                OwnedVector::Rabitq4(v) => {
-                    recorder.send(&text::rabitq4_out(v.as_borrowed()));
-                }
-            }
+        let iter: Box<dyn Iterator<Item = (f32, [u16; 3], bool)> + 'b> =
+            Box::new(iter.map(move |(distance, pointer)| {
+                let (key, _) = pointer_to_kv(pointer);
+                (distance, key, recheck)
+            }));
+        if let Some((value, settings)) = sample {
+            Box::new(Recording::new(
+                iter,
+                recorder,
+                value,
+                settings,
+                Some(scanned),
+                Some(reranked),
+            ))
+        } else {
+            iter
         }
-        Box::new(iter.map(move |(distance, pointer)| {
-            let (key, _) = pointer_to_kv(pointer);
-            (distance, key, recheck)
-        }))
     }
 }
 
@@ -33,10 +33,12 @@ use std::collections::BinaryHeap;
 use std::num::NonZero;
 use vchordrq::types::{DistanceKind, OwnedVector, VectorKind};
 use vchordrq::{RerankMethod, how, maxsim_search, rerank_index};
-use vector::VectorOwned;
+use vector::rabitq1::Rabitq1Owned;
+use vector::rabitq2::Rabitq2Owned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
-use vector::vect::VectOwned;
+use vector::vect::{VectBorrowed, VectOwned};
+use vector::{VectorBorrowed, VectorOwned};
 
 pub struct MaxsimBuilder {
     opfamily: Opfamily,
@@ -57,6 +59,8 @@ impl SearchBuilder for MaxsimBuilder {
                 | Opfamily::HalfvecMaxsim
                 | Opfamily::Rabitq8Maxsim
                 | Opfamily::Rabitq4Maxsim
+                | Opfamily::Rabitq2Maxsim
+                | Opfamily::Rabitq1Maxsim
         ));
         Self {
             opfamily,
@@ -80,7 +84,7 @@ impl SearchBuilder for MaxsimBuilder {
         options: SearchOptions,
         mut fetcher: impl Fetcher + 'b,
         bump: &'b impl Bump,
-        _sender: impl Recorder,
+        _sender: impl Recorder + 'b,
     ) -> Box<dyn Iterator<Item = (f32, [u16; 3], bool)> + 'b>
     where
         R: RelationRead + RelationPrefetch + RelationReadStream,
@@ -97,16 +101,17 @@ impl SearchBuilder for MaxsimBuilder {
         if let Some(_max_scan_tuples) = options.max_scan_tuples {
             pgrx::error!("maxsim search with max_scan_tuples is not supported");
         }
-        let maxsim_refine = options.maxsim_refine;
         let maxsim_threshold = options.maxsim_threshold;
         let opfamily = self.opfamily;
         let Some(vectors) = vectors else {
             return Box::new(std::iter::empty()) as Box<dyn Iterator<Item = (f32, [u16; 3], bool)>>;
         };
-        let method = how(index);
-        if !matches!(method, RerankMethod::Index) {
-            pgrx::error!("maxsim search with rerank_in_table is not supported");
-        }
+        // Without vectors in the index, tokens cannot be refined one by one, so
+        // candidate documents are fetched from the table and scored exactly instead.
+        let (maxsim_refine, queries) = match how(index) {
+            RerankMethod::Index => (options.maxsim_refine, None),
+            RerankMethod::Heap => (0, Some(vectors.clone())),
+        };
         assert!(matches!(opfamily.distance_kind(), DistanceKind::Dot));
         let search_hints = Hints::default().full(true);
         let rerank_hints = Hints::default().full(false);
@@ -124,6 +129,7 @@ impl SearchBuilder for MaxsimBuilder {
                 _,
                 AlwaysEqual<PackedRefMut8<(NonZero<u64>, _, _)>>,
             )| (rough, payload);
+        let filter_fetcher = &mut fetcher;
         let iter: Box<dyn Iterator<Item = _>> = match opfamily.vector_kind() {
             VectorKind::Vecf32 => {
                 type Op = vchordrq::operator::Op<VectOwned<f32>, Dot>;
@@ -145,6 +151,7 @@ impl SearchBuilder for MaxsimBuilder {
                     let (results, estimation_by_threshold) = match options.io_search {
                         Io::Plain => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             projected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -155,6 +162,7 @@ impl SearchBuilder for MaxsimBuilder {
                         ),
                         Io::Simple => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             projected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -165,6 +173,7 @@ impl SearchBuilder for MaxsimBuilder {
                         ),
                         Io::Stream => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             projected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -191,7 +200,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -218,7 +227,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -246,7 +255,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -289,6 +298,7 @@ impl SearchBuilder for MaxsimBuilder {
                     let (results, estimation_by_threshold) = match options.io_search {
                         Io::Plain => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             projected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -299,6 +309,7 @@ impl SearchBuilder for MaxsimBuilder {
                         ),
                         Io::Simple => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             projected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -309,6 +320,7 @@ impl SearchBuilder for MaxsimBuilder {
                         ),
                         Io::Stream => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             projected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -335,7 +347,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -362,7 +374,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -390,7 +402,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -429,6 +441,7 @@ impl SearchBuilder for MaxsimBuilder {
                     let (results, estimation_by_threshold) = match options.io_search {
                         Io::Plain => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -439,6 +452,7 @@ impl SearchBuilder for MaxsimBuilder {
                         ),
                         Io::Simple => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -449,6 +463,7 @@ impl SearchBuilder for MaxsimBuilder {
                         ),
                         Io::Stream => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -475,7 +490,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -502,7 +517,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -530,7 +545,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -569,6 +584,293 @@ impl SearchBuilder for MaxsimBuilder {
                     let (results, estimation_by_threshold) = match options.io_search {
                         Io::Plain => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
+                            unprojected[i].as_borrowed(),
+                            options.probes.clone(),
+                            options.epsilon,
+                            maxsim_threshold,
+                            bump,
+                            make_h1_plain_prefetcher.clone(),
+                            make_h0_plain_prefetcher.clone(),
+                        ),
+                        Io::Simple => maxsim_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected[i].as_borrowed(),
+                            options.probes.clone(),
+                            options.epsilon,
+                            maxsim_threshold,
+                            bump,
+                            make_h1_plain_prefetcher.clone(),
+                            make_h0_simple_prefetcher.clone(),
+                        ),
+                        Io::Stream => maxsim_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected[i].as_borrowed(),
+                            options.probes.clone(),
+                            options.epsilon,
+                            maxsim_threshold,
+                            bump,
+                            make_h1_plain_prefetcher.clone(),
+                            make_h0_stream_prefetcher.clone(),
+                        ),
+                    };
+                    let (mut accu_set, mut rough_set) = (Vec::new(), Vec::new());
+                    if maxsim_refine != 0 && !results.is_empty() {
+                        let sequence = Heap::from(results);
+                        match (options.io_rerank, options.prefilter) {
+                            (Io::Plain, false) => {
+                                let prefetcher = PlainPrefetcher::new(index, sequence);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                            (Io::Plain, true) => {
+                                let predicate =
+                                    id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
+                                        let (key, _) = pointer_to_kv(*pointer);
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
+                                            return false;
+                                        };
+                                        tuple.filter()
+                                    });
+                                let sequence = filter(sequence, predicate);
+                                let prefetcher = PlainPrefetcher::new(index, sequence);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                            (Io::Simple, false) => {
+                                let prefetcher = SimplePrefetcher::new(index, sequence);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                            (Io::Simple, true) => {
+                                let predicate =
+                                    id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
+                                        let (key, _) = pointer_to_kv(*pointer);
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
+                                            return false;
+                                        };
+                                        tuple.filter()
+                                    });
+                                let sequence = filter(sequence, predicate);
+                                let prefetcher = SimplePrefetcher::new(index, sequence);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                            (Io::Stream, false) => {
+                                let prefetcher =
+                                    StreamPrefetcher::new(index, sequence, rerank_hints);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                            (Io::Stream, true) => {
+                                let predicate =
+                                    id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
+                                        let (key, _) = pointer_to_kv(*pointer);
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
+                                            return false;
+                                        };
+                                        tuple.filter()
+                                    });
+                                let sequence = filter(sequence, predicate);
+                                let prefetcher =
+                                    StreamPrefetcher::new(index, sequence, rerank_hints);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                        }
+                    } else {
+                        let rough_iter = results.into_iter();
+                        rough_set.extend(rough_iter.map(rough_map));
+                    }
+                    (accu_set, rough_set, estimation_by_threshold)
+                }))
+            }
+            VectorKind::Rabitq2 => {
+                type Op = vchordrq::operator::Op<Rabitq2Owned, Dot>;
+                let unprojected = vectors
+                    .into_iter()
+                    .map(|vector| {
+                        if let OwnedVector::Rabitq2(vector) = vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Box::new((0..n).map(move |i| {
+                    let (results, estimation_by_threshold) = match options.io_search {
+                        Io::Plain => maxsim_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected[i].as_borrowed(),
+                            options.probes.clone(),
+                            options.epsilon,
+                            maxsim_threshold,
+                            bump,
+                            make_h1_plain_prefetcher.clone(),
+                            make_h0_plain_prefetcher.clone(),
+                        ),
+                        Io::Simple => maxsim_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected[i].as_borrowed(),
+                            options.probes.clone(),
+                            options.epsilon,
+                            maxsim_threshold,
+                            bump,
+                            make_h1_plain_prefetcher.clone(),
+                            make_h0_simple_prefetcher.clone(),
+                        ),
+                        Io::Stream => maxsim_search::<_, Op>(
+                            index,
+                            options.upper,
+                            unprojected[i].as_borrowed(),
+                            options.probes.clone(),
+                            options.epsilon,
+                            maxsim_threshold,
+                            bump,
+                            make_h1_plain_prefetcher.clone(),
+                            make_h0_stream_prefetcher.clone(),
+                        ),
+                    };
+                    let (mut accu_set, mut rough_set) = (Vec::new(), Vec::new());
+                    if maxsim_refine != 0 && !results.is_empty() {
+                        let sequence = Heap::from(results);
+                        match (options.io_rerank, options.prefilter) {
+                            (Io::Plain, false) => {
+                                let prefetcher = PlainPrefetcher::new(index, sequence);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                            (Io::Plain, true) => {
+                                let predicate =
+                                    id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
+                                        let (key, _) = pointer_to_kv(*pointer);
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
+                                            return false;
+                                        };
+                                        tuple.filter()
+                                    });
+                                let sequence = filter(sequence, predicate);
+                                let prefetcher = PlainPrefetcher::new(index, sequence);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                            (Io::Simple, false) => {
+                                let prefetcher = SimplePrefetcher::new(index, sequence);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                            (Io::Simple, true) => {
+                                let predicate =
+                                    id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
+                                        let (key, _) = pointer_to_kv(*pointer);
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
+                                            return false;
+                                        };
+                                        tuple.filter()
+                                    });
+                                let sequence = filter(sequence, predicate);
+                                let prefetcher = SimplePrefetcher::new(index, sequence);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                            (Io::Stream, false) => {
+                                let prefetcher =
+                                    StreamPrefetcher::new(index, sequence, rerank_hints);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                            (Io::Stream, true) => {
+                                let predicate =
+                                    id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
+                                        let (key, _) = pointer_to_kv(*pointer);
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
+                                            return false;
+                                        };
+                                        tuple.filter()
+                                    });
+                                let sequence = filter(sequence, predicate);
+                                let prefetcher =
+                                    StreamPrefetcher::new(index, sequence, rerank_hints);
+                                let mut reranker =
+                                    rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
+                                accu_set.extend(reranker.by_ref().take(maxsim_refine as _));
+                                let (rough_iter, accu_iter) = reranker.finish();
+                                accu_set.extend(accu_iter.map(accu_map));
+                                rough_set.extend(rough_iter.into_iter().map(rough_map));
+                            }
+                        }
+                    } else {
+                        let rough_iter = results.into_iter();
+                        rough_set.extend(rough_iter.map(rough_map));
+                    }
+                    (accu_set, rough_set, estimation_by_threshold)
+                }))
+            }
+            VectorKind::Rabitq1 => {
+                type Op = vchordrq::operator::Op<Rabitq1Owned, Dot>;
+                let unprojected = vectors
+                    .into_iter()
+                    .map(|vector| {
+                        if let OwnedVector::Rabitq1(vector) = vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Box::new((0..n).map(move |i| {
+                    let (results, estimation_by_threshold) = match options.io_search {
+                        Io::Plain => maxsim_search::<_, Op>(
+                            index,
+                            options.upper,
                             unprojected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -579,6 +881,7 @@ impl SearchBuilder for MaxsimBuilder {
                         ),
                         Io::Simple => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -589,6 +892,7 @@ impl SearchBuilder for MaxsimBuilder {
                         ),
                         Io::Stream => maxsim_search::<_, Op>(
                             index,
+                            options.upper,
                             unprojected[i].as_borrowed(),
                             options.probes.clone(),
                             options.epsilon,
@@ -615,7 +919,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -642,7 +946,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -670,7 +974,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
-                                        let Some(mut tuple) = fetcher.fetch(key) else {
+                                        let Some(mut tuple) = filter_fetcher.fetch(key) else {
                                             return false;
                                         };
                                         tuple.filter()
@@ -693,6 +997,8 @@ impl SearchBuilder for MaxsimBuilder {
                     (accu_set, rough_set, estimation_by_threshold)
                 }))
             }
+            VectorKind::Veci8 => unreachable!(),
+            VectorKind::Vecbf16 => unreachable!(),
         };
         let mut updates = Vec::new();
         let mut estimations = Vec::new();
@@ -718,8 +1024,16 @@ impl SearchBuilder for MaxsimBuilder {
         updates.sort_unstable_by_key(|&(key, ..)| key);
         let iter = updates
             .chunk_by(|(kl, ..), (kr, ..)| kl == kr)
-            .map(|chunk| {
+            .filter_map(|chunk| {
                 let key = chunk[0].0;
+                if let Some(queries) = queries.as_ref() {
+                    let mut tuple = fetcher.fetch(key)?;
+                    let (datums, is_nulls) = tuple.build();
+                    let datum = (!is_nulls[0]).then_some(datums[0]);
+                    let documents = unsafe { datum.and_then(|x| opfamily.input_vectors(x)) }?;
+                    let distance = operator_maxsim(&documents, queries);
+                    return Some((Reverse(distance), AlwaysEqual(key)));
+                }
                 let mut value = vec![None; n];
                 for &(_, query_id, distance) in chunk {
                     let this = value[query_id].get_or_insert(Distance::INFINITY);
@@ -730,7 +1044,7 @@ impl SearchBuilder for MaxsimBuilder {
                     let d = distance.unwrap_or(estimations[query_id]);
                     maxsim += Distance::to_f32(d);
                 }
-                (Reverse(Distance::from_f32(maxsim)), AlwaysEqual(key))
+                Some((Reverse(Distance::from_f32(maxsim)), AlwaysEqual(key)))
             })
             .collect::<BinaryHeap<_>>()
             .into_iter_sorted_polyfill()
@@ -750,6 +1064,62 @@ impl SearchBuilder for MaxsimBuilder {
     }
 }
 
+fn operator_maxsim(lhs: &[OwnedVector], rhs: &[OwnedVector]) -> Distance {
+    match rhs.first() {
+        None => Distance::ZERO,
+        Some(OwnedVector::Vecf32(_)) => {
+            let unwrap = |vector: &OwnedVector| {
+                if let OwnedVector::Vecf32(vector) = vector {
+                    vector.as_borrowed()
+                } else {
+                    unreachable!()
+                }
+            };
+            let lhs = lhs.iter().map(unwrap).collect::<Vec<_>>();
+            let rhs = rhs.iter().map(unwrap).collect::<Vec<_>>();
+            VectBorrowed::operator_maxsim(&lhs, &rhs)
+        }
+        Some(OwnedVector::Vecf16(_)) => {
+            let unwrap = |vector: &OwnedVector| {
+                if let OwnedVector::Vecf16(vector) = vector {
+                    vector.as_borrowed()
+                } else {
+                    unreachable!()
+                }
+            };
+            let lhs = lhs.iter().map(unwrap).collect::<Vec<_>>();
+            let rhs = rhs.iter().map(unwrap).collect::<Vec<_>>();
+            VectBorrowed::operator_maxsim(&lhs, &rhs)
+        }
+        Some(_) => {
+            let mut maxsim = 0.0f32;
+            for rhs in rhs {
+                let mut d = f32::INFINITY;
+                for lhs in lhs {
+                    let distance = match (lhs, rhs) {
+                        (OwnedVector::Rabitq8(lhs), OwnedVector::Rabitq8(rhs)) => {
+                            VectorBorrowed::operator_dot(lhs.as_borrowed(), rhs.as_borrowed())
+                        }
+                        (OwnedVector::Rabitq4(lhs), OwnedVector::Rabitq4(rhs)) => {
+                            VectorBorrowed::operator_dot(lhs.as_borrowed(), rhs.as_borrowed())
+                        }
+                        (OwnedVector::Rabitq2(lhs), OwnedVector::Rabitq2(rhs)) => {
+                            VectorBorrowed::operator_dot(lhs.as_borrowed(), rhs.as_borrowed())
+                        }
+                        (OwnedVector::Rabitq1(lhs), OwnedVector::Rabitq1(rhs)) => {
+                            VectorBorrowed::operator_dot(lhs.as_borrowed(), rhs.as_borrowed())
+                        }
+                        _ => unreachable!(),
+                    };
+                    d = d.min(distance.to_f32());
+                }
+                maxsim += d;
+            }
+            Distance::from_f32(maxsim)
+        }
+    }
+}
+
 // Emulate unstable library feature `binary_heap_into_iter_sorted`.
 // See https://github.com/rust-lang/rust/issues/59278.
 
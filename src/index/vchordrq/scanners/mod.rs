@@ -12,11 +12,13 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+mod batch;
 mod default;
 mod maxsim;
 
 use crate::index::scanners::Io;
 
+pub use batch::{Limit, near_duplicates, search_batch};
 pub use default::DefaultBuilder;
 pub use maxsim::MaxsimBuilder;
 
@@ -30,4 +32,5 @@ pub struct SearchOptions {
     pub io_search: Io,
     pub io_rerank: Io,
     pub prefilter: bool,
+    pub upper: Option<vchordrq::Upper<'static>>,
 }
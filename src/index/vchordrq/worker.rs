@@ -0,0 +1,158 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+//! Background workers, one for each database in
+//! `vchordrq.maintenance_databases`, that maintain lists with many live tuples
+//! in their appendable tapes, so that they don't have to wait for vacuum.
+
+use crate::index::gucs;
+use crate::index::storage::PostgresRelation;
+use crate::index::vchordrq::cache;
+use crate::index::vchordrq::opclass::opfamily;
+use pgrx::bgworkers::{BackgroundWorker, BackgroundWorkerBuilder, SignalWakeFlags};
+use pgrx::pg_sys::Oid;
+use std::time::Duration;
+use vchordrq::MaintainChooser;
+
+pub fn init() {
+    assert!(crate::is_main());
+    // The setting can only be set at server start, so workers are registered
+    // for all databases here, and each of them finds its database by index.
+    let databases = gucs::vchordrq_maintenance_databases();
+    for (i, database) in databases.iter().enumerate() {
+        BackgroundWorkerBuilder::new(&format!("vchordrq maintenance worker for {database}"))
+            .set_type("vchordrq maintenance worker")
+            .set_library("vchord")
+            .set_function("_vchordrq_maintenance_main")
+            .set_argument(Some(pgrx::pg_sys::Datum::from(i)))
+            .enable_spi_access()
+            .set_restart_time(Some(Duration::from_secs(60)))
+            .load();
+    }
+}
+
+#[pgrx::pg_guard]
+#[unsafe(export_name = "_vchordrq_maintenance_main")]
+extern "C-unwind" fn maintenance_main(arg: pgrx::pg_sys::Datum) {
+    BackgroundWorker::attach_signal_handlers(SignalWakeFlags::SIGHUP | SignalWakeFlags::SIGTERM);
+    let databases = gucs::vchordrq_maintenance_databases();
+    let Some(database) = databases.get(arg.value()) else {
+        return;
+    };
+    BackgroundWorker::connect_worker_to_spi(Some(database), None);
+    pgrx::log!("vchordrq maintenance worker: started on database {database:?}");
+    while BackgroundWorker::wait_latch(Some(gucs::vchordrq_maintenance_naptime())) {
+        if BackgroundWorker::sighup_received() {
+            unsafe {
+                pgrx::pg_sys::ProcessConfigFile(pgrx::pg_sys::GucContext::PGC_SIGHUP);
+            }
+        }
+        let indexes = BackgroundWorker::transaction(indexes);
+        for (indexrelid, heaprelid) in indexes {
+            if BackgroundWorker::sigterm_received() {
+                break;
+            }
+            // Locks are released between indexes.
+            BackgroundWorker::transaction(move || maintain(indexrelid, heaprelid));
+        }
+    }
+}
+
+// Indexes of temporary tables are skipped, since they are invisible to the
+// worker, and so are indexes that are being built.
+fn indexes() -> Vec<(Oid, Oid)> {
+    use pgrx::pg_sys::panic::ErrorReportable;
+    let query = "SELECT i.indexrelid, i.indrelid
+        FROM pg_catalog.pg_index i
+        JOIN pg_catalog.pg_class c ON c.oid = i.indexrelid
+        JOIN pg_catalog.pg_am a ON a.oid = c.relam
+        WHERE a.amname = 'vchordrq' AND i.indisvalid AND i.indisready
+        AND c.relpersistence <> 't';";
+    pgrx::spi::Spi::connect(|client| {
+        let mut result = Vec::new();
+        for row in client.select(query, None, &[]).unwrap_or_report() {
+            let indexrelid: Option<Oid> = row.get(1).unwrap();
+            let heaprelid: Option<Oid> = row.get(2).unwrap();
+            if let (Some(indexrelid), Some(heaprelid)) = (indexrelid, heaprelid) {
+                result.push((indexrelid, heaprelid));
+            }
+        }
+        result
+    })
+}
+
+fn maintain(indexrelid: Oid, heaprelid: Oid) {
+    struct Chooser {
+        appendable: Vec<u64>,
+        threshold: u64,
+        delay: Duration,
+        chosen: bool,
+    }
+    impl MaintainChooser for Chooser {
+        fn choose(&mut self, i: usize) -> bool {
+            if self.appendable[i] <= self.threshold || BackgroundWorker::sigterm_received() {
+                return false;
+            }
+            // The latch is set on SIGTERM and the wait ends on postmaster death,
+            // so shutdown and cancellation do not wait for the delay.
+            if std::mem::replace(&mut self.chosen, true) && !self.delay.is_zero() {
+                if !BackgroundWorker::wait_latch(Some(self.delay)) {
+                    return false;
+                }
+                pgrx::check_for_interrupts!();
+            }
+            true
+        }
+    }
+
+    use pgrx::pg_sys::{
+        ConditionalLockRelationOid, RowExclusiveLock, ShareUpdateExclusiveLock, index_close,
+        index_open,
+    };
+    // The lock on the table conflicts with vacuum and DDL, and the worker
+    // skips the index rather than waiting for them.
+    if !unsafe { ConditionalLockRelationOid(heaprelid, ShareUpdateExclusiveLock as _) } {
+        return;
+    }
+    // The index may be dropped before the lock is taken.
+    let pg_class = pgrx_catalog::PgClass::search_reloid(indexrelid).unwrap();
+    if pg_class.get().is_none() {
+        return;
+    }
+    let check = || {
+        pgrx::check_for_interrupts!();
+    };
+    let raw = unsafe { index_open(indexrelid, RowExclusiveLock as _) };
+    let opfamily = unsafe { opfamily(raw) };
+    let index = unsafe { PostgresRelation::new(raw) };
+    let appendable = vchordrq::appendable(&index, check);
+    let threshold = gucs::vchordrq_maintenance_threshold();
+    let lists = appendable.iter().filter(|&&x| x > threshold).count();
+    if lists > 0 {
+        pgrx::debug1!(
+            "vchordrq maintenance worker: maintaining {lists} lists of index {indexrelid:?}"
+        );
+        let _invalidation = unsafe { cache::Invalidation::new(raw) };
+        let mut chooser = Chooser {
+            appendable,
+            threshold,
+            delay: gucs::vchordrq_maintenance_delay(),
+            chosen: false,
+        };
+        crate::index::vchordrq::dispatch::maintain(opfamily, &index, &mut chooser, check);
+    }
+    unsafe {
+        index_close(raw, RowExclusiveLock as _);
+    }
+}
@@ -0,0 +1,144 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use pgrx::pg_sys::Oid;
+use simd::{bf16, f16};
+use std::ffi::CStr;
+use vector::VectorBorrowed;
+use vector::rabitq1::Rabitq1Borrowed;
+use vector::rabitq2::Rabitq2Borrowed;
+use vector::rabitq4::Rabitq4Borrowed;
+use vector::rabitq8::Rabitq8Borrowed;
+use vector::veci8::Veci8Borrowed;
+use vector::vect::VectBorrowed;
+
+// the same as `vector_send` of pgvector
+pub fn vector_send(vector: VectBorrowed<'_, f32>) -> Vec<u8> {
+    let mut stream = Vec::<u8>::new();
+    stream.extend((vector.dim() as u16).to_be_bytes());
+    stream.extend(0_u16.to_be_bytes());
+    for x in vector.slice() {
+        stream.extend(x.to_be_bytes());
+    }
+    stream
+}
+
+// the same as `halfvec_send` of pgvector
+pub fn halfvec_send(vector: VectBorrowed<'_, f16>) -> Vec<u8> {
+    let mut stream = Vec::<u8>::new();
+    stream.extend((vector.dim() as u16).to_be_bytes());
+    stream.extend(0_u16.to_be_bytes());
+    for x in vector.slice() {
+        stream.extend(x.to_bits().to_be_bytes());
+    }
+    stream
+}
+
+// the same as `_vchord_rabitq8_send`
+pub fn rabitq8_send(vector: Rabitq8Borrowed<'_>) -> Vec<u8> {
+    let mut stream = Vec::<u8>::new();
+    stream.extend(vector.dim().to_be_bytes());
+    stream.extend(vector.sum_of_x2().to_be_bytes());
+    stream.extend(vector.norm_of_lattice().to_be_bytes());
+    stream.extend(vector.sum_of_code().to_be_bytes());
+    stream.extend(vector.sum_of_abs_x().to_be_bytes());
+    for &c in vector.packed_code() {
+        stream.extend(c.to_be_bytes());
+    }
+    stream
+}
+
+// the same as `_vchord_rabitq4_send`
+pub fn rabitq4_send(vector: Rabitq4Borrowed<'_>) -> Vec<u8> {
+    let mut stream = Vec::<u8>::new();
+    stream.extend(vector.dim().to_be_bytes());
+    stream.extend(vector.sum_of_x2().to_be_bytes());
+    stream.extend(vector.norm_of_lattice().to_be_bytes());
+    stream.extend(vector.sum_of_code().to_be_bytes());
+    stream.extend(vector.sum_of_abs_x().to_be_bytes());
+    for &c in vector.packed_code() {
+        stream.extend(c.to_be_bytes());
+    }
+    stream
+}
+
+// the same as `_vchord_rabitq2_send`
+pub fn rabitq2_send(vector: Rabitq2Borrowed<'_>) -> Vec<u8> {
+    let mut stream = Vec::<u8>::new();
+    stream.extend(vector.dim().to_be_bytes());
+    stream.extend(vector.sum_of_x2().to_be_bytes());
+    stream.extend(vector.norm_of_lattice().to_be_bytes());
+    stream.extend(vector.sum_of_code().to_be_bytes());
+    stream.extend(vector.sum_of_abs_x().to_be_bytes());
+    for &c in vector.packed_code() {
+        stream.extend(c.to_be_bytes());
+    }
+    stream
+}
+
+// the same as `_vchord_rabitq1_send`
+pub fn rabitq1_send(vector: Rabitq1Borrowed<'_>) -> Vec<u8> {
+    let mut stream = Vec::<u8>::new();
+    stream.extend(vector.dim().to_be_bytes());
+    stream.extend(vector.sum_of_x2().to_be_bytes());
+    stream.extend(vector.norm_of_lattice().to_be_bytes());
+    stream.extend(vector.sum_of_code().to_be_bytes());
+    stream.extend(vector.sum_of_abs_x().to_be_bytes());
+    for &c in vector.packed_code() {
+        stream.extend(c.to_be_bytes());
+    }
+    stream
+}
+
+// the same as `_vchord_int8vec_send`
+pub fn int8vec_send(vector: Veci8Borrowed<'_>) -> Vec<u8> {
+    let mut stream = Vec::<u8>::new();
+    stream.extend(vector.dim().to_be_bytes());
+    for &c in vector.slice() {
+        stream.extend(c.to_be_bytes());
+    }
+    stream
+}
+// the same as `_vchord_bf16vec_send`
+pub fn bf16vec_send(vector: VectBorrowed<'_, bf16>) -> Vec<u8> {
+    let mut stream = Vec::<u8>::new();
+    stream.extend(vector.dim().to_be_bytes());
+    for &c in vector.slice() {
+        stream.extend(c.to_be_bytes());
+    }
+    stream
+}
+
+/// Renders a value in the binary format of the type as text, using the
+/// receive function and the output function of the type.
+pub fn output(typid: u32, data: &[u8]) -> String {
+    let typid = Oid::from(typid);
+    unsafe {
+        let mut typreceive = Oid::INVALID;
+        let mut typioparam = Oid::INVALID;
+        pgrx::pg_sys::getTypeBinaryInputInfo(typid, &mut typreceive, &mut typioparam);
+        let mut buf = std::mem::zeroed::<pgrx::pg_sys::StringInfoData>();
+        pgrx::pg_sys::initStringInfo(&mut buf);
+        pgrx::pg_sys::appendBinaryStringInfo(&mut buf, data.as_ptr().cast(), data.len() as _);
+        let datum = pgrx::pg_sys::OidReceiveFunctionCall(typreceive, &mut buf, typioparam, -1);
+        let mut typoutput = Oid::INVALID;
+        let mut typisvarlena = false;
+        pgrx::pg_sys::getTypeOutputInfo(typid, &mut typoutput, &mut typisvarlena);
+        let cstr = pgrx::pg_sys::OidOutputFunctionCall(typoutput, datum);
+        let result = CStr::from_ptr(cstr).to_string_lossy().into_owned();
+        pgrx::pg_sys::pfree(cstr.cast());
+        pgrx::pg_sys::pfree(buf.data.cast());
+        result
+    }
+}
@@ -12,14 +12,14 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
-pub use types::{DefaultRecorder, Recorder};
-pub use worker::dump;
+pub use types::{Counter, DefaultRecorder, Recorder, Recording};
+pub use worker::{Value, dump};
 
 mod hook;
 mod types;
 mod worker;
 
-pub mod text;
+pub mod binary;
 
 pub fn init() {
     hook::init();
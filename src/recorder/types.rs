@@ -14,11 +14,26 @@
 
 use crate::recorder::worker::push;
 use rand::RngExt;
-use std::cell::RefMut;
+use std::cell::{Cell, RefMut};
+use std::rc::Rc;
+use std::time::Instant;
 
 pub trait Recorder {
     fn is_enabled(&self) -> bool;
-    fn send(&self, sample: &str);
+    fn send(&self, sample: &Sample<'_>);
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sample<'a> {
+    // the query vector, in the binary format of its type
+    pub value: &'a [u8],
+    // a JSON object of the effective search options
+    pub options: &'a str,
+    // in milliseconds
+    pub elapsed: f64,
+    pub rows: u64,
+    pub scanned: Option<u64>,
+    pub reranked: Option<u64>,
 }
 
 #[derive(Debug)]
@@ -27,6 +42,7 @@ pub struct DefaultRecorder {
     pub rate: Option<f64>,
     pub max_records: u32,
     pub index: u32,
+    pub typid: u32,
 }
 
 pub struct PgRefCell<T>(std::cell::RefCell<T>);
@@ -51,12 +67,82 @@ impl Recorder for DefaultRecorder {
     fn is_enabled(&self) -> bool {
         self.enable
     }
-    fn send(&self, sample: &str) {
+    fn send(&self, sample: &Sample<'_>) {
         if let Some(rate) = self.rate {
             let mut rng = rand::rng();
             if rng.random_bool(rate) {
-                push(self.index, sample, self.max_records);
+                push(self.index, self.typid, sample, self.max_records);
             }
         }
     }
 }
+
+#[derive(Debug, Clone, Default)]
+pub struct Counter(Rc<Cell<u64>>);
+
+impl Counter {
+    pub fn add(&self, n: u64) {
+        self.0.set(self.0.get() + n);
+    }
+    pub fn get(&self) -> u64 {
+        self.0.get()
+    }
+}
+
+/// Counts rows returned by a scan, and sends the sample to the recorder
+/// once the scan is dropped.
+pub struct Recording<I, R: Recorder> {
+    iter: I,
+    recorder: R,
+    value: Vec<u8>,
+    options: String,
+    start: Instant,
+    rows: u64,
+    scanned: Option<Counter>,
+    reranked: Option<Counter>,
+}
+
+impl<I, R: Recorder> Recording<I, R> {
+    pub fn new(
+        iter: I,
+        recorder: R,
+        value: Vec<u8>,
+        options: String,
+        scanned: Option<Counter>,
+        reranked: Option<Counter>,
+    ) -> Self {
+        Self {
+            iter,
+            recorder,
+            value,
+            options,
+            start: Instant::now(),
+            rows: 0,
+            scanned,
+            reranked,
+        }
+    }
+}
+
+impl<I: Iterator, R: Recorder> Iterator for Recording<I, R> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        self.rows += 1;
+        Some(item)
+    }
+}
+
+impl<I, R: Recorder> Drop for Recording<I, R> {
+    fn drop(&mut self) {
+        self.recorder.send(&Sample {
+            value: &self.value,
+            options: &self.options,
+            elapsed: self.start.elapsed().as_secs_f64() * 1000.0,
+            rows: self.rows,
+            scanned: self.scanned.as_ref().map(Counter::get),
+            reranked: self.reranked.as_ref().map(Counter::get),
+        });
+    }
+}
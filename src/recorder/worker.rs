@@ -12,14 +12,15 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
-use crate::recorder::types::PgRefCell;
+use crate::recorder::types::{PgRefCell, Sample};
+use rusqlite::types::ValueRef;
 use std::cell::RefMut;
 use std::fs;
 use std::path::Path;
 
 // The directory name must start with "pgsql_tmp" to be excluded by pg_basebackup
 const RECORDER_DIR: &str = "pgsql_tmp_vchord_sampling";
-const RECORDER_VERSION: u32 = 1;
+const RECORDER_VERSION: u32 = 3;
 
 static CONNECTION: PgRefCell<Option<rusqlite::Connection>> =
     PgRefCell::<Option<rusqlite::Connection>>::new(None);
@@ -56,10 +57,28 @@ fn get<'a>(create: bool) -> Option<RefMut<'a, rusqlite::Connection>> {
                 let mut statement = tx.prepare(
                     "SELECT name FROM sqlite_master WHERE type='table' AND name LIKE 'index_%';",
                 )?;
-                let tables = statement.query_map((), |row| row.get::<usize, String>(0))?;
-                for name in tables.into_iter().flatten() {
-                    let drop_statement = format!("DROP TABLE IF EXISTS {name}");
-                    tx.execute(&drop_statement, ())?;
+                let tables = statement
+                    .query_map((), |row| row.get::<usize, String>(0))?
+                    .flatten()
+                    .collect::<Vec<_>>();
+                // samples recorded by older versions are kept as text, with a NULL `typid`
+                for name in tables {
+                    match version {
+                        1 => tx.execute_batch(&format!(
+                            "
+                            ALTER TABLE {name} ADD COLUMN options TEXT;
+                            ALTER TABLE {name} ADD COLUMN elapsed REAL;
+                            ALTER TABLE {name} ADD COLUMN rows INTEGER;
+                            ALTER TABLE {name} ADD COLUMN scanned INTEGER;
+                            ALTER TABLE {name} ADD COLUMN reranked INTEGER;
+                            ALTER TABLE {name} ADD COLUMN typid INTEGER;
+                            "
+                        ))?,
+                        2 => tx.execute_batch(&format!(
+                            "ALTER TABLE {name} ADD COLUMN typid INTEGER;"
+                        ))?,
+                        _ => tx.execute_batch(&format!("DROP TABLE IF EXISTS {name};"))?,
+                    }
                 }
             }
             tx.pragma_update(Some("main"), "user_version", RECORDER_VERSION)?;
@@ -77,19 +96,24 @@ fn get<'a>(create: bool) -> Option<RefMut<'a, rusqlite::Connection>> {
     RefMut::filter_map(connection, |c| c.as_mut()).ok()
 }
 
-pub fn push(index: u32, sample: &str, max_records: u32) {
+pub fn push(index: u32, typid: u32, sample: &Sample<'_>, max_records: u32) {
     let mut connection = match get(true) {
         Some(c) => c,
         None => return,
     };
     let init_statement = format!(
         "
-        CREATE TABLE IF NOT EXISTS index_{index} (sample TEXT, create_at REAL);
+        CREATE TABLE IF NOT EXISTS index_{index} (
+            sample BLOB, create_at REAL, options TEXT, elapsed REAL,
+            rows INTEGER, scanned INTEGER, reranked INTEGER, typid INTEGER
+        );
         CREATE INDEX IF NOT EXISTS i ON index_{index} (create_at);
         "
     );
-    let insert_statement =
-        format!("INSERT INTO index_{index} (sample, create_at) VALUES (?1, unixepoch('subsec'))");
+    let insert_statement = format!(
+        "INSERT INTO index_{index} (sample, options, elapsed, rows, scanned, reranked, typid, create_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, unixepoch('subsec'))"
+    );
     let count_statement = format!("SELECT COUNT(create_at) FROM index_{index}");
     let maintain_statement = format!(
         "DELETE FROM index_{index} WHERE rowid = (
@@ -98,7 +122,15 @@ pub fn push(index: u32, sample: &str, max_records: u32) {
     if let Err(err) = || -> rusqlite::Result<()> {
         let tx = connection.transaction()?;
         tx.execute_batch(&init_statement)?;
-        tx.prepare_cached(&insert_statement)?.execute((sample,))?;
+        tx.prepare_cached(&insert_statement)?.execute((
+            sample.value,
+            sample.options,
+            sample.elapsed,
+            sample.rows as i64,
+            sample.scanned.map(|x| x as i64),
+            sample.reranked.map(|x| x as i64),
+            typid,
+        ))?;
         let records = tx.query_one(&count_statement, (), |row| row.get::<usize, u32>(0))?;
         if records > max_records {
             tx.execute(&maintain_statement, (records - max_records,))?;
@@ -127,19 +159,65 @@ pub fn delete_database(database_oid: u32) {
     let _ = fs::remove_file(format!("{RECORDER_DIR}/database_{database_oid}.sqlite-wal"));
 }
 
-pub fn dump(index: u32) -> Vec<String> {
+#[derive(Debug, Clone)]
+pub enum Value {
+    // samples recorded before version 3
+    Text(String),
+    Binary { typid: u32, data: Vec<u8> },
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub value: Value,
+    pub options: Option<String>,
+    pub elapsed: Option<f64>,
+    pub rows: Option<i64>,
+    pub scanned: Option<i64>,
+    pub reranked: Option<i64>,
+}
+
+pub fn dump(index: u32) -> Vec<Record> {
     let connection = match get(false) {
         Some(c) => c,
         None => return Vec::new(),
     };
-    let load_statement = format!("SELECT sample FROM index_{index} ORDER BY create_at DESC");
-    match || -> rusqlite::Result<Vec<String>> {
+    let load_statement = format!(
+        "SELECT sample, typid, options, elapsed, rows, scanned, reranked
+        FROM index_{index} ORDER BY create_at DESC"
+    );
+    match || -> rusqlite::Result<Vec<Record>> {
         let mut stmt = connection.prepare(&load_statement)?;
         let mut rows = stmt.query(())?;
         let mut result = Vec::new();
         while let Some(row) = rows.next()? {
-            if let Ok(sample) = row.get::<usize, String>(0) {
-                result.push(sample);
+            let record = || -> rusqlite::Result<Record> {
+                let value = match (row.get_ref(0)?, row.get::<usize, Option<u32>>(1)?) {
+                    (ValueRef::Blob(data), Some(typid)) => Value::Binary {
+                        typid,
+                        data: data.to_vec(),
+                    },
+                    (ValueRef::Text(text), None) => {
+                        Value::Text(String::from_utf8_lossy(text).into_owned())
+                    }
+                    (value, _) => {
+                        return Err(rusqlite::Error::InvalidColumnType(
+                            0,
+                            "sample".to_string(),
+                            value.data_type(),
+                        ));
+                    }
+                };
+                Ok(Record {
+                    value,
+                    options: row.get(2)?,
+                    elapsed: row.get(3)?,
+                    rows: row.get(4)?,
+                    scanned: row.get(5)?,
+                    reranked: row.get(6)?,
+                })
+            }();
+            if let Ok(record) = record {
+                result.push(record);
             }
         }
         Ok(result)
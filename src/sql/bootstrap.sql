@@ -6,3 +6,11 @@ CREATE TYPE rabitq8;
 CREATE TYPE sphere_rabitq8;
 CREATE TYPE rabitq4;
 CREATE TYPE sphere_rabitq4;
+CREATE TYPE rabitq2;
+CREATE TYPE sphere_rabitq2;
+CREATE TYPE rabitq1;
+CREATE TYPE sphere_rabitq1;
+CREATE TYPE int8vec;
+CREATE TYPE sphere_int8vec;
+CREATE TYPE bf16vec;
+CREATE TYPE sphere_bf16vec;
@@ -18,6 +18,42 @@ CREATE TYPE rabitq4 (
     STORAGE = external
 );
 
+CREATE TYPE rabitq2 (
+    INPUT = _vchord_rabitq2_in,
+    OUTPUT = _vchord_rabitq2_out,
+    TYPMOD_IN = _vchord_rabitq2_typmod_in,
+    RECEIVE = _vchord_rabitq2_recv,
+    SEND = _vchord_rabitq2_send,
+    STORAGE = external
+);
+
+CREATE TYPE rabitq1 (
+    INPUT = _vchord_rabitq1_in,
+    OUTPUT = _vchord_rabitq1_out,
+    TYPMOD_IN = _vchord_rabitq1_typmod_in,
+    RECEIVE = _vchord_rabitq1_recv,
+    SEND = _vchord_rabitq1_send,
+    STORAGE = external
+);
+
+CREATE TYPE int8vec (
+    INPUT = _vchord_int8vec_in,
+    OUTPUT = _vchord_int8vec_out,
+    TYPMOD_IN = _vchord_int8vec_typmod_in,
+    RECEIVE = _vchord_int8vec_recv,
+    SEND = _vchord_int8vec_send,
+    STORAGE = external
+);
+
+CREATE TYPE bf16vec (
+    INPUT = _vchord_bf16vec_in,
+    OUTPUT = _vchord_bf16vec_out,
+    TYPMOD_IN = _vchord_bf16vec_typmod_in,
+    RECEIVE = _vchord_bf16vec_recv,
+    SEND = _vchord_bf16vec_send,
+    STORAGE = external
+);
+
 CREATE TYPE sphere_vector AS (
     center vector,
     radius REAL
@@ -38,6 +74,26 @@ CREATE TYPE sphere_rabitq4 AS (
     radius REAL
 );
 
+CREATE TYPE sphere_rabitq2 AS (
+    center rabitq2,
+    radius REAL
+);
+
+CREATE TYPE sphere_rabitq1 AS (
+    center rabitq1,
+    radius REAL
+);
+
+CREATE TYPE sphere_int8vec AS (
+    center int8vec,
+    radius REAL
+);
+
+CREATE TYPE sphere_bf16vec AS (
+    center bf16vec,
+    radius REAL
+);
+
 -- List of internal functions
 
 CREATE FUNCTION _vchord_rabitq8_operator_maxsim(rabitq8[], rabitq8[]) RETURNS real
@@ -46,6 +102,69 @@ IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq8
 CREATE FUNCTION _vchord_rabitq4_operator_maxsim(rabitq4[], rabitq4[]) RETURNS real
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4_operator_maxsim_wrapper';
 
+CREATE FUNCTION _vchord_rabitq2_operator_maxsim(rabitq2[], rabitq2[]) RETURNS real
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq2_operator_maxsim_wrapper';
+
+CREATE FUNCTION _vchord_rabitq1_operator_maxsim(rabitq1[], rabitq1[]) RETURNS real
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq1_operator_maxsim_wrapper';
+
+CREATE FUNCTION _vchord_int8vec_operator_maxsim(int8vec[], int8vec[]) RETURNS real
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_int8vec_operator_maxsim_wrapper';
+
+CREATE FUNCTION _vchord_bf16vec_operator_maxsim(bf16vec[], bf16vec[]) RETURNS real
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_bf16vec_operator_maxsim_wrapper';
+
+CREATE FUNCTION _vchord_vector_accum(double precision[], vector) RETURNS double precision[]
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_vector_accum_wrapper';
+
+CREATE FUNCTION _vchord_vector_weighted_accum(double precision[], vector, real) RETURNS double precision[]
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_vector_weighted_accum_wrapper';
+
+CREATE FUNCTION _vchord_vector_sum(double precision[]) RETURNS vector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_vector_sum_wrapper';
+
+CREATE FUNCTION _vchord_vector_avg(double precision[]) RETURNS vector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_vector_avg_wrapper';
+
+CREATE FUNCTION _vchord_halfvec_accum(double precision[], halfvec) RETURNS double precision[]
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_halfvec_accum_wrapper';
+
+CREATE FUNCTION _vchord_halfvec_weighted_accum(double precision[], halfvec, real) RETURNS double precision[]
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_halfvec_weighted_accum_wrapper';
+
+CREATE FUNCTION _vchord_halfvec_sum(double precision[]) RETURNS halfvec
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_halfvec_sum_wrapper';
+
+CREATE FUNCTION _vchord_halfvec_avg(double precision[]) RETURNS halfvec
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_halfvec_avg_wrapper';
+
+CREATE FUNCTION _vchord_rabitq8_accum(double precision[], rabitq8) RETURNS double precision[]
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq8_accum_wrapper';
+
+CREATE FUNCTION _vchord_rabitq8_weighted_accum(double precision[], rabitq8, real) RETURNS double precision[]
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq8_weighted_accum_wrapper';
+
+CREATE FUNCTION _vchord_rabitq8_sum(double precision[]) RETURNS rabitq8
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq8_sum_wrapper';
+
+CREATE FUNCTION _vchord_rabitq8_avg(double precision[]) RETURNS rabitq8
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq8_avg_wrapper';
+
+CREATE FUNCTION _vchord_rabitq4_accum(double precision[], rabitq4) RETURNS double precision[]
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4_accum_wrapper';
+
+CREATE FUNCTION _vchord_rabitq4_weighted_accum(double precision[], rabitq4, real) RETURNS double precision[]
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4_weighted_accum_wrapper';
+
+CREATE FUNCTION _vchord_rabitq4_sum(double precision[]) RETURNS rabitq4
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4_sum_wrapper';
+
+CREATE FUNCTION _vchord_rabitq4_avg(double precision[]) RETURNS rabitq4
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4_avg_wrapper';
+
+CREATE FUNCTION _vchord_accum_combine(double precision[], double precision[]) RETURNS double precision[]
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_accum_combine_wrapper';
+
 -- List of operators
 
 CREATE OPERATOR <-> (
@@ -90,6 +209,90 @@ CREATE OPERATOR <=> (
     COMMUTATOR = <=>
 );
 
+CREATE OPERATOR <-> (
+    PROCEDURE = _vchord_rabitq2_operator_l2,
+    LEFTARG = rabitq2,
+    RIGHTARG = rabitq2,
+    COMMUTATOR = <->
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vchord_rabitq2_operator_ip,
+    LEFTARG = rabitq2,
+    RIGHTARG = rabitq2,
+    COMMUTATOR = <#>
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vchord_rabitq2_operator_cosine,
+    LEFTARG = rabitq2,
+    RIGHTARG = rabitq2,
+    COMMUTATOR = <=>
+);
+
+CREATE OPERATOR <-> (
+    PROCEDURE = _vchord_rabitq1_operator_l2,
+    LEFTARG = rabitq1,
+    RIGHTARG = rabitq1,
+    COMMUTATOR = <->
+);
+
+CREATE OPERATOR <-> (
+    PROCEDURE = _vchord_int8vec_operator_l2,
+    LEFTARG = int8vec,
+    RIGHTARG = int8vec,
+    COMMUTATOR = <->
+);
+
+CREATE OPERATOR <-> (
+    PROCEDURE = _vchord_bf16vec_operator_l2,
+    LEFTARG = bf16vec,
+    RIGHTARG = bf16vec,
+    COMMUTATOR = <->
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vchord_rabitq1_operator_ip,
+    LEFTARG = rabitq1,
+    RIGHTARG = rabitq1,
+    COMMUTATOR = <#>
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vchord_int8vec_operator_ip,
+    LEFTARG = int8vec,
+    RIGHTARG = int8vec,
+    COMMUTATOR = <#>
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vchord_bf16vec_operator_ip,
+    LEFTARG = bf16vec,
+    RIGHTARG = bf16vec,
+    COMMUTATOR = <#>
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vchord_rabitq1_operator_cosine,
+    LEFTARG = rabitq1,
+    RIGHTARG = rabitq1,
+    COMMUTATOR = <=>
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vchord_int8vec_operator_cosine,
+    LEFTARG = int8vec,
+    RIGHTARG = int8vec,
+    COMMUTATOR = <=>
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vchord_bf16vec_operator_cosine,
+    LEFTARG = bf16vec,
+    RIGHTARG = bf16vec,
+    COMMUTATOR = <=>
+);
+
 CREATE OPERATOR <<->> (
     PROCEDURE = _vchord_vector_sphere_l2_in,
     LEFTARG = vector,
@@ -114,6 +317,30 @@ CREATE OPERATOR <<->> (
     RIGHTARG = sphere_rabitq4
 );
 
+CREATE OPERATOR <<->> (
+    PROCEDURE = _vchord_rabitq2_sphere_l2_in,
+    LEFTARG = rabitq2,
+    RIGHTARG = sphere_rabitq2
+);
+
+CREATE OPERATOR <<->> (
+    PROCEDURE = _vchord_rabitq1_sphere_l2_in,
+    LEFTARG = rabitq1,
+    RIGHTARG = sphere_rabitq1
+);
+
+CREATE OPERATOR <<->> (
+    PROCEDURE = _vchord_int8vec_sphere_l2_in,
+    LEFTARG = int8vec,
+    RIGHTARG = sphere_int8vec
+);
+
+CREATE OPERATOR <<->> (
+    PROCEDURE = _vchord_bf16vec_sphere_l2_in,
+    LEFTARG = bf16vec,
+    RIGHTARG = sphere_bf16vec
+);
+
 CREATE OPERATOR <<#>> (
     PROCEDURE = _vchord_vector_sphere_ip_in,
     LEFTARG = vector,
@@ -138,6 +365,30 @@ CREATE OPERATOR <<#>> (
     RIGHTARG = sphere_rabitq4
 );
 
+CREATE OPERATOR <<#>> (
+    PROCEDURE = _vchord_rabitq2_sphere_ip_in,
+    LEFTARG = rabitq2,
+    RIGHTARG = sphere_rabitq2
+);
+
+CREATE OPERATOR <<#>> (
+    PROCEDURE = _vchord_rabitq1_sphere_ip_in,
+    LEFTARG = rabitq1,
+    RIGHTARG = sphere_rabitq1
+);
+
+CREATE OPERATOR <<#>> (
+    PROCEDURE = _vchord_int8vec_sphere_ip_in,
+    LEFTARG = int8vec,
+    RIGHTARG = sphere_int8vec
+);
+
+CREATE OPERATOR <<#>> (
+    PROCEDURE = _vchord_bf16vec_sphere_ip_in,
+    LEFTARG = bf16vec,
+    RIGHTARG = sphere_bf16vec
+);
+
 CREATE OPERATOR <<=>> (
     PROCEDURE = _vchord_vector_sphere_cosine_in,
     LEFTARG = vector,
@@ -162,6 +413,30 @@ CREATE OPERATOR <<=>> (
     RIGHTARG = sphere_rabitq4
 );
 
+CREATE OPERATOR <<=>> (
+    PROCEDURE = _vchord_rabitq2_sphere_cosine_in,
+    LEFTARG = rabitq2,
+    RIGHTARG = sphere_rabitq2
+);
+
+CREATE OPERATOR <<=>> (
+    PROCEDURE = _vchord_rabitq1_sphere_cosine_in,
+    LEFTARG = rabitq1,
+    RIGHTARG = sphere_rabitq1
+);
+
+CREATE OPERATOR <<=>> (
+    PROCEDURE = _vchord_int8vec_sphere_cosine_in,
+    LEFTARG = int8vec,
+    RIGHTARG = sphere_int8vec
+);
+
+CREATE OPERATOR <<=>> (
+    PROCEDURE = _vchord_bf16vec_sphere_cosine_in,
+    LEFTARG = bf16vec,
+    RIGHTARG = sphere_bf16vec
+);
+
 CREATE OPERATOR @# (
     PROCEDURE = _vchord_vector_operator_maxsim,
     LEFTARG = vector[],
@@ -186,6 +461,56 @@ CREATE OPERATOR @# (
     RIGHTARG = rabitq4[]
 );
 
+CREATE OPERATOR @# (
+    PROCEDURE = _vchord_rabitq2_operator_maxsim,
+    LEFTARG = rabitq2[],
+    RIGHTARG = rabitq2[]
+);
+
+CREATE OPERATOR @# (
+    PROCEDURE = _vchord_rabitq1_operator_maxsim,
+    LEFTARG = rabitq1[],
+    RIGHTARG = rabitq1[]
+);
+
+CREATE OPERATOR @# (
+    PROCEDURE = _vchord_int8vec_operator_maxsim,
+    LEFTARG = int8vec[],
+    RIGHTARG = int8vec[]
+);
+
+CREATE OPERATOR @# (
+    PROCEDURE = _vchord_bf16vec_operator_maxsim,
+    LEFTARG = bf16vec[],
+    RIGHTARG = bf16vec[]
+);
+
+CREATE OPERATOR + (
+    PROCEDURE = _vchord_rabitq8_operator_add,
+    LEFTARG = rabitq8,
+    RIGHTARG = rabitq8,
+    COMMUTATOR = +
+);
+
+CREATE OPERATOR + (
+    PROCEDURE = _vchord_rabitq4_operator_add,
+    LEFTARG = rabitq4,
+    RIGHTARG = rabitq4,
+    COMMUTATOR = +
+);
+
+CREATE OPERATOR - (
+    PROCEDURE = _vchord_rabitq8_operator_sub,
+    LEFTARG = rabitq8,
+    RIGHTARG = rabitq8
+);
+
+CREATE OPERATOR - (
+    PROCEDURE = _vchord_rabitq4_operator_sub,
+    LEFTARG = rabitq4,
+    RIGHTARG = rabitq4
+);
+
 -- List of functions
 
 CREATE FUNCTION sphere(vector, real) RETURNS sphere_vector
@@ -200,6 +525,18 @@ IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::sphere_rabitq8';
 CREATE FUNCTION sphere(rabitq4, real) RETURNS sphere_rabitq4
 IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::sphere_rabitq4';
 
+CREATE FUNCTION sphere(rabitq2, real) RETURNS sphere_rabitq2
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::sphere_rabitq2';
+
+CREATE FUNCTION sphere(rabitq1, real) RETURNS sphere_rabitq1
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::sphere_rabitq1';
+
+CREATE FUNCTION sphere(int8vec, real) RETURNS sphere_int8vec
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::sphere_int8vec';
+
+CREATE FUNCTION sphere(bf16vec, real) RETURNS sphere_bf16vec
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::sphere_bf16vec';
+
 CREATE FUNCTION quantize_to_rabitq8(vector) RETURNS rabitq8
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_vector_quantize_to_rabitq8_wrapper';
 
@@ -224,7 +561,59 @@ IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4
 CREATE FUNCTION dequantize_to_halfvec(rabitq4) RETURNS halfvec
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4_dequantize_to_halfvec_wrapper';
 
-CREATE FUNCTION vchordrq_sampled_values(regclass) RETURNS SETOF TEXT
+CREATE FUNCTION normalize(rabitq8) RETURNS rabitq8
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq8_normalize_wrapper';
+
+CREATE FUNCTION normalize(rabitq4) RETURNS rabitq4
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4_normalize_wrapper';
+
+CREATE FUNCTION quantize_to_rabitq2(vector) RETURNS rabitq2
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_vector_quantize_to_rabitq2_wrapper';
+
+CREATE FUNCTION quantize_to_rabitq2(halfvec) RETURNS rabitq2
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_halfvec_quantize_to_rabitq2_wrapper';
+
+CREATE FUNCTION dequantize_to_vector(rabitq2) RETURNS vector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq2_dequantize_to_vector_wrapper';
+
+CREATE FUNCTION dequantize_to_halfvec(rabitq2) RETURNS halfvec
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq2_dequantize_to_halfvec_wrapper';
+
+CREATE FUNCTION quantize_to_rabitq1(vector) RETURNS rabitq1
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_vector_quantize_to_rabitq1_wrapper';
+
+CREATE FUNCTION quantize_to_rabitq1(halfvec) RETURNS rabitq1
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_halfvec_quantize_to_rabitq1_wrapper';
+
+CREATE FUNCTION dequantize_to_vector(rabitq1) RETURNS vector
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq1_dequantize_to_vector_wrapper';
+
+CREATE FUNCTION dequantize_to_halfvec(rabitq1) RETURNS halfvec
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq1_dequantize_to_halfvec_wrapper';
+
+CREATE FUNCTION vchord_kmeans(
+    query TEXT,
+    k INTEGER,
+    iterations INTEGER DEFAULT 10,
+    spherical BOOLEAN DEFAULT false,
+    algorithm TEXT DEFAULT 'lloyd',
+    threads INTEGER DEFAULT 1
+)
+RETURNS TABLE(id INTEGER, centroid vector)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_kmeans_wrapper';
+
+CREATE FUNCTION vchord_kmeans_assign(vector, centroids vector[], spherical BOOLEAN DEFAULT false) RETURNS INTEGER
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_kmeans_assign_wrapper';
+
+CREATE FUNCTION vchordrq_sampled_values(regclass)
+RETURNS TABLE(
+    value TEXT,
+    options TEXT,
+    elapsed_ms DOUBLE PRECISION,
+    rows BIGINT,
+    scanned BIGINT,
+    reranked BIGINT
+)
 STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_sampled_values_wrapper';
 
 CREATE FUNCTION vchordrq_sampled_queries(regclass)
@@ -234,7 +623,14 @@ RETURNS TABLE(
     table_name NAME,
     column_name NAME,
     operator NAME,
-    value TEXT
+    value TEXT,
+    probes INT[],
+    epsilon REAL,
+    max_scan_tuples INT,
+    elapsed_ms DOUBLE PRECISION,
+    rows BIGINT,
+    scanned BIGINT,
+    reranked BIGINT
 )
 STRICT LANGUAGE plpgsql AS $$
 DECLARE
@@ -291,10 +687,116 @@ BEGIN
             im.table_name,
             im.column_name,
             im.operator,
-            s.value
+            s.value,
+            CASE WHEN s.options IS NOT NULL THEN ARRAY(
+                SELECT pg_catalog.jsonb_array_elements_text(s.options::jsonb -> 'probes')::int
+            ) END,
+            (s.options::jsonb ->> 'epsilon')::real,
+            (s.options::jsonb ->> 'max_scan_tuples')::int,
+            s.elapsed_ms,
+            s.rows,
+            s.scanned,
+            s.reranked
+        FROM
+            index_metadata im,
+            LATERAL %2$I.vchordrq_sampled_values(%1$s) AS s;
+        $q$,
+        $1::oid,
+        ext_schema
+    );
+    RETURN QUERY EXECUTE query_text;
+END;
+$$;
+
+CREATE FUNCTION vchordg_sampled_values(regclass)
+RETURNS TABLE(
+    value TEXT,
+    options TEXT,
+    elapsed_ms DOUBLE PRECISION,
+    rows BIGINT,
+    scanned BIGINT,
+    reranked BIGINT
+)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_sampled_values_wrapper';
+
+CREATE FUNCTION vchordg_sampled_queries(regclass)
+RETURNS TABLE(
+    schema_name NAME,
+    index_name NAME,
+    table_name NAME,
+    column_name NAME,
+    operator NAME,
+    value TEXT,
+    ef_search INT,
+    beam_search INT,
+    max_scan_tuples INT,
+    elapsed_ms DOUBLE PRECISION,
+    rows BIGINT
+)
+STRICT LANGUAGE plpgsql AS $$
+DECLARE
+    ext_schema TEXT;
+    query_text TEXT;
+BEGIN
+    SELECT n.nspname
+    INTO ext_schema
+    FROM pg_catalog.pg_extension e
+    JOIN pg_catalog.pg_namespace n ON n.oid = e.extnamespace
+    WHERE e.extname = 'vchord';
+
+    IF ext_schema IS NULL THEN
+        RAISE EXCEPTION 'vchord is not installed';
+    END IF;
+
+    query_text := format(
+        $q$
+        WITH index_metadata AS (
+            SELECT
+                NS.nspname AS schema_name,
+                I.relname AS index_name,
+                C.relname AS table_name,
+                PA.attname AS column_name,
+                OP.oprname AS operator
+            FROM
+                pg_catalog.pg_index X
+            JOIN
+                pg_catalog.pg_class C ON C.oid = X.indrelid
+            JOIN
+                pg_catalog.pg_namespace NS ON C.relnamespace = NS.oid
+            JOIN
+                pg_catalog.pg_class I ON I.oid = X.indexrelid
+            JOIN
+                pg_catalog.pg_am A ON A.oid = I.relam
+            LEFT JOIN
+                pg_catalog.pg_opclass AS OPC ON OPC.oid = X.indclass[0]
+            LEFT JOIN
+                pg_catalog.pg_amop AO ON OPC.opcfamily = AO.amopfamily
+            LEFT JOIN
+                pg_catalog.pg_operator OP ON OP.oid = AO.amopopr
+            LEFT JOIN
+                pg_catalog.pg_attribute PA ON PA.attrelid = X.indrelid AND PA.attnum = X.indkey[0]
+            WHERE
+                A.amname = 'vchordg'
+                AND AO.amopstrategy = 1
+                AND C.relkind = 'r'
+                AND X.indnkeyatts IN (1, 2)
+                AND X.indexrelid = %1$s
+        )
+        SELECT
+            im.schema_name,
+            im.index_name,
+            im.table_name,
+            im.column_name,
+            im.operator,
+            s.value,
+            (s.options::jsonb ->> 'ef_search')::int,
+            (s.options::jsonb ->> 'beam_search')::int,
+            (s.options::jsonb ->> 'max_scan_tuples')::int,
+            s.elapsed_ms,
+            s.rows
         FROM
             index_metadata im,
-            LATERAL %2$I.vchordrq_sampled_values(%1$s) AS s(value);
+            LATERAL %2$I.vchordg_sampled_values(%1$s) AS s;
         $q$,
         $1::oid,
         ext_schema
@@ -309,6 +811,40 @@ IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_amhan
 CREATE FUNCTION vchordrq_prewarm(regclass, integer default 0) RETURNS TEXT
 STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_prewarm_wrapper';
 
+CREATE FUNCTION vchordrq_verify(regclass, heapallindexed boolean default false)
+RETURNS TABLE(block BIGINT, problem TEXT)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_verify_wrapper';
+
+CREATE FUNCTION vchordrq_import(regclass, path TEXT) RETURNS BIGINT
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_import_wrapper';
+
+CREATE FUNCTION vchordrq_compact(regclass) RETURNS BIGINT
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_compact_wrapper';
+
+CREATE FUNCTION vchordrq_assign(regclass, anyelement, depth INTEGER DEFAULT 8) RETURNS INTEGER[]
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_assign_wrapper';
+
+CREATE FUNCTION vchordrq_list_members(regclass, list INTEGER) RETURNS SETOF tid
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_list_members_wrapper';
+
+CREATE FUNCTION vchordrq_appendable(regclass) RETURNS TABLE(list INTEGER, tuples BIGINT)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_appendable_wrapper';
+
+CREATE FUNCTION vchordrq_shared_cache_stats() RETURNS TABLE(hits BIGINT, misses BIGINT)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_shared_cache_stats_wrapper';
+
+CREATE FUNCTION vchordrq_search_batch(regclass, queries anyarray, k INTEGER)
+RETURNS TABLE(query_no INTEGER, tid tid, distance REAL)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_search_batch_wrapper';
+
+CREATE FUNCTION vchord_knn_join(outer_query TEXT, index regclass, k INTEGER)
+RETURNS TABLE(outer_key TEXT, inner_tid tid, distance REAL)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_knn_join_wrapper';
+
+CREATE FUNCTION vchordrq_near_duplicates(regclass, radius REAL)
+RETURNS TABLE(tid_a tid, tid_b tid, distance REAL)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_near_duplicates_wrapper';
+
 CREATE FUNCTION vchordrq_evaluate_query_recall(
     query text,
     exact_search boolean default false,
@@ -382,12 +918,365 @@ BEGIN
 END;
 $$;
 
+CREATE FUNCTION _vchordrq_lists(regclass) RETURNS int[]
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_lists_wrapper';
+
+CREATE FUNCTION _vchordrq_tune_measure(
+    queries TEXT[],
+    truths TEXT[],
+    probes TEXT,
+    epsilon real,
+    OUT recall real,
+    OUT latency_ms double precision
+)
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    rough tid[];
+    accu tid[];
+    match_count integer;
+    recall_sum real := 0;
+    latency_sum double precision := 0;
+    started timestamptz;
+    saved_probes TEXT := pg_catalog.current_setting('vchordrq.probes');
+    saved_epsilon TEXT := pg_catalog.current_setting('vchordrq.epsilon');
+BEGIN
+    PERFORM pg_catalog.set_config('vchordrq.probes', probes, true);
+    PERFORM pg_catalog.set_config('vchordrq.epsilon', epsilon::TEXT, true);
+    FOR i IN 1 .. cardinality(queries) LOOP
+        started := pg_catalog.clock_timestamp();
+        EXECUTE
+            format('SELECT coalesce(array_agg(id), array[]::tid[]) FROM (%s) AS result(id)', queries[i])
+        INTO
+            rough;
+        latency_sum := latency_sum
+            + extract(epoch FROM pg_catalog.clock_timestamp() - started) * 1000;
+        accu := truths[i]::tid[];
+        IF cardinality(accu) = 0 THEN
+            recall_sum := recall_sum + 1;
+        ELSE
+            SELECT COUNT(*) INTO match_count
+            FROM (SELECT unnest(rough) INTERSECT SELECT unnest(accu)) AS tids;
+            recall_sum := recall_sum + match_count::real / cardinality(accu)::real;
+        END IF;
+    END LOOP;
+    recall := (recall_sum / cardinality(queries))::real;
+    latency_ms := latency_sum / cardinality(queries);
+    PERFORM pg_catalog.set_config('vchordrq.probes', saved_probes, true);
+    PERFORM pg_catalog.set_config('vchordrq.epsilon', saved_epsilon, true);
+END;
+$$;
+
+CREATE FUNCTION vchordrq_tune(
+    index regclass,
+    target_recall real,
+    k integer,
+    apply boolean default false
+)
+RETURNS TABLE(
+    probes TEXT,
+    epsilon real,
+    recall real,
+    latency_ms double precision,
+    chosen boolean
+)
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    ext_schema TEXT;
+    lists int[];
+    queries TEXT[];
+    truths TEXT[];
+    curve_probes TEXT[] := ARRAY[]::TEXT[];
+    curve_epsilon real[] := ARRAY[]::real[];
+    curve_recall real[] := ARRAY[]::real[];
+    curve_latency double precision[] := ARRAY[]::double precision[];
+    best integer;
+    bottom integer;
+    step integer;
+    base int[];
+    combination int[];
+    combinations TEXT[];
+    current_probes TEXT;
+    current_epsilon real;
+    low real;
+    high real;
+    measured record;
+    accu tid[];
+    saved_enable_scan TEXT := pg_catalog.current_setting('vchordrq.enable_scan');
+    saved_max_scan_tuples TEXT := pg_catalog.current_setting('vchordrq.max_scan_tuples');
+    sampling_disabled boolean := false;
+BEGIN
+    IF index IS NULL OR target_recall IS NULL OR k IS NULL OR apply IS NULL THEN
+        RETURN;
+    END IF;
+    IF target_recall <= 0 OR target_recall > 1 THEN
+        RAISE EXCEPTION 'target_recall must be in the range (0, 1]';
+    END IF;
+    IF k <= 0 THEN
+        RAISE EXCEPTION 'k must be a positive integer';
+    END IF;
+
+    SELECT n.nspname
+    INTO ext_schema
+    FROM pg_catalog.pg_extension e
+    JOIN pg_catalog.pg_namespace n ON n.oid = e.extnamespace
+    WHERE e.extname = 'vchord';
+
+    IF ext_schema IS NULL THEN
+        RAISE EXCEPTION 'vchord is not installed';
+    END IF;
+
+    EXECUTE format('SELECT %I._vchordrq_lists(%s)', ext_schema, index::oid) INTO lists;
+
+    EXECUTE format(
+        $q$
+        SELECT coalesce(array_agg(format(
+            'SELECT ctid FROM %%I.%%I ORDER BY %%I OPERATOR(%%s) %%L LIMIT %s',
+            s.schema_name, s.table_name, s.column_name, s.operator, s.value
+        )), ARRAY[]::TEXT[])
+        FROM %I.vchordrq_sampled_queries(%s) AS s
+        WHERE s.column_name IS NOT NULL
+        $q$,
+        k,
+        ext_schema,
+        index::oid
+    ) INTO queries;
+
+    IF cardinality(queries) = 0 THEN
+        RAISE EXCEPTION 'no sampled queries are available for the index %', index;
+    END IF;
+
+    -- Queries of the sweep must not be sampled, or they would evict the real
+    -- samples that the sweep is based on.
+    IF pg_catalog.current_setting('vchordrq.query_sampling_enable')::boolean THEN
+        BEGIN
+            PERFORM pg_catalog.set_config('vchordrq.query_sampling_enable', 'off', true);
+            sampling_disabled := true;
+        EXCEPTION WHEN insufficient_privilege THEN
+            RAISE EXCEPTION 'permission denied to disable query sampling during the sweep'
+                USING HINT = 'Run vchordrq_tune as a superuser, or disable vchordrq.query_sampling_enable.';
+        END;
+    END IF;
+
+    truths := ARRAY[]::TEXT[];
+    PERFORM pg_catalog.set_config('vchordrq.enable_scan', 'off', true);
+    FOR i IN 1 .. cardinality(queries) LOOP
+        EXECUTE
+            format('SELECT coalesce(array_agg(id), array[]::tid[]) FROM (%s) AS result(id)', queries[i])
+        INTO
+            accu;
+        truths := truths || accu::TEXT;
+    END LOOP;
+    PERFORM pg_catalog.set_config('vchordrq.enable_scan', 'on', true);
+    PERFORM pg_catalog.set_config('vchordrq.max_scan_tuples', '-1', true);
+
+    bottom := coalesce(lists[cardinality(lists)], 1);
+    step := 1;
+    LOOP
+        -- Probes of every level grow with the probes of the leaf level, and then
+        -- probes of each upper level are doubled on their own.
+        SELECT coalesce(array_agg(
+            least(l, greatest(1, ceil(l::double precision * step / bottom)::integer))
+            ORDER BY o
+        ), ARRAY[]::int[])
+        INTO base
+        FROM unnest(lists) WITH ORDINALITY AS u(l, o);
+        combinations := ARRAY[array_to_string(base, ',')];
+        FOR o IN 1 .. cardinality(lists) - 1 LOOP
+            combination := base;
+            WHILE combination[o] < lists[o] LOOP
+                combination[o] := least(combination[o] * 2, lists[o]);
+                combinations := combinations || array_to_string(combination, ',');
+            END LOOP;
+        END LOOP;
+        FOREACH current_probes IN ARRAY combinations LOOP
+            -- Epsilon is bisected on [0, 4] for the smallest value that reaches the
+            -- target, starting from the largest one.
+            low := 0;
+            high := 4;
+            current_epsilon := high;
+            FOR j IN 0 .. 4 LOOP
+                EXECUTE format('SELECT * FROM %I._vchordrq_tune_measure($1, $2, $3, $4)', ext_schema)
+                INTO measured
+                USING queries, truths, current_probes, current_epsilon;
+                curve_probes := curve_probes || current_probes;
+                curve_epsilon := curve_epsilon || current_epsilon;
+                curve_recall := curve_recall || measured.recall;
+                curve_latency := curve_latency || measured.latency_ms;
+                IF measured.recall >= target_recall THEN
+                    IF best IS NULL OR measured.latency_ms < curve_latency[best] THEN
+                        best := cardinality(curve_recall);
+                    END IF;
+                    high := current_epsilon;
+                ELSIF j = 0 THEN
+                    EXIT;
+                ELSE
+                    low := current_epsilon;
+                END IF;
+                current_epsilon := (low + high) / 2;
+            END LOOP;
+        END LOOP;
+        EXIT WHEN best IS NOT NULL OR step >= bottom;
+        step := least(step * 2, bottom);
+    END LOOP;
+
+    -- Settings of the sweep are restored, so that they do not leak into the
+    -- rest of the transaction.
+    PERFORM pg_catalog.set_config('vchordrq.enable_scan', saved_enable_scan, true);
+    PERFORM pg_catalog.set_config('vchordrq.max_scan_tuples', saved_max_scan_tuples, true);
+    IF sampling_disabled THEN
+        PERFORM pg_catalog.set_config('vchordrq.query_sampling_enable', 'on', true);
+    END IF;
+
+    IF best IS NULL THEN
+        RAISE WARNING 'no setting reaches the target recall % for the index %', target_recall, index;
+    ELSIF apply THEN
+        EXECUTE format(
+            'ALTER INDEX %s SET (probes = %L, epsilon = %s)',
+            index, curve_probes[best], curve_epsilon[best]
+        );
+    END IF;
+
+    FOR i IN 1 .. cardinality(curve_probes) LOOP
+        probes := curve_probes[i];
+        epsilon := curve_epsilon[i];
+        recall := curve_recall[i];
+        latency_ms := curve_latency[i];
+        chosen := i IS NOT DISTINCT FROM best;
+        RETURN NEXT;
+    END LOOP;
+END;
+$$;
+
 CREATE FUNCTION vchordg_amhandler(internal) RETURNS index_am_handler
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_amhandler_wrapper';
 
 CREATE FUNCTION vchordg_prewarm(regclass) RETURNS TEXT
 STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_prewarm_wrapper';
 
+CREATE FUNCTION vchordg_verify(regclass) RETURNS TABLE(block BIGINT, problem TEXT)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_verify_wrapper';
+
+CREATE FUNCTION vchordg_repair(regclass) RETURNS BIGINT
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_repair_wrapper';
+
+CREATE FUNCTION vchordg_import(regclass, path TEXT) RETURNS BIGINT
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_import_wrapper';
+
+CREATE FUNCTION vchord_upgrade_index(regclass) RETURNS BIGINT
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_upgrade_index_wrapper';
+
+-- List of aggregates
+
+CREATE AGGREGATE vchord_sum(vector) (
+    SFUNC = _vchord_vector_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_vector_sum,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_sum(halfvec) (
+    SFUNC = _vchord_halfvec_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_halfvec_sum,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_sum(rabitq8) (
+    SFUNC = _vchord_rabitq8_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_rabitq8_sum,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_sum(rabitq4) (
+    SFUNC = _vchord_rabitq4_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_rabitq4_sum,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_avg(vector) (
+    SFUNC = _vchord_vector_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_vector_avg,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_avg(halfvec) (
+    SFUNC = _vchord_halfvec_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_halfvec_avg,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_avg(rabitq8) (
+    SFUNC = _vchord_rabitq8_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_rabitq8_avg,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_avg(rabitq4) (
+    SFUNC = _vchord_rabitq4_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_rabitq4_avg,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_weighted_avg(vector, real) (
+    SFUNC = _vchord_vector_weighted_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_vector_avg,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_weighted_avg(halfvec, real) (
+    SFUNC = _vchord_halfvec_weighted_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_halfvec_avg,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_weighted_avg(rabitq8, real) (
+    SFUNC = _vchord_rabitq8_weighted_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_rabitq8_avg,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
+CREATE AGGREGATE vchord_weighted_avg(rabitq4, real) (
+    SFUNC = _vchord_rabitq4_weighted_accum,
+    STYPE = double precision[],
+    FINALFUNC = _vchord_rabitq4_avg,
+    COMBINEFUNC = _vchord_accum_combine,
+    INITCOND = '{0}',
+    PARALLEL = SAFE
+);
+
 -- List of access methods
 
 CREATE ACCESS METHOD vchordrq TYPE INDEX HANDLER vchordrq_amhandler;
@@ -407,10 +1296,23 @@ CREATE OPERATOR FAMILY rabitq8_cosine_ops USING vchordrq;
 CREATE OPERATOR FAMILY rabitq4_l2_ops USING vchordrq;
 CREATE OPERATOR FAMILY rabitq4_ip_ops USING vchordrq;
 CREATE OPERATOR FAMILY rabitq4_cosine_ops USING vchordrq;
+CREATE OPERATOR FAMILY rabitq2_l2_ops USING vchordrq;
+CREATE OPERATOR FAMILY rabitq2_ip_ops USING vchordrq;
+CREATE OPERATOR FAMILY rabitq2_cosine_ops USING vchordrq;
+CREATE OPERATOR FAMILY rabitq1_l2_ops USING vchordrq;
+CREATE OPERATOR FAMILY rabitq1_ip_ops USING vchordrq;
+CREATE OPERATOR FAMILY rabitq1_cosine_ops USING vchordrq;
+CREATE OPERATOR FAMILY int8vec_l2_ops USING vchordrq;
+CREATE OPERATOR FAMILY int8vec_ip_ops USING vchordrq;
+CREATE OPERATOR FAMILY bf16vec_l2_ops USING vchordrq;
+CREATE OPERATOR FAMILY bf16vec_ip_ops USING vchordrq;
+CREATE OPERATOR FAMILY bf16vec_cosine_ops USING vchordrq;
 CREATE OPERATOR FAMILY vector_maxsim_ops USING vchordrq;
 CREATE OPERATOR FAMILY halfvec_maxsim_ops USING vchordrq;
 CREATE OPERATOR FAMILY rabitq8_maxsim_ops USING vchordrq;
 CREATE OPERATOR FAMILY rabitq4_maxsim_ops USING vchordrq;
+CREATE OPERATOR FAMILY rabitq2_maxsim_ops USING vchordrq;
+CREATE OPERATOR FAMILY rabitq1_maxsim_ops USING vchordrq;
 CREATE OPERATOR FAMILY vector_l2_ops USING vchordg;
 CREATE OPERATOR FAMILY vector_ip_ops USING vchordg;
 CREATE OPERATOR FAMILY vector_cosine_ops USING vchordg;
@@ -423,6 +1325,18 @@ CREATE OPERATOR FAMILY rabitq8_cosine_ops USING vchordg;
 CREATE OPERATOR FAMILY rabitq4_l2_ops USING vchordg;
 CREATE OPERATOR FAMILY rabitq4_ip_ops USING vchordg;
 CREATE OPERATOR FAMILY rabitq4_cosine_ops USING vchordg;
+CREATE OPERATOR FAMILY rabitq2_l2_ops USING vchordg;
+CREATE OPERATOR FAMILY rabitq2_ip_ops USING vchordg;
+CREATE OPERATOR FAMILY rabitq2_cosine_ops USING vchordg;
+CREATE OPERATOR FAMILY rabitq1_l2_ops USING vchordg;
+CREATE OPERATOR FAMILY rabitq1_ip_ops USING vchordg;
+CREATE OPERATOR FAMILY rabitq1_cosine_ops USING vchordg;
+CREATE OPERATOR FAMILY int8vec_l2_ops USING vchordg;
+CREATE OPERATOR FAMILY int8vec_ip_ops USING vchordg;
+CREATE OPERATOR FAMILY bf16vec_l2_ops USING vchordg;
+CREATE OPERATOR FAMILY bf16vec_ip_ops USING vchordg;
+CREATE OPERATOR FAMILY bf16vec_cosine_ops USING vchordg;
+CREATE OPERATOR FAMILY int4_array_ops USING vchordg;
 
 -- List of operator classes
 
@@ -498,6 +1412,72 @@ CREATE OPERATOR CLASS rabitq4_cosine_ops
     OPERATOR 2 <<=>> (rabitq4, sphere_rabitq4) FOR SEARCH,
     FUNCTION 1 _vchordrq_support_rabitq4_cosine_ops();
 
+CREATE OPERATOR CLASS rabitq2_l2_ops
+    FOR TYPE rabitq2 USING vchordrq FAMILY rabitq2_l2_ops AS
+    OPERATOR 1 <-> (rabitq2, rabitq2) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (rabitq2, sphere_rabitq2) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_rabitq2_l2_ops();
+
+CREATE OPERATOR CLASS rabitq2_ip_ops
+    FOR TYPE rabitq2 USING vchordrq FAMILY rabitq2_ip_ops AS
+    OPERATOR 1 <#> (rabitq2, rabitq2) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (rabitq2, sphere_rabitq2) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_rabitq2_ip_ops();
+
+CREATE OPERATOR CLASS rabitq2_cosine_ops
+    FOR TYPE rabitq2 USING vchordrq FAMILY rabitq2_cosine_ops AS
+    OPERATOR 1 <=> (rabitq2, rabitq2) FOR ORDER BY float_ops,
+    OPERATOR 2 <<=>> (rabitq2, sphere_rabitq2) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_rabitq2_cosine_ops();
+
+CREATE OPERATOR CLASS rabitq1_l2_ops
+    FOR TYPE rabitq1 USING vchordrq FAMILY rabitq1_l2_ops AS
+    OPERATOR 1 <-> (rabitq1, rabitq1) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (rabitq1, sphere_rabitq1) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_rabitq1_l2_ops();
+
+CREATE OPERATOR CLASS rabitq1_ip_ops
+    FOR TYPE rabitq1 USING vchordrq FAMILY rabitq1_ip_ops AS
+    OPERATOR 1 <#> (rabitq1, rabitq1) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (rabitq1, sphere_rabitq1) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_rabitq1_ip_ops();
+
+CREATE OPERATOR CLASS rabitq1_cosine_ops
+    FOR TYPE rabitq1 USING vchordrq FAMILY rabitq1_cosine_ops AS
+    OPERATOR 1 <=> (rabitq1, rabitq1) FOR ORDER BY float_ops,
+    OPERATOR 2 <<=>> (rabitq1, sphere_rabitq1) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_rabitq1_cosine_ops();
+
+CREATE OPERATOR CLASS int8vec_l2_ops
+    FOR TYPE int8vec USING vchordrq FAMILY int8vec_l2_ops AS
+    OPERATOR 1 <-> (int8vec, int8vec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (int8vec, sphere_int8vec) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_int8vec_l2_ops();
+
+CREATE OPERATOR CLASS int8vec_ip_ops
+    FOR TYPE int8vec USING vchordrq FAMILY int8vec_ip_ops AS
+    OPERATOR 1 <#> (int8vec, int8vec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (int8vec, sphere_int8vec) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_int8vec_ip_ops();
+
+CREATE OPERATOR CLASS bf16vec_l2_ops
+    FOR TYPE bf16vec USING vchordrq FAMILY bf16vec_l2_ops AS
+    OPERATOR 1 <-> (bf16vec, bf16vec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (bf16vec, sphere_bf16vec) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_bf16vec_l2_ops();
+
+CREATE OPERATOR CLASS bf16vec_ip_ops
+    FOR TYPE bf16vec USING vchordrq FAMILY bf16vec_ip_ops AS
+    OPERATOR 1 <#> (bf16vec, bf16vec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (bf16vec, sphere_bf16vec) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_bf16vec_ip_ops();
+
+CREATE OPERATOR CLASS bf16vec_cosine_ops
+    FOR TYPE bf16vec USING vchordrq FAMILY bf16vec_cosine_ops AS
+    OPERATOR 1 <=> (bf16vec, bf16vec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<=>> (bf16vec, sphere_bf16vec) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_bf16vec_cosine_ops();
+
 CREATE OPERATOR CLASS vector_maxsim_ops
     FOR TYPE vector[] USING vchordrq FAMILY vector_maxsim_ops AS
     OPERATOR 3 @# (vector[], vector[]) FOR ORDER BY float_ops,
@@ -518,6 +1498,16 @@ CREATE OPERATOR CLASS rabitq4_maxsim_ops
     OPERATOR 3 @# (rabitq4[], rabitq4[]) FOR ORDER BY float_ops,
     FUNCTION 1 _vchordrq_support_rabitq4_maxsim_ops();
 
+CREATE OPERATOR CLASS rabitq2_maxsim_ops
+    FOR TYPE rabitq2[] USING vchordrq FAMILY rabitq2_maxsim_ops AS
+    OPERATOR 3 @# (rabitq2[], rabitq2[]) FOR ORDER BY float_ops,
+    FUNCTION 1 _vchordrq_support_rabitq2_maxsim_ops();
+
+CREATE OPERATOR CLASS rabitq1_maxsim_ops
+    FOR TYPE rabitq1[] USING vchordrq FAMILY rabitq1_maxsim_ops AS
+    OPERATOR 3 @# (rabitq1[], rabitq1[]) FOR ORDER BY float_ops,
+    FUNCTION 1 _vchordrq_support_rabitq1_maxsim_ops();
+
 CREATE OPERATOR CLASS vector_l2_ops
     FOR TYPE vector USING vchordg FAMILY vector_l2_ops AS
     OPERATOR 1 <-> (vector, vector) FOR ORDER BY float_ops,
@@ -590,6 +1580,77 @@ CREATE OPERATOR CLASS rabitq4_cosine_ops
     OPERATOR 2 <<=>> (rabitq4, sphere_rabitq4) FOR SEARCH,
     FUNCTION 1 _vchordg_support_rabitq4_cosine_ops();
 
+CREATE OPERATOR CLASS rabitq2_l2_ops
+    FOR TYPE rabitq2 USING vchordg FAMILY rabitq2_l2_ops AS
+    OPERATOR 1 <-> (rabitq2, rabitq2) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (rabitq2, sphere_rabitq2) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_rabitq2_l2_ops();
+
+CREATE OPERATOR CLASS rabitq2_ip_ops
+    FOR TYPE rabitq2 USING vchordg FAMILY rabitq2_ip_ops AS
+    OPERATOR 1 <#> (rabitq2, rabitq2) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (rabitq2, sphere_rabitq2) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_rabitq2_ip_ops();
+
+CREATE OPERATOR CLASS rabitq2_cosine_ops
+    FOR TYPE rabitq2 USING vchordg FAMILY rabitq2_cosine_ops AS
+    OPERATOR 1 <=> (rabitq2, rabitq2) FOR ORDER BY float_ops,
+    OPERATOR 2 <<=>> (rabitq2, sphere_rabitq2) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_rabitq2_cosine_ops();
+
+CREATE OPERATOR CLASS rabitq1_l2_ops
+    FOR TYPE rabitq1 USING vchordg FAMILY rabitq1_l2_ops AS
+    OPERATOR 1 <-> (rabitq1, rabitq1) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (rabitq1, sphere_rabitq1) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_rabitq1_l2_ops();
+
+CREATE OPERATOR CLASS rabitq1_ip_ops
+    FOR TYPE rabitq1 USING vchordg FAMILY rabitq1_ip_ops AS
+    OPERATOR 1 <#> (rabitq1, rabitq1) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (rabitq1, sphere_rabitq1) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_rabitq1_ip_ops();
+
+CREATE OPERATOR CLASS rabitq1_cosine_ops
+    FOR TYPE rabitq1 USING vchordg FAMILY rabitq1_cosine_ops AS
+    OPERATOR 1 <=> (rabitq1, rabitq1) FOR ORDER BY float_ops,
+    OPERATOR 2 <<=>> (rabitq1, sphere_rabitq1) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_rabitq1_cosine_ops();
+
+CREATE OPERATOR CLASS int8vec_l2_ops
+    FOR TYPE int8vec USING vchordg FAMILY int8vec_l2_ops AS
+    OPERATOR 1 <-> (int8vec, int8vec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (int8vec, sphere_int8vec) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_int8vec_l2_ops();
+
+CREATE OPERATOR CLASS int8vec_ip_ops
+    FOR TYPE int8vec USING vchordg FAMILY int8vec_ip_ops AS
+    OPERATOR 1 <#> (int8vec, int8vec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (int8vec, sphere_int8vec) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_int8vec_ip_ops();
+
+CREATE OPERATOR CLASS bf16vec_l2_ops
+    FOR TYPE bf16vec USING vchordg FAMILY bf16vec_l2_ops AS
+    OPERATOR 1 <-> (bf16vec, bf16vec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (bf16vec, sphere_bf16vec) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_bf16vec_l2_ops();
+
+CREATE OPERATOR CLASS bf16vec_ip_ops
+    FOR TYPE bf16vec USING vchordg FAMILY bf16vec_ip_ops AS
+    OPERATOR 1 <#> (bf16vec, bf16vec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (bf16vec, sphere_bf16vec) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_bf16vec_ip_ops();
+
+CREATE OPERATOR CLASS bf16vec_cosine_ops
+    FOR TYPE bf16vec USING vchordg FAMILY bf16vec_cosine_ops AS
+    OPERATOR 1 <=> (bf16vec, bf16vec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<=>> (bf16vec, sphere_bf16vec) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_bf16vec_cosine_ops();
+
+CREATE OPERATOR CLASS int4_array_ops
+    FOR TYPE int4[] USING vchordg FAMILY int4_array_ops AS
+    OPERATOR 4 && (anyarray, anyarray),
+    FUNCTION 1 _vchordg_support_int4_array_ops();
+
 -- List of views
 
 CREATE VIEW vchordrq_sampled_queries AS
@@ -599,7 +1660,14 @@ SELECT
     record.table_name,
     record.column_name,
     record.operator,
-    record.value
+    record.value,
+    record.probes,
+    record.epsilon,
+    record.max_scan_tuples,
+    record.elapsed_ms,
+    record.rows,
+    record.scanned,
+    record.reranked
 FROM
     (
         SELECT i.oid
@@ -610,3 +1678,27 @@ FROM
         WHERE am.amname = 'vchordrq'
     ) AS index_oids
 CROSS JOIN LATERAL vchordrq_sampled_queries(index_oids.oid::regclass) AS record;
+
+CREATE VIEW vchordg_sampled_queries AS
+SELECT
+    record.schema_name,
+    record.index_name,
+    record.table_name,
+    record.column_name,
+    record.operator,
+    record.value,
+    record.ef_search,
+    record.beam_search,
+    record.max_scan_tuples,
+    record.elapsed_ms,
+    record.rows
+FROM
+    (
+        SELECT i.oid
+        FROM pg_catalog.pg_class AS i
+        JOIN pg_catalog.pg_index AS ix ON i.oid = ix.indexrelid
+        JOIN pg_catalog.pg_opclass AS opc ON ix.indclass[0] = opc.oid
+        JOIN pg_catalog.pg_am AS am ON opc.opcmethod = am.oid
+        WHERE am.amname = 'vchordg'
+    ) AS index_oids
+CROSS JOIN LATERAL vchordg_sampled_queries(index_oids.oid::regclass) AS record;